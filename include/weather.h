@@ -0,0 +1,33 @@
+#ifndef WEATHER_H
+#define WEATHER_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Fetch the current weather at `location` from `provider`, with the API keys from the settings
+// file and environment the CLI would use.
+//
+// On return `*out_json` holds the result as printed by `weather --format json`, or
+// `{"error": "..."}`; release it with [`weather_string_free`]. Returns 0 on success, otherwise
+// the exit code the CLI would end with, or 101 if the call panicked. The passphrase of encrypted
+// settings is read from `WEATHER_PASSPHRASE`. Do not call it from a thread running a Tokio
+// runtime.
+//
+// # Safety
+//
+// `provider` and `location` must be NUL-terminated strings or null, `out_json` a valid pointer
+// or null.
+int weather_fetch(const char *provider, const char *location, char **out_json);
+
+// Release a string returned by [`weather_fetch`]; null is ignored.
+//
+// # Safety
+//
+// `s` must come from [`weather_fetch`] and not have been freed before.
+void weather_string_free(char *s);
+
+#endif  /* WEATHER_H */
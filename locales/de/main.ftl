@@ -0,0 +1,422 @@
+# Weather output
+weather-in = Wetter in { $location }: { $condition }
+label-location = Ort
+label-condition = Wetterlage
+label-date = Datum
+label-temperature = Temperatur
+label-humidity = Luftfeuchtigkeit
+label-pressure = Luftdruck
+label-wind = Wind
+wind-value = { $speed } aus { $degree }
+label-provider = Anbieter
+filled-from = Ergänzt von { $provider }: { $fields }
+field-feels-like-c = gefühlte Temperatur
+field-uv-index = UV-Index
+field-precipitation-probability = Niederschlagswahrscheinlichkeit
+field-visibility-km = Sichtweite
+
+# Beaufort scale
+beaufort-calm = Windstille
+beaufort-light-air = Leiser Zug
+beaufort-light-breeze = Leichte Brise
+beaufort-gentle-breeze = Schwache Brise
+beaufort-moderate-breeze = Mäßige Brise
+beaufort-fresh-breeze = Frische Brise
+beaufort-strong-breeze = Starker Wind
+beaufort-near-gale = Steifer Wind
+beaufort-gale = Stürmischer Wind
+beaufort-strong-gale = Sturm
+beaufort-storm = Schwerer Sturm
+beaufort-violent-storm = Orkanartiger Sturm
+beaufort-hurricane = Orkan
+
+# Configure command
+default-provider-saved = Standardanbieter gespeichert in { $path }
+provider-not-supported = Anbieter `{ $provider }` wird nicht unterstützt
+did-you-mean = Meinten Sie `{ $name }`?
+default-provider = Standardanbieter: { $provider }
+available-providers = Verfügbare Anbieter: { $providers }
+
+# Check command
+check-temp-above = Temperatur { $temp }°C liegt über { $limit }°C
+check-temp-below = Temperatur { $temp }°C liegt unter { $limit }°C
+check-wind-above = Wind { $speed } liegt über { $limit }
+check-rain = Regen: { $condition }
+check-snow = Schnee: { $condition }
+
+# Snow command
+snow-in = Schnee in { $location }
+label-snow-depth = Schneehöhe
+label-fresh-snow = Neuschnee
+label-freezing-level = Nullgradgrenze
+not-available = k. A.
+
+# Surf command
+surf-at = Surfen in { $location }: { $rating }
+surf-flat = flach
+surf-poor = schlecht
+surf-fair = mäßig
+surf-good = gut
+surf-epic = episch
+label-waves = Wellen
+waves-value = { $height } m alle { $period } s
+label-swell = Dünung
+swell-value = aus { $degree }
+
+# Degree days
+gdd-total = Wachstumsgradtage in { $location } von { $start } bis { $end } (Basis { $base }°C): { $total }
+days-missing = { $count } Tage ohne Messwerte wurden übersprungen
+degree-days-heating = HGT
+degree-days-cooling = KGT
+degree-days-total = Summe
+
+# Fire command
+fire-warning = Waldbrandgefahr: { $danger }
+fire-index = Brandindex in { $location }: { $index } ({ $danger })
+fire-inputs = > { $temp }, { $humidity }% Luftfeuchtigkeit, Wind { $wind }, { $rain } mm Regen in { $days } Tagen
+fire-low = gering bis mäßig
+fire-high = hoch
+fire-very-high = sehr hoch
+fire-severe = ernst
+fire-extreme = extrem
+fire-catastrophic = katastrophal
+
+# Activity command
+activity-best = Beste Zeit zum { $activity } in { $location }: { $time } (Wertung { $score })
+activity-run = Laufen
+activity-bike = Radfahren
+activity-hike = Wandern
+precipitation-chance = { $probability }% Regen
+
+# Clothing advice
+label-wear = Kleidung
+wear-winter-coat = Wintermantel, Mütze und Handschuhe
+wear-warm-jacket = warme Jacke
+wear-layers = leichte Schichten
+wear-light = T-Shirt
+wear-rain-jacket = Regenjacke
+wear-windbreaker = Windjacke
+wear-sunscreen = Sonnencreme
+
+# Umbrella command
+umbrella-yes = Ja, nimm einen Schirm mit: Regen ab { $time } erwartet ({ $probability })
+umbrella-snow = Ja, Schirm mitnehmen: Schnee ab { $time } erwartet ({ $probability }, { $snowfall } cm)
+umbrella-no = Kein Schirm nötig in den nächsten { $hours } Stunden (höchstens { $probability })
+
+# Record fixture command
+fixture-saved = Fixture in { $path } gespeichert
+
+# Self-update command
+up-to-date = weather { $version } ist die neueste Version
+update-available = weather { $version } ist verfügbar, { $current } ist installiert
+updated = weather von { $current } auf { $version } aktualisiert
+
+# Command line help, in place of the English doc comments
+help-weather = Aktuelles Wetter, Vorhersagen und Warnungen von mehreren Anbietern
+help-weather-configure = Den Standardanbieter festlegen oder ohne Angabe die registrierten auflisten
+help-weather-providers = Die registrierten Anbieter prüfen
+help-weather-providers-status = Alle Anbieter gleichzeitig anpingen und Erreichbarkeit und Latenz melden
+help-weather-providers-stats = Bisherige Aufrufe je Anbieter, nach Ergebnis, mit ihrer Latenz
+help-weather-providers-check-versions = Alle Anbieter anpingen und die verwendete API-Version sowie Hinweise auf deren Abkündigung melden
+help-weather-config = Die Einstellungsdatei pflegen
+help-weather-config-migrate = Die Einstellungsdatei in das aktuelle Format umschreiben und die alte als Sicherung behalten
+help-weather-config-encrypt = Die Einstellungsdatei mit einer Passphrase oder `--key-file` nach `<Datei>.enc` verschlüsseln und den Klartext entfernen
+help-weather-config-decrypt = Die verschlüsselten Einstellungen wieder in eine Klartextdatei umwandeln
+help-weather-store = Die Verlaufsdatenbank pflegen
+help-weather-store-prune = Verlauf älter als `[storage] retention` löschen und die Datenbank verkleinern
+help-weather-store-query = Eine lesende SQL-Abfrage ausführen, z. B. "SELECT location, max(temp_c) FROM readings GROUP BY 1"
+help-weather-get = Aktuelles Wetter an einem Ort oder das Wetter zu `--date`
+help-weather-batch = Wetter für mehrere Orte, gefolgt von einer Übersicht der Extreme und Fehler
+help-weather-diff = Das Wetter zu zwei Zeitpunkten vergleichen, standardmäßig jetzt mit vor 24 Stunden
+help-weather-check = Mit 1 beenden, wenn eine der angegebenen Bedingungen zutrifft, sonst mit 0
+help-weather-route = Das entlang einer Reise erwartete Wetter anzeigen
+help-weather-snow = Schneehöhe, Neuschnee und Nullgradgrenze in einem Skigebiet anzeigen
+help-weather-surf = Die Brandung an einem Spot nach Wellen, Dünung und Wind bewerten
+help-weather-gdd = Wachstumsgradtage über eine Saison summieren
+help-weather-degree-days = Heiz- und Kühlgradtage je Monat, um den Energieverbrauch zu verfolgen
+help-weather-backfill = Den täglichen Verlauf eines Zeitraums zur späteren Auswertung in die Verlaufsdatenbank laden
+help-weather-report = Den gespeicherten Verlauf einer Woche oder eines Monats als Markdown oder HTML zusammenfassen
+help-weather-windrose = Vorherrschende Windrichtungen und -geschwindigkeiten als Windrose aus der Verlaufsdatenbank
+help-weather-climate = Projizierte Monatsmittel für ein Jahrzehnt aus regionalisierten CMIP6-Klimamodellen
+help-weather-fire = Die Waldbrandgefahr aus Wetter und jüngstem Regen abschätzen
+help-weather-activity = Die kommenden Stunden für eine Aktivität im Freien bewerten
+help-weather-umbrella = Sagen, ob ein Schirm nötig ist; beendet sich dann mit 10
+help-weather-ensemble = Stündliche Vorhersage mit der Spanne, in der die Ensembleläufe übereinstimmen, um ihre Verlässlichkeit einzuschätzen
+help-weather-laundry = Das beste Zeitfenster heute zum Wäschetrocknen draußen finden
+help-weather-frost = Vor frostigen Nächten warnen; benachrichtigt und sendet an Webhooks, wenn es welche gibt
+help-weather-pressure = Luftdruck mit seiner Tendenz über 3 Stunden aus der Verlaufsdatenbank; regelmäßig ausführen, z. B. stündlich per cron, um den Verlauf aufzubauen
+help-weather-radar = Das neueste Niederschlagsradarbild um einen Ort von RainViewer herunterladen
+help-weather-satellite = Das neueste geostationäre Satellitenbild der Region um einen Ort herunterladen, z. B. als Desktophintergrund
+help-weather-moon = Mondphasen: die kommenden oder die eines Monats
+help-weather-sun = Sonnenaufgang, Sonnenuntergang, goldene und blaue Stunde und wo die Sonne gerade steht
+help-weather-trend = Zusammenfassen, wohin sich das Wetter in den kommenden Tagen entwickelt
+help-weather-alerts = Geltende amtliche Wetterwarnungen, die schwersten zuerst
+help-weather-serve = Eine REST-API mit den konfigurierten Anbietern und dem Cache bereitstellen
+help-weather-publish = Das aktuelle Wetter an einen Message Broker veröffentlichen
+help-weather-self-update = Diese Programmdatei nach Prüfung der Prüfsumme durch die neueste Version von GitHub ersetzen
+help-weather--config-path = Einstellungsdatei
+help-weather--env-file = Umgebungsvariablen aus dieser dotenv-Datei laden statt aus `.env` im aktuellen Verzeichnis
+help-weather--key-file = age-Identitätsdatei, die die verschlüsselten Einstellungen statt einer Passphrase entsperrt
+help-weather--quiet = Nur das Ergebnis in einer Zeile ausgeben, ohne Protokollausgaben
+help-weather--verbose = Protokollausgaben erweitern (-v Info, -vv Debug, -vvv Trace); RUST_LOG hat Vorrang
+help-weather--no-cache = Immer beim Anbieter abfragen und die Ergebnis- und Ortsnamen-Caches umgehen
+help-weather--strict = Bei Antworten, die nicht dem erwarteten Schema entsprechen, abbrechen statt Standardwerte einzusetzen, und mit 7 beenden nach einer Warnung oder wenn Felder von einem anderen Anbieter ergänzt wurden
+help-weather--log-json = Protokollzeilen als JSON ausgeben
+help-weather--demo = Erfundene Daten des Offline-Demoanbieters statt der echten Anbieter verwenden
+help-weather--dry-run = Ausgeben, welchen Anbieter und welche URLs `get` und `check` verwenden würden, ohne etwas abzurufen
+help-weather--notify = Das Ergebnis zusätzlich als Desktopbenachrichtigung anzeigen
+help-weather--post-url = Das JSON-Ergebnis zusätzlich zu `[webhooks] urls` an diese URL senden; wiederholbar
+help-weather--post-slack = Das Ergebnis als Slack-Block-Kit-Nachricht an diesen eingehenden Webhook senden; wiederholbar
+help-weather--post-discord = Das Ergebnis als Discord-Embed an diesen Kanal-Webhook senden; wiederholbar
+help-weather--tz = Zeiten in dieser IANA-Zeitzone (z. B. "Europe/Kyiv") statt der des Ortes anzeigen
+help-weather--no-emoji = Emoji in der Ausgabe weglassen
+help-weather--ascii = Die Ausgabe auf reines ASCII beschränken
+help-weather--accessible = Textausgabe für Screenreader: ein Sachverhalt pro Zeile, Einheiten und Richtungen ausgeschrieben, ohne Emoji und Rahmenlinien
+help-weather--color = Wann die Ausgabe eingefärbt wird
+help-weather--advice = Der Textausgabe eine Zeile „Was anziehen“ hinzufügen
+help-weather--allergy = Der Textausgabe einen Abschnitt zum Allergierisiko hinzufügen, aus dem Pollenflug, wo der Anbieter ihn kennt
+help-weather--wind-unit = Einheit, in der Windgeschwindigkeiten vorrangig angezeigt werden
+help-weather--oneline = Das Ergebnis in einer Zeile ausgeben, wie `--format oneline`
+help-weather--format = Ausgabeformat: text, oneline, json, table, tsv, template, waybar, slack oder discord
+help-weather--no-header = Die Zeile mit den Spaltennamen von `--format tsv` und `store query --as tsv`/`csv` weglassen
+
+# Accessible output
+label-pressure-tendency = Drucktendenz
+spoken-celsius = { $value } Grad Celsius
+spoken-percent = { $value } Prozent
+spoken-hpa = { $value } Hektopascal
+spoken-pressure-change = { $tendency } um { $change } Hektopascal in 3 Stunden
+spoken-kmh = { $value } Kilometer pro Stunde
+spoken-ms = { $value } Meter pro Sekunde
+spoken-knots = { $value } Knoten
+spoken-mph = { $value } Meilen pro Stunde
+spoken-beaufort = Windstärke { $force }, { $term }
+spoken-wind = { $speed } aus { $direction }
+spoken-cm = { $value } Zentimeter
+spoken-m = { $value } Meter
+spoken-km = { $value } Kilometer
+spoken-grains = { $value } Pollen pro Kubikmeter
+compass-n = Nord
+compass-nne = Nordnordost
+compass-ne = Nordost
+compass-ene = Ostnordost
+compass-e = Ost
+compass-ese = Ostsüdost
+compass-se = Südost
+compass-sse = Südsüdost
+compass-s = Süd
+compass-ssw = Südsüdwest
+compass-sw = Südwest
+compass-wsw = Westsüdwest
+compass-w = West
+compass-wnw = Westnordwest
+compass-nw = Nordwest
+compass-nnw = Nordnordwest
+
+# Allergy risk
+allergy-risk = Allergierisiko: { $level } ({ $score }/10)
+allergy-low = gering
+allergy-moderate = mäßig
+allergy-high = hoch
+allergy-very-high = sehr hoch
+allergy-pollen = { $group } { $level } ({ $grains })
+pollen-grains = { $value } Pollen/m³
+pollen-tree = Baumpollen
+pollen-grass = Gräserpollen
+pollen-weed = Kräuterpollen
+allergy-no-pollen = keine Pollendaten für diesen Ort
+allergy-wind = Wind mit { $speed } verteilt Pollen
+allergy-dry = trockene Luft ({ $humidity }) hält Pollen in der Luft
+allergy-humid = feuchte Luft ({ $humidity }) begünstigt Schimmel und Hausstaubmilben
+allergy-rain = Regen wäscht Pollen aus der Luft
+
+# Heat warnings
+heat-caution = Hitzehinweis
+heat-danger = Hitzewarnung
+heat-warning = { $warning }: WBGT { $wbgt }, Feuchttemperatur { $wet_bulb }. { $advice }
+heat-caution-advice = Pausen im Schatten einlegen und regelmäßig trinken.
+heat-danger-advice = Anstrengende Tätigkeiten im Freien einstellen.
+
+# Humidity comfort
+humidity-comfort = { $comfort }, Taupunkt { $dew_point }
+comfort-dry = trocken
+comfort-comfortable = angenehm
+comfort-muggy = schwül
+comfort-oppressive = drückend
+
+# Wind rose command
+windrose-title = Wind in { $location } über { $days } Tage, { $count } Messungen
+windrose-calm = Windstill: { $percent } %
+windrose-prevailing = Vorherrschender Wind: aus { $direction }
+
+# Fog advisory
+fog-advisory = Nebelhinweis
+visibility-advisory = Hinweis auf schlechte Sicht
+advisory-visibility = { $advisory }: Sichtweite { $visibility }
+
+# Satellite command
+satellite-saved = Bild von { $satellite } in { $path } gespeichert
+
+# Radar command
+radar-saved = Radarbild von { $time } in { $path } gespeichert
+
+# Pressure command
+pressure-now = Luftdruck in { $location }: { $pressure } hPa
+pressure-rising = steigend
+pressure-steady = gleichbleibend
+pressure-falling = fallend
+pressure-no-history = Noch keine Messung von vor 1 bis 6 Stunden, später erneut ausführen für die Tendenz.
+pressure-drop-warning = Rascher Druckabfall
+pressure-drop = Der Luftdruck in { $location } ist in 3 Stunden um { $change } hPa gefallen.
+
+# Report command
+report-title = Wetterbericht { $start } .. { $end }
+report-empty = In diesem Zeitraum wurde nichts aufgezeichnet.
+report-readings = Messungen
+report-mean = Mittlere Temperatur
+report-highest = Höchstwert
+report-lowest = Tiefstwert
+report-wind = Stärkster Wind
+report-humidity = Mittlere Luftfeuchtigkeit
+report-pressure = Luftdruck
+report-precipitation = Niederschlag
+report-chart = Tagesmitteltemperatur
+report-notable = Auffällig
+report-saved = Bericht nach { $path } geschrieben
+
+# Store command
+store-pruned = { $count } alte Einträge aus { $path } gelöscht
+
+# Backfill command
+backfill-done = { $count } Tage für { $location } in { $path } gespeichert
+
+# Climate command
+climate-title = Projiziertes Klima für { $location } in den { $decade }er-Jahren:
+climate-mean = Mittel
+climate-precipitation = Niederschl.
+
+# Ensemble command
+ensemble-title = Ensemblevorhersage für { $location }, Median und Spanne von 80 % der Läufe:
+
+# Laundry command
+laundry-best = Beste Zeit zum Wäschetrocknen in { $location }: { $start }–{ $end } ({ $rating }, Wertung { $score })
+laundry-no-window = Kein Zeitfenster zum Trocknen bei Tag in { $location } in der Vorhersage
+laundry-poor = schlecht
+laundry-fair = mäßig
+laundry-good = gut
+
+# Frost command
+frost-warning = Frostwarnung
+frost-night = Frost in { $location }: bis { $temp } in der Nacht zum { $date }
+frost-none = Kein Frost in { $location } in den nächsten { $days } Nächten erwartet
+
+# Moon command
+moon-new = Neumond
+moon-waxing-crescent = Zunehmende Sichel
+moon-first-quarter = Erstes Viertel
+moon-waxing-gibbous = Zunehmender Mond
+moon-full = Vollmond
+moon-waning-gibbous = Abnehmender Mond
+moon-last-quarter = Letztes Viertel
+moon-waning-crescent = Abnehmende Sichel
+moon-now = Jetzt: { $phase }, { $illumination }% beleuchtet
+
+# Sun command
+sun-at = Sonne in { $location }
+label-sunrise = Sonnenaufgang
+label-solar-noon = Sonnenhöchststand
+label-sunset = Sonnenuntergang
+label-day-length = Tageslänge
+day-length = { $length } ({ $yesterday } seit gestern, { $week } seit letzter Woche)
+label-golden-hour = Goldene Stunde
+label-blue-hour = Blaue Stunde
+label-sun-position = Jetzt
+sun-position = { $elevation } Höhe, { $azimuth } Azimut
+
+# Sun calendar
+ical-sunrise = Sonnenaufgang{ $offset } in { $location }
+ical-sunset = Sonnenuntergang{ $offset } in { $location }
+ical-saved = Sonnenzeiten für { $days } Tage in { $path } gespeichert
+
+# Anomalies
+anomaly-warm = Ungewöhnlich warm für die Jahreszeit
+anomaly-cold = Ungewöhnlich kalt für die Jahreszeit
+anomaly-windy = Ungewöhnlich windig für die Jahreszeit
+anomaly-humid = Ungewöhnlich feucht für die Jahreszeit
+anomaly-dry = Ungewöhnlich trocken für die Jahreszeit
+anomaly-high-pressure = Ungewöhnlich hoher Luftdruck für die Jahreszeit
+anomaly-low-pressure = Ungewöhnlich niedriger Luftdruck für die Jahreszeit
+
+# Trend command
+trend-summary = { $location }: { $temp }, { $rain }.
+trend-warming = { $change } wärmer in den nächsten { $days } Tagen
+trend-cooling = { $change } kühler in den nächsten { $days } Tagen
+trend-steady = gleichbleibend um { $temp } in den nächsten { $days } Tagen
+trend-unknown = keine Temperaturvorhersage
+trend-dry = trocken
+trend-rain-arriving = Regen ab { $when }
+trend-rain-clearing = Regen endet { $when }
+trend-rain-lasting = durchgehend Regen
+trend-when = { $day } { $part }
+day-today = heute
+day-tomorrow = morgen
+day-monday = Montag
+day-tuesday = Dienstag
+day-wednesday = Mittwoch
+day-thursday = Donnerstag
+day-friday = Freitag
+day-saturday = Samstag
+day-sunday = Sonntag
+part-morning = Morgen
+part-afternoon = Nachmittag
+part-evening = Abend
+part-night = Nacht
+
+# Diff command
+diff-title = { $location }: { $date } gegenüber { $against }
+diff-values = { $value } gegenüber { $against }
+
+# Providers status
+provider-ok = OK
+api-current = aktuell
+api-deprecated = veraltet
+provider-error = Fehler
+
+# Dry run
+label-cache = Cache
+label-request = Anfrage
+plan-cache-hit = Treffer, der Anbieter würde nicht gefragt
+plan-cache-miss = kein Treffer
+plan-cache-disabled = deaktiviert
+plan-offline = keine, der Anbieter arbeitet offline
+label-order = Reihenfolge
+plan-order-race = { $providers } gleichzeitig, die erste Antwort gilt
+plan-order-fill = { $order }, dann { $fill } für die fehlenden Felder
+
+# Alerts command
+alerts-none = Keine Warnungen für { $location }.
+alerts-until = bis { $time }
+feed-day = Vorhersage für { $date }: { $min } bis { $max }
+feed-day-precipitation = { $mm } mm Niederschlag
+severity-advisory = Hinweis
+severity-watch = Vorwarnung
+severity-warning = Warnung
+severity-emergency = Unwetter
+
+# Batch command
+batch-summary = { $total } Orte, { $failures } fehlgeschlagen
+batch-hottest = Am wärmsten: { $location }, { $value }
+batch-coldest = Am kältesten: { $location }, { $value }
+batch-rainiest = Am nassesten: { $location }, { $value }
+
+# Provider stats
+stats-empty = Noch keine Anbieteraufrufe erfasst.
+stats-provider = Anbieter
+stats-outcome = Ergebnis
+stats-calls = Aufrufe
+stats-mean = Mittel
+# Config migrate
+config-migrated = { $path } von Einstellungsversion { $from } auf { $to } migriert, die alte Datei bleibt als Sicherung erhalten.
+config-up-to-date = { $path } ist bereits aktuell.
+config-encrypted = Einstellungen nach { $path } verschlüsselt, die Klartextdatei wurde entfernt.
+config-decrypted = Einstellungen nach { $path } entschlüsselt.
@@ -0,0 +1,356 @@
+# Weather output
+weather-in = Weather in { $location }: { $condition }
+label-location = Location
+label-condition = Condition
+label-date = Date
+label-temperature = Temperature
+label-humidity = Humidity
+label-pressure = Pressure
+label-wind = Wind
+wind-value = { $speed } at { $degree }
+label-provider = Provider
+filled-from = Filled in from { $provider }: { $fields }
+field-feels-like-c = feels-like temperature
+field-uv-index = UV index
+field-precipitation-probability = chance of precipitation
+field-visibility-km = visibility
+
+# Beaufort scale
+beaufort-calm = Calm
+beaufort-light-air = Light air
+beaufort-light-breeze = Light breeze
+beaufort-gentle-breeze = Gentle breeze
+beaufort-moderate-breeze = Moderate breeze
+beaufort-fresh-breeze = Fresh breeze
+beaufort-strong-breeze = Strong breeze
+beaufort-near-gale = Near gale
+beaufort-gale = Gale
+beaufort-strong-gale = Strong gale
+beaufort-storm = Storm
+beaufort-violent-storm = Violent storm
+beaufort-hurricane = Hurricane force
+
+# Configure command
+default-provider-saved = Default provider saved to { $path }
+provider-not-supported = Provider `{ $provider }` not supported
+did-you-mean = Did you mean `{ $name }`?
+default-provider = Default provider: { $provider }
+available-providers = Available providers: { $providers }
+
+# Check command
+check-temp-above = Temperature { $temp }°C is above { $limit }°C
+check-temp-below = Temperature { $temp }°C is below { $limit }°C
+check-wind-above = Wind { $speed } is above { $limit }
+check-rain = Rain: { $condition }
+check-snow = Snow: { $condition }
+
+# Snow command
+snow-in = Snow in { $location }
+label-snow-depth = Snow depth
+label-fresh-snow = Fresh snow
+label-freezing-level = Freezing level
+not-available = n/a
+
+# Surf command
+surf-at = Surf at { $location }: { $rating }
+surf-flat = flat
+surf-poor = poor
+surf-fair = fair
+surf-good = good
+surf-epic = epic
+label-waves = Waves
+waves-value = { $height } m every { $period } s
+label-swell = Swell
+swell-value = from { $degree }
+
+# Degree days
+gdd-total = Growing degree days in { $location } from { $start } to { $end } (base { $base }°C): { $total }
+days-missing = { $count } days without records were skipped
+degree-days-heating = HDD
+degree-days-cooling = CDD
+degree-days-total = Total
+
+# Fire command
+fire-warning = Fire danger: { $danger }
+fire-index = Fire index in { $location }: { $index } ({ $danger })
+fire-inputs = > { $temp }, { $humidity }% humidity, wind { $wind }, { $rain } mm rain in { $days } days
+fire-low = low to moderate
+fire-high = high
+fire-very-high = very high
+fire-severe = severe
+fire-extreme = extreme
+fire-catastrophic = catastrophic
+
+# Activity command
+activity-best = Best time to { $activity } in { $location }: { $time } (score { $score })
+activity-run = run
+activity-bike = bike
+activity-hike = hike
+precipitation-chance = { $probability }% rain
+
+# Clothing advice
+label-wear = What to wear
+wear-winter-coat = winter coat, hat and gloves
+wear-warm-jacket = warm jacket
+wear-layers = light layers
+wear-light = t-shirt
+wear-rain-jacket = rain jacket
+wear-windbreaker = windbreaker
+wear-sunscreen = sunscreen
+
+# Umbrella command
+umbrella-yes = Yes, take an umbrella: rain expected at { $time } ({ $probability })
+umbrella-snow = Yes, take an umbrella: snow expected at { $time } ({ $probability }, { $snowfall } cm)
+umbrella-no = No umbrella needed in the next { $hours } hours (at most { $probability })
+
+# Record fixture command
+fixture-saved = Fixture saved to { $path }
+
+# Self-update command
+up-to-date = weather { $version } is the latest release
+update-available = weather { $version } is available, { $current } is installed
+updated = Updated weather from { $current } to { $version }
+
+# Command line help: the doc comments in src/commands.rs are the English text, other locales
+# translate them as help-weather[-<subcommand>...][--<option>], see src/help.rs.
+
+# Accessible output
+label-pressure-tendency = Pressure tendency
+spoken-celsius = { $value } degrees Celsius
+spoken-percent = { $value } percent
+spoken-hpa = { $value } hectopascals
+spoken-pressure-change = { $tendency } by { $change } hectopascals in 3 hours
+spoken-kmh = { $value } kilometers per hour
+spoken-ms = { $value } meters per second
+spoken-knots = { $value } knots
+spoken-mph = { $value } miles per hour
+spoken-beaufort = Beaufort force { $force }, { $term }
+spoken-wind = { $speed } from the { $direction }
+spoken-cm = { $value } centimeters
+spoken-m = { $value } meters
+spoken-km = { $value } kilometers
+spoken-grains = { $value } grains per cubic meter
+compass-n = north
+compass-nne = north-northeast
+compass-ne = northeast
+compass-ene = east-northeast
+compass-e = east
+compass-ese = east-southeast
+compass-se = southeast
+compass-sse = south-southeast
+compass-s = south
+compass-ssw = south-southwest
+compass-sw = southwest
+compass-wsw = west-southwest
+compass-w = west
+compass-wnw = west-northwest
+compass-nw = northwest
+compass-nnw = north-northwest
+
+# Allergy risk
+allergy-risk = Allergy risk: { $level } ({ $score }/10)
+allergy-low = low
+allergy-moderate = moderate
+allergy-high = high
+allergy-very-high = very high
+allergy-pollen = { $group } { $level } ({ $grains })
+pollen-grains = { $value } grains/m³
+pollen-tree = tree pollen
+pollen-grass = grass pollen
+pollen-weed = weed pollen
+allergy-no-pollen = no pollen data for this location
+allergy-wind = wind of { $speed } spreads pollen
+allergy-dry = dry air ({ $humidity }) keeps pollen airborne
+allergy-humid = humid air ({ $humidity }) favours mould and dust mites
+allergy-rain = rain washes pollen out of the air
+
+# Heat warnings
+heat-caution = Heat caution
+heat-danger = Heat danger
+heat-warning = { $warning }: WBGT { $wbgt }, wet-bulb { $wet_bulb }. { $advice }
+heat-caution-advice = Take breaks in the shade and drink regularly.
+heat-danger-advice = Stop strenuous activity outdoors.
+
+# Humidity comfort
+humidity-comfort = { $comfort }, dew point { $dew_point }
+comfort-dry = dry
+comfort-comfortable = comfortable
+comfort-muggy = muggy
+comfort-oppressive = oppressive
+
+# Wind rose command
+windrose-title = Wind at { $location } over { $days } days, { $count } readings
+windrose-calm = Calm: { $percent }%
+windrose-prevailing = Prevailing wind: from { $direction }
+
+# Fog advisory
+fog-advisory = Fog advisory
+visibility-advisory = Low visibility advisory
+advisory-visibility = { $advisory }: visibility { $visibility }
+
+# Satellite command
+satellite-saved = { $satellite } image saved to { $path }
+
+# Radar command
+radar-saved = Radar image of { $time } saved to { $path }
+
+# Pressure command
+pressure-now = Pressure in { $location }: { $pressure } hPa
+pressure-rising = rising
+pressure-steady = steady
+pressure-falling = falling
+pressure-no-history = No reading from 1 to 6 hours ago yet, run again later for the tendency.
+pressure-drop-warning = Rapid pressure drop
+pressure-drop = Pressure in { $location } fell by { $change } hPa in 3 hours.
+
+# Report command
+report-title = Weather report { $start } .. { $end }
+report-empty = Nothing recorded in this period.
+report-readings = Readings
+report-mean = Mean temperature
+report-highest = Highest
+report-lowest = Lowest
+report-wind = Strongest wind
+report-humidity = Mean humidity
+report-pressure = Pressure
+report-precipitation = Precipitation
+report-chart = Daily mean temperature
+report-notable = Notable
+report-saved = Report written to { $path }
+
+# Store command
+store-pruned = Deleted { $count } old rows from { $path }
+
+# Backfill command
+backfill-done = Stored { $count } days of { $location } in { $path }
+
+# Climate command
+climate-title = Projected climate for { $location } in the { $decade }s:
+climate-mean = Mean
+climate-precipitation = Precip.
+
+# Ensemble command
+ensemble-title = Ensemble forecast for { $location }, median and the range of 80% of the runs:
+
+# Laundry command
+laundry-best = Best time to dry laundry in { $location }: { $start }–{ $end } ({ $rating }, score { $score })
+laundry-no-window = No daytime drying window in { $location } within the forecast
+laundry-poor = poor
+laundry-fair = fair
+laundry-good = good
+
+# Frost command
+frost-warning = Frost warning
+frost-night = Frost in { $location }: down to { $temp } in the night to { $date }
+frost-none = No frost expected in { $location } over the next { $days } nights
+
+# Moon command
+moon-new = New moon
+moon-waxing-crescent = Waxing crescent
+moon-first-quarter = First quarter
+moon-waxing-gibbous = Waxing gibbous
+moon-full = Full moon
+moon-waning-gibbous = Waning gibbous
+moon-last-quarter = Last quarter
+moon-waning-crescent = Waning crescent
+moon-now = Now: { $phase }, { $illumination }% illuminated
+
+# Sun command
+sun-at = Sun in { $location }
+label-sunrise = Sunrise
+label-solar-noon = Solar noon
+label-sunset = Sunset
+label-day-length = Day length
+day-length = { $length } ({ $yesterday } since yesterday, { $week } since last week)
+label-golden-hour = Golden hour
+label-blue-hour = Blue hour
+label-sun-position = Now
+sun-position = { $elevation } elevation, { $azimuth } azimuth
+
+# Sun calendar
+ical-sunrise = Sunrise{ $offset } in { $location }
+ical-sunset = Sunset{ $offset } in { $location }
+ical-saved = Sun times of { $days } days saved to { $path }
+
+# Anomalies
+anomaly-warm = Unusually warm for the time of year
+anomaly-cold = Unusually cold for the time of year
+anomaly-windy = Unusually windy for the time of year
+anomaly-humid = Unusually humid for the time of year
+anomaly-dry = Unusually dry for the time of year
+anomaly-high-pressure = Unusually high pressure for the time of year
+anomaly-low-pressure = Unusually low pressure for the time of year
+
+# Trend command
+trend-summary = { $location }: { $temp }, { $rain }.
+trend-warming = warming { $change } over the next { $days } days
+trend-cooling = cooling { $change } over the next { $days } days
+trend-steady = steady around { $temp } over the next { $days } days
+trend-unknown = no temperature forecast
+trend-dry = staying dry
+trend-rain-arriving = rain arriving { $when }
+trend-rain-clearing = rain clearing { $when }
+trend-rain-lasting = rain throughout
+trend-when = { $day } { $part }
+day-today = today
+day-tomorrow = tomorrow
+day-monday = Monday
+day-tuesday = Tuesday
+day-wednesday = Wednesday
+day-thursday = Thursday
+day-friday = Friday
+day-saturday = Saturday
+day-sunday = Sunday
+part-morning = morning
+part-afternoon = afternoon
+part-evening = evening
+part-night = night
+
+# Diff command
+diff-title = { $location }: { $date } vs { $against }
+diff-values = { $value } vs { $against }
+
+# Providers status
+provider-ok = OK
+api-current = current
+api-deprecated = deprecated
+provider-error = error
+
+# Dry run
+label-cache = Cache
+label-request = Request
+plan-cache-hit = hit, the provider would not be asked
+plan-cache-miss = miss
+plan-cache-disabled = disabled
+plan-offline = none, the provider works offline
+label-order = Order
+plan-order-race = { $providers } at once, the first answer wins
+plan-order-fill = { $order }, then { $fill } for the fields it leaves out
+
+# Alerts command
+alerts-none = No alerts in effect for { $location }.
+alerts-until = until { $time }
+feed-day = Forecast for { $date }: { $min } to { $max }
+feed-day-precipitation = { $mm } mm precipitation
+severity-advisory = Advisory
+severity-watch = Watch
+severity-warning = Warning
+severity-emergency = Emergency
+
+# Batch command
+batch-summary = { $total } locations, { $failures } failed
+batch-hottest = Hottest: { $location }, { $value }
+batch-coldest = Coldest: { $location }, { $value }
+batch-rainiest = Rainiest: { $location }, { $value }
+
+# Provider stats
+stats-empty = No provider calls recorded yet.
+stats-provider = Provider
+stats-outcome = Outcome
+stats-calls = Calls
+stats-mean = Mean
+# Config migrate
+config-migrated = Migrated { $path } from settings version { $from } to { $to }, the old file is kept as a backup.
+config-up-to-date = { $path } is already up to date.
+config-encrypted = Settings encrypted to { $path }, the plaintext file was removed.
+config-decrypted = Settings decrypted to { $path }.
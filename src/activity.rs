@@ -0,0 +1,234 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::HourlyData;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Outdoor activities hours can be scored for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Activity {
+    Run,
+    Bike,
+    Hike,
+}
+
+impl Activity {
+    /// Built-in preferences, overridden by `[activities.<name>]`.
+    fn defaults(self) -> Preferences {
+        match self {
+            Self::Run => Preferences {
+                min_temp: 5.0,
+                max_temp: 18.0,
+                max_wind: 25.0,
+                max_uv: 5.0,
+                max_precipitation_probability: 30.0,
+            },
+            Self::Bike => Preferences {
+                min_temp: 12.0,
+                max_temp: 26.0,
+                max_wind: 20.0,
+                max_uv: 6.0,
+                max_precipitation_probability: 20.0,
+            },
+            Self::Hike => Preferences {
+                min_temp: 8.0,
+                max_temp: 22.0,
+                max_wind: 30.0,
+                max_uv: 6.0,
+                max_precipitation_probability: 30.0,
+            },
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Run => "activity-run",
+            Self::Bike => "activity-bike",
+            Self::Hike => "activity-hike",
+        }
+    }
+
+    /// Preferences from `settings` on top of the built-in ones.
+    pub fn preferences(self, settings: &HashMap<Activity, ActivitySettings>) -> Preferences {
+        let mut preferences = self.defaults();
+        if let Some(custom) = settings.get(&self) {
+            let fields = [
+                (&mut preferences.min_temp, custom.min_temp),
+                (&mut preferences.max_temp, custom.max_temp),
+                (&mut preferences.max_wind, custom.max_wind),
+                (&mut preferences.max_uv, custom.max_uv),
+                (
+                    &mut preferences.max_precipitation_probability,
+                    custom.max_precipitation_probability,
+                ),
+            ];
+            for (field, value) in fields {
+                if let Some(value) = value {
+                    *field = value;
+                }
+            }
+        }
+        preferences
+    }
+}
+
+/// Personal preferences as written in an `[activities.<name>]` section, every value is optional.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ActivitySettings {
+    /// Lowest comfortable feels-like temperature, °C.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_temp: Option<f64>,
+    /// Highest comfortable feels-like temperature, °C.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_temp: Option<f64>,
+    /// km/h
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_wind: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uv: Option<f64>,
+    /// Percent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_precipitation_probability: Option<f64>,
+}
+
+/// Resolved limits used while scoring.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preferences {
+    pub min_temp: f64,
+    pub max_temp: f64,
+    pub max_wind: f64,
+    pub max_uv: f64,
+    pub max_precipitation_probability: f64,
+}
+
+/// Comfort score of an hour from 0 (stay in) to 100 (perfect).
+///
+/// Every limit the hour exceeds costs points in proportion to how far it is exceeded.
+pub fn score(hour: &HourlyData, preferences: &Preferences) -> u8 {
+    let mut penalty = 0.0;
+
+    if let Some(temp) = hour.feels_like_c.or(hour.temp_c) {
+        let off = (preferences.min_temp - temp).max(temp - preferences.max_temp);
+        penalty += (off.max(0.0) * 5.0).min(50.0);
+    }
+    if let Some(wind) = hour.wind_kph {
+        penalty += ((wind - preferences.max_wind).max(0.0) * 3.0).min(40.0);
+    }
+    if let Some(probability) = hour.precipitation_probability {
+        penalty += (probability - preferences.max_precipitation_probability).max(0.0);
+    }
+    if let Some(mm) = hour.precipitation_mm {
+        penalty += (mm * 10.0).min(40.0);
+    }
+    if let Some(uv) = hour.uv_index {
+        penalty += ((uv - preferences.max_uv).max(0.0) * 10.0).min(30.0);
+    }
+
+    (100.0 - penalty).clamp(0.0, 100.0).round() as u8
+}
+
+/// Scored hours with a bar per hour and the best one called out.
+pub fn render_activity(
+    activity: Activity,
+    location: &str,
+    hours: &[HourlyData],
+    preferences: &Preferences,
+    options: &OutputOptions,
+) -> String {
+    let scores: Vec<u8> = hours.iter().map(|hour| score(hour, preferences)).collect();
+    let Some((best, _)) = scores
+        .iter()
+        .enumerate()
+        .max_by_key(|(i, s)| (**s, -(*i as i64)))
+    else {
+        return tr("not-available");
+    };
+    let (full, empty) = if options.ascii {
+        ('#', '.')
+    } else {
+        ('█', '░')
+    };
+
+    let mut lines = vec![tr_args(
+        "activity-best",
+        &[
+            ("activity", tr(activity.key())),
+            ("location", location.to_string()),
+            (
+                "time",
+                hours[best].time.format(&options.time_format).to_string(),
+            ),
+            ("score", scores[best].to_string()),
+        ],
+    )];
+    for (hour, score) in hours.iter().zip(&scores) {
+        let filled = usize::from(*score).div_ceil(10);
+        let bar: String = std::iter::repeat_n(full, filled)
+            .chain(std::iter::repeat_n(empty, 10 - filled))
+            .collect();
+        let mut details = Vec::new();
+        if let Some(temp) = hour.feels_like_c.or(hour.temp_c) {
+            details.push(options.temperature(temp));
+        }
+        if let Some(wind) = hour.wind_kph {
+            details.push(options.wind_speed(wind));
+        }
+        if let Some(probability) = hour.precipitation_probability {
+            details.push(tr_args(
+                "precipitation-chance",
+                &[("probability", format!("{probability:.0}"))],
+            ));
+        }
+        if let Some(uv) = hour.uv_index {
+            details.push(format!("UV {uv:.0}"));
+        }
+        lines.push(format!(
+            "{} {bar} {score:>3}  {}",
+            hour.time.format(&options.time_format),
+            details.join(", ")
+        ));
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hour(temp: f64, wind: f64, probability: f64, uv: f64) -> HourlyData {
+        HourlyData {
+            feels_like_c: Some(temp),
+            wind_kph: Some(wind),
+            precipitation_probability: Some(probability),
+            precipitation_mm: Some(0.0),
+            uv_index: Some(uv),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scores_penalize_exceeded_limits() {
+        let run = Activity::Run.preferences(&HashMap::new());
+
+        assert_eq!(score(&hour(12.0, 10.0, 0.0, 2.0), &run), 100);
+        assert_eq!(score(&hour(22.0, 10.0, 0.0, 2.0), &run), 80);
+        assert_eq!(score(&hour(12.0, 10.0, 60.0, 7.0), &run), 50);
+    }
+
+    #[test]
+    fn settings_override_defaults() {
+        let settings = HashMap::from([(
+            Activity::Run,
+            ActivitySettings {
+                max_temp: Some(25.0),
+                ..Default::default()
+            },
+        )]);
+
+        let run = Activity::Run.preferences(&settings);
+        assert_eq!(run.max_temp, 25.0);
+        assert_eq!(run.min_temp, 5.0);
+        assert_eq!(score(&hour(22.0, 10.0, 0.0, 2.0), &run), 100);
+    }
+}
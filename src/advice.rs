@@ -0,0 +1,185 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::{HourlyData, WeatherData};
+
+/// Chance of precipitation from which a rain jacket is advised.
+const RAIN_PROBABILITY: f64 = 40.0;
+/// Wind speed in km/h from which a windproof layer is advised.
+const WINDY_KPH: f64 = 30.0;
+/// UV index from which sunscreen is advised.
+const SUNSCREEN_UV: f64 = 3.0;
+
+/// What to wear, as i18n keys, from feels-like temperature, rain, wind and UV.
+///
+/// Without a feels-like temperature the measured one is used; without a chance of
+/// precipitation the current condition decides about rain.
+pub fn clothing(data: &WeatherData) -> Vec<&'static str> {
+    let feels_like = data.feels_like_c.unwrap_or(data.temp_c);
+    let mut items = vec![match feels_like {
+        t if t < 0.0 => "wear-winter-coat",
+        t if t < 10.0 => "wear-warm-jacket",
+        t if t < 18.0 => "wear-layers",
+        _ => "wear-light",
+    }];
+
+    let rain = match data.precipitation_probability {
+        Some(probability) => probability >= RAIN_PROBABILITY,
+        None => data.kind.is_rain(),
+    };
+    if rain {
+        items.push("wear-rain-jacket");
+    } else if data.wind_kph >= WINDY_KPH && feels_like >= 0.0 {
+        items.push("wear-windbreaker");
+    }
+    if data.uv_index.is_some_and(|uv| uv >= SUNSCREEN_UV) {
+        items.push("wear-sunscreen");
+    }
+    items
+}
+
+/// The "what to wear" line shown with `--advice`.
+pub fn render_clothing(data: &WeatherData) -> String {
+    let items: Vec<String> = clothing(data).into_iter().map(tr).collect();
+    format!("{}: {}", tr("label-wear"), items.join(", "))
+}
+
+/// Precipitation in mm from which an hour without a chance of precipitation counts as rainy.
+const RAIN_MM: f64 = 0.2;
+
+/// The first hour whose chance of precipitation reaches `threshold` percent.
+///
+/// Hours without a chance of precipitation count when rain is expected in them.
+pub fn umbrella(hours: &[HourlyData], threshold: f64) -> Option<&HourlyData> {
+    hours
+        .iter()
+        .find(|hour| match hour.precipitation_probability {
+            Some(probability) => probability >= threshold,
+            None => hour.kind.is_rain() || hour.precipitation_mm.is_some_and(|mm| mm >= RAIN_MM),
+        })
+}
+
+/// Yes/no answer of `weather umbrella`.
+pub fn render_umbrella(
+    wet: Option<&HourlyData>,
+    hours: &[HourlyData],
+    options: &OutputOptions,
+) -> String {
+    let text = match wet {
+        Some(hour) => {
+            let snowfall = hour.snowfall_cm.filter(|cm| *cm > 0.0);
+            let mut args = vec![
+                ("time", hour.time.format(&options.time_format).to_string()),
+                (
+                    "probability",
+                    hour.precipitation_probability
+                        .map_or_else(|| tr("not-available"), |p| format!("{p:.0}%")),
+                ),
+            ];
+            match snowfall {
+                Some(cm) => {
+                    args.push(("snowfall", format!("{cm:.1}")));
+                    tr_args("umbrella-snow", &args)
+                },
+                None => tr_args("umbrella-yes", &args),
+            }
+        },
+        None => {
+            let highest = hours
+                .iter()
+                .filter_map(|hour| hour.precipitation_probability)
+                .fold(0.0, f64::max);
+            tr_args(
+                "umbrella-no",
+                &[
+                    ("hours", hours.len().to_string()),
+                    ("probability", format!("{highest:.0}%")),
+                ],
+            )
+        },
+    };
+    options.sanitize(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::condition::ConditionKind;
+
+    #[test]
+    fn cold_and_wet() {
+        let data = WeatherData {
+            temp_c: 4.0,
+            feels_like_c: Some(-2.0),
+            precipitation_probability: Some(70.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            clothing(&data),
+            vec!["wear-winter-coat", "wear-rain-jacket"]
+        );
+    }
+
+    #[test]
+    fn warm_windy_and_sunny() {
+        let data = WeatherData {
+            temp_c: 24.0,
+            wind_kph: 35.0,
+            uv_index: Some(7.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            clothing(&data),
+            vec!["wear-light", "wear-windbreaker", "wear-sunscreen"]
+        );
+    }
+
+    #[test]
+    fn condition_without_probability() {
+        let data = WeatherData {
+            temp_c: 14.0,
+            kind: ConditionKind::Rain,
+            ..Default::default()
+        };
+        assert_eq!(clothing(&data), vec!["wear-layers", "wear-rain-jacket"]);
+    }
+
+    #[test]
+    fn first_wet_hour() {
+        let hour = |probability: Option<f64>, kind| HourlyData {
+            precipitation_probability: probability,
+            kind,
+            ..Default::default()
+        };
+        let dry = [
+            hour(Some(10.0), ConditionKind::Clear),
+            hour(Some(30.0), ConditionKind::Cloudy),
+        ];
+        assert!(umbrella(&dry, 50.0).is_none());
+
+        let wet = [
+            hour(Some(10.0), ConditionKind::Clear),
+            hour(None, ConditionKind::Rain),
+            hour(Some(80.0), ConditionKind::Rain),
+        ];
+        assert_eq!(umbrella(&wet, 50.0), Some(&wet[1]));
+    }
+
+    #[test]
+    fn snow_told_apart_from_rain() {
+        let snowy = HourlyData {
+            precipitation_probability: Some(90.0),
+            snowfall_cm: Some(2.5),
+            kind: ConditionKind::Snow,
+            ..Default::default()
+        };
+        let text = render_umbrella(
+            Some(&snowy),
+            std::slice::from_ref(&snowy),
+            &OutputOptions::default(),
+        );
+        assert!(
+            text.contains("snow expected at 00:00 (90%, 2.5 cm)"),
+            "{text}"
+        );
+    }
+}
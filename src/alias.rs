@@ -0,0 +1,136 @@
+//! `[aliases]` from the settings, expanded before the command line is parsed, so that
+//! `weather home` runs e.g. `weather get Porto,PT --oneline`.
+
+use crate::commands::Cli;
+use clap::CommandFactory;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// Whether the option named by `flag`, e.g. "--config-path" or "-c", takes a value.
+fn takes_value(command: &clap::Command, flag: &str) -> bool {
+    command.get_arguments().any(|arg| {
+        let named = match flag.strip_prefix("--") {
+            Some(long) => arg.get_long() == Some(long),
+            None => flag
+                .strip_prefix('-')
+                .and_then(|short| short.chars().last())
+                .is_some_and(|short| arg.get_short() == Some(short)),
+        };
+        named && arg.get_action().takes_values()
+    })
+}
+
+/// Position of the subcommand in `args`, after the program name and any options before it.
+fn subcommand_index(command: &clap::Command, args: &[OsString]) -> Option<usize> {
+    let mut i = 1;
+    while let Some(arg) = args.get(i) {
+        let arg = arg.to_str()?;
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            return Some(i);
+        }
+        // "--config-path=x" and "-cx" carry their value.
+        let inline = arg.contains('=') || (!arg.starts_with("--") && arg.len() > 2);
+        i += if !inline && takes_value(command, arg) {
+            2
+        } else {
+            1
+        };
+    }
+    None
+}
+
+/// Words of `line`, split at whitespace outside of single or double quotes.
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_default().push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_default();
+            },
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_default().push(c),
+        }
+    }
+    words.extend(word);
+    words
+}
+
+/// Settings file given with `--config-path` before the subcommand, if any.
+pub fn config_path(args: &[OsString]) -> Option<PathBuf> {
+    let command = Cli::command();
+    let end = subcommand_index(&command, args).unwrap_or(args.len());
+    let args = args.get(1..end)?;
+    args.iter().enumerate().find_map(|(i, arg)| {
+        let arg = arg.to_str()?;
+        match arg {
+            "--config-path" | "-c" => args.get(i + 1).map(PathBuf::from),
+            _ => arg
+                .strip_prefix("--config-path=")
+                .or_else(|| arg.strip_prefix("-c"))
+                .map(PathBuf::from),
+        }
+    })
+}
+
+/// `args` with an alias in place of the subcommand replaced by its words. Built-in subcommands
+/// take precedence, and aliases are not expanded within aliases.
+pub fn expand(args: Vec<OsString>, aliases: &HashMap<String, String>) -> Vec<OsString> {
+    let command = Cli::command();
+    let Some(index) = subcommand_index(&command, &args) else {
+        return args;
+    };
+    let Some(words) = args[index]
+        .to_str()
+        .filter(|name| command.find_subcommand(name).is_none())
+        .and_then(|name| aliases.get(name))
+        .map(|line| split_words(line))
+    else {
+        return args;
+    };
+    let mut expanded = args;
+    expanded.splice(index..=index, words.into_iter().map(OsString::from));
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<OsString> {
+        line.split(' ').map(OsString::from).collect()
+    }
+
+    #[test]
+    fn alias_replaces_subcommand() {
+        let aliases = HashMap::from([
+            ("home".to_string(), "get 'Porto, PT' --oneline".to_string()),
+            ("get".to_string(), "get Kyiv".to_string()),
+        ]);
+
+        assert_eq!(
+            expand(args("weather -c other.toml -q home --no-emoji"), &aliases),
+            args("weather -c other.toml -q get")
+                .into_iter()
+                .chain(["Porto, PT", "--oneline", "--no-emoji"].map(OsString::from))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            expand(args("weather get home"), &aliases),
+            args("weather get home")
+        );
+        assert_eq!(
+            config_path(&args("weather --config-path=a.toml home")),
+            Some(PathBuf::from("a.toml"))
+        );
+        assert_eq!(config_path(&args("weather home -c b.toml")), None);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::{PollenData, WeatherData};
+
+/// Grains/m³ from which a pollen group counts as moderate and as high, roughly the bands of the
+/// European Aeroallergen Network; tree pollen is shed in far greater numbers.
+const TREE_POLLEN: (f64, f64) = (15.0, 90.0);
+const GRASS_POLLEN: (f64, f64) = (20.0, 50.0);
+const WEED_POLLEN: (f64, f64) = (10.0, 50.0);
+/// Wind speed in km/h from which pollen is carried far.
+const WINDY_KPH: f64 = 20.0;
+/// Relative humidity below which pollen stays airborne longer.
+const DRY_HUMIDITY: f64 = 40.0;
+/// Relative humidity from which mould and dust mites thrive.
+const HUMID_HUMIDITY: f64 = 80.0;
+
+/// Overall allergy risk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AllergyLevel {
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+}
+
+impl AllergyLevel {
+    fn of(score: u8) -> Self {
+        match score {
+            0..=2 => Self::Low,
+            3..=5 => Self::Moderate,
+            6..=7 => Self::High,
+            _ => Self::VeryHigh,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Low => "allergy-low",
+            Self::Moderate => "allergy-moderate",
+            Self::High => "allergy-high",
+            Self::VeryHigh => "allergy-very-high",
+        }
+    }
+}
+
+/// Allergy risk from 0 to 10, with what went into it as rendered lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllergyRisk {
+    pub score: u8,
+    pub level: AllergyLevel,
+    pub factors: Vec<String>,
+}
+
+/// Points and factor line for one pollen group, `None` below moderate.
+fn pollen_factor(
+    group: &str,
+    grains: Option<f64>,
+    (moderate, high): (f64, f64),
+    options: &OutputOptions,
+) -> Option<(i32, String)> {
+    let grains = grains?;
+    let grains_key = if options.accessible {
+        "spoken-grains"
+    } else {
+        "pollen-grains"
+    };
+    let (points, level) = if grains >= high {
+        (4, AllergyLevel::High)
+    } else if grains >= moderate {
+        (2, AllergyLevel::Moderate)
+    } else {
+        return None;
+    };
+    let text = tr_args(
+        "allergy-pollen",
+        &[
+            ("group", tr(group)),
+            ("level", tr(level.key())),
+            (
+                "grains",
+                tr_args(grains_key, &[("value", format!("{grains:.0}"))]),
+            ),
+        ],
+    );
+    Some((points, text))
+}
+
+/// Combine pollen, when known, with humidity, wind and rain into one risk score.
+pub fn risk(data: &WeatherData, options: &OutputOptions) -> AllergyRisk {
+    let mut score = 0;
+    let mut factors = Vec::new();
+    // Open-Meteo answers with no values at all outside Europe.
+    let pollen = data.pollen.as_ref().filter(|pollen| !pollen.is_empty());
+    match pollen {
+        Some(PollenData { tree, grass, weed }) => {
+            let groups = [
+                pollen_factor("pollen-tree", *tree, TREE_POLLEN, options),
+                pollen_factor("pollen-grass", *grass, GRASS_POLLEN, options),
+                pollen_factor("pollen-weed", *weed, WEED_POLLEN, options),
+            ];
+            // The worst group counts in full, every other one adds a point.
+            let mut points: Vec<i32> = Vec::new();
+            for (p, text) in groups.into_iter().flatten() {
+                points.push(p);
+                factors.push(text);
+            }
+            points.sort_unstable_by(|a, b| b.cmp(a));
+            if let Some((worst, rest)) = points.split_first() {
+                score += worst + rest.len() as i32;
+            }
+        },
+        None => factors.push(tr("allergy-no-pollen")),
+    }
+
+    let pollen_in_air = score > 0 || pollen.is_none();
+    if data.wind_kph >= WINDY_KPH && pollen_in_air {
+        score += 2;
+        factors.push(tr_args(
+            "allergy-wind",
+            &[("speed", options.wind_speed(data.wind_kph))],
+        ));
+    }
+    if data.humidity >= HUMID_HUMIDITY {
+        score += 2;
+        factors.push(tr_args(
+            "allergy-humid",
+            &[(
+                "humidity",
+                options.measure(format!("{:.0}", data.humidity), "%", "spoken-percent"),
+            )],
+        ));
+    } else if data.humidity > 0.0 && data.humidity < DRY_HUMIDITY && pollen_in_air {
+        score += 1;
+        factors.push(tr_args(
+            "allergy-dry",
+            &[(
+                "humidity",
+                options.measure(format!("{:.0}", data.humidity), "%", "spoken-percent"),
+            )],
+        ));
+    }
+    if data.kind.is_rain() {
+        score -= 2;
+        factors.push(tr("allergy-rain"));
+    }
+
+    let score = score.clamp(0, 10) as u8;
+    AllergyRisk {
+        score,
+        level: AllergyLevel::of(score),
+        factors,
+    }
+}
+
+/// Section of the text output shown with `--allergy`: the score, then its factors.
+pub fn render_risk(risk: &AllergyRisk, options: &OutputOptions) -> String {
+    let mut lines = vec![tr_args(
+        "allergy-risk",
+        &[
+            ("level", tr(risk.level.key())),
+            ("score", risk.score.to_string()),
+        ],
+    )];
+    lines.extend(risk.factors.iter().map(|factor| format!("  - {factor}")));
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::condition::ConditionKind;
+
+    #[test]
+    fn pollen_wind_and_rain() {
+        let mut data = WeatherData {
+            humidity: 35.0,
+            wind_kph: 25.0,
+            pollen: Some(PollenData {
+                tree: Some(5.0),
+                grass: Some(120.0),
+                weed: Some(12.0),
+            }),
+            ..Default::default()
+        };
+
+        let options = OutputOptions::default();
+        let high = risk(&data, &options);
+        assert_eq!(high.score, 8);
+        assert_eq!(high.level, AllergyLevel::VeryHigh);
+        assert_eq!(
+            render_risk(&high, &options),
+            "Allergy risk: very high (8/10)\n\
+             \x20 - grass pollen high (120 grains/m³)\n\
+             \x20 - weed pollen moderate (12 grains/m³)\n\
+             \x20 - wind of 25.0 km/h spreads pollen\n\
+             \x20 - dry air (35%) keeps pollen airborne"
+        );
+
+        data.kind = ConditionKind::Rain;
+        data.pollen = Some(PollenData {
+            tree: Some(0.0),
+            grass: Some(0.0),
+            weed: Some(0.0),
+        });
+        data.humidity = 90.0;
+        let low = risk(&data, &options);
+        assert_eq!(low.score, 0);
+        assert_eq!(low.level, AllergyLevel::Low);
+        assert_eq!(low.factors.len(), 2);
+
+        data.pollen = Some(PollenData::default());
+        let unknown = risk(&data, &options);
+        data.pollen = None;
+        assert_eq!(unknown, risk(&data, &options));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::i18n::tr;
+use crate::output::OutputOptions;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::store::{Store, StoreError};
+use crate::weather_providers::WeatherData;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Readings needed before the history is trusted to say what is normal.
+pub const MIN_SAMPLES: usize = 30;
+/// Readings within this many days of the date, in any year, make up the season.
+pub const SEASON_DAYS: u32 = 15;
+/// Standard deviations from the seasonal mean that count as unusual.
+const THRESHOLD: f64 = 2.0;
+
+/// One past observation, as kept by [`crate::store::Store`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    pub recorded_at: DateTime<Utc>,
+    pub temp_c: f64,
+    pub humidity: f64,
+    pub pressure: f64,
+    pub wind_kph: f64,
+}
+
+/// A value far outside what the location's history shows for the time of year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Anomaly {
+    Warm,
+    Cold,
+    Windy,
+    Humid,
+    Dry,
+    HighPressure,
+    LowPressure,
+}
+
+impl Anomaly {
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            Self::Warm => "anomaly-warm",
+            Self::Cold => "anomaly-cold",
+            Self::Windy => "anomaly-windy",
+            Self::Humid => "anomaly-humid",
+            Self::Dry => "anomaly-dry",
+            Self::HighPressure => "anomaly-high-pressure",
+            Self::LowPressure => "anomaly-low-pressure",
+        }
+    }
+}
+
+/// Distance of `value` from the mean of `samples` in standard deviations,
+/// `None` when the samples do not vary.
+fn z_score(value: f64, samples: impl Iterator<Item = f64> + Clone) -> Option<f64> {
+    let n = samples.clone().count() as f64;
+    let mean = samples.clone().sum::<f64>() / n;
+    let variance = samples.map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    let sd = variance.sqrt();
+    (sd > f64::EPSILON).then(|| (value - mean) / sd)
+}
+
+/// Outliers of `data` against `history`; nothing until there are [`MIN_SAMPLES`] readings.
+pub fn detect(data: &WeatherData, history: &[Reading]) -> Vec<Anomaly> {
+    if history.len() < MIN_SAMPLES {
+        return Vec::new();
+    }
+
+    let mut anomalies = Vec::new();
+    let mut check = |value: f64,
+                     field: fn(&Reading) -> f64,
+                     high: Anomaly,
+                     low: Option<Anomaly>| {
+        match z_score(value, history.iter().map(field)) {
+            Some(z) if z >= THRESHOLD => anomalies.push(high),
+            Some(z) if z <= -THRESHOLD => anomalies.extend(low),
+            _ => {},
+        }
+    };
+    check(
+        data.temp_c,
+        |r| r.temp_c,
+        Anomaly::Warm,
+        Some(Anomaly::Cold),
+    );
+    // A calm day is not worth a note.
+    check(data.wind_kph, |r| r.wind_kph, Anomaly::Windy, None);
+    check(
+        data.humidity,
+        |r| r.humidity,
+        Anomaly::Humid,
+        Some(Anomaly::Dry),
+    );
+    check(
+        data.pressure,
+        |r| r.pressure,
+        Anomaly::HighPressure,
+        Some(Anomaly::LowPressure),
+    );
+    anomalies
+}
+
+/// Set `data.anomalies` from the seasonal history of its location in `store`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn annotate(store: &Store, data: &mut WeatherData) -> Result<(), StoreError> {
+    let history = store.seasonal(&data.location, data.datetime, SEASON_DAYS)?;
+    data.anomalies = detect(data, &history);
+    Ok(())
+}
+
+/// One note per anomaly, e.g. "Unusually warm for the time of year".
+pub fn render_anomalies(anomalies: &[Anomaly], options: &OutputOptions) -> String {
+    let lines: Vec<String> = anomalies.iter().map(|a| tr(a.key())).collect();
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn history(n: usize) -> Vec<Reading> {
+        let start = Utc.with_ymd_and_hms(2025, 7, 1, 12, 0, 0).unwrap();
+        (0..n)
+            .map(|i| Reading {
+                recorded_at: start + Duration::hours(i as i64),
+                temp_c: 20.0 + (i % 5) as f64,
+                humidity: 60.0 + (i % 3) as f64 * 5.0,
+                pressure: 1015.0,
+                wind_kph: 10.0 + (i % 4) as f64,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flags_outliers() {
+        let data = WeatherData {
+            temp_c: 35.0,
+            humidity: 62.0,
+            pressure: 1015.0,
+            wind_kph: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(detect(&data, &history(40)), vec![Anomaly::Warm]);
+    }
+
+    #[test]
+    fn needs_enough_history() {
+        let data = WeatherData {
+            temp_c: 35.0,
+            ..Default::default()
+        };
+
+        assert!(detect(&data, &history(MIN_SAMPLES - 1)).is_empty());
+    }
+}
@@ -1,16 +1,139 @@
+use crate::cache::{CacheKey, WeatherCache};
+use crate::condition_map::ConditionMap;
 use crate::errors::AppError;
+use crate::metrics::{self, Metrics};
+use crate::pressure;
 use crate::provider_registry::ProviderRegistry;
-use crate::weather_providers::WeatherData;
-use chrono::NaiveDateTime;
+use crate::singleflight::Group;
+use crate::suggest;
+use crate::weather_providers::alert::{Severity, WeatherAlert};
+use crate::weather_providers::deprecation::{self, Notice};
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::raw::{self, RawResponse};
+use crate::weather_providers::{
+    DailyData, EnsembleHour, HourlyData, MarineData, PollenData, QUALITY, WeatherData,
+    WeatherProvider,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard};
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tracing::{debug, warn};
+
+/// How failed provider requests are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for every following one.
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Only failures that may go away on their own are worth another attempt.
+    fn is_retryable(error: &ProviderError) -> bool {
+        error.is_transient()
+    }
+}
 
 /// App for querying weather providers.
 pub struct WeatherApp {
+    /// Swapped as a whole when the settings are reloaded.
+    registry: RwLock<ProviderRegistry>,
+    retry: RetryPolicy,
+    cache: Option<Arc<dyn WeatherCache>>,
+    conditions: ConditionMap,
+    /// Provider requests in flight, shared by concurrent identical calls of [`WeatherApp::run`].
+    inflight: Group<CacheKey, Result<WeatherData, ProviderError>>,
+    metrics: Arc<Metrics>,
+    /// Pressure of the recent current readings per provider and location, to derive the
+    /// tendency from where the provider reports none. Only long-running processes, such as
+    /// `weather serve`, get to see earlier readings.
+    pressures: Mutex<HashMap<CacheKey, Vec<(DateTime<Utc>, f64)>>>,
+}
+
+/// Assembles a [`WeatherApp`] without going through settings.
+#[derive(Default)]
+pub struct WeatherAppBuilder {
     registry: ProviderRegistry,
+    retry: RetryPolicy,
+    cache: Option<Arc<dyn WeatherCache>>,
+    conditions: ConditionMap,
+    metrics: Arc<Metrics>,
+}
+
+impl WeatherAppBuilder {
+    /// Register a provider by name.
+    pub fn with_provider<P>(mut self, name: &str, provider: P) -> Self
+    where
+        P: WeatherProvider + 'static,
+    {
+        self.registry.register(name, provider);
+        self
+    }
+
+    /// Use an already populated registry, replacing providers added so far.
+    pub fn with_registry(mut self, registry: ProviderRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Serve repeated requests from `cache` instead of the provider.
+    pub fn with_cache<C>(mut self, cache: C) -> Self
+    where
+        C: WeatherCache + 'static,
+    {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Rename conditions in every result, after the provider's codes have been normalized.
+    pub fn with_conditions(mut self, conditions: ConditionMap) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    /// Record provider calls into `metrics`, e.g. ones loaded from earlier runs.
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn build(self) -> WeatherApp {
+        WeatherApp {
+            registry: RwLock::new(self.registry),
+            retry: self.retry,
+            cache: self.cache,
+            conditions: self.conditions,
+            inflight: Group::default(),
+            metrics: self.metrics,
+            pressures: Mutex::default(),
+        }
+    }
 }
 
 impl WeatherApp {
     pub fn new(manager: ProviderRegistry) -> Self {
-        Self { registry: manager }
+        Self::builder().with_registry(manager).build()
+    }
+
+    pub fn builder() -> WeatherAppBuilder {
+        WeatherAppBuilder::default()
     }
 
     /// Fetch weather for a provider, location, and optional date.
@@ -20,35 +143,450 @@ impl WeatherApp {
         location: &str,
         date: Option<NaiveDateTime>,
     ) -> Result<WeatherData, AppError> {
-        let Some(provider) = self.registry.get(provider_name) else {
-            return Err(AppError::InvalidProvider(format!(
-                "Provider '{provider_name}' not found"
-            )));
+        let provider = self.provider(provider_name)?;
+
+        let key = CacheKey::new(provider_name, location, date);
+        if let Some(cache) = &self.cache
+            && let Some(mut data) = cache.get(&key).await
+        {
+            debug!("Serving {key} from cache");
+            self.conditions.apply(&mut data);
+            return Ok(data);
+        }
+
+        // Widgets refreshing at once must not each hit the provider.
+        let mut data = self
+            .inflight
+            .run(key.clone(), || self.fetch(provider, &key, location, date))
+            .await?;
+        self.conditions.apply(&mut data);
+        Ok(data)
+    }
+
+    /// Like [`WeatherApp::run`], but always asking the provider, and with the responses it sent.
+    /// These are returned when the request or mapping failed too, since that is when they help.
+    pub async fn run_raw(
+        &self,
+        provider_name: &str,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> (Result<WeatherData, AppError>, Vec<RawResponse>) {
+        let provider = match self.provider(provider_name) {
+            Ok(provider) => provider,
+            Err(e) => return (Err(e), Vec::new()),
         };
+        let key = CacheKey::new(provider_name, location, date);
+        let (data, responses) = raw::capture(self.fetch(provider, &key, location, date)).await;
+        let data = data.map(|mut data| {
+            self.conditions.apply(&mut data);
+            data
+        });
+        (data.map_err(AppError::from), responses)
+    }
 
-        provider
-            .fetch(location, date)
-            .await
-            .map_err(|e| AppError::InvalidDate(format!("Failed to fetch weather: {e}")))
+    /// Fill in the fields `data` lacks from what `provider_name` reports for the same location
+    /// and date, keeping `data` as it was when that fails.
+    pub async fn fill(
+        &self,
+        data: &mut WeatherData,
+        provider_name: &str,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) {
+        let missing = data.missing_fields();
+        if missing.is_empty() {
+            return;
+        }
+        debug!("Asking {provider_name} for {}", missing.join(", "));
+        match self.run(provider_name, location, date).await {
+            Ok(other) => data.fill_from(other, provider_name),
+            Err(e) => warn!(target: QUALITY, "Could not fill in {}: {e}", missing.join(", ")),
+        }
+    }
+
+    /// Ask the provider, retrying transient failures, and cache the result.
+    async fn fetch(
+        &self,
+        provider: Arc<dyn WeatherProvider>,
+        key: &CacheKey,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<WeatherData, ProviderError> {
+        let mut attempt = 1;
+        let mut backoff = self.retry.backoff;
+        loop {
+            match self
+                .timed(&key.provider, provider.fetch(location, date))
+                .await
+            {
+                Ok(mut data) => {
+                    if date.is_none() {
+                        self.derive_pressure_change(key, &mut data);
+                    }
+                    if let Some(cache) = &self.cache {
+                        cache.put(key, &data).await;
+                    }
+                    return Ok(data);
+                },
+                Err(e) if attempt < self.retry.max_attempts && RetryPolicy::is_retryable(&e) => {
+                    warn!("Attempt {attempt} failed: {e}, retrying in {backoff:?}");
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                    backoff *= 2;
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Remember the pressure of a current reading, and fill in its tendency from the earlier
+    /// ones when the provider did not report it.
+    fn derive_pressure_change(&self, key: &CacheKey, data: &mut WeatherData) {
+        let window = chrono::Duration::hours(6);
+        let mut pressures = self
+            .pressures
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Forget the places no longer asked about, or a long-running server keeps every one.
+        pressures.retain(|_, readings| {
+            readings
+                .last()
+                .is_some_and(|(at, _)| data.datetime - *at <= window)
+        });
+        let readings = pressures.entry(key.clone()).or_default();
+        readings.retain(|(at, _)| *at < data.datetime && data.datetime - *at <= window);
+        readings.push((data.datetime, data.pressure));
+        if data.pressure_change_3h.is_none() {
+            data.pressure_change_3h = pressure::change_per_3h(readings);
+        }
+    }
+
+    /// Ask every registered provider at once and return the first successful answer, with the
+    /// name of the provider that gave it. The slower requests are cancelled.
+    pub async fn race(
+        &self,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<(String, WeatherData), AppError> {
+        let names = self.list();
+        if let Some(cache) = &self.cache {
+            for name in &names {
+                let key = CacheKey::new(name, location, date);
+                if let Some(mut data) = cache.get(&key).await {
+                    debug!("Serving {key} from cache");
+                    self.conditions.apply(&mut data);
+                    return Ok((name.clone(), data));
+                }
+            }
+        }
+
+        let mut tasks = JoinSet::new();
+        for name in names {
+            let Ok(provider) = self.provider(&name) else {
+                continue;
+            };
+            let metrics = Arc::clone(&self.metrics);
+            let location = location.to_string();
+            tasks.spawn(async move {
+                let start = Instant::now();
+                let result = provider.fetch(&location, date).await;
+                metrics.observe(&name, metrics::outcome(&result), start.elapsed());
+                (name, result)
+            });
+        }
+
+        let mut error = None;
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((name, Ok(mut data))) => {
+                    debug!("{name} answered first");
+                    if let Some(cache) = &self.cache {
+                        cache
+                            .put(&CacheKey::new(&name, location, date), &data)
+                            .await;
+                    }
+                    self.conditions.apply(&mut data);
+                    // Dropping `tasks` aborts the requests still running.
+                    return Ok((name, data));
+                },
+                Ok((name, Err(e))) => {
+                    debug!("{name} failed: {e}");
+                    error = Some(e);
+                },
+                Err(e) => warn!("Provider request panicked: {e}"),
+            }
+        }
+        Err(error.map_or_else(
+            || AppError::InvalidProvider("No provider registered".to_string()),
+            AppError::from,
+        ))
+    }
+
+    /// What [`WeatherApp::run`] would do, without contacting the provider.
+    pub async fn plan(
+        &self,
+        provider_name: &str,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<RequestPlan, AppError> {
+        let provider = self.provider(provider_name)?;
+        let cached = match &self.cache {
+            Some(cache) => Some(
+                cache
+                    .get(&CacheKey::new(provider_name, location, date))
+                    .await
+                    .is_some(),
+            ),
+            None => None,
+        };
+
+        Ok(RequestPlan {
+            provider: provider_name.to_string(),
+            cached,
+            requests: provider.plan(location, date)?,
+        })
+    }
+
+    /// Fetch the current sea state from a provider with marine data.
+    pub async fn marine(
+        &self,
+        provider_name: &str,
+        location: &str,
+    ) -> Result<MarineData, AppError> {
+        let provider = self.provider(provider_name)?;
+        Ok(self
+            .timed(provider_name, provider.fetch_marine(location))
+            .await?)
+    }
+
+    /// Fetch the current pollen concentration from a provider with air quality data.
+    pub async fn pollen(
+        &self,
+        provider_name: &str,
+        location: &str,
+    ) -> Result<PollenData, AppError> {
+        let provider = self.provider(provider_name)?;
+        Ok(self
+            .timed(provider_name, provider.fetch_pollen(location))
+            .await?)
+    }
+
+    /// Fetch daily summaries from `start` to `end`, both included.
+    pub async fn daily(
+        &self,
+        provider_name: &str,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, AppError> {
+        let provider = self.provider(provider_name)?;
+        Ok(self
+            .timed(provider_name, provider.fetch_daily(location, start, end))
+            .await?)
+    }
+
+    /// Fetch daily values projected by a climate model from `start` to `end`, both included.
+    pub async fn climate(
+        &self,
+        provider_name: &str,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, AppError> {
+        let provider = self.provider(provider_name)?;
+        Ok(self
+            .timed(provider_name, provider.fetch_climate(location, start, end))
+            .await?)
+    }
+
+    /// Fetch the forecast for the next `hours` hours.
+    pub async fn hourly(
+        &self,
+        provider_name: &str,
+        location: &str,
+        hours: u32,
+    ) -> Result<Vec<HourlyData>, AppError> {
+        let provider = self.provider(provider_name)?;
+        Ok(self
+            .timed(provider_name, provider.fetch_hourly(location, hours))
+            .await?)
+    }
+
+    /// Fetch the ensemble spread for the next `hours` hours.
+    pub async fn ensemble(
+        &self,
+        provider_name: &str,
+        location: &str,
+        hours: u32,
+    ) -> Result<Vec<EnsembleHour>, AppError> {
+        let provider = self.provider(provider_name)?;
+        Ok(self
+            .timed(provider_name, provider.fetch_ensemble(location, hours))
+            .await?)
+    }
+
+    /// Fetch the alerts in effect at `min_severity` or above, most severe first.
+    pub async fn alerts(
+        &self,
+        provider_name: &str,
+        location: &str,
+        min_severity: Severity,
+    ) -> Result<Vec<WeatherAlert>, AppError> {
+        let provider = self.provider(provider_name)?;
+        let mut alerts = self
+            .timed(provider_name, provider.fetch_alerts(location))
+            .await?;
+        alerts.retain(|alert| alert.severity >= min_severity);
+        alerts.sort_by_key(|alert| std::cmp::Reverse(alert.severity));
+        Ok(alerts)
+    }
+
+    /// Counters and latency histograms of the provider calls made so far.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Await a provider call, recording its latency and outcome.
+    async fn timed<T>(
+        &self,
+        provider_name: &str,
+        call: impl Future<Output = Result<T, ProviderError>>,
+    ) -> Result<T, ProviderError> {
+        let start = Instant::now();
+        let result = call.await;
+        self.metrics
+            .observe(provider_name, metrics::outcome(&result), start.elapsed());
+        result
+    }
+
+    /// Replace every provider, e.g. after the settings changed; requests in flight finish with the old ones.
+    pub fn set_registry(&self, registry: ProviderRegistry) {
+        *self
+            .registry
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = registry;
+    }
+
+    fn registry(&self) -> RwLockReadGuard<'_, ProviderRegistry> {
+        self.registry.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn provider(&self, name: &str) -> Result<Arc<dyn WeatherProvider>, AppError> {
+        let provider = self.registry().get(name).ok_or_else(|| {
+            let list = self.list();
+            AppError::InvalidProvider(
+                match suggest::closest(name, list.iter().map(String::as_str)) {
+                    Some(closest) => {
+                        format!("Provider '{name}' not found, did you mean '{closest}'?")
+                    },
+                    None => format!("Provider '{name}' not found"),
+                },
+            )
+        })?;
+        if let Some(message) = provider.api_version().and_then(|api| api.deprecation) {
+            deprecation::record(Notice {
+                provider: name.to_string(),
+                message: message.to_string(),
+            });
+        }
+        Ok(provider)
+    }
+
+    /// Ping every registered provider concurrently, sorted by name.
+    pub async fn status(&self) -> Vec<ProviderStatus> {
+        let mut tasks = JoinSet::new();
+        for name in self.list() {
+            let Ok(provider) = self.provider(&name) else {
+                continue;
+            };
+            tasks.spawn(async move {
+                let start = Instant::now();
+                let result = provider.ping().await;
+                ProviderStatus {
+                    name,
+                    latency: start.elapsed(),
+                    error: result.err(),
+                }
+            });
+        }
+
+        let mut statuses = tasks.join_all().await;
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+
+    /// API version of every registered provider, sorted by name, with the deprecation notices it
+    /// announces or its answer to a ping carries.
+    pub async fn api_versions(&self) -> Vec<ApiStatus> {
+        let statuses = self.status().await;
+        let notices = deprecation::notices();
+        statuses
+            .into_iter()
+            .map(|status| ApiStatus {
+                version: self
+                    .provider(&status.name)
+                    .ok()
+                    .and_then(|provider| provider.api_version())
+                    .map(|api| api.version),
+                notices: notices
+                    .iter()
+                    .filter(|notice| notice.provider == status.name)
+                    .map(|notice| notice.message.clone())
+                    .collect(),
+                name: status.name,
+                error: status.error,
+            })
+            .collect()
     }
 
     /// Check if a provider exists.
     pub fn provider_exist(&self, name: &str) -> bool {
-        self.registry.get(name).is_some()
+        self.registry().get(name).is_some()
     }
 
     /// List all provider names.
     pub fn list(&self) -> Vec<String> {
-        self.registry.list_providers()
+        self.registry().list_providers()
     }
 }
 
+/// Dry run of a request, from [`WeatherApp::plan`].
+#[derive(Debug)]
+pub struct RequestPlan {
+    pub provider: String,
+    /// Whether a fresh result is cached, `None` without a cache.
+    pub cached: Option<bool>,
+    /// URLs the provider would request, with API keys masked.
+    pub requests: Vec<String>,
+}
+
+/// Outcome of pinging one provider.
+#[derive(Debug)]
+pub struct ProviderStatus {
+    pub name: String,
+    /// Time until the provider answered or the request failed.
+    pub latency: Duration,
+    pub error: Option<ProviderError>,
+}
+
+/// API version of one provider, for `weather providers check-versions`.
+#[derive(Debug)]
+pub struct ApiStatus {
+    pub name: String,
+    /// `None` for offline providers.
+    pub version: Option<&'static str>,
+    pub notices: Vec<String>,
+    /// Why the provider could not be asked, leaving header notices unknown.
+    pub error: Option<ProviderError>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::weather_providers::WeatherProvider;
-    use crate::weather_providers::error::ProviderError;
+    use crate::cache::memory::MemoryCache;
     use async_trait::async_trait;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
 
     #[tokio::test]
     async fn weather_app_empty_registry() {
@@ -81,4 +619,323 @@ mod tests {
         let res = wapp.run("something", "location", None).await;
         assert!(res.is_ok());
     }
+
+    /// Answers after half a minute, long after the others.
+    struct SlowProvider;
+
+    #[async_trait]
+    impl WeatherProvider for SlowProvider {
+        async fn fetch(
+            &self,
+            _location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(WeatherData::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn race_returns_first_success() {
+        let broken = || FlakyProvider {
+            calls: AtomicU32::new(0),
+            failures: u32::MAX,
+        };
+        let wapp = WeatherApp::new(
+            ProviderRegistry::new()
+                .with_provider("slow", SlowProvider)
+                .with_provider("broken", broken())
+                .with_provider("fast", MockProvider),
+        );
+        let start = Instant::now();
+        let (name, _) = wapp.race("location", None).await.unwrap();
+        assert_eq!(name, "fast");
+        assert!(start.elapsed() < Duration::from_secs(30));
+
+        wapp.set_registry(ProviderRegistry::new().with_provider("broken", broken()));
+        assert!(matches!(
+            wapp.race("location", None).await,
+            Err(AppError::Provider(ProviderError::ServerError { .. }))
+        ));
+    }
+
+    /// Fails until `failures` calls have been made.
+    struct FlakyProvider {
+        calls: AtomicU32,
+        failures: u32,
+    }
+
+    #[async_trait]
+    impl WeatherProvider for FlakyProvider {
+        async fn fetch(
+            &self,
+            _location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(ProviderError::ServerError {
+                    provider: "flaky".to_string(),
+                    status: 503,
+                });
+            }
+            Ok(WeatherData::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn builder_with_retry() {
+        let wapp = WeatherApp::builder()
+            .with_provider(
+                "flaky",
+                FlakyProvider {
+                    calls: AtomicU32::new(0),
+                    failures: 2,
+                },
+            )
+            .with_retry(RetryPolicy {
+                max_attempts: 3,
+                backoff: Duration::from_millis(1),
+            })
+            .build();
+
+        assert_eq!(wapp.list(), vec!["flaky".to_string()]);
+        assert!(wapp.run("flaky", "location", None).await.is_ok());
+
+        let series = wapp.metrics().snapshot();
+        assert_eq!(
+            series[&("flaky".to_string(), "server_error".to_string())].count,
+            2
+        );
+        assert_eq!(series[&("flaky".to_string(), "ok".to_string())].count, 1);
+    }
+
+    struct CountingProvider(Arc<AtomicU32>);
+
+    #[async_trait]
+    impl WeatherProvider for CountingProvider {
+        async fn fetch(
+            &self,
+            _location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(WeatherData::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn second_request_is_served_from_cache() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let wapp = WeatherApp::builder()
+            .with_provider("counting", CountingProvider(calls.clone()))
+            .with_cache(MemoryCache::new(Duration::from_secs(60)))
+            .build();
+
+        assert!(wapp.run("counting", "Porto", None).await.is_ok());
+        assert!(wapp.run("counting", "porto", None).await.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// Reports a pressure 2 hPa lower every 2 hours, without a tendency.
+    struct FallingPressureProvider(AtomicU32);
+
+    #[async_trait]
+    impl WeatherProvider for FallingPressureProvider {
+        async fn fetch(
+            &self,
+            _location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            let call = self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(WeatherData {
+                datetime: DateTime::UNIX_EPOCH + chrono::Duration::hours(2 * i64::from(call)),
+                pressure: 1015.0 - 2.0 * f64::from(call),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn pressure_change_from_earlier_reading() {
+        let wapp = WeatherApp::builder()
+            .with_provider("falling", FallingPressureProvider(AtomicU32::new(0)))
+            .build();
+
+        let first = wapp.run("falling", "Porto", None).await.unwrap();
+        assert_eq!(first.pressure_change_3h, None);
+        let second = wapp.run("falling", "Porto", None).await.unwrap();
+        assert_eq!(second.pressure_change_3h, Some(-3.0));
+
+        // Porto's readings expire while only Lisbon is asked about.
+        for _ in 0..4 {
+            wapp.run("falling", "Lisbon", None).await.unwrap();
+        }
+        let pressures = wapp.pressures.lock().unwrap();
+        assert_eq!(pressures.len(), 1);
+        assert!(pressures.keys().all(|key| key.location == "lisbon"));
+    }
+
+    /// Reports everything but the temperature.
+    struct UvProvider;
+
+    #[async_trait]
+    impl WeatherProvider for UvProvider {
+        async fn fetch(
+            &self,
+            _location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            Ok(WeatherData {
+                temp_c: 30.0,
+                uv_index: Some(7.0),
+                visibility_km: Some(10.0),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_fields_filled_from_secondary() {
+        let wapp = WeatherApp::builder()
+            .with_provider("uv", UvProvider)
+            .build();
+        let mut data = WeatherData {
+            temp_c: 20.0,
+            visibility_km: Some(4.0),
+            ..Default::default()
+        };
+
+        wapp.fill(&mut data, "uv", "Porto", None).await;
+        assert_eq!(data.temp_c, 20.0);
+        assert_eq!(data.uv_index, Some(7.0));
+        assert_eq!(data.visibility_km, Some(4.0));
+        assert_eq!(
+            data.sources,
+            BTreeMap::from([("uv_index".to_string(), "uv".to_string())])
+        );
+
+        wapp.fill(&mut data, "missing", "Porto", None).await;
+        assert_eq!(data.sources.len(), 1);
+
+        // No snow and no pressure tendency are nothing to fill in.
+        let complete = WeatherData {
+            feels_like_c: Some(19.0),
+            uv_index: Some(3.0),
+            precipitation_probability: Some(0.0),
+            visibility_km: Some(10.0),
+            ..Default::default()
+        };
+        assert!(complete.missing_fields().is_empty());
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_are_coalesced() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let wapp = WeatherApp::builder()
+            .with_provider("counting", CountingProvider(calls.clone()))
+            .build();
+
+        let (a, b) = tokio::join!(
+            wapp.run("counting", "Porto", None),
+            wapp.run("counting", "porto", None)
+        );
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn plan_reports_cache_without_fetching() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let wapp = WeatherApp::builder()
+            .with_provider("counting", CountingProvider(calls.clone()))
+            .with_cache(MemoryCache::new(Duration::from_secs(60)))
+            .build();
+
+        let plan = wapp.plan("counting", "Porto", None).await.unwrap();
+        assert_eq!(plan.cached, Some(false));
+        assert!(plan.requests.is_empty());
+
+        wapp.run("counting", "Porto", None).await.unwrap();
+        let plan = wapp.plan("counting", "Porto", None).await.unwrap();
+        assert_eq!(plan.cached, Some(true));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn status_of_each_provider() {
+        let wapp = WeatherApp::builder()
+            .with_provider("counting", CountingProvider(Arc::new(AtomicU32::new(0))))
+            .with_provider(
+                "flaky",
+                FlakyProvider {
+                    calls: AtomicU32::new(0),
+                    failures: 1,
+                },
+            )
+            .build();
+
+        let statuses = wapp.status().await;
+        assert_eq!(statuses[0].name, "counting");
+        assert!(statuses[0].error.is_none());
+        assert_eq!(statuses[1].name, "flaky");
+        assert!(matches!(
+            statuses[1].error,
+            Some(ProviderError::ServerError { status: 503, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let wapp = WeatherApp::builder()
+            .with_provider(
+                "flaky",
+                FlakyProvider {
+                    calls: AtomicU32::new(0),
+                    failures: 5,
+                },
+            )
+            .with_retry(RetryPolicy {
+                max_attempts: 2,
+                backoff: Duration::from_millis(1),
+            })
+            .build();
+
+        assert!(wapp.run("flaky", "location", None).await.is_err());
+    }
+
+    struct RejectingProvider(Arc<AtomicU32>);
+
+    #[async_trait]
+    impl WeatherProvider for RejectingProvider {
+        async fn fetch(
+            &self,
+            _location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Err(ProviderError::Unauthorized {
+                provider: "rejecting".to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_are_not_retried() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let wapp = WeatherApp::builder()
+            .with_provider("rejecting", RejectingProvider(calls.clone()))
+            .with_retry(RetryPolicy {
+                max_attempts: 3,
+                backoff: Duration::from_millis(1),
+            })
+            .build();
+
+        let err = wapp.run("rejecting", "location", None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::Provider(ProviderError::Unauthorized { .. })
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,159 @@
+//! Sun times as an iCalendar file, for scheduling around daylight.
+
+use crate::astro::sun;
+use crate::i18n::tr_args;
+use crate::output::slug;
+use crate::weather_providers::Coordinates;
+use chrono::{DateTime, Days, Duration, NaiveDate, Utc};
+
+/// Lines longer than this many bytes are folded, as RFC 5545 asks.
+const MAX_LINE: usize = 75;
+const STAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Sun event to put in the calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SunEvent {
+    Sunrise,
+    Sunset,
+}
+
+impl SunEvent {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sunrise => "sunrise",
+            Self::Sunset => "sunset",
+        }
+    }
+}
+
+/// Escape commas, semicolons, backslashes and newlines in a text value.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Append `line` to `out`, folded into continuation lines starting with a space.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// "-30 min", "+1h 15 min", or nothing without an offset.
+fn format_offset(offset: Duration) -> String {
+    let minutes = offset.num_minutes();
+    let sign = if minutes < 0 { '-' } else { '+' };
+    match (minutes.abs() / 60, minutes.abs() % 60) {
+        (0, 0) => String::new(),
+        (0, m) => format!(" {sign}{m} min"),
+        (h, 0) => format!(" {sign}{h}h"),
+        (h, m) => format!(" {sign}{h}h {m} min"),
+    }
+}
+
+/// iCalendar with an event at each of `events` on `days` days from `start`, moved by `offset`,
+/// e.g. 30 minutes before sunrise. Days without the event, around the poles, are left out.
+pub fn render_ical(
+    location: &str,
+    at: Coordinates,
+    start: NaiveDate,
+    days: u64,
+    events: &[SunEvent],
+    offset: Duration,
+    stamp: DateTime<Utc>,
+) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//weather//sun times//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    let dates = (0..days).filter_map(|day| start.checked_add_days(Days::new(day)));
+    for date in dates {
+        let day = sun::sun_day(at, date);
+        for &event in events {
+            let time = match event {
+                SunEvent::Sunrise => day.sunrise,
+                SunEvent::Sunset => day.sunset,
+            };
+            let Some(time) = time else { continue };
+            let summary = tr_args(
+                &format!("ical-{}", event.name()),
+                &[
+                    ("location", location.to_string()),
+                    ("offset", format_offset(offset)),
+                ],
+            );
+            push_line(&mut out, "BEGIN:VEVENT");
+            push_line(
+                &mut out,
+                &format!(
+                    "UID:{}-{}-{}@weather",
+                    date.format("%Y%m%d"),
+                    event.name(),
+                    slug(location)
+                ),
+            );
+            push_line(&mut out, &format!("DTSTAMP:{}", stamp.format(STAMP_FORMAT)));
+            push_line(
+                &mut out,
+                &format!("DTSTART:{}", (time + offset).format(STAMP_FORMAT)),
+            );
+            push_line(&mut out, "DURATION:PT15M");
+            push_line(&mut out, &format!("SUMMARY:{}", escape(&summary)));
+            push_line(&mut out, &format!("GEO:{:.4};{:.4}", at.lat, at.lon));
+            push_line(&mut out, "BEGIN:VALARM");
+            push_line(&mut out, "ACTION:DISPLAY");
+            push_line(&mut out, &format!("DESCRIPTION:{}", escape(&summary)));
+            push_line(&mut out, "TRIGGER:PT0S");
+            push_line(&mut out, "END:VALARM");
+            push_line(&mut out, "END:VEVENT");
+        }
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn sunrise_events_with_offset() {
+        let london = Coordinates {
+            lat: 51.5074,
+            lon: -0.1278,
+        };
+        let start = NaiveDate::from_ymd_opt(2025, 6, 21).unwrap();
+        let stamp = Utc.with_ymd_and_hms(2025, 6, 1, 8, 0, 0).unwrap();
+
+        let ical = render_ical(
+            "London, United Kingdom",
+            london,
+            start,
+            2,
+            &[SunEvent::Sunrise],
+            Duration::minutes(-30),
+            stamp,
+        );
+
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ical.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ical.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ical.contains("UID:20250621-sunrise-london_united_kingdom@weather\r\n"));
+        // Sunrise is at about 03:43 UTC.
+        assert!(ical.contains("DTSTART:20250621T031"), "{ical}");
+        assert!(ical.contains("SUMMARY:Sunrise -30 min in London\\, United Kingdom\r\n"));
+        assert!(ical.lines().all(|line| line.len() <= MAX_LINE));
+        assert_eq!(format_offset(Duration::minutes(75)), " +1h 15 min");
+    }
+}
@@ -0,0 +1,44 @@
+//! Astronomy computed locally, without provider requests.
+
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+pub mod ical;
+pub mod moon;
+pub mod sun;
+
+/// Julian day of the Unix epoch.
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Julian day number of an instant.
+pub(crate) fn julian_day(time: DateTime<Utc>) -> f64 {
+    time.timestamp_millis() as f64 / 1000.0 / SECONDS_PER_DAY + UNIX_EPOCH_JD
+}
+
+/// Instant of a Julian day number, to the second.
+pub(crate) fn from_julian_day(jd: f64) -> DateTime<Utc> {
+    let seconds = ((jd - UNIX_EPOCH_JD) * SECONDS_PER_DAY).round() as i64;
+    DateTime::from_timestamp(seconds, 0).unwrap_or_default()
+}
+
+/// UTC offset of `tz` at an instant, the machine's local zone when `None`.
+pub fn offset_in(tz: Option<Tz>) -> impl Fn(DateTime<Utc>) -> FixedOffset {
+    move |time| match tz {
+        Some(tz) => tz.offset_from_utc_datetime(&time.naive_utc()).fix(),
+        None => Local.offset_from_utc_datetime(&time.naive_utc()).fix(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn julian_day_roundtrip() {
+        let j2000 = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(julian_day(j2000), 2_451_545.0);
+        assert_eq!(from_julian_day(2_451_545.0), j2000);
+    }
+}
@@ -0,0 +1,273 @@
+//! Moon phases after Meeus, "Astronomical Algorithms", chapter 49, to within a few minutes.
+
+use crate::astro::{from_julian_day, julian_day};
+use crate::i18n::tr;
+use crate::output::OutputOptions;
+use chrono::{DateTime, FixedOffset, Utc};
+use std::f64::consts::TAU;
+
+/// Mean length of a lunation in days.
+const SYNODIC_MONTH: f64 = 29.530_588_861;
+
+/// Principal phases of the moon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    New,
+    FirstQuarter,
+    Full,
+    LastQuarter,
+}
+
+impl Phase {
+    /// Fraction of the lunation `k` counts from the new moon.
+    fn offset(self) -> f64 {
+        match self {
+            Self::New => 0.0,
+            Self::FirstQuarter => 0.25,
+            Self::Full => 0.5,
+            Self::LastQuarter => 0.75,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::New => "moon-new",
+            Self::FirstQuarter => "moon-first-quarter",
+            Self::Full => "moon-full",
+            Self::LastQuarter => "moon-last-quarter",
+        }
+    }
+
+    fn icon(self) -> &'static str {
+        match self {
+            Self::New => "🌑",
+            Self::FirstQuarter => "🌓",
+            Self::Full => "🌕",
+            Self::LastQuarter => "🌗",
+        }
+    }
+}
+
+/// A principal phase and when it occurs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhaseEvent {
+    pub phase: Phase,
+    pub time: DateTime<Utc>,
+    /// Illuminated fraction of the disk (0–1).
+    pub illumination: f64,
+}
+
+/// Instant of `phase` in lunation `k`, counted from the new moon of 2000-01-06.
+fn phase_time(k: f64, phase: Phase) -> DateTime<Utc> {
+    let k = k + phase.offset();
+    let t = k / 1236.85;
+    let (t2, t3, t4) = (t * t, t * t * t, t * t * t * t);
+
+    let jde = 2_451_550.097_66 + SYNODIC_MONTH * k + 0.000_154_37 * t2 - 0.000_000_150 * t3
+        + 0.000_000_000_73 * t4;
+    let e = 1.0 - 0.002_516 * t - 0.000_007_4 * t2;
+    let m = (2.5534 + 29.105_356_70 * k - 0.000_001_4 * t2 - 0.000_000_11 * t3).to_radians();
+    let mp = (201.5643 + 385.816_935_28 * k + 0.010_758_2 * t2 + 0.000_012_38 * t3
+        - 0.000_000_058 * t4)
+        .to_radians();
+    let f = (160.7108 + 390.670_502_84 * k - 0.001_611_8 * t2 - 0.000_002_27 * t3
+        + 0.000_000_011 * t4)
+        .to_radians();
+    let omega = (124.7746 - 1.563_755_88 * k + 0.002_067_2 * t2 + 0.000_002_15 * t3).to_radians();
+
+    let correction = match phase {
+        Phase::New | Phase::Full => {
+            let (a, b, c, d, g) = match phase {
+                Phase::New => (-0.40720, 0.17241, 0.01608, 0.01039, 0.00739),
+                _ => (-0.40614, 0.17302, 0.01614, 0.01043, 0.00734),
+            };
+            a * mp.sin()
+                + b * e * m.sin()
+                + c * (2.0 * mp).sin()
+                + d * (2.0 * f).sin()
+                + g * e * (mp - m).sin()
+                - 0.00514 * e * (mp + m).sin()
+                + 0.00208 * e * e * (2.0 * m).sin()
+                - 0.00111 * (mp - 2.0 * f).sin()
+                - 0.00057 * (mp + 2.0 * f).sin()
+                + 0.00056 * e * (2.0 * mp + m).sin()
+                - 0.00042 * (3.0 * mp).sin()
+                + 0.00042 * e * (m + 2.0 * f).sin()
+                + 0.00038 * e * (m - 2.0 * f).sin()
+                - 0.00024 * e * (2.0 * mp - m).sin()
+                - 0.00017 * omega.sin()
+        },
+        Phase::FirstQuarter | Phase::LastQuarter => {
+            let correction = -0.62801 * mp.sin() + 0.17172 * e * m.sin()
+                - 0.01183 * e * (mp + m).sin()
+                + 0.00862 * (2.0 * mp).sin()
+                + 0.00804 * (2.0 * f).sin()
+                + 0.00454 * e * (mp - m).sin()
+                + 0.00204 * e * e * (2.0 * m).sin()
+                - 0.00180 * (mp - 2.0 * f).sin()
+                - 0.00070 * (mp + 2.0 * f).sin()
+                - 0.00040 * (3.0 * mp).sin()
+                - 0.00034 * e * (2.0 * mp - m).sin()
+                + 0.00032 * e * (m + 2.0 * f).sin()
+                + 0.00032 * e * (m - 2.0 * f).sin()
+                - 0.00028 * e * e * (mp + 2.0 * m).sin()
+                + 0.00027 * e * (2.0 * mp + m).sin()
+                - 0.00017 * omega.sin();
+            let w = 0.00306 - 0.00038 * e * m.cos() + 0.00026 * mp.cos() - 0.00002 * (mp - m).cos()
+                + 0.00002 * (mp + m).cos()
+                + 0.00002 * (2.0 * f).cos();
+            if phase == Phase::FirstQuarter {
+                correction + w
+            } else {
+                correction - w
+            }
+        },
+    };
+
+    from_julian_day(jde + correction)
+}
+
+/// Lunation number whose new moon is close to `time`.
+fn lunation(time: DateTime<Utc>) -> f64 {
+    ((julian_day(time) - 2_451_550.097_66) / SYNODIC_MONTH).floor()
+}
+
+/// The principal phases between `start` and `end`, in order.
+pub fn phases_between(start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<PhaseEvent> {
+    let phases = [
+        Phase::New,
+        Phase::FirstQuarter,
+        Phase::Full,
+        Phase::LastQuarter,
+    ];
+    let mut events = Vec::new();
+    let mut k = lunation(start) - 1.0;
+    loop {
+        for phase in phases {
+            let time = phase_time(k, phase);
+            if time > end {
+                return events;
+            }
+            if time >= start {
+                events.push(PhaseEvent {
+                    phase,
+                    time,
+                    illumination: illumination(time),
+                });
+            }
+        }
+        k += 1.0;
+    }
+}
+
+/// Fraction of the lunation (0–1) elapsed at `time`, 0 at new moon and 0.5 at full moon.
+///
+/// Interpolated between the surrounding principal phases, so quarters are exactly half lit.
+fn lunation_fraction(time: DateTime<Utc>) -> f64 {
+    let phases = [
+        Phase::New,
+        Phase::FirstQuarter,
+        Phase::Full,
+        Phase::LastQuarter,
+    ];
+    let mut k = lunation(time) - 1.0;
+    loop {
+        for (i, phase) in phases.iter().enumerate() {
+            let start = phase_time(k, *phase);
+            let end = match phases.get(i + 1) {
+                Some(next) => phase_time(k, *next),
+                None => phase_time(k + 1.0, Phase::New),
+            };
+            if start <= time && time < end {
+                let part = (time - start).num_seconds() as f64 / (end - start).num_seconds() as f64;
+                return (i as f64 + part) / 4.0;
+            }
+        }
+        k += 1.0;
+    }
+}
+
+/// Illuminated fraction of the disk (0–1).
+pub fn illumination(time: DateTime<Utc>) -> f64 {
+    (1.0 - (TAU * lunation_fraction(time)).cos()) / 2.0
+}
+
+/// i18n key naming the phase the moon is in at `time`.
+pub fn phase_name(time: DateTime<Utc>) -> &'static str {
+    match lunation_fraction(time) {
+        f if f < 0.03 => "moon-new",
+        f if f < 0.22 => "moon-waxing-crescent",
+        f if f < 0.28 => "moon-first-quarter",
+        f if f < 0.47 => "moon-waxing-gibbous",
+        f if f < 0.53 => "moon-full",
+        f if f < 0.72 => "moon-waning-gibbous",
+        f if f < 0.78 => "moon-last-quarter",
+        f if f < 0.97 => "moon-waning-crescent",
+        _ => "moon-new",
+    }
+}
+
+/// One line per phase event, in the timezone `offset` gives for an instant.
+pub fn render_phases(
+    events: &[PhaseEvent],
+    offset: impl Fn(DateTime<Utc>) -> FixedOffset,
+    options: &OutputOptions,
+) -> String {
+    let lines: Vec<String> = events
+        .iter()
+        .map(|event| {
+            let time = event.time.with_timezone(&offset(event.time));
+            let name = tr(event.phase.key());
+            let line = format!(
+                "{:<14} {} {}  {:>3.0}%",
+                name,
+                time.format(&options.date_format),
+                time.format(&options.time_format),
+                event.illumination * 100.0
+            );
+            if options.emoji && !options.ascii {
+                format!("{} {line}", event.phase.icon())
+            } else {
+                line
+            }
+        })
+        .collect();
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn january_2025_phases() {
+        let events = phases_between(utc(2025, 1, 1, 0, 0), utc(2025, 2, 1, 0, 0));
+        let expected = [
+            (Phase::FirstQuarter, utc(2025, 1, 6, 23, 56)),
+            (Phase::Full, utc(2025, 1, 13, 22, 27)),
+            (Phase::LastQuarter, utc(2025, 1, 21, 20, 31)),
+            (Phase::New, utc(2025, 1, 29, 12, 36)),
+        ];
+
+        assert_eq!(events.len(), expected.len());
+        for (event, (phase, time)) in events.iter().zip(expected) {
+            assert_eq!(event.phase, phase);
+            // Within five minutes of the published times.
+            assert!((event.time - time).num_minutes().abs() <= 5, "{event:?}");
+        }
+    }
+
+    #[test]
+    fn illumination_follows_phase() {
+        assert!(illumination(utc(2025, 1, 13, 22, 27)) > 0.99);
+        assert!((illumination(utc(2025, 1, 21, 20, 31)) - 0.5).abs() < 0.01);
+        assert!(illumination(utc(2025, 1, 29, 12, 36)) < 0.01);
+        assert_eq!(phase_name(utc(2025, 1, 10, 0, 0)), "moon-waxing-gibbous");
+        assert_eq!(phase_name(utc(2025, 1, 25, 0, 0)), "moon-waning-crescent");
+    }
+}
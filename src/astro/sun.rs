@@ -0,0 +1,328 @@
+//! Sun position and rise/set times after the NOAA solar calculator, to within a minute or two.
+
+use crate::astro::julian_day;
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::Coordinates;
+use chrono::{DateTime, Days, Duration, FixedOffset, NaiveDate, NaiveTime, Utc};
+
+/// Sun elevation at sunrise and sunset, accounting for refraction and the solar disk.
+const HORIZON: f64 = -0.833;
+/// Golden hour: the sun between these elevations.
+const GOLDEN_LOW: f64 = -4.0;
+const GOLDEN_HIGH: f64 = 6.0;
+/// Blue hour: the sun between these elevations.
+const BLUE_LOW: f64 = -6.0;
+
+/// Declination and equation of time at an instant.
+struct SolarParams {
+    /// Radians.
+    declination: f64,
+    /// Minutes.
+    equation_of_time: f64,
+}
+
+fn solar_params(time: DateTime<Utc>) -> SolarParams {
+    let jc = (julian_day(time) - 2_451_545.0) / 36_525.0;
+
+    let mean_long = (280.466_46 + jc * (36_000.769_83 + jc * 0.000_303_2)).rem_euclid(360.0);
+    let mean_anomaly = 357.529_11 + jc * (35_999.050_29 - 0.000_153_7 * jc);
+    let eccentricity = 0.016_708_634 - jc * (0.000_042_037 + 0.000_000_126_7 * jc);
+    let m = mean_anomaly.to_radians();
+    let center = m.sin() * (1.914_602 - jc * (0.004_817 + 0.000_014 * jc))
+        + (2.0 * m).sin() * (0.019_993 - 0.000_101 * jc)
+        + (3.0 * m).sin() * 0.000_289;
+    let omega = (125.04 - 1934.136 * jc).to_radians();
+    let apparent_long = (mean_long + center - 0.005_69 - 0.004_78 * omega.sin()).to_radians();
+    let mean_obliquity =
+        23.0 + (26.0 + (21.448 - jc * (46.815 + jc * (0.000_59 - jc * 0.001_813))) / 60.0) / 60.0;
+    let obliquity = (mean_obliquity + 0.002_56 * omega.cos()).to_radians();
+
+    let declination = (obliquity.sin() * apparent_long.sin()).asin();
+    let y = (obliquity / 2.0).tan().powi(2);
+    let l0 = mean_long.to_radians();
+    let equation_of_time = 4.0
+        * (y * (2.0 * l0).sin() - 2.0 * eccentricity * m.sin()
+            + 4.0 * eccentricity * y * m.sin() * (2.0 * l0).cos()
+            - 0.5 * y * y * (4.0 * l0).sin()
+            - 1.25 * eccentricity * eccentricity * (2.0 * m).sin())
+        .to_degrees();
+
+    SolarParams {
+        declination,
+        equation_of_time,
+    }
+}
+
+/// Where the sun stands, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunPosition {
+    /// Above the horizon, negative below; without refraction.
+    pub elevation: f64,
+    /// Clockwise from north.
+    pub azimuth: f64,
+}
+
+/// Position of the sun seen from `at` at `time`.
+pub fn position(at: Coordinates, time: DateTime<Utc>) -> SunPosition {
+    let params = solar_params(time);
+    let minutes = time
+        .time()
+        .signed_duration_since(NaiveTime::MIN)
+        .num_seconds() as f64
+        / 60.0;
+    let solar_time = (minutes + params.equation_of_time + 4.0 * at.lon).rem_euclid(1440.0);
+    let hour_angle = (solar_time / 4.0 - 180.0).to_radians();
+
+    let lat = at.lat.to_radians();
+    let dec = params.declination;
+    let cos_zenith = lat.sin() * dec.sin() + lat.cos() * dec.cos() * hour_angle.cos();
+    let elevation = 90.0 - cos_zenith.clamp(-1.0, 1.0).acos().to_degrees();
+    let azimuth = (hour_angle
+        .sin()
+        .atan2(hour_angle.cos() * lat.sin() - dec.tan() * lat.cos())
+        .to_degrees()
+        + 180.0)
+        .rem_euclid(360.0);
+
+    SunPosition { elevation, azimuth }
+}
+
+/// Solar noon on `date`, when the sun crosses the meridian.
+fn solar_noon(at: Coordinates, date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN).and_utc();
+    let mut noon = midnight + Duration::minutes(720);
+    // A second pass with the equation of time at the estimate itself.
+    for _ in 0..2 {
+        let params = solar_params(noon);
+        let minutes = 720.0 - 4.0 * at.lon - params.equation_of_time;
+        noon = midnight + Duration::seconds((minutes * 60.0) as i64);
+    }
+    noon
+}
+
+/// When the sun passes `elevation` degrees on `date`, rising or setting.
+///
+/// `None` when it stays above or below that elevation all day, e.g. in polar summer.
+fn crossing(
+    at: Coordinates,
+    date: NaiveDate,
+    elevation: f64,
+    rising: bool,
+) -> Option<DateTime<Utc>> {
+    let noon = solar_noon(at, date);
+    let mut time = noon;
+    for _ in 0..2 {
+        let dec = solar_params(time).declination;
+        let lat = at.lat.to_radians();
+        let cos_hour_angle =
+            (elevation.to_radians().sin() - lat.sin() * dec.sin()) / (lat.cos() * dec.cos());
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+        let minutes = 4.0 * cos_hour_angle.acos().to_degrees();
+        let offset = Duration::seconds((minutes * 60.0) as i64);
+        time = if rising { noon - offset } else { noon + offset };
+    }
+    Some(time)
+}
+
+/// A span of time, e.g. a golden hour.
+pub type Window = (DateTime<Utc>, DateTime<Utc>);
+
+/// Sun times of one day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SunDay {
+    pub sunrise: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub solar_noon: DateTime<Utc>,
+    /// Time from sunrise to sunset; a full day or none at all around the poles.
+    pub day_length: Duration,
+    pub blue_morning: Option<Window>,
+    pub golden_morning: Option<Window>,
+    pub golden_evening: Option<Window>,
+    pub blue_evening: Option<Window>,
+}
+
+/// Sun times at `at` on `date`.
+pub fn sun_day(at: Coordinates, date: NaiveDate) -> SunDay {
+    let rise = |elevation| crossing(at, date, elevation, true);
+    let set = |elevation| crossing(at, date, elevation, false);
+    let window = |a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>| Some((a?, b?));
+
+    let (sunrise, sunset, noon) = (rise(HORIZON), set(HORIZON), solar_noon(at, date));
+    let day_length = match (sunrise, sunset) {
+        (Some(sunrise), Some(sunset)) => sunset - sunrise,
+        _ if position(at, noon).elevation > HORIZON => Duration::hours(24),
+        _ => Duration::zero(),
+    };
+
+    SunDay {
+        sunrise,
+        sunset,
+        solar_noon: noon,
+        day_length,
+        blue_morning: window(rise(BLUE_LOW), rise(GOLDEN_LOW)),
+        golden_morning: window(rise(GOLDEN_LOW), rise(GOLDEN_HIGH)),
+        golden_evening: window(set(GOLDEN_HIGH), set(GOLDEN_LOW)),
+        blue_evening: window(set(GOLDEN_LOW), set(BLUE_LOW)),
+    }
+}
+
+/// How much longer the day at `at` on `date` is than the day before and a week before.
+pub fn daylight_change(at: Coordinates, date: NaiveDate) -> (Duration, Duration) {
+    let length = |date: Option<NaiveDate>| date.map(|date| sun_day(at, date).day_length);
+    let today = sun_day(at, date).day_length;
+    (
+        today - length(date.checked_sub_days(Days::new(1))).unwrap_or(today),
+        today - length(date.checked_sub_days(Days::new(7))).unwrap_or(today),
+    )
+}
+
+/// "14h 05m 12s".
+fn format_length(length: Duration) -> String {
+    let s = length.num_seconds();
+    format!("{}h {:02}m {:02}s", s / 3600, s % 3600 / 60, s % 60)
+}
+
+/// Signed change, e.g. "+2m 13s" or "-0m 48s".
+fn format_change(change: Duration) -> String {
+    let s = change.num_seconds();
+    let sign = if s < 0 { '-' } else { '+' };
+    format!("{sign}{}m {:02}s", s.abs() / 60, s.abs() % 60)
+}
+
+/// Sun report for `weather sun`, times in the zone `offset` gives for an instant.
+pub fn render_sun(
+    location: &str,
+    day: &SunDay,
+    (since_yesterday, since_last_week): (Duration, Duration),
+    now: SunPosition,
+    offset: impl Fn(DateTime<Utc>) -> FixedOffset,
+    options: &OutputOptions,
+) -> String {
+    let time = |time: DateTime<Utc>| {
+        time.with_timezone(&offset(time))
+            .format(&options.time_format)
+            .to_string()
+    };
+    let maybe = |t: Option<DateTime<Utc>>| t.map_or_else(|| tr("not-available"), time);
+    let window = |w: Option<Window>| {
+        w.map_or_else(
+            || tr("not-available"),
+            |(start, end)| format!("{}–{}", time(start), time(end)),
+        )
+    };
+
+    let lines = [
+        tr_args("sun-at", &[("location", location.to_string())]),
+        format!("> {}: {}", tr("label-sunrise"), maybe(day.sunrise)),
+        format!("> {}: {}", tr("label-solar-noon"), time(day.solar_noon)),
+        format!("> {}: {}", tr("label-sunset"), maybe(day.sunset)),
+        format!(
+            "> {}: {}",
+            tr("label-day-length"),
+            tr_args(
+                "day-length",
+                &[
+                    ("length", format_length(day.day_length)),
+                    ("yesterday", format_change(since_yesterday)),
+                    ("week", format_change(since_last_week)),
+                ],
+            )
+        ),
+        format!(
+            "> {}: {}, {}",
+            tr("label-golden-hour"),
+            window(day.golden_morning),
+            window(day.golden_evening)
+        ),
+        format!(
+            "> {}: {}, {}",
+            tr("label-blue-hour"),
+            window(day.blue_morning),
+            window(day.blue_evening)
+        ),
+        format!(
+            "> {}: {}",
+            tr("label-sun-position"),
+            tr_args(
+                "sun-position",
+                &[
+                    (
+                        "elevation",
+                        format!("{:.1}{}", now.elevation, options.degree())
+                    ),
+                    ("azimuth", format!("{:.0}{}", now.azimuth, options.degree())),
+                ],
+            )
+        ),
+    ];
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const LONDON: Coordinates = Coordinates {
+        lat: 51.5074,
+        lon: -0.1278,
+    };
+
+    fn close(a: DateTime<Utc>, b: DateTime<Utc>) -> bool {
+        (a - b).num_minutes().abs() <= 2
+    }
+
+    #[test]
+    fn london_midsummer() {
+        let day = sun_day(LONDON, NaiveDate::from_ymd_opt(2025, 6, 21).unwrap());
+        let utc = |h, m| Utc.with_ymd_and_hms(2025, 6, 21, h, m, 0).unwrap();
+
+        assert!(close(day.sunrise.unwrap(), utc(3, 43)), "{day:?}");
+        assert!(close(day.sunset.unwrap(), utc(20, 21)), "{day:?}");
+        assert!(close(day.solar_noon, utc(12, 2)), "{day:?}");
+
+        let (start, end) = day.golden_evening.unwrap();
+        assert!(start < day.sunset.unwrap() && day.sunset.unwrap() < end);
+    }
+
+    #[test]
+    fn days_get_longer_in_spring() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+        let (yesterday, week) = daylight_change(LONDON, date);
+
+        // Close to four minutes a day around the equinox.
+        assert!(
+            (220..=240).contains(&yesterday.num_seconds()),
+            "{yesterday}"
+        );
+        assert!((1500..=1700).contains(&week.num_seconds()), "{week}");
+        assert_eq!(format_change(Duration::seconds(133)), "+2m 13s");
+        assert_eq!(format_change(-Duration::seconds(48)), "-0m 48s");
+        assert_eq!(format_length(Duration::seconds(50_712)), "14h 05m 12s");
+    }
+
+    #[test]
+    fn polar_day_has_no_sunset() {
+        let tromso = Coordinates {
+            lat: 69.65,
+            lon: 18.96,
+        };
+        let day = sun_day(tromso, NaiveDate::from_ymd_opt(2025, 6, 21).unwrap());
+        assert_eq!(day.sunset, None);
+        assert_eq!(day.blue_evening, None);
+        assert_eq!(day.day_length, Duration::hours(24));
+    }
+
+    #[test]
+    fn noon_position() {
+        let noon = sun_day(LONDON, NaiveDate::from_ymd_opt(2025, 6, 21).unwrap()).solar_noon;
+        let sun = position(LONDON, noon);
+
+        // 90° - latitude + declination of 23.44°.
+        assert!((sun.elevation - 61.93).abs() < 0.2, "{sun:?}");
+        assert!((sun.azimuth - 180.0).abs() < 1.0, "{sun:?}");
+    }
+}
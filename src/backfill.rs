@@ -0,0 +1,127 @@
+use crate::app::WeatherApp;
+use crate::errors::AppError;
+use crate::store::Store;
+use crate::weather_providers::rate_limit::TokenBucket;
+use chrono::{Days, NaiveDate};
+
+/// Days asked for per request; a month keeps requests small and progress visible.
+pub const PAGE_DAYS: u64 = 31;
+
+/// Width of the progress bar, in characters.
+const BAR_WIDTH: u64 = 30;
+
+/// Fetch the daily history of `location` from `from` to `to` a page at a time, each page taking
+/// a token from `bucket`, and store it. `progress` is told how many of the days are done after
+/// every page. Returns the number of days stored.
+///
+/// Pages stored before a failure are kept, so running again with a later `from` resumes.
+pub async fn backfill(
+    app: &WeatherApp,
+    store: &mut Store,
+    provider: &str,
+    location: &str,
+    (from, to): (NaiveDate, NaiveDate),
+    bucket: &TokenBucket,
+    mut progress: impl FnMut(u64, u64),
+) -> Result<usize, AppError> {
+    let total = span(from, to);
+    let mut stored = 0;
+    let mut start = from;
+    while start <= to {
+        let end = (start + Days::new(PAGE_DAYS - 1)).min(to);
+        bucket.acquire().await;
+        let days = app.daily(provider, location, start, end).await?;
+        store.record_days(provider, location, &days)?;
+        stored += days.len();
+        progress(span(from, end), total);
+        start = end + Days::new(1);
+    }
+    Ok(stored)
+}
+
+/// Number of days from `from` to `to`, both included.
+fn span(from: NaiveDate, to: NaiveDate) -> u64 {
+    u64::try_from((to - from).num_days() + 1).unwrap_or(0)
+}
+
+/// "[#######-------]  50% (184/365 days)"
+pub fn render_progress(done: u64, total: u64, ascii: bool) -> String {
+    let (full, empty) = if ascii { ('#', '-') } else { ('█', '░') };
+    let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH);
+    let percent = (done * 100).checked_div(total).unwrap_or(100);
+    let bar: String = (0..BAR_WIDTH)
+        .map(|i| if i < filled { full } else { empty })
+        .collect();
+    format!("[{bar}] {percent:>3}% ({done}/{total})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider_registry::ProviderRegistry;
+    use crate::weather_providers::error::ProviderError;
+    use crate::weather_providers::{DailyData, WeatherData, WeatherProvider};
+    use async_trait::async_trait;
+    use chrono::NaiveDateTime;
+
+    /// Has a record for every day asked for.
+    struct History;
+
+    #[async_trait]
+    impl WeatherProvider for History {
+        async fn fetch(
+            &self,
+            _location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            Ok(WeatherData::default())
+        }
+
+        async fn fetch_daily(
+            &self,
+            _location: &str,
+            start: NaiveDate,
+            end: NaiveDate,
+        ) -> Result<Vec<DailyData>, ProviderError> {
+            Ok(start
+                .iter_days()
+                .take_while(|date| *date <= end)
+                .map(|date| DailyData {
+                    date,
+                    temp_min_c: Some(1.0),
+                    temp_max_c: Some(9.0),
+                    precipitation_mm: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn pages_through_the_period() {
+        let app = WeatherApp::new(ProviderRegistry::new().with_provider("history", History));
+        let mut store = Store::in_memory().unwrap();
+        let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+
+        let mut pages = Vec::new();
+        let stored = backfill(
+            &app,
+            &mut store,
+            "history",
+            "Kyiv",
+            (from, to),
+            &TokenBucket::per_minute(600),
+            |done, total| pages.push((done, total)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(stored, 70);
+        assert_eq!(pages, [(31, 70), (62, 70), (70, 70)]);
+        assert_eq!(store.days(None, "Kyiv", from, to).unwrap().len(), 70);
+        assert_eq!(
+            render_progress(35, 70, true),
+            "[###############---------------]  50% (35/70)"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::app::WeatherApp;
+use crate::errors::AppError;
+use crate::i18n::tr_args;
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+use chrono::NaiveDateTime;
+use std::cmp::Ordering;
+
+/// Outcome of one location of a batch run.
+pub type BatchResult = (String, Result<WeatherData, AppError>);
+
+/// What to order the results of a batch run by, instead of the order of the arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "snake_case")]
+pub enum SortKey {
+    TempC,
+    Name,
+    WindKph,
+}
+
+/// Location with the most extreme value of a batch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extreme {
+    pub location: String,
+    pub value: f64,
+}
+
+/// At-a-glance overview of a batch run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    pub total: usize,
+    pub failures: usize,
+    pub hottest: Option<Extreme>,
+    pub coldest: Option<Extreme>,
+    /// Highest chance of precipitation, left out when no location expects any.
+    pub rainiest: Option<Extreme>,
+}
+
+/// Fetch every location in turn; a failing location does not stop the others.
+pub async fn fetch_all(
+    app: &WeatherApp,
+    provider: &str,
+    locations: &[String],
+    date: Option<NaiveDateTime>,
+) -> Vec<BatchResult> {
+    let mut results = Vec::with_capacity(locations.len());
+    for location in locations {
+        results.push((location.clone(), app.run(provider, location, date).await));
+    }
+    results
+}
+
+/// Order `results` by `key`, ascending unless `desc`; failed locations always come last.
+pub fn sort(results: &mut [BatchResult], key: SortKey, desc: bool) {
+    results.sort_by(|(_, a), (_, b)| match (a, b) {
+        (Ok(a), Ok(b)) => {
+            let order = match key {
+                SortKey::TempC => a.temp_c.total_cmp(&b.temp_c),
+                SortKey::Name => a.location.to_lowercase().cmp(&b.location.to_lowercase()),
+                SortKey::WindKph => a.wind_kph.total_cmp(&b.wind_kph),
+            };
+            if desc { order.reverse() } else { order }
+        },
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => Ordering::Equal,
+    });
+}
+
+/// The location for which `value` is largest by `better`, ties going to the first one.
+fn extreme(
+    results: &[BatchResult],
+    value: impl Fn(&WeatherData) -> Option<f64>,
+    better: impl Fn(f64, f64) -> bool,
+) -> Option<Extreme> {
+    results
+        .iter()
+        .filter_map(|(_, res)| res.as_ref().ok())
+        .filter_map(|data| Some((data, value(data)?)))
+        .fold(None, |best: Option<Extreme>, (data, value)| match best {
+            Some(best) if !better(value, best.value) => Some(best),
+            _ => Some(Extreme {
+                location: data.location.clone(),
+                value,
+            }),
+        })
+}
+
+impl Summary {
+    pub fn of(results: &[BatchResult]) -> Self {
+        Self {
+            total: results.len(),
+            failures: results.iter().filter(|(_, res)| res.is_err()).count(),
+            hottest: extreme(results, |d| Some(d.temp_c), |a, b| a > b),
+            coldest: extreme(results, |d| Some(d.temp_c), |a, b| a < b),
+            rainiest: extreme(
+                results,
+                |d| d.precipitation_probability.filter(|p| *p > 0.0),
+                |a, b| a > b,
+            ),
+        }
+    }
+}
+
+/// Footer printed after the results of a batch run.
+pub fn render_summary(summary: &Summary, options: &OutputOptions) -> String {
+    let mut lines = vec![tr_args(
+        "batch-summary",
+        &[
+            ("total", summary.total.to_string()),
+            ("failures", summary.failures.to_string()),
+        ],
+    )];
+    let mut push = |key: &str, extreme: &Option<Extreme>, value: &dyn Fn(f64) -> String| {
+        if let Some(extreme) = extreme {
+            lines.push(tr_args(
+                key,
+                &[
+                    ("location", extreme.location.clone()),
+                    ("value", value(extreme.value)),
+                ],
+            ));
+        }
+    };
+    push("batch-hottest", &summary.hottest, &|t| {
+        options.temperature(t)
+    });
+    push("batch-coldest", &summary.coldest, &|t| {
+        options.temperature(t)
+    });
+    push("batch-rainiest", &summary.rainiest, &|p| format!("{p:.0}%"));
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::error::ProviderError;
+
+    fn site(location: &str, temp_c: f64, rain: Option<f64>) -> BatchResult {
+        let data = WeatherData {
+            location: location.to_string(),
+            temp_c,
+            precipitation_probability: rain,
+            ..Default::default()
+        };
+        (location.to_string(), Ok(data))
+    }
+
+    #[test]
+    fn summary_of_sites() {
+        let results = vec![
+            site("Porto", 16.0, Some(40.0)),
+            site("Oslo", -3.0, None),
+            (
+                "Nowhere".to_string(),
+                Err(ProviderError::InvalidLocation("Nowhere".to_string()).into()),
+            ),
+            site("Bergen", 4.0, Some(90.0)),
+        ];
+        let summary = Summary::of(&results);
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.failures, 1);
+        assert_eq!(summary.hottest.unwrap().location, "Porto");
+        assert_eq!(summary.coldest.unwrap().value, -3.0);
+        assert_eq!(summary.rainiest.unwrap().location, "Bergen");
+    }
+
+    #[test]
+    fn sorted_with_failures_last() {
+        let mut results = vec![
+            (
+                "Nowhere".to_string(),
+                Err(ProviderError::InvalidLocation("Nowhere".to_string()).into()),
+            ),
+            site("Porto", 16.0, None),
+            site("oslo", -3.0, None),
+            site("Bergen", 4.0, None),
+        ];
+        fn order(results: &[BatchResult]) -> Vec<&str> {
+            results
+                .iter()
+                .map(|(location, _)| location.as_str())
+                .collect()
+        }
+
+        sort(&mut results, SortKey::TempC, true);
+        assert_eq!(order(&results), ["Porto", "Bergen", "oslo", "Nowhere"]);
+        sort(&mut results, SortKey::Name, false);
+        assert_eq!(order(&results), ["Bergen", "oslo", "Porto", "Nowhere"]);
+    }
+
+    #[test]
+    fn footer_lines() {
+        let summary = Summary {
+            total: 2,
+            failures: 0,
+            hottest: Some(Extreme {
+                location: "Porto".to_string(),
+                value: 16.0,
+            }),
+            coldest: Some(Extreme {
+                location: "Oslo".to_string(),
+                value: -3.0,
+            }),
+            rainiest: None,
+        };
+
+        assert_eq!(
+            render_summary(&summary, &OutputOptions::default()),
+            "2 locations, 0 failed\nHottest: Porto, 16.0°C\nColdest: Oslo, -3.0°C"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use crate::cache::{CacheKey, WeatherCache};
+use crate::weather_providers::WeatherData;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    stored_at: DateTime<Utc>,
+    data: WeatherData,
+}
+
+/// One JSON file per key in a cache directory, shared between CLI invocations.
+pub struct DiskCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl DiskCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    /// Platform cache directory, e.g. `~/.cache/weather` on Linux.
+    pub fn default_dir() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from(".cache"))
+            .join("weather")
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        let name: String = key
+            .to_string()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+#[async_trait]
+impl WeatherCache for DiskCache {
+    async fn get(&self, key: &CacheKey) -> Option<WeatherData> {
+        let path = self.path(key);
+        let content = tokio::fs::read(&path).await.ok()?;
+        let entry: Entry = serde_json::from_slice(&content)
+            .inspect_err(|e| warn!("Ignoring corrupt cache entry {}: {e}", path.display()))
+            .ok()?;
+
+        let age = (Utc::now() - entry.stored_at).to_std().unwrap_or_default();
+        if age >= self.ttl {
+            debug!("Cache entry {} expired", path.display());
+            return None;
+        }
+
+        debug!("Cache hit {}", path.display());
+        Some(entry.data)
+    }
+
+    async fn put(&self, key: &CacheKey, data: &WeatherData) {
+        let entry = Entry {
+            stored_at: Utc::now(),
+            data: data.clone(),
+        };
+        let path = self.path(key);
+
+        let result = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            let content = serde_json::to_vec(&entry).map_err(std::io::Error::other)?;
+            tokio::fs::write(&path, content).await
+        }
+        .await;
+
+        if let Err(e) = result {
+            warn!("Failed to write cache entry {}: {e}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn roundtrip_through_files() {
+        let dir = PathBuf::from("tests/tmp_disk_cache");
+        let key = CacheKey::new("weatherapi", "Porto, PT", None);
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            temp_c: 16.1,
+            ..Default::default()
+        };
+
+        let cache = DiskCache::new(&dir, Duration::from_secs(60));
+        assert!(cache.get(&key).await.is_none());
+        cache.put(&key, &data).await;
+
+        let cached = cache.get(&key).await.unwrap();
+        assert_eq!(cached.location, "Porto, Portugal");
+        assert_eq!(cached.temp_c, 16.1);
+        assert!(cache.path(&key).starts_with(&dir));
+
+        let expired = DiskCache::new(&dir, Duration::ZERO);
+        assert!(expired.get(&key).await.is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+use crate::cache::{CacheKey, WeatherCache};
+use crate::weather_providers::WeatherData;
+use async_trait::async_trait;
+use moka::future::Cache;
+use std::time::Duration;
+
+/// Bounded in-memory cache evicting the least recently used entries, for the server's hot locations.
+pub struct LruCache {
+    entries: Cache<CacheKey, WeatherData>,
+}
+
+impl LruCache {
+    /// Keep at most `capacity` results, each for `ttl` after it was stored.
+    pub fn new(capacity: u64, ttl: Duration) -> Self {
+        Self {
+            entries: Cache::builder()
+                .max_capacity(capacity)
+                .time_to_live(ttl)
+                .build(),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherCache for LruCache {
+    async fn get(&self, key: &CacheKey) -> Option<WeatherData> {
+        self.entries.get(key).await
+    }
+
+    async fn put(&self, key: &CacheKey, data: &WeatherData) {
+        self.entries.insert(key.clone(), data.clone()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hit_and_expiry() {
+        let key = CacheKey::new("p", "Porto", None);
+        let data = WeatherData {
+            location: "Porto".to_string(),
+            ..Default::default()
+        };
+
+        let cache = LruCache::new(10, Duration::from_secs(60));
+        assert!(cache.get(&key).await.is_none());
+        cache.put(&key, &data).await;
+        assert_eq!(cache.get(&key).await.unwrap().location, "Porto");
+
+        let expired = LruCache::new(10, Duration::ZERO);
+        expired.put(&key, &data).await;
+        assert!(expired.get(&key).await.is_none());
+    }
+}
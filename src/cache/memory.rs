@@ -0,0 +1,66 @@
+use crate::cache::{CacheKey, WeatherCache};
+use crate::weather_providers::WeatherData;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Process-local cache, useful for long-running modes and tests.
+pub struct MemoryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<CacheKey, (Instant, WeatherData)>>,
+}
+
+impl MemoryCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherCache for MemoryCache {
+    async fn get(&self, key: &CacheKey) -> Option<WeatherData> {
+        let mut entries = self.entries.lock().await;
+        match entries.get(key) {
+            Some((stored_at, data)) if stored_at.elapsed() < self.ttl => Some(data.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            },
+            None => None,
+        }
+    }
+
+    async fn put(&self, key: &CacheKey, data: &WeatherData) {
+        self.entries
+            .lock()
+            .await
+            .insert(key.clone(), (Instant::now(), data.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn hit_and_expiry() {
+        let key = CacheKey::new("p", "Porto", None);
+        let data = WeatherData {
+            location: "Porto".to_string(),
+            ..Default::default()
+        };
+
+        let cache = MemoryCache::new(Duration::from_secs(60));
+        assert!(cache.get(&key).await.is_none());
+        cache.put(&key, &data).await;
+        assert_eq!(cache.get(&key).await.unwrap().location, "Porto");
+
+        let expired = MemoryCache::new(Duration::ZERO);
+        expired.put(&key, &data).await;
+        assert!(expired.get(&key).await.is_none());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::weather_providers::WeatherData;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use std::fmt::{Display, Formatter};
+
+pub mod disk;
+pub mod lru;
+pub mod memory;
+pub mod tiered;
+
+/// Identifies a cached result: the same provider, location and date give the same key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub provider: String,
+    pub location: String,
+    pub date: Option<NaiveDateTime>,
+}
+
+impl CacheKey {
+    pub fn new(provider: &str, location: &str, date: Option<NaiveDateTime>) -> Self {
+        Self {
+            provider: provider.to_lowercase(),
+            location: location.trim().to_lowercase(),
+            date,
+        }
+    }
+}
+
+impl Display for CacheKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.date {
+            Some(date) => write!(f, "{}|{}|{}", self.provider, self.location, date),
+            None => write!(f, "{}|{}|now", self.provider, self.location),
+        }
+    }
+}
+
+/// Storage for fetched results; entries older than the implementation's TTL are not returned.
+#[async_trait]
+pub trait WeatherCache: Send + Sync {
+    async fn get(&self, key: &CacheKey) -> Option<WeatherData>;
+
+    async fn put(&self, key: &CacheKey, data: &WeatherData);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_is_normalized() {
+        let a = CacheKey::new("WeatherApi", " Porto,PT ", None);
+        let b = CacheKey::new("weatherapi", "porto,pt", None);
+
+        assert_eq!(a, b);
+        assert_eq!(a.to_string(), "weatherapi|porto,pt|now");
+    }
+}
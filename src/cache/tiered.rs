@@ -0,0 +1,60 @@
+use crate::cache::{CacheKey, WeatherCache};
+use crate::weather_providers::WeatherData;
+use async_trait::async_trait;
+
+/// A fast cache in front of a slower one: hits of the back cache are copied to the front,
+/// results are stored in both.
+pub struct TieredCache<F, B> {
+    front: F,
+    back: B,
+}
+
+impl<F, B> TieredCache<F, B> {
+    pub fn new(front: F, back: B) -> Self {
+        Self { front, back }
+    }
+}
+
+#[async_trait]
+impl<F, B> WeatherCache for TieredCache<F, B>
+where
+    F: WeatherCache,
+    B: WeatherCache,
+{
+    async fn get(&self, key: &CacheKey) -> Option<WeatherData> {
+        if let Some(data) = self.front.get(key).await {
+            return Some(data);
+        }
+        let data = self.back.get(key).await?;
+        self.front.put(key, &data).await;
+        Some(data)
+    }
+
+    async fn put(&self, key: &CacheKey, data: &WeatherData) {
+        self.front.put(key, data).await;
+        self.back.put(key, data).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::memory::MemoryCache;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn back_hits_are_promoted() {
+        let key = CacheKey::new("p", "Porto", None);
+        let data = WeatherData {
+            location: "Porto".to_string(),
+            ..Default::default()
+        };
+        let back = MemoryCache::new(Duration::from_secs(60));
+        back.put(&key, &data).await;
+
+        let cache = TieredCache::new(MemoryCache::new(Duration::from_secs(60)), back);
+        assert!(cache.front.get(&key).await.is_none());
+        assert_eq!(cache.get(&key).await.unwrap().location, "Porto");
+        assert!(cache.front.get(&key).await.is_some());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::i18n::tr_args;
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+use clap::Args;
+
+/// Conditions tested by `weather check`; any one that holds fails the check.
+#[derive(Debug, Default, Clone, Args)]
+pub struct Thresholds {
+    /// Fail when the temperature is above this many °C.
+    #[arg(long, value_name = "CELSIUS", allow_negative_numbers = true)]
+    pub max_temp: Option<f64>,
+
+    /// Fail when the temperature is below this many °C.
+    #[arg(long, value_name = "CELSIUS", allow_negative_numbers = true)]
+    pub min_temp: Option<f64>,
+
+    /// Fail when the wind is faster than this many km/h.
+    #[arg(long, value_name = "KPH")]
+    pub max_wind: Option<f64>,
+
+    /// Fail when it rains.
+    #[arg(long)]
+    pub rain: bool,
+
+    /// Fail when it snows.
+    #[arg(long)]
+    pub snow: bool,
+}
+
+impl Thresholds {
+    /// Human-readable description of every condition that holds for `data`.
+    pub fn violations(&self, data: &WeatherData, options: &OutputOptions) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        if let Some(max) = self.max_temp
+            && data.temp_c > max
+        {
+            violations.push(tr_args(
+                "check-temp-above",
+                &[
+                    ("temp", format!("{:.1}", data.temp_c)),
+                    ("limit", format!("{max:.1}")),
+                ],
+            ));
+        }
+        if let Some(min) = self.min_temp
+            && data.temp_c < min
+        {
+            violations.push(tr_args(
+                "check-temp-below",
+                &[
+                    ("temp", format!("{:.1}", data.temp_c)),
+                    ("limit", format!("{min:.1}")),
+                ],
+            ));
+        }
+        if let Some(max) = self.max_wind
+            && data.wind_kph > max
+        {
+            violations.push(tr_args(
+                "check-wind-above",
+                &[
+                    ("speed", options.wind_speed(data.wind_kph)),
+                    ("limit", options.wind_speed(max)),
+                ],
+            ));
+        }
+        if self.rain && data.kind.is_rain() {
+            violations.push(tr_args(
+                "check-rain",
+                &[("condition", data.condition.clone())],
+            ));
+        }
+        if self.snow && data.kind.is_snow() {
+            violations.push(tr_args(
+                "check-snow",
+                &[("condition", data.condition.clone())],
+            ));
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::condition::ConditionKind;
+
+    fn sample() -> WeatherData {
+        WeatherData {
+            temp_c: 31.5,
+            wind_kph: 12.0,
+            condition: "Light rain".to_string(),
+            kind: ConditionKind::Rain,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn no_thresholds_pass() {
+        assert!(
+            Thresholds::default()
+                .violations(&sample(), &OutputOptions::default())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn exceeded_thresholds() {
+        let thresholds = Thresholds {
+            max_temp: Some(30.0),
+            min_temp: Some(0.0),
+            max_wind: Some(10.0),
+            rain: true,
+            snow: true,
+        };
+
+        assert_eq!(
+            thresholds.violations(&sample(), &OutputOptions::default()),
+            vec![
+                "Temperature 31.5°C is above 30.0°C".to_string(),
+                "Wind 12.0 km/h is above 10.0 km/h".to_string(),
+                "Rain: Light rain".to_string(),
+            ]
+        );
+    }
+}
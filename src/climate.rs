@@ -0,0 +1,132 @@
+use crate::degree_days::mean_temp;
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::DailyData;
+use chrono::{Datelike, Month};
+use std::collections::BTreeMap;
+
+/// Projected climate of one calendar month, averaged over the years asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClimateMonth {
+    pub month: u32,
+    /// Mean of the daily mean temperatures.
+    pub temp_mean_c: Option<f64>,
+    /// Mean monthly precipitation total.
+    pub precipitation_mm: Option<f64>,
+}
+
+fn average(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Monthly means of daily projections, in calendar order; months without days are left out.
+pub fn monthly(days: &[DailyData]) -> Vec<ClimateMonth> {
+    let mut temps: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+    let mut totals: BTreeMap<(u32, i32), f64> = BTreeMap::new();
+    for day in days {
+        let month = day.date.month();
+        let temps = temps.entry(month).or_default();
+        temps.extend(mean_temp(day));
+        if let Some(mm) = day.precipitation_mm {
+            *totals.entry((month, day.date.year())).or_default() += mm;
+        }
+    }
+
+    temps
+        .into_iter()
+        .map(|(month, temps)| {
+            let totals: Vec<f64> = totals
+                .range((month, i32::MIN)..=(month, i32::MAX))
+                .map(|(_, total)| *total)
+                .collect();
+            ClimateMonth {
+                month,
+                temp_mean_c: average(&temps),
+                precipitation_mm: average(&totals),
+            }
+        })
+        .collect()
+}
+
+/// Table of projected monthly means for `weather climate`.
+pub fn render_climate(
+    location: &str,
+    decade: i32,
+    months: &[ClimateMonth],
+    options: &OutputOptions,
+) -> String {
+    let mut lines = vec![
+        tr_args(
+            "climate-title",
+            &[
+                ("location", location.to_string()),
+                ("decade", decade.to_string()),
+            ],
+        ),
+        format!(
+            "{:<4} {:>8} {:>8}",
+            "",
+            tr("climate-mean"),
+            tr("climate-precipitation")
+        ),
+    ];
+    for m in months {
+        let name = Month::try_from(m.month as u8).map_or("", |month| month.name());
+        let temp = m
+            .temp_mean_c
+            .map_or_else(|| tr("not-available"), |t| options.temperature(t));
+        let rain = m
+            .precipitation_mm
+            .map_or_else(|| tr("not-available"), |mm| format!("{mm:.0} mm"));
+        lines.push(format!(
+            "{:<4} {temp:>8} {rain:>8}",
+            &name[..name.len().min(3)]
+        ));
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn on(year: i32, month: u32, d: u32, min: f64, max: f64, mm: Option<f64>) -> DailyData {
+        DailyData {
+            date: NaiveDate::from_ymd_opt(year, month, d).unwrap(),
+            temp_min_c: Some(min),
+            temp_max_c: Some(max),
+            precipitation_mm: mm,
+        }
+    }
+
+    #[test]
+    fn means_over_the_years() {
+        let days = [
+            on(2041, 1, 1, 4.0, 10.0, Some(5.0)),
+            on(2041, 1, 2, 6.0, 12.0, Some(15.0)),
+            on(2042, 1, 1, 8.0, 14.0, Some(40.0)),
+            on(2041, 7, 1, 18.0, 30.0, None),
+        ];
+
+        let months = monthly(&days);
+
+        assert_eq!(
+            months,
+            [
+                ClimateMonth {
+                    month: 1,
+                    temp_mean_c: Some(9.0),
+                    precipitation_mm: Some(30.0),
+                },
+                ClimateMonth {
+                    month: 7,
+                    temp_mean_c: Some(24.0),
+                    precipitation_mm: None,
+                },
+            ]
+        );
+        let text = render_climate("Porto", 2040, &months, &OutputOptions::default());
+        assert!(text.contains("Jan     9.0°C    30 mm"), "{text}");
+    }
+}
@@ -0,0 +1,79 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+
+/// Magnus formula coefficients, good to about 0.4 °C from -45 to 60 °C.
+const MAGNUS_B: f64 = 17.62;
+const MAGNUS_C: f64 = 243.12;
+
+/// How humid the air feels, going by its dew point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comfort {
+    /// Dew point below 10 °C.
+    Dry,
+    /// Dew point from 10 to 16 °C.
+    Comfortable,
+    /// Dew point from 16 to 21 °C.
+    Muggy,
+    /// Dew point of 21 °C or more.
+    Oppressive,
+}
+
+impl Comfort {
+    pub fn of(dew_point_c: f64) -> Self {
+        match dew_point_c {
+            d if d < 10.0 => Self::Dry,
+            d if d < 16.0 => Self::Comfortable,
+            d if d < 21.0 => Self::Muggy,
+            _ => Self::Oppressive,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Dry => "comfort-dry",
+            Self::Comfortable => "comfort-comfortable",
+            Self::Muggy => "comfort-muggy",
+            Self::Oppressive => "comfort-oppressive",
+        }
+    }
+}
+
+/// Dew point in °C of air at `temp_c` with `humidity` percent relative humidity; `None` without
+/// any humidity.
+pub fn dew_point(temp_c: f64, humidity: f64) -> Option<f64> {
+    if humidity <= 0.0 {
+        return None;
+    }
+    let gamma = (humidity.min(100.0) / 100.0).ln() + MAGNUS_B * temp_c / (MAGNUS_C + temp_c);
+    Some(MAGNUS_C * gamma / (MAGNUS_B - gamma))
+}
+
+/// "muggy, dew point 17.2°C", shown after the humidity.
+pub fn render_comfort(temp_c: f64, humidity: f64, options: &OutputOptions) -> Option<String> {
+    let dew_point = dew_point(temp_c, humidity)?;
+    Some(tr_args(
+        "humidity-comfort",
+        &[
+            ("comfort", tr(Comfort::of(dew_point).key())),
+            ("dew_point", options.temperature(dew_point)),
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bands_by_dew_point() {
+        assert_eq!(dew_point(20.0, 0.0), None);
+        let dew = dew_point(30.0, 70.0).unwrap();
+        assert!((dew - 23.9).abs() < 0.1, "{dew}");
+        assert_eq!(Comfort::of(dew), Comfort::Oppressive);
+        assert_eq!(Comfort::of(dew_point(25.0, 30.0).unwrap()), Comfort::Dry);
+        assert_eq!(
+            render_comfort(20.0, 60.0, &OutputOptions::default()).as_deref(),
+            Some("comfortable, dew point 12.0°C")
+        );
+    }
+}
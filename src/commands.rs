@@ -1,13 +1,71 @@
+use crate::activity::{self, Activity};
+use crate::advice;
+use crate::anomaly;
 use crate::app::WeatherApp;
-use crate::config::save_settings;
+use crate::astro::ical::{self, SunEvent};
+use crate::astro::{self, moon, sun};
+use crate::backfill;
+use crate::batch;
+use crate::check::Thresholds;
+use crate::climate;
+use crate::config::{Settings, SettingsError, save_default_provider};
+use crate::degree_days;
+use crate::email::send_alert;
+use crate::encryption::{Key, decrypt_settings, encrypt_settings, encrypted_path};
 use crate::errors::AppError;
-use crate::weather_providers::WeatherData;
-use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
-use clap::{Parser, Subcommand};
+use crate::fire;
+use crate::fog;
+use crate::frost;
+use crate::heat;
+use crate::hooks;
+use crate::i18n::{tr, tr_args};
+use crate::laundry;
+use crate::migrate::{self, SETTINGS_VERSION};
+use crate::mqtt;
+use crate::notify::{self, notify};
+use crate::output::alerts::render_alerts;
+use crate::output::color::ColorChoice;
+use crate::output::diff::render_diff;
+use crate::output::ensemble::render_ensemble;
+use crate::output::plan::{render_order, render_plan};
+use crate::output::query::{QueryFormat, render_query};
+use crate::output::registry::FormatterRegistry;
+use crate::output::snow::render_snow;
+use crate::output::status::{render_stats, render_status, render_versions};
+use crate::output::wind::WindUnit;
+use crate::output::{Formatter, OutputOptions};
+use crate::pressure;
+use crate::reload;
+use crate::report::{self, ReportFormat, ReportPeriod};
+use crate::route::{self, Trip};
+#[cfg(feature = "self-update")]
+use crate::self_update;
+use crate::server;
+use crate::store::{Retention, Store};
+use crate::suggest;
+use crate::surf;
+use crate::trend;
+use crate::weather_providers::Coordinates;
+use crate::weather_providers::alert::Severity;
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::fixtures::{FIXTURE_DIR, fixture_path};
+use crate::weather_providers::parse::ParseMode;
+use crate::weather_providers::radar::RainViewer;
+use crate::weather_providers::rate_limit::TokenBucket;
+use crate::weather_providers::raw::{RawMode, render_raw};
+use crate::weather_providers::satellite::{Band, SatelliteImagery};
+use crate::webhook::Targets;
+use crate::windrose::{self, WindRose};
+use chrono::{DateTime, Days, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use clap::{ArgGroup, Args, Parser, Subcommand};
+use std::io::IsTerminal;
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
-use std::str::FromStr;
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+/// Current weather, forecasts and warnings from several providers.
 #[derive(Debug, Parser)]
 #[command(author, version, about, arg_required_else_help = true)]
 pub struct Cli {
@@ -20,26 +78,646 @@ pub struct Cli {
         value_name = "CONF_FILE",
         default_value = default_settings_path().into_os_string()
     )]
-    pub(crate) config_path: PathBuf,
+    pub config_path: PathBuf,
+
+    /// Load environment variables from this dotenv file instead of `.env` in the current directory.
+    #[arg(long, global = true, value_name = "ENV_FILE")]
+    pub env_file: Option<PathBuf>,
+
+    /// age identity file unlocking the encrypted settings, instead of a passphrase.
+    #[arg(long, global = true, value_name = "KEY_FILE")]
+    pub key_file: Option<PathBuf>,
+
+    /// Print only the result on a single line, without any log output.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Increase log verbosity (-v info, -vv debug, -vvv trace); RUST_LOG takes precedence.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Always fetch from the provider, bypassing the result and place name caches.
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    /// Fail on provider responses that do not match the expected schema instead of filling in defaults,
+    /// and exit with 7 after any warning or when fields were filled in from another provider.
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// Emit log lines as JSON.
+    #[arg(long, global = true)]
+    pub log_json: bool,
+
+    /// Use made-up data from the offline demo provider instead of the real providers.
+    #[cfg(feature = "demo")]
+    #[arg(long, global = true)]
+    pub demo: bool,
+
+    /// Print which provider and URLs `get` and `check` would use, without fetching anything.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Also show the result as a desktop notification.
+    #[arg(long, global = true)]
+    pub notify: bool,
+
+    /// POST the JSON result to this URL, in addition to `[webhooks] urls`; may be repeated.
+    #[arg(long, global = true, value_name = "URL")]
+    pub post_url: Vec<String>,
+
+    /// POST the result as a Slack Block Kit message to this incoming webhook; may be repeated.
+    #[arg(long, global = true, value_name = "URL")]
+    pub post_slack: Vec<String>,
+
+    /// POST the result as a Discord embed to this channel webhook; may be repeated.
+    #[arg(long, global = true, value_name = "URL")]
+    pub post_discord: Vec<String>,
+
+    #[command(flatten)]
+    pub(crate) output: OutputArgs,
 }
 
+impl Cli {
+    /// Whether the command is the long-running `serve`.
+    pub fn serves(&self) -> bool {
+        matches!(self.command, Some(Commands::Serve { .. }))
+    }
+
+    /// Key unlocking the settings, asked for only when they are encrypted or a key file is given.
+    pub fn settings_key(&self) -> Result<Option<Key>, SettingsError> {
+        let encrypted = !self.config_path.exists() && encrypted_path(&self.config_path).exists();
+        if encrypted || self.key_file.is_some() {
+            Key::resolve(self.key_file.clone(), false).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Flags adjusting how results are printed, overriding the `[display]` settings.
+#[derive(Debug, Args)]
+pub struct OutputArgs {
+    /// Show times in this IANA timezone (e.g. "Europe/Kyiv") instead of the location's own.
+    #[arg(long, global = true, value_name = "TIMEZONE", value_parser = parse_timezone)]
+    pub(crate) tz: Option<Tz>,
+
+    /// Leave emoji out of the output.
+    #[arg(long, global = true)]
+    pub(crate) no_emoji: bool,
+
+    /// Restrict output to pure ASCII.
+    #[arg(long, global = true)]
+    pub(crate) ascii: bool,
+
+    /// Screen reader friendly text output: one fact per line, units and directions spelled out,
+    /// no emoji or box drawing.
+    #[arg(long, global = true)]
+    pub(crate) accessible: bool,
+
+    /// When to colorize output.
+    #[arg(long, global = true, value_name = "WHEN")]
+    pub(crate) color: Option<ColorChoice>,
+
+    /// Add a "what to wear" line to the text output.
+    #[arg(long, global = true)]
+    pub(crate) advice: bool,
+
+    /// Add an allergy risk section to the text output, from pollen where the provider has it.
+    #[arg(long, global = true)]
+    pub(crate) allergy: bool,
+
+    /// Unit wind speeds are primarily shown in.
+    #[arg(long, global = true, value_name = "UNIT")]
+    pub(crate) wind_unit: Option<WindUnit>,
+
+    /// Print the result on a single line, same as `--format oneline`.
+    #[arg(long, global = true)]
+    pub(crate) oneline: bool,
+
+    /// Output format: text, oneline, json, table, tsv, template, waybar, slack or discord.
+    #[arg(short, long, global = true, value_name = "FORMAT")]
+    pub(crate) format: Option<String>,
+
+    /// Leave out the line of column names of `--format tsv` and `store query --as tsv`/`csv`.
+    #[arg(long, global = true)]
+    pub(crate) no_header: bool,
+}
+
+impl OutputArgs {
+    /// Name of the formatter to use: flags first, then settings, then `text`.
+    fn format_name(&self, quiet: bool, settings: &Settings) -> String {
+        if let Some(format) = &self.format {
+            return format.clone();
+        }
+        if self.accessible || settings.display.accessible {
+            return "text".to_string();
+        }
+        if quiet || self.oneline {
+            return "oneline".to_string();
+        }
+        settings
+            .display
+            .format
+            .clone()
+            .unwrap_or_else(|| "text".to_string())
+    }
+
+    fn formatter(&self, quiet: bool, settings: &Settings) -> Result<Arc<dyn Formatter>, AppError> {
+        let formatters = FormatterRegistry::with_builtin();
+        let name = self.format_name(quiet, settings);
+        formatters.get(&name).ok_or_else(|| {
+            AppError::InvalidFormat(format!(
+                "'{name}', available: {:?}",
+                formatters.list_formatters()
+            ))
+        })
+    }
+
+    fn options(&self, settings: &Settings) -> Result<OutputOptions, AppError> {
+        let mut options = OutputOptions::from_settings(settings).map_err(AppError::Config)?;
+        if self.no_emoji {
+            options.emoji = false;
+        }
+        if self.ascii {
+            options.ascii = true;
+        }
+        if self.accessible {
+            options.accessible = true;
+            options.emoji = false;
+        }
+        if let Some(color) = self.color {
+            options.color = color;
+        }
+        if self.advice {
+            options.advice = true;
+        }
+        if self.allergy {
+            options.allergy = true;
+        }
+        if let Some(unit) = self.wind_unit {
+            options.wind_unit = unit;
+        }
+        if self.no_header {
+            options.header = false;
+        }
+        Ok(options)
+    }
+}
+
+/// `settings.toml` in the current directory when there is one, else in the platform config dir,
+/// e.g. `~/.config/weather` on Linux or `%APPDATA%\weather` on Windows.
 pub fn default_settings_path() -> PathBuf {
-    PathBuf::from_str("settings.toml").expect("Could not find default settings.toml")
+    let local = PathBuf::from("settings.toml");
+    if local.exists() || encrypted_path(&local).exists() {
+        return local;
+    }
+    dirs::config_dir()
+        .map(|dir| dir.join("weather").join("settings.toml"))
+        .unwrap_or(local)
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProvidersCommand {
+    /// Ping every provider concurrently and report reachability and latency.
+    Status,
+    /// Calls made to each provider so far, by outcome, with their latency.
+    Stats,
+    /// Ping every provider and report the API version in use and any deprecation notices.
+    CheckVersions,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Rewrite the settings file in the current layout, keeping a backup of the old one.
+    Migrate,
+    /// Encrypt the settings file to `<file>.enc` with a passphrase or `--key-file`, removing the plaintext.
+    Encrypt,
+    /// Turn the encrypted settings back into a plaintext file.
+    Decrypt,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StoreCommand {
+    /// Delete history older than `[storage] retention` and compact the database.
+    Prune {
+        /// Keep this much instead, e.g. "90d", "12w", "6m" or "2y".
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+    },
+    /// Run a read-only SQL query, e.g. "SELECT location, max(temp_c) FROM readings GROUP BY 1".
+    Query {
+        sql: String,
+        /// How to print the rows.
+        #[arg(
+            id = "query_format",
+            long = "as",
+            value_enum,
+            default_value_t,
+            value_name = "FORMAT"
+        )]
+        format: QueryFormat,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DevCommand {
+    /// Save the provider's responses for a location to the fixtures the mapping tests replay.
+    RecordFixture {
+        provider: String,
+        location: String,
+        /// Directory to save to, one subdirectory per provider.
+        #[arg(long, default_value = FIXTURE_DIR)]
+        dir: PathBuf,
+    },
 }
 
 #[derive(Debug, Subcommand)]
 pub enum Commands {
-    Configure {
-        provider: Option<String>,
+    /// Set the default provider, or list the registered ones when none is given.
+    Configure { provider: Option<String> },
+    /// Inspect the registered providers.
+    Providers {
+        #[command(subcommand)]
+        command: ProvidersCommand,
+    },
+    /// Maintain the settings file.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Maintain the history database.
+    Store {
+        #[command(subcommand)]
+        command: StoreCommand,
     },
+    /// Current weather at a location, or the weather at `--date`.
     Get {
         address: String,
         #[arg(long, value_parser = parse_datetime)]
         date: Option<NaiveDateTime>,
+        /// Registered provider to ask this time instead of `default_provider`.
+        #[arg(short, long)]
+        provider: Option<String>,
+        /// Ask every registered provider at once and show the first answer.
+        #[arg(long, conflicts_with = "provider")]
+        race: bool,
+        /// Provider to ask for the fields the first one leaves out, instead of `fill_provider`.
+        #[arg(long, value_name = "PROVIDER")]
+        fill_from: Option<String>,
+        /// Also print the provider's responses as received, bypassing the cache; `only` prints
+        /// them instead of the weather.
+        #[arg(long, value_enum, value_name = "MODE", num_args = 0..=1, require_equals = true,
+              default_missing_value = "also", conflicts_with = "race")]
+        raw: Option<RawMode>,
+    },
+    /// Weather for several locations, followed by a summary of the extremes and failures.
+    Batch {
+        #[arg(required = true)]
+        addresses: Vec<String>,
+        #[arg(long, value_parser = parse_datetime)]
+        date: Option<NaiveDateTime>,
+        /// Order the results by this field instead of as given.
+        #[arg(long, value_name = "FIELD")]
+        sort: Option<batch::SortKey>,
+        /// Sort from the largest value down.
+        #[arg(long, requires = "sort")]
+        desc: bool,
+        /// End each result with a NUL byte instead of a newline, for `xargs -0`, and leave out the
+        /// summary and any header.
+        #[arg(short = '0', long)]
+        print0: bool,
+    },
+    /// Compare the weather at two times, by default now against 24 hours ago.
+    Diff {
+        address: String,
+        /// Time to look at, now by default.
+        #[arg(long, value_parser = parse_datetime)]
+        date: Option<NaiveDateTime>,
+        /// Time to compare against, a day before `--date` by default.
+        #[arg(long, value_parser = parse_datetime)]
+        against: Option<NaiveDateTime>,
+    },
+    /// Exit with 1 when any of the given conditions holds, 0 otherwise.
+    Check {
+        address: String,
+        #[arg(long, value_parser = parse_datetime)]
+        date: Option<NaiveDateTime>,
+        #[command(flatten)]
+        thresholds: Thresholds,
+        /// Send an email alert via the `[email]` settings when a condition holds.
+        #[arg(long)]
+        email: bool,
+    },
+    /// Show the weather expected along a trip.
+    Route {
+        from: String,
+        to: String,
+        /// Stop on the way; may be repeated.
+        #[arg(long)]
+        via: Vec<String>,
+        /// Departure, "HH:MM" today or a full date; now by default.
+        #[arg(long, value_parser = parse_depart)]
+        depart: Option<NaiveDateTime>,
+        /// Average travel speed in km/h.
+        #[arg(long, default_value_t = 80.0, value_parser = parse_positive)]
+        speed: f64,
+        /// Distance between sampled points in km.
+        #[arg(long, default_value_t = 50.0, value_parser = parse_positive)]
+        step: f64,
+    },
+    /// Show snow depth, fresh snow and freezing level at a resort.
+    Snow {
+        resort: String,
+        /// Provider to ask; needs one that reports snow.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Rate the surf at a spot from waves, swell and wind.
+    Surf {
+        spot: String,
+        /// Provider to ask; needs one with marine data.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Sum growing degree days over a season.
+    Gdd {
+        address: String,
+        /// Temperature below which the crop does not develop, in °C.
+        #[arg(long, default_value_t = 10.0, allow_negative_numbers = true)]
+        base: f64,
+        /// First day, "YYYY-MM-DD".
+        #[arg(long)]
+        since: NaiveDate,
+        /// Last day; today by default.
+        #[arg(long)]
+        until: Option<NaiveDate>,
+        /// Provider to ask; needs one with daily history.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Heating and cooling degree days per month, for tracking energy use.
+    DegreeDays {
+        address: String,
+        /// Daily mean temperature needing neither heating nor cooling, in °C.
+        #[arg(long, default_value_t = 18.0, allow_negative_numbers = true)]
+        base: f64,
+        /// First day, "YYYY-MM-DD".
+        #[arg(long)]
+        since: NaiveDate,
+        /// Last day; today by default.
+        #[arg(long)]
+        until: Option<NaiveDate>,
+        /// Provider to ask; needs one with daily history.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Fetch the daily history of a period into the history database, for later analysis.
+    Backfill {
+        address: String,
+        /// First day, "YYYY-MM-DD".
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last day; today by default.
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Requests allowed per minute, each covering a month.
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        per_minute: u32,
+        /// Provider to ask; needs one with daily history.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Summarize the stored history of a week or month as Markdown or HTML.
+    Report {
+        /// Location to report on; every stored location by default.
+        address: Option<String>,
+        #[arg(long, value_enum, default_value = "month")]
+        period: ReportPeriod,
+        /// Only use what this provider reported; every provider's by default.
+        #[arg(short, long)]
+        provider: Option<String>,
+        /// File to write instead of printing; a ".html" name implies --html.
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Write HTML rather than Markdown.
+        #[arg(long)]
+        html: bool,
+    },
+    /// Prevailing wind directions and speeds, as a wind rose drawn from the history database.
+    Windrose {
+        /// Location as stored in the history database.
+        address: String,
+        /// How far back to look, e.g. "90d", "12w", "6m" or "1y".
+        #[arg(long, default_value = "90d", value_parser = parse_age)]
+        since: Retention,
+    },
+    /// Projected monthly means for a decade, from downscaled CMIP6 climate models.
+    Climate {
+        address: String,
+        /// First year of the decade, e.g. "2040" or "2040s"; from 1950 to 2040.
+        #[arg(long, value_parser = parse_decade)]
+        decade: i32,
+        /// Provider to ask; needs one with climate projections.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Estimate the fire danger from weather and recent rain.
+    Fire {
+        address: String,
+        /// Provider to ask; needs one with daily history.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Score the coming hours for an outdoor activity.
+    Activity {
+        activity: Activity,
+        address: String,
+        /// Number of hours to score.
+        #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u32).range(1..=168))]
+        hours: u32,
+        /// Provider to ask; needs one with an hourly forecast.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Tell whether an umbrella is needed; exits with 10 when it is.
+    Umbrella {
+        address: String,
+        /// Number of hours to look ahead.
+        #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u32).range(1..=168))]
+        hours: u32,
+        /// Chance of precipitation in percent from which to take one.
+        #[arg(long, default_value_t = 50.0)]
+        threshold: f64,
+        /// Provider to ask; needs one with an hourly forecast.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Hourly forecast with the range the ensemble runs agree on, to judge its confidence.
+    Ensemble {
+        address: String,
+        /// Number of hours to look ahead.
+        #[arg(long, default_value_t = 24, value_parser = clap::value_parser!(u32).range(1..=384))]
+        hours: u32,
+        /// Provider to ask; needs one with ensemble forecasts.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Find the best window today to dry laundry outside.
+    Laundry {
+        address: String,
+        /// Provider to ask; needs one with an hourly forecast.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Warn about frosty nights ahead; notifies and posts to webhooks when there are any.
+    Frost {
+        address: String,
+        /// Warn below this temperature in °C, `[frost] threshold` by default.
+        #[arg(long, allow_negative_numbers = true)]
+        below: Option<f64>,
+        /// Number of nights to look ahead.
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..=14))]
+        nights: u64,
+        /// Provider to ask; needs one with daily forecasts.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Barometric pressure with its tendency over 3 hours, from the readings in the history
+    /// database; run it regularly, e.g. hourly from cron, to build up the history.
+    Pressure {
+        address: String,
+        /// Warn when the pressure falls by at least this many hPa in 3 hours.
+        #[arg(long, value_name = "HPA", default_value_t = pressure::RAPID_DROP_HPA)]
+        drop: f64,
+        /// Registered provider to ask instead of `default_provider`.
+        #[arg(short, long)]
+        provider: Option<String>,
+    },
+    /// Download the latest precipitation radar image around a location, from RainViewer.
+    Radar {
+        address: String,
+        /// PNG file to write.
+        #[arg(long, default_value = "radar.png")]
+        out: PathBuf,
+        /// Map zoom, from 1 for a continent to 7 for a region.
+        #[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u8).range(1..=7))]
+        zoom: u8,
+        /// Registered provider to look up the location with instead of `default_provider`.
+        #[arg(short, long)]
+        provider: Option<String>,
+    },
+    /// Download the latest geostationary satellite image of the region around a location, e.g.
+    /// for a desktop wallpaper.
+    Satellite {
+        address: String,
+        #[arg(long, value_enum, default_value = "infrared")]
+        band: Band,
+        /// PNG file to write.
+        #[arg(long, default_value = "satellite.png")]
+        out: PathBuf,
+        /// Width of the region in degrees of longitude.
+        #[arg(long, default_value_t = 30.0, value_parser = parse_positive)]
+        span: f64,
+        /// Image size in pixels, "WIDTHxHEIGHT".
+        #[arg(long, default_value = "1920x1080", value_parser = parse_size)]
+        size: (u32, u32),
+        /// Registered provider to look up the location with instead of `default_provider`.
+        #[arg(short, long)]
+        provider: Option<String>,
+    },
+    /// Moon phases: the coming ones, or those of a month.
+    Moon {
+        /// Month to list, "YYYY-MM".
+        #[arg(long, value_parser = parse_month)]
+        month: Option<NaiveDate>,
+    },
+    /// Sunrise, sunset, golden and blue hours, and where the sun is now.
+    Sun {
+        /// Place name, or "lat,lon" to skip the provider lookup.
+        address: String,
+        /// Day to show, "YYYY-MM-DD"; today by default.
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Provider used to look up the place.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+        /// Write the sun times of the coming days to this iCalendar file instead.
+        #[arg(long, value_name = "FILE")]
+        ical: Option<PathBuf>,
+        /// Move the calendar events by this much, e.g. "-30m" or "+1h15m".
+        #[arg(long, requires = "ical", allow_hyphen_values = true, value_parser = parse_offset)]
+        offset: Option<chrono::Duration>,
+        /// Events to put in the calendar.
+        #[arg(long, requires = "ical", value_enum, value_delimiter = ',', default_values_t = [SunEvent::Sunrise, SunEvent::Sunset])]
+        events: Vec<SunEvent>,
+        /// Number of days in the calendar, starting with --date.
+        #[arg(long, requires = "ical", default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..=366))]
+        days: u64,
+    },
+    /// Summarize where the weather is heading over the coming days.
+    Trend {
+        address: String,
+        /// Number of days to look ahead.
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..=14))]
+        days: u64,
+        /// Provider to ask; needs one with daily and hourly forecasts.
+        #[arg(long, default_value = "openmeteo")]
+        provider: String,
+    },
+    /// Official weather warnings in effect, most severe first.
+    Alerts {
+        address: String,
+        /// Leave out alerts below this level.
+        #[arg(long, value_enum, default_value_t = Severity::Advisory)]
+        min_severity: Severity,
+        /// Provider to ask; needs one with an alert feed.
+        #[arg(long, default_value = "weatherapi")]
+        provider: String,
+    },
+    /// Serve a REST API backed by the configured providers and cache.
+    Serve {
+        /// Address to listen on.
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: IpAddr,
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Publish the current weather to a message broker.
+    #[command(group(ArgGroup::new("target").required(true).args(["mqtt"])))]
+    Publish {
+        address: String,
+        /// Publish to the `[mqtt]` broker, with Home Assistant discovery.
+        #[arg(long)]
+        mqtt: bool,
+    },
+    /// Replace this binary with the latest release from GitHub, after checking its checksum.
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Only report whether a newer release is available.
+        #[arg(long)]
+        check: bool,
     },
+    /// Tools for working on weather itself.
+    #[command(hide = true)]
+    Dev {
+        #[command(subcommand)]
+        command: DevCommand,
+    },
+}
+
+/// Last day of a `--since`/`--until` period, today when not given.
+fn period_end(since: NaiveDate, until: Option<NaiveDate>) -> Result<NaiveDate, AppError> {
+    let until = until.unwrap_or_else(|| Local::now().date_naive());
+    if until < since {
+        return Err(AppError::InvalidDate(format!(
+            "--until {until} is before --since {since}"
+        )));
+    }
+    Ok(until)
 }
 
-fn parse_datetime(s: &str) -> Result<NaiveDateTime, AppError> {
+pub(crate) fn parse_datetime(s: &str) -> Result<NaiveDateTime, AppError> {
     // RFC3339 format
     if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
         return Ok(dt.with_timezone(&Local).naive_local());
@@ -58,12 +736,119 @@ fn parse_datetime(s: &str) -> Result<NaiveDateTime, AppError> {
     Err(AppError::InvalidDate(s.to_string()))
 }
 
+/// "HH:MM" today, or anything [`parse_datetime`] accepts.
+fn parse_depart(s: &str) -> Result<NaiveDateTime, AppError> {
+    if let Ok(time) = NaiveTime::parse_from_str(s, "%H:%M") {
+        return Ok(Local::now().date_naive().and_time(time));
+    }
+    parse_datetime(s)
+}
+
+/// Parse "YYYY-MM" into the first day of the month.
+fn parse_month(s: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(&format!("{s}-01"), "%Y-%m-%d")
+        .map_err(|_| AppError::InvalidDate(format!("'{s}', expected YYYY-MM")))
+}
+
+/// Decade as the year it starts, within the 1950–2050 span of the climate models.
+fn parse_decade(s: &str) -> Result<i32, String> {
+    match s.trim_end_matches('s').parse::<i32>() {
+        Ok(year) if year % 10 == 0 && (1950..=2040).contains(&year) => Ok(year),
+        _ => Err(format!("'{s}' is not a decade from 1950 to 2040")),
+    }
+}
+
+/// A number of days, weeks, months or years, like `[storage] retention`.
+fn parse_age(s: &str) -> Result<Retention, String> {
+    s.parse()
+        .map_err(|_| format!("'{s}' is not an age like 90d, 12w, 6m or 1y"))
+}
+
+/// Signed hours and minutes, e.g. "-30m", "+1h" or "1h15m", at most a day either way.
+fn parse_offset(s: &str) -> Result<chrono::Duration, String> {
+    const MAX_MINUTES: i64 = 24 * 60;
+    let invalid = || format!("'{s}' is not an offset like -30m, +1h or 1h15m within 24h");
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let mut minutes: i64 = 0;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 'm' => {
+                let value: i64 = number.parse().map_err(|_| invalid())?;
+                let value = if c == 'h' {
+                    value.checked_mul(60)
+                } else {
+                    Some(value)
+                };
+                minutes = value
+                    .and_then(|value| minutes.checked_add(value))
+                    .ok_or_else(invalid)?;
+                number.clear();
+            },
+            _ => return Err(invalid()),
+        }
+    }
+    if !number.is_empty() || rest.is_empty() || minutes > MAX_MINUTES {
+        return Err(invalid());
+    }
+    Ok(chrono::Duration::minutes(sign * minutes))
+}
+
+fn parse_positive(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(value) if value > 0.0 => Ok(value),
+        _ => Err(format!("'{s}' is not a positive number")),
+    }
+}
+
+/// "WIDTHxHEIGHT" in pixels, each from 1 to 8192.
+fn parse_size(s: &str) -> Result<(u32, u32), String> {
+    let size = s
+        .split_once(['x', 'X'])
+        .and_then(|(w, h)| Some((w.trim().parse::<u32>().ok()?, h.trim().parse::<u32>().ok()?)));
+    match size {
+        Some((w, h)) if (1..=8192).contains(&w) && (1..=8192).contains(&h) => Ok((w, h)),
+        _ => Err(format!("'{s}' is not a size like 1920x1080")),
+    }
+}
+
+/// Position of `address`: taken as is when it is "lat,lon", otherwise looked up via `provider`.
+async fn locate(wapp: &WeatherApp, provider: &str, address: &str) -> Result<Coordinates, AppError> {
+    if let Ok(coordinates) = address.parse::<Coordinates>() {
+        return Ok(coordinates);
+    }
+    let coordinates = wapp.run(provider, address, None).await?.coordinates;
+    Ok(coordinates.ok_or_else(|| {
+        ProviderError::Error(format!(
+            "{provider} did not return coordinates for '{address}'"
+        ))
+    })?)
+}
+
+fn parse_timezone(s: &str) -> Result<Tz, AppError> {
+    s.parse::<Tz>()
+        .map_err(|_| AppError::InvalidTimezone(s.to_string()))
+}
+
 pub async fn run(
     cli: Cli,
     wapp: WeatherApp,
-    mut settings: crate::config::Settings,
+    settings: Settings,
+    key: Option<Key>,
 ) -> Result<(), AppError> {
     let config_path = cli.config_path;
+    let mut options = cli.output.options(&settings)?;
+    options.sources = cli.verbose > 0;
+    let formatter = cli.output.formatter(cli.quiet, &settings)?;
+    let webhooks = Targets {
+        json: [settings.webhooks.urls.as_slice(), &cli.post_url].concat(),
+        slack: [settings.webhooks.slack.as_slice(), &cli.post_slack].concat(),
+        discord: [settings.webhooks.discord.as_slice(), &cli.post_discord].concat(),
+    };
 
     if let Some(command) = cli.command {
         match command {
@@ -72,34 +857,1037 @@ pub async fn run(
                     info!("User provider: {}", provider);
                     let provider = provider.to_lowercase();
                     if wapp.provider_exist(&provider) {
-                        settings.default_provider = provider;
-                        save_settings(&settings, &config_path).map_err(AppError::Config)?;
-                        println!("Default provider saved to {}", config_path.display());
+                        save_default_provider(&config_path, &provider).map_err(AppError::Config)?;
+                        println!(
+                            "{}",
+                            options.sanitize(&tr_args(
+                                "default-provider-saved",
+                                &[("path", config_path.display().to_string())]
+                            ))
+                        );
                     } else {
                         warn!("Provider `{provider}` not supported");
-                        eprintln!("Provider `{provider}` not supported");
+                        let list = wapp.list();
+                        let mut message =
+                            tr_args("provider-not-supported", &[("provider", provider.clone())]);
+                        if let Some(closest) =
+                            suggest::closest(&provider, list.iter().map(String::as_str))
+                        {
+                            message.push('\n');
+                            message.push_str(&tr_args(
+                                "did-you-mean",
+                                &[("name", closest.to_string())],
+                            ));
+                        }
+                        eprintln!("{}", options.sanitize(&message));
                     }
                 } else {
-                    println!("Default provider: {}", settings.default_provider);
-                    println!("Available providers: {:?}", wapp.list());
+                    println!(
+                        "{}",
+                        options.sanitize(&tr_args(
+                            "default-provider",
+                            &[("provider", settings.default_provider.clone())]
+                        ))
+                    );
+                    println!(
+                        "{}",
+                        options.sanitize(&tr_args(
+                            "available-providers",
+                            &[("providers", format!("{:?}", wapp.list()))]
+                        ))
+                    );
                 }
             },
-            Commands::Get { address, date } => {
+            Commands::Providers {
+                command: ProvidersCommand::Status,
+            } => {
+                println!("{}", render_status(&wapp.status().await, &options));
+            },
+            Commands::Providers {
+                command: ProvidersCommand::CheckVersions,
+            } => {
+                println!("{}", render_versions(&wapp.api_versions().await, &options));
+            },
+            Commands::Providers {
+                command: ProvidersCommand::Stats,
+            } => {
+                println!("{}", render_stats(&wapp.metrics().snapshot(), &options));
+            },
+            Commands::Config {
+                command: ConfigCommand::Migrate,
+            } => {
+                let message = match migrate::migrate_file(&config_path).map_err(AppError::Config)? {
+                    Some(from) => tr_args(
+                        "config-migrated",
+                        &[
+                            ("path", config_path.display().to_string()),
+                            ("from", from.to_string()),
+                            ("to", SETTINGS_VERSION.to_string()),
+                        ],
+                    ),
+                    None => tr_args(
+                        "config-up-to-date",
+                        &[("path", config_path.display().to_string())],
+                    ),
+                };
+                println!("{}", options.sanitize(&message));
+            },
+            Commands::Config {
+                command: ConfigCommand::Encrypt,
+            } => {
+                let key = match key {
+                    Some(key) => key,
+                    None => Key::resolve(None, true).map_err(AppError::Config)?,
+                };
+                let encrypted = encrypted_path(&config_path);
+                encrypt_settings(&config_path, &encrypted, &key).map_err(AppError::Config)?;
+                println!(
+                    "{}",
+                    options.sanitize(&tr_args(
+                        "config-encrypted",
+                        &[("path", encrypted.display().to_string())]
+                    ))
+                );
+            },
+            Commands::Config {
+                command: ConfigCommand::Decrypt,
+            } => {
+                let encrypted = encrypted_path(&config_path);
+                let key = key.ok_or_else(|| {
+                    AppError::Config(SettingsError::Invalid(format!(
+                        "{} is not encrypted",
+                        config_path.display()
+                    )))
+                })?;
+                decrypt_settings(&encrypted, &config_path, &key).map_err(AppError::Config)?;
+                println!(
+                    "{}",
+                    options.sanitize(&tr_args(
+                        "config-decrypted",
+                        &[("path", config_path.display().to_string())]
+                    ))
+                );
+            },
+            Commands::Store {
+                command: StoreCommand::Prune { older_than },
+            } => {
+                let retention: Retention = older_than
+                    .or(settings.storage.retention)
+                    .ok_or_else(|| {
+                        AppError::Config(SettingsError::Invalid(
+                            "set [storage] retention or pass --older-than".to_string(),
+                        ))
+                    })?
+                    .parse()
+                    .map_err(AppError::Config)?;
+                let path = settings.storage.path.unwrap_or_else(Store::default_path);
+                let store = Store::open(&path)?;
+                let pruned = store.prune(retention.cutoff(Utc::now()))?;
+                store.vacuum()?;
+                println!(
+                    "{}",
+                    options.sanitize(&tr_args(
+                        "store-pruned",
+                        &[
+                            ("count", pruned.to_string()),
+                            ("path", path.display().to_string()),
+                        ]
+                    ))
+                );
+            },
+            Commands::Store {
+                command: StoreCommand::Query { sql, format },
+            } => {
+                let path = settings.storage.path.unwrap_or_else(Store::default_path);
+                let result = Store::open_read_only(&path)?.query(&sql)?;
+                println!("{}", render_query(&result, format, &options));
+            },
+            Commands::Get {
+                address,
+                date,
+                provider,
+                race,
+                fill_from,
+                raw,
+            } => {
+                let provider = provider
+                    .map(|p| p.to_lowercase())
+                    .unwrap_or_else(|| settings.default_provider.clone());
                 debug!("Cli address: {}", address);
                 debug!("Cli date: {:?}", date);
-                debug!("Provider: {:?}", settings.default_provider);
+                debug!("Provider: {:?}", provider);
 
-                let res = wapp.run(&settings.default_provider, &address, date).await?;
+                let fill_provider = fill_from
+                    .map(|p| p.to_lowercase())
+                    .or(settings.fill_provider.clone());
+                if cli.dry_run {
+                    let providers = if race {
+                        wapp.list()
+                    } else {
+                        vec![provider.clone()]
+                    };
+                    let fill = fill_provider
+                        .filter(|p| race || *p != provider)
+                        .filter(|p| wapp.provider_exist(p));
+                    let mut plans = vec![render_order(&providers, race, fill.as_deref(), &options)];
+                    for provider in providers.iter().chain(&fill) {
+                        let plan = wapp.plan(provider, &address, date).await?;
+                        plans.push(render_plan(&plan, &options));
+                    }
+                    println!("{}", plans.join("\n\n"));
+                    return Ok(());
+                }
+                // Any of the raced providers may answer, so the hook is told about them all.
+                let asked = if race {
+                    wapp.list().join(",")
+                } else {
+                    provider.clone()
+                };
+                hooks::pre_fetch(&settings.hooks, &asked, &address).await;
+                let (provider, mut res) = if race {
+                    wapp.race(&address, date).await?
+                } else if let Some(mode) = raw {
+                    let (res, responses) = wapp.run_raw(&provider, &address, date).await;
+                    println!("{}", render_raw(&responses));
+                    let res = res?;
+                    if mode == RawMode::Only {
+                        return Ok(());
+                    }
+                    (provider, res)
+                } else {
+                    let res = wapp.run(&provider, &address, date).await?;
+                    (provider, res)
+                };
+                if let Some(fill_provider) = fill_provider.filter(|p| *p != provider) {
+                    wapp.fill(&mut res, &fill_provider, &address, date).await;
+                }
+                if let Some(tz) = cli.output.tz {
+                    res.apply_timezone(tz);
+                }
+                if options.allergy && date.is_none() {
+                    res.pollen = wapp
+                        .pollen(&provider, &address)
+                        .await
+                        .inspect_err(|e| debug!("No pollen: {e}"))
+                        .ok()
+                        .filter(|pollen| !pollen.is_empty());
+                }
+                // Forecasts and history are neither observations to keep nor comparable with
+                // the averages of observed readings.
+                let store = match date {
+                    None => Store::from_settings(&settings.storage),
+                    Some(_) => Ok(None),
+                };
+                match store {
+                    Ok(Some(store)) => {
+                        if let Err(e) = anomaly::annotate(&store, &mut res)
+                            .and_then(|()| store.record(&provider, &res))
+                        {
+                            warn!("{e}");
+                        }
+                    },
+                    Ok(None) => {},
+                    Err(e) => warn!("{e}"),
+                }
                 debug!("{:#?}", res);
 
-                display_weather_info(&res, &settings.default_provider);
+                println!("{}", formatter.format(&res, &provider, &options));
+                if cli.notify {
+                    notify(&res, &[], &options).await;
+                }
+                webhooks.deliver(&res, &provider, &options).await;
+                hooks::post_fetch(&settings.hooks, &res, &provider).await;
+                if let Some(advisory) = fog::advisory(&res, settings.fog.visibility_km) {
+                    let title = advisory.title();
+                    let text = fog::render_advisory(&advisory, &options);
+                    if cli.notify {
+                        notify::alert(&title, &text).await;
+                    }
+                    webhooks.alert("fog", &res.location, &title, &text).await;
+                }
+                if let Some(warning) = heat::warning(&res, &settings.heat) {
+                    let title = warning.title();
+                    let text = heat::render_warning(&warning, &options);
+                    if cli.notify {
+                        notify::alert(&title, &text).await;
+                    }
+                    webhooks.alert("heat", &res.location, &title, &text).await;
+                }
+                if cli.strict && !res.sources.is_empty() {
+                    return Err(AppError::Degraded(
+                        res.sources
+                            .iter()
+                            .map(|(field, provider)| format!("{field} filled in from {provider}"))
+                            .collect(),
+                    ));
+                }
+            },
+            Commands::Batch {
+                addresses,
+                date,
+                sort,
+                desc,
+                print0,
+            } => {
+                let provider = &settings.default_provider;
+                let mut results = batch::fetch_all(&wapp, provider, &addresses, date).await;
+                if let Some(key) = sort {
+                    batch::sort(&mut results, key, desc);
+                }
+                // One header for all rows, and nothing else on stdout, for pipelines.
+                let tsv = cli.output.format_name(cli.quiet, &settings) == "tsv";
+                let mut options = options.clone();
+                options.header &= !print0;
+                let terminator = if print0 { '\0' } else { '\n' };
+                for (location, res) in &results {
+                    match res {
+                        Ok(res) => {
+                            print!("{}{terminator}", formatter.format(res, provider, &options));
+                            options.header = false;
+                        },
+                        Err(e) => eprintln!("{location}: {e}"),
+                    }
+                }
+                if !tsv && !print0 {
+                    println!(
+                        "\n{}",
+                        batch::render_summary(&batch::Summary::of(&results), &options)
+                    );
+                }
+            },
+            Commands::Diff {
+                address,
+                date,
+                against,
+            } => {
+                let against = against.unwrap_or_else(|| {
+                    date.unwrap_or_else(|| Local::now().naive_local()) - Days::new(1)
+                });
+                let (mut res, mut other) = tokio::try_join!(
+                    wapp.run(&settings.default_provider, &address, date),
+                    wapp.run(&settings.default_provider, &address, Some(against)),
+                )?;
+                if let Some(tz) = cli.output.tz {
+                    res.apply_timezone(tz);
+                    other.apply_timezone(tz);
+                }
+                println!("{}", render_diff(&res, &other, &options));
+            },
+            Commands::Check {
+                address,
+                date,
+                thresholds,
+                email,
+            } => {
+                if cli.dry_run {
+                    let plan = wapp
+                        .plan(&settings.default_provider, &address, date)
+                        .await?;
+                    println!("{}", render_plan(&plan, &options));
+                    return Ok(());
+                }
+                let email = match (email, &settings.email) {
+                    (false, _) => None,
+                    (true, Some(email)) => Some(email),
+                    (true, None) => {
+                        return Err(AppError::Config(SettingsError::Invalid(
+                            "--email requires an [email] section".to_string(),
+                        )));
+                    },
+                };
+
+                let res = wapp.run(&settings.default_provider, &address, date).await?;
+                webhooks
+                    .deliver(&res, &settings.default_provider, &options)
+                    .await;
+                let violations = thresholds.violations(&res, &options);
+                if cli.notify && !violations.is_empty() {
+                    notify(&res, &violations, &options).await;
+                }
+                if let Some(email) = email
+                    && !violations.is_empty()
+                    && let Err(e) =
+                        send_alert(email, &res, &settings.default_provider, &violations).await
+                {
+                    warn!("{e}");
+                }
+                if !violations.is_empty() {
+                    return Err(AppError::ThresholdExceeded(
+                        violations.iter().map(|v| options.sanitize(v)).collect(),
+                    ));
+                }
+            },
+            Commands::Route {
+                from,
+                to,
+                via,
+                depart,
+                speed,
+                step,
+            } => {
+                let trip = Trip {
+                    stops: std::iter::once(from).chain(via).chain([to]).collect(),
+                    depart: depart.unwrap_or_else(|| Local::now().naive_local()),
+                    speed_kph: speed,
+                    step_km: step,
+                };
+
+                let points = route::plan(&wapp, &settings.default_provider, &trip).await?;
+                println!("{}", route::render_route(&points, &options));
+            },
+            Commands::Snow { resort, provider } => {
+                let res = wapp.run(&provider, &resort, None).await?;
+                println!("{}", render_snow(&res, &options));
+            },
+            Commands::Surf { spot, provider } => {
+                let (weather, marine) = tokio::try_join!(
+                    wapp.run(&provider, &spot, None),
+                    wapp.marine(&provider, &spot)
+                )?;
+                println!("{}", surf::render_surf(&weather, &marine, &options));
+            },
+            Commands::Gdd {
+                address,
+                base,
+                since,
+                until,
+                provider,
+            } => {
+                let until = period_end(since, until)?;
+                let days = wapp.daily(&provider, &address, since, until).await?;
+                let (total, missing) = degree_days::growing(&days, base);
+                println!(
+                    "{}",
+                    options.sanitize(&tr_args(
+                        "gdd-total",
+                        &[
+                            ("location", address),
+                            ("start", since.to_string()),
+                            ("end", until.to_string()),
+                            ("base", base.to_string()),
+                            ("total", format!("{total:.1}")),
+                        ],
+                    ))
+                );
+                if missing > 0 {
+                    println!(
+                        "{}",
+                        options
+                            .sanitize(&tr_args("days-missing", &[("count", missing.to_string())]))
+                    );
+                }
+            },
+            Commands::DegreeDays {
+                address,
+                base,
+                since,
+                until,
+                provider,
+            } => {
+                let until = period_end(since, until)?;
+                let days = wapp.daily(&provider, &address, since, until).await?;
+                let months = degree_days::heating_cooling(&days, base);
+                println!("{}", degree_days::render_monthly(&months, &options));
+
+                let missing: usize = months.iter().map(|m| m.missing).sum();
+                if missing > 0 {
+                    println!(
+                        "{}",
+                        options
+                            .sanitize(&tr_args("days-missing", &[("count", missing.to_string())]))
+                    );
+                }
+            },
+            Commands::Backfill {
+                address,
+                from,
+                to,
+                per_minute,
+                provider,
+            } => {
+                let to = to.unwrap_or_else(|| Local::now().date_naive());
+                if to < from {
+                    return Err(AppError::InvalidDate(format!(
+                        "--to {to} is before --from {from}"
+                    )));
+                }
+                // Asked for explicitly, so `[storage] enabled` is not required.
+                let path = settings
+                    .storage
+                    .path
+                    .clone()
+                    .unwrap_or_else(Store::default_path);
+                let mut store = Store::open(&path)?;
+                let show_progress = !cli.quiet && std::io::stderr().is_terminal();
+                let stored = backfill::backfill(
+                    &wapp,
+                    &mut store,
+                    &provider,
+                    &address,
+                    (from, to),
+                    &TokenBucket::per_minute(per_minute),
+                    |done, total| {
+                        if show_progress {
+                            eprint!(
+                                "\r{}",
+                                backfill::render_progress(done, total, options.ascii)
+                            );
+                        }
+                    },
+                )
+                .await;
+                if show_progress {
+                    eprintln!();
+                }
+                let stored = stored?;
+                println!(
+                    "{}",
+                    options.sanitize(&tr_args(
+                        "backfill-done",
+                        &[
+                            ("count", stored.to_string()),
+                            ("location", address),
+                            ("path", path.display().to_string()),
+                        ]
+                    ))
+                );
+            },
+            Commands::Report {
+                address,
+                period,
+                provider,
+                out,
+                html,
+            } => {
+                let path = settings
+                    .storage
+                    .path
+                    .clone()
+                    .unwrap_or_else(Store::default_path);
+                let store = Store::open_read_only(&path)?;
+                let until = Utc::now();
+                let since = until - chrono::Duration::days(period.days() as i64);
+                let locations = match address {
+                    Some(address) => vec![address],
+                    None => store.locations(since, until)?,
+                };
+                let mut reports = Vec::new();
+                for location in locations {
+                    let provider = provider.as_deref();
+                    let readings = store.readings(provider, &location, since, until)?;
+                    let days =
+                        store.days(provider, &location, since.date_naive(), until.date_naive())?;
+                    if !readings.is_empty() || !days.is_empty() {
+                        reports.push(report::summarize(&location, &readings, &days));
+                    }
+                }
+                let is_html = out
+                    .as_ref()
+                    .and_then(|out| out.extension())
+                    .is_some_and(|ext| {
+                        ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm")
+                    });
+                let format = if html || is_html {
+                    ReportFormat::Html
+                } else {
+                    ReportFormat::Markdown
+                };
+                let period = (
+                    since.with_timezone(&Local).date_naive(),
+                    until.with_timezone(&Local).date_naive(),
+                );
+                let text = report::render_report(period, &reports, format, &options);
+                match out {
+                    Some(out) => {
+                        std::fs::write(&out, text + "\n")
+                            .map_err(|e| AppError::Output(out.clone(), e))?;
+                        if !cli.quiet {
+                            println!(
+                                "{}",
+                                options.sanitize(&tr_args(
+                                    "report-saved",
+                                    &[("path", out.display().to_string())]
+                                ))
+                            );
+                        }
+                    },
+                    None => println!("{text}"),
+                }
+            },
+            Commands::Windrose { address, since } => {
+                let path = settings
+                    .storage
+                    .path
+                    .clone()
+                    .unwrap_or_else(Store::default_path);
+                let store = Store::open_read_only(&path)?;
+                let winds = store.winds(&address, since.cutoff(Utc::now()))?;
+                let rose = WindRose::from_winds(&winds);
+                println!(
+                    "{}",
+                    windrose::render_windrose(&address, since.days, &rose, &options)
+                );
+            },
+            Commands::Climate {
+                address,
+                decade,
+                provider,
+            } => {
+                let start = NaiveDate::from_ymd_opt(decade, 1, 1).expect("valid decade");
+                let end = NaiveDate::from_ymd_opt(decade + 9, 12, 31).expect("valid decade");
+                let days = wapp.climate(&provider, &address, start, end).await?;
+                let months = climate::monthly(&days);
+                println!(
+                    "{}",
+                    climate::render_climate(&address, decade, &months, &options)
+                );
+            },
+            Commands::Fire { address, provider } => {
+                let today = Local::now().date_naive();
+                let since = today - Days::new(fire::RAIN_WINDOW_DAYS - 1);
+                let (res, days) = tokio::try_join!(
+                    wapp.run(&provider, &address, None),
+                    wapp.daily(&provider, &address, since, today)
+                )?;
+                let rain: f64 = days.iter().filter_map(|day| day.precipitation_mm).sum();
+                println!("{}", fire::render_fire(&res, rain, &options));
+            },
+            Commands::Activity {
+                activity,
+                address,
+                hours,
+                provider,
+            } => {
+                let forecast = wapp.hourly(&provider, &address, hours).await?;
+                let preferences = activity.preferences(&settings.activities);
+                println!(
+                    "{}",
+                    activity::render_activity(
+                        activity,
+                        &address,
+                        &forecast,
+                        &preferences,
+                        &options
+                    )
+                );
+            },
+            Commands::Umbrella {
+                address,
+                hours,
+                threshold,
+                provider,
+            } => {
+                let forecast = wapp.hourly(&provider, &address, hours).await?;
+                let wet = advice::umbrella(&forecast, threshold);
+                println!("{}", advice::render_umbrella(wet, &forecast, &options));
+                if wet.is_some() {
+                    return Err(AppError::UmbrellaNeeded);
+                }
+            },
+            Commands::Ensemble {
+                address,
+                hours,
+                provider,
+            } => {
+                let forecast = wapp.ensemble(&provider, &address, hours).await?;
+                println!("{}", render_ensemble(&address, &forecast, &options));
+            },
+            Commands::Laundry { address, provider } => {
+                let forecast = wapp.hourly(&provider, &address, 24).await?;
+                println!("{}", laundry::render_laundry(&address, &forecast, &options));
+            },
+            Commands::Frost {
+                address,
+                below,
+                nights,
+                provider,
+            } => {
+                let tomorrow = Local::now().date_naive() + Days::new(1);
+                let until = tomorrow + Days::new(nights - 1);
+                let days = wapp.daily(&provider, &address, tomorrow, until).await?;
+                let frosty = frost::frost_nights(&days, below.unwrap_or(settings.frost.threshold));
+
+                if frosty.is_empty() {
+                    println!(
+                        "{}",
+                        options.sanitize(&tr_args(
+                            "frost-none",
+                            &[("location", address), ("days", nights.to_string())]
+                        ))
+                    );
+                } else {
+                    let title = tr("frost-warning");
+                    let text = frost::render_frost(&address, &frosty, &options);
+                    let banner = if options.emoji { "❄ " } else { "" };
+                    println!(
+                        "{}",
+                        options.sanitize(&format!("{banner}{}", title.to_uppercase()))
+                    );
+                    println!("{text}");
+                    if cli.notify {
+                        notify::alert(&title, &text).await;
+                    }
+                    webhooks.alert("frost", &address, &title, &text).await;
+                }
+            },
+            Commands::Pressure {
+                address,
+                drop,
+                provider,
+            } => {
+                let provider = provider
+                    .map(|p| p.to_lowercase())
+                    .unwrap_or_else(|| settings.default_provider.clone());
+                let res = wapp.run(&provider, &address, None).await?;
+                // The history is the point, so `[storage] enabled` is not required.
+                let path = settings
+                    .storage
+                    .path
+                    .clone()
+                    .unwrap_or_else(Store::default_path);
+                let store = Store::open(&path)?;
+                store.record(&provider, &res)?;
+                let since = res.datetime - chrono::Duration::hours(pressure::HISTORY_HOURS);
+                let readings = store.pressures(&provider, &res.location, since, res.datetime)?;
+                // The provider's own tendency beats one from readings taken whenever this ran.
+                let change = res
+                    .pressure_change_3h
+                    .or_else(|| pressure::change_per_3h(&readings));
+                println!(
+                    "{}",
+                    pressure::render_pressure(&res.location, &readings, change, &options)
+                );
+
+                if let Some(change) = change
+                    && change <= -drop
+                {
+                    let title = tr("pressure-drop-warning");
+                    let text = tr_args(
+                        "pressure-drop",
+                        &[
+                            ("location", res.location.clone()),
+                            ("change", format!("{:.1}", -change)),
+                        ],
+                    );
+                    let banner = if options.emoji { "⚠ " } else { "" };
+                    println!(
+                        "{}",
+                        options.sanitize(&format!("{banner}{}", title.to_uppercase()))
+                    );
+                    println!("{}", options.sanitize(&text));
+                    if cli.notify {
+                        notify::alert(&title, &text).await;
+                    }
+                    webhooks
+                        .alert("pressure", &res.location, &title, &text)
+                        .await;
+                }
+            },
+            Commands::Radar {
+                address,
+                out,
+                zoom,
+                provider,
+            } => {
+                let provider = provider
+                    .map(|p| p.to_lowercase())
+                    .unwrap_or_else(|| settings.default_provider.clone());
+                let coordinates = locate(&wapp, &provider, &address).await?;
+                let image = RainViewer::new()?.latest(coordinates, zoom, 512).await?;
+                std::fs::write(&out, &image.png).map_err(|e| AppError::Output(out.clone(), e))?;
+                if !cli.quiet {
+                    let pattern = format!("{} {}", options.date_format, options.time_format);
+                    let time = image.time.with_timezone(&Local).format(&pattern);
+                    println!(
+                        "{}",
+                        options.sanitize(&tr_args(
+                            "radar-saved",
+                            &[
+                                ("time", time.to_string()),
+                                ("path", out.display().to_string())
+                            ]
+                        ))
+                    );
+                }
+            },
+            Commands::Satellite {
+                address,
+                band,
+                out,
+                span,
+                size,
+                provider,
+            } => {
+                let provider = provider
+                    .map(|p| p.to_lowercase())
+                    .unwrap_or_else(|| settings.default_provider.clone());
+                let coordinates = locate(&wapp, &provider, &address).await?;
+                let image = SatelliteImagery::new()?
+                    .latest(coordinates, band, span, size)
+                    .await?;
+                std::fs::write(&out, &image.png).map_err(|e| AppError::Output(out.clone(), e))?;
+                if !cli.quiet {
+                    println!(
+                        "{}",
+                        options.sanitize(&tr_args(
+                            "satellite-saved",
+                            &[
+                                ("satellite", image.satellite.to_string()),
+                                ("path", out.display().to_string())
+                            ]
+                        ))
+                    );
+                }
+            },
+            Commands::Moon { month } => {
+                let offset = astro::offset_in(cli.output.tz);
+                match month {
+                    Some(month) => {
+                        // Local midnight as an instant.
+                        let start_of = |date: NaiveDate| {
+                            let midnight = date.and_time(NaiveTime::MIN).and_utc();
+                            midnight - offset(midnight)
+                        };
+                        let end = month + Months::new(1);
+                        let events = moon::phases_between(start_of(month), start_of(end));
+                        println!("{}", moon::render_phases(&events, &offset, &options));
+                    },
+                    None => {
+                        let now = Utc::now();
+                        let mut events = moon::phases_between(now, now + Days::new(30));
+                        events.truncate(4);
+                        println!("{}", moon::render_phases(&events, &offset, &options));
+                        println!(
+                            "{}",
+                            options.sanitize(&tr_args(
+                                "moon-now",
+                                &[
+                                    ("phase", tr(moon::phase_name(now))),
+                                    (
+                                        "illumination",
+                                        format!("{:.0}", moon::illumination(now) * 100.0)
+                                    ),
+                                ],
+                            ))
+                        );
+                    },
+                }
+            },
+            Commands::Sun {
+                address,
+                date,
+                provider,
+                ical,
+                offset: shift,
+                events,
+                days,
+            } => {
+                let (location, coordinates, utc_offset) = match address.parse::<Coordinates>() {
+                    Ok(coordinates) => {
+                        // Coordinates alone do not tell the timezone, the provider does unless
+                        // --tz makes asking it unnecessary.
+                        let utc_offset = match cli.output.tz {
+                            Some(_) => None,
+                            None => wapp
+                                .run(&provider, &address, None)
+                                .await
+                                .inspect_err(|e| {
+                                    warn!("Times are in the machine's timezone, {provider} failed: {e}")
+                                })
+                                .ok()
+                                .and_then(|res| res.utc_offset),
+                        };
+                        (address, coordinates, utc_offset)
+                    },
+                    Err(()) => {
+                        let res = wapp.run(&provider, &address, None).await?;
+                        let coordinates = res.coordinates.ok_or_else(|| {
+                            ProviderError::Error(format!(
+                                "{provider} did not return coordinates for '{address}'"
+                            ))
+                        })?;
+                        (res.location, coordinates, res.utc_offset)
+                    },
+                };
+                // The location's own zone unless --tz says otherwise.
+                let local = astro::offset_in(cli.output.tz);
+                let offset = |time| match (cli.output.tz, utc_offset) {
+                    (None, Some(offset)) => offset,
+                    _ => local(time),
+                };
+
+                let now = Utc::now();
+                let date = date.unwrap_or_else(|| now.with_timezone(&offset(now)).date_naive());
+                if let Some(path) = ical {
+                    let calendar = ical::render_ical(
+                        &location,
+                        coordinates,
+                        date,
+                        days,
+                        &events,
+                        shift.unwrap_or_default(),
+                        now,
+                    );
+                    std::fs::write(&path, calendar)
+                        .map_err(|e| AppError::Output(path.clone(), e))?;
+                    if !cli.quiet {
+                        println!(
+                            "{}",
+                            options.sanitize(&tr_args(
+                                "ical-saved",
+                                &[
+                                    ("days", days.to_string()),
+                                    ("path", path.display().to_string())
+                                ]
+                            ))
+                        );
+                    }
+                    return Ok(());
+                }
+                let day = sun::sun_day(coordinates, date);
+                let change = sun::daylight_change(coordinates, date);
+                let position = sun::position(coordinates, now);
+                println!(
+                    "{}",
+                    sun::render_sun(&location, &day, change, position, offset, &options)
+                );
+            },
+            Commands::Trend {
+                address,
+                days,
+                provider,
+            } => {
+                let today = Local::now().date_naive();
+                let until = today + Days::new(days);
+                let (daily, hourly) = tokio::try_join!(
+                    wapp.daily(&provider, &address, today, until),
+                    wapp.hourly(
+                        &provider,
+                        &address,
+                        u32::try_from(days * 24).unwrap_or(u32::MAX)
+                    ),
+                )?;
+                let digest = trend::trend(&daily, &hourly);
+                println!(
+                    "{}",
+                    trend::render_trend(&address, &digest, days, today, &options)
+                );
+            },
+            Commands::Alerts {
+                address,
+                min_severity,
+                provider,
+            } => {
+                let alerts = wapp.alerts(&provider, &address, min_severity).await?;
+                println!("{}", render_alerts(&address, &alerts, &options));
+            },
+            Commands::Serve { bind, port } => {
+                let wapp = Arc::new(wapp);
+                #[cfg(feature = "demo")]
+                let demo = cli.demo;
+                #[cfg(not(feature = "demo"))]
+                let demo = false;
+                // Demo providers stand in for the configured ones and must not be replaced.
+                let (strict, no_cache) = (cli.strict, cli.no_cache);
+                let _watcher = (!demo)
+                    .then(|| {
+                        reload::watch(config_path.clone(), wapp.clone(), move |settings| {
+                            if strict {
+                                settings.parse_mode = ParseMode::Strict;
+                            }
+                            if no_cache {
+                                settings.cache.enabled = false;
+                            }
+                        })
+                    })
+                    .transpose()
+                    .inspect_err(|e| warn!("Settings will not be reloaded: {e}"))
+                    .ok()
+                    .flatten();
+                server::serve(
+                    wapp,
+                    settings.default_provider,
+                    options,
+                    SocketAddr::new(bind, port),
+                )
+                .await
+                .map_err(AppError::Server)?;
+            },
+            Commands::Publish { address, mqtt } => {
+                hooks::pre_fetch(&settings.hooks, &settings.default_provider, &address).await;
+                let res = wapp.run(&settings.default_provider, &address, None).await?;
+                webhooks
+                    .deliver(&res, &settings.default_provider, &options)
+                    .await;
+                hooks::post_fetch(&settings.hooks, &res, &settings.default_provider).await;
+                if mqtt {
+                    mqtt::publish(&settings.mqtt, &res, &settings.default_provider).await?;
+                    info!("Published {} to {}", res.location, settings.mqtt.host);
+                }
+            },
+            #[cfg(feature = "self-update")]
+            Commands::SelfUpdate { check } => {
+                let current = semver::Version::parse(env!("CARGO_PKG_VERSION"))
+                    .expect("the package version is semver");
+                let release = self_update::latest(self_update::RELEASES_URL).await?;
+                let version = release.version()?;
+                let args = [
+                    ("version", version.to_string()),
+                    ("current", current.to_string()),
+                ];
+                let message = if version <= current {
+                    "up-to-date"
+                } else if check {
+                    "update-available"
+                } else {
+                    let binary = self_update::fetch_binary(&release).await?;
+                    let exe = std::env::current_exe()
+                        .map_err(|e| self_update::UpdateError::Install(PathBuf::new(), e))?;
+                    self_update::install(&binary, &exe)?;
+                    "updated"
+                };
+                println!("{}", options.sanitize(&tr_args(message, &args)));
+            },
+            Commands::Dev {
+                command:
+                    DevCommand::RecordFixture {
+                        provider,
+                        location,
+                        dir,
+                    },
+            } => {
+                let (res, responses) = wapp
+                    .run_raw(&provider.to_lowercase(), &location, None)
+                    .await;
+                // Responses the mapping fails on are saved too, they show the schema drift.
+                for response in &responses {
+                    if !response.is_success() {
+                        warn!(
+                            "Not saving the HTTP {} response from {}",
+                            response.status, response.url
+                        );
+                        continue;
+                    }
+                    let path = fixture_path(&dir, response, &location);
+                    let body = serde_json::to_string_pretty(&response.body).unwrap_or_default();
+                    path.parent()
+                        .map_or(Ok(()), std::fs::create_dir_all)
+                        .and_then(|()| std::fs::write(&path, body + "\n"))
+                        .map_err(|e| AppError::Output(path.clone(), e))?;
+                    if !cli.quiet {
+                        println!(
+                            "{}",
+                            options.sanitize(&tr_args(
+                                "fixture-saved",
+                                &[("path", path.display().to_string())]
+                            ))
+                        );
+                    }
+                }
+                res?;
             },
         }
     }
 
     Ok(())
 }
-
-fn display_weather_info(response: &WeatherData, provider: &str) {
-    println!("{}\nProvider: {}", response, provider.to_uppercase());
-}
@@ -0,0 +1,91 @@
+use crate::config::SettingsError;
+use crate::weather_providers::WeatherData;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// User overrides of condition texts, e.g. "Patchy light drizzle" → "Drizzle".
+///
+/// Keys are either a provider's condition text, matched ignoring case, or a WMO weather code
+/// such as "61", which then applies whichever provider reported it.
+#[derive(Debug, Default, Clone)]
+pub struct ConditionMap {
+    by_text: HashMap<String, String>,
+    by_code: HashMap<u8, String>,
+}
+
+impl ConditionMap {
+    /// Read a TOML file of `"from" = "to"` pairs.
+    pub fn load(path: &Path) -> Result<Self, SettingsError> {
+        let invalid =
+            |e: String| SettingsError::Invalid(format!("condition map {}: {e}", path.display()));
+        let text = fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        let entries: HashMap<String, String> =
+            toml::from_str(&text).map_err(|e| invalid(e.to_string()))?;
+        Ok(Self::from_iter(entries))
+    }
+
+    /// Replace `data.condition` when a code or text entry matches, codes first.
+    pub fn apply(&self, data: &mut WeatherData) {
+        let replacement = data
+            .code
+            .and_then(|code| self.by_code.get(&u8::from(code)))
+            .or_else(|| self.by_text.get(&data.condition.trim().to_lowercase()));
+        if let Some(replacement) = replacement {
+            data.condition = replacement.clone();
+        }
+    }
+}
+
+impl<K: AsRef<str>, V: Into<String>> FromIterator<(K, V)> for ConditionMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(entries: I) -> Self {
+        let mut map = Self::default();
+        for (from, to) in entries {
+            let from = from.as_ref().trim();
+            match from.parse::<u8>() {
+                Ok(code) => map.by_code.insert(code, to.into()),
+                Err(_) => map.by_text.insert(from.to_lowercase(), to.into()),
+            };
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::condition::WeatherCode;
+
+    #[test]
+    fn codes_before_texts() {
+        let map: ConditionMap = [
+            ("Patchy light drizzle", "Drizzle"),
+            ("61", "Light rain"),
+            ("slight rain", "ignored"),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut drizzle = WeatherData {
+            condition: "Patchy Light Drizzle".to_string(),
+            ..Default::default()
+        };
+        map.apply(&mut drizzle);
+        assert_eq!(drizzle.condition, "Drizzle");
+
+        let mut rain = WeatherData {
+            condition: "Slight rain".to_string(),
+            code: Some(WeatherCode::SlightRain),
+            ..Default::default()
+        };
+        map.apply(&mut rain);
+        assert_eq!(rain.condition, "Light rain");
+
+        let mut clear = WeatherData {
+            condition: "Clear".to_string(),
+            ..Default::default()
+        };
+        map.apply(&mut clear);
+        assert_eq!(clear.condition, "Clear");
+    }
+}
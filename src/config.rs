@@ -1,10 +1,26 @@
-use config::{Config, File};
+use crate::activity::{Activity, ActivitySettings};
+use crate::output::color::{ColorChoice, ThemeSettings};
+use crate::output::icons::IconSet;
+use crate::output::wind::WindUnit;
+use crate::weather_providers::parse::ParseMode;
 use dotenvy::var;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
-use std::{fs, io, path::PathBuf};
-use tracing::debug;
+use std::path::PathBuf;
+// Reading and writing the settings file, which WebAssembly builds have no use for.
+#[cfg(not(target_arch = "wasm32"))]
+use {
+    crate::encryption::{Key, PASSPHRASE_VAR, encrypted_path},
+    crate::migrate::{self, SETTINGS_VERSION},
+    config::{Config, Environment, File, FileFormat},
+    std::path::Path,
+    std::{fs, io},
+    toml::Table,
+    tracing::{debug, warn},
+};
+
+/// Prefix of the environment variables overriding settings, `__` separating nested keys.
+pub const ENV_PREFIX: &str = "WEATHER";
 
 #[derive(Debug, thiserror::Error)]
 pub enum SettingsError {
@@ -16,17 +32,433 @@ pub enum SettingsError {
 
     #[error("Failed to save settings: {0}")]
     Save(String),
+
+    #[error("Invalid setting: {0}")]
+    Invalid(String),
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ProviderSettings {
+    #[serde(default)]
     pub api_key: String,
+    /// Root of the provider's API, e.g. a self-hosted gateway or a mock server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Requests allowed per minute; further ones wait for their turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+    /// Extra query parameters for the provider's requests, e.g. `aqi = "yes"` for WeatherAPI.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub options: HashMap<String, toml::Value>,
+    /// Model of `weather ensemble`, Open-Meteo only, e.g. "icon_seamless".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ensemble_model: Option<String>,
+}
+
+impl ProviderSettings {
+    /// [`ProviderSettings::options`] as query parameters, sorted by name.
+    pub fn query_options(&self) -> Vec<(String, String)> {
+        let mut options: Vec<(String, String)> = self
+            .options
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    toml::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (name.clone(), value)
+            })
+            .collect();
+        options.sort();
+        options
+    }
+}
+
+/// How results are presented, the `[display]` section.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct DisplaySettings {
+    /// `12h`, `24h` or a strftime pattern, e.g. "%H:%M:%S".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+    /// strftime pattern for dates, e.g. "%d.%m.%Y".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    /// Set to `false` to leave emoji out of the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<bool>,
+    /// Restrict output to pure ASCII.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ascii: bool,
+    /// Render the text output for screen readers.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub accessible: bool,
+    /// Icon set for conditions: `emoji`, `nerd-font` or `text`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icons: Option<IconSet>,
+    /// When to colorize output: `auto`, `always` or `never`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<ColorChoice>,
+    /// Name of a theme from the `[themes]` section, or "default".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Default output format, e.g. `text`, `json` or `table`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Layout for the `template` format, e.g. "{location}: {temp_c}°C".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Add a "what to wear" line to the text output.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub advice: bool,
+    /// Add an allergy risk section to the text output.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allergy: bool,
+    /// Unit wind speeds are primarily shown in: `kmh`, `ms`, `knots`, `mph` or `beaufort`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_unit: Option<WindUnit>,
+    /// TOML file overriding condition texts, e.g. `"Patchy light drizzle" = "Drizzle"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition_map: Option<PathBuf>,
+}
+
+/// Shape of emitted log lines.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line, for journald/ELK ingestion.
+    Json,
+}
+
+/// How often the log file is rotated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Minutely,
+    Hourly,
+    #[default]
+    Daily,
+    Never,
+}
+
+/// Log output, the `[logging]` section.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct LoggingSettings {
+    /// `text` or `json`.
+    #[serde(default)]
+    pub format: LogFormat,
+    /// Write logs to this file instead of stderr.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+    /// `minutely`, `hourly`, `daily` or `never`.
+    #[serde(default)]
+    pub rotation: LogRotation,
+    /// Number of rotated files to keep, all by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<usize>,
+}
+
+/// Result cache, the `[cache]` section.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CacheSettings {
+    #[serde(default = "default_cache_enabled")]
+    pub enabled: bool,
+    /// How long a cached result stays fresh, in seconds.
+    #[serde(default = "default_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Cache directory, the platform cache dir by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<PathBuf>,
+    /// Results `weather serve` keeps in memory in front of the disk cache.
+    #[serde(default = "default_cache_memory_capacity")]
+    pub memory_capacity: u64,
+    /// How long a result stays in memory, in seconds, counted from when it got there.
+    #[serde(default = "default_cache_memory_ttl_secs")]
+    pub memory_ttl_secs: u64,
+    /// How long a looked up place name is trusted, in seconds.
+    #[serde(default = "default_cache_geocode_ttl_secs")]
+    pub geocode_ttl_secs: u64,
+}
+
+fn default_cache_enabled() -> bool {
+    true
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    600
+}
+
+fn default_cache_memory_capacity() -> u64 {
+    10_000
+}
+
+fn default_cache_memory_ttl_secs() -> u64 {
+    60
+}
+
+fn default_cache_geocode_ttl_secs() -> u64 {
+    30 * 24 * 60 * 60
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_cache_enabled(),
+            ttl_secs: default_cache_ttl_secs(),
+            dir: None,
+            memory_capacity: default_cache_memory_capacity(),
+            memory_ttl_secs: default_cache_memory_ttl_secs(),
+            geocode_ttl_secs: default_cache_geocode_ttl_secs(),
+        }
+    }
+}
+
+/// Endpoints results are POSTed to, the `[webhooks]` section.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct WebhookSettings {
+    /// URLs receiving the JSON result after every fetch.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub urls: Vec<String>,
+    /// Slack incoming webhooks receiving a Block Kit message.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slack: Vec<String>,
+    /// Discord channel webhooks receiving an embed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub discord: Vec<String>,
+}
+
+/// Shell commands run around every fetch, the `[hooks]` section.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct HookSettings {
+    /// Run before asking the provider, with `WEATHER_PROVIDER` and `WEATHER_LOCATION` set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_fetch: Option<String>,
+    /// Run after a fetch with the result, as in `--format json`, on stdin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_fetch: Option<String>,
+}
+
+/// SMTP delivery of `weather check --email` alerts, the `[email]` section.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct EmailSettings {
+    pub smtp_host: String,
+    /// SMTP submission port, 587 by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Overridden by the `SMTP_PASSWORD` environment variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Sender, e.g. "Weather <weather@example.com>".
+    pub from: String,
+    pub to: Vec<String>,
+    /// Subject template, e.g. "Weather alert: {location}".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// Body template; `{violations}` lists the matched conditions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// MQTT broker for `weather publish --mqtt`, the `[mqtt]` section.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MqttSettings {
+    #[serde(default = "default_mqtt_host")]
+    pub host: String,
+    #[serde(default = "default_mqtt_port")]
+    pub port: u16,
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Overridden by the `MQTT_PASSWORD` environment variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Home Assistant discovery prefix.
+    #[serde(default = "default_mqtt_discovery_prefix")]
+    pub discovery_prefix: String,
+    /// Readings go to `<topic_prefix>/<location>/state`.
+    #[serde(default = "default_mqtt_topic_prefix")]
+    pub topic_prefix: String,
+}
+
+fn default_mqtt_host() -> String {
+    "localhost".to_string()
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
 }
 
+fn default_mqtt_client_id() -> String {
+    "weather".to_string()
+}
+
+fn default_mqtt_discovery_prefix() -> String {
+    "homeassistant".to_string()
+}
+
+fn default_mqtt_topic_prefix() -> String {
+    "weather".to_string()
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        Self {
+            host: default_mqtt_host(),
+            port: default_mqtt_port(),
+            client_id: default_mqtt_client_id(),
+            username: None,
+            password: None,
+            discovery_prefix: default_mqtt_discovery_prefix(),
+            topic_prefix: default_mqtt_topic_prefix(),
+        }
+    }
+}
+
+/// Frost warnings of `weather frost`, the `[frost]` section.
 #[derive(Debug, Deserialize, Serialize)]
+pub struct FrostSettings {
+    /// Warn when the overnight minimum falls below this temperature, °C.
+    #[serde(default = "default_frost_threshold")]
+    pub threshold: f64,
+}
+
+/// Ground frost is likely well before the air 2 m up reaches 0 °C.
+fn default_frost_threshold() -> f64 {
+    2.0
+}
+
+impl Default for FrostSettings {
+    fn default() -> Self {
+        Self {
+            threshold: default_frost_threshold(),
+        }
+    }
+}
+
+/// Fog advisories in the text output, the `[fog]` section.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FogSettings {
+    /// Advise when visibility drops below this many kilometers.
+    #[serde(default = "default_fog_visibility")]
+    pub visibility_km: f64,
+}
+
+/// Below 1 km is what meteorologists call fog.
+fn default_fog_visibility() -> f64 {
+    1.0
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self {
+            visibility_km: default_fog_visibility(),
+        }
+    }
+}
+
+/// Heat-safety warnings in the text output, the `[heat]` section. Thresholds apply to the
+/// wet-bulb globe temperature (WBGT) in the shade, in °C.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct HeatSettings {
+    /// Warn to take it easy and drink from this WBGT on.
+    #[serde(default = "default_heat_caution")]
+    pub caution: f64,
+    /// Warn to stop strenuous activity outdoors from this WBGT on.
+    #[serde(default = "default_heat_danger")]
+    pub danger: f64,
+}
+
+/// High risk of heat illness during exercise, by the ACSM guidelines.
+fn default_heat_caution() -> f64 {
+    28.0
+}
+
+/// Extreme risk, where the ACSM guidelines call off training and races.
+fn default_heat_danger() -> f64 {
+    32.0
+}
+
+impl Default for HeatSettings {
+    fn default() -> Self {
+        Self {
+            caution: default_heat_caution(),
+            danger: default_heat_danger(),
+        }
+    }
+}
+
+/// Local history of fetched readings, the `[storage]` section.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct StorageSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// SQLite database file, in the platform data dir by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    /// How long rows are kept, e.g. "90d", "12w", "6m" or "2y"; forever when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Settings {
+    /// Layout version of the file, see [`crate::migrate`].
+    #[serde(default)]
+    pub version: u32,
+    /// File loaded with an older layout and its version, brought up to date in memory only.
+    #[serde(skip)]
+    pub outdated: Option<(PathBuf, u32)>,
+    /// Further files merged over this one, relative to it, e.g. `["secrets.toml"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<PathBuf>,
     pub default_provider: String,
+    /// Provider asked for the fields `default_provider` leaves out, e.g. the UV index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fill_provider: Option<String>,
     pub providers: HashMap<String, ProviderSettings>,
+    /// Language of user-facing messages, e.g. "de".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub display: DisplaySettings,
+    #[serde(default)]
+    pub logging: LoggingSettings,
+    #[serde(default)]
+    pub cache: CacheSettings,
+    #[serde(default)]
+    pub webhooks: WebhookSettings,
+    #[serde(default)]
+    pub hooks: HookSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailSettings>,
+    #[serde(default)]
+    pub mqtt: MqttSettings,
+    #[serde(default)]
+    pub frost: FrostSettings,
+    #[serde(default)]
+    pub fog: FogSettings,
+    #[serde(default)]
+    pub heat: HeatSettings,
+    #[serde(default)]
+    pub storage: StorageSettings,
+    /// How to treat provider responses that drifted from the expected schema.
+    #[serde(default)]
+    pub parse_mode: ParseMode,
+    /// User-defined color themes, `[themes.<name>]`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub themes: HashMap<String, ThemeSettings>,
+    /// Personal comfort limits for `weather activity`, `[activities.<name>]`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub activities: HashMap<Activity, ActivitySettings>,
+    /// Shortcuts for command lines, e.g. `home = "get Porto,PT --oneline"`, expanded before the
+    /// command line is parsed, see [`load_aliases`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub aliases: HashMap<String, String>,
 }
 
 impl Settings {
@@ -37,13 +469,30 @@ impl Settings {
         }
         self.providers.get(provider_name).map(|p| p.api_key.clone())
     }
+
+    /// Suggest `weather config migrate` when the file has an older layout. Loading happens before
+    /// logging is set up, so this is left to the caller.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn warn_if_outdated(&self) {
+        if let Some((path, from)) = &self.outdated {
+            warn!(
+                "{} uses settings version {from}, run `weather config migrate` to update it",
+                path.display()
+            );
+        }
+    }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub fn init_settings_file(config_path: &Path) -> io::Result<()> {
-    if !config_path.exists() {
+    if !config_path.exists() && !encrypted_path(config_path).exists() {
+        if let Some(dir) = config_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
         fs::write(
             config_path,
-            r#"default_provider = "weatherapi"
+            r#"version = 1
+default_provider = "weatherapi"
 
 [providers.weatherapi]
 api_key = "YourApiKey"
@@ -56,35 +505,187 @@ api_key = "YourApiKey""#,
     Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 pub fn load_settings(config_path: &Path) -> Result<Settings, SettingsError> {
+    load_settings_with_key(config_path, None)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Like [`load_settings`], falling back to the encrypted `<config_path>.enc` unlocked with `key`
+/// when there is no plaintext file.
+pub fn load_settings_with_key(
+    config_path: &Path,
+    key: Option<&Key>,
+) -> Result<Settings, SettingsError> {
     let mut builder = Config::builder();
 
     builder = builder.set_default("default_provider", "weatherapi")?;
 
     debug!("{:#?}", config_path);
-    if config_path.exists() {
-        builder = builder.add_source(File::from(PathBuf::from(config_path)).required(false));
+    let encrypted = encrypted_path(config_path);
+    let file = if config_path.exists() {
+        Some((migrate::read(config_path)?, config_path))
+    } else if encrypted.exists() {
+        let key = key.ok_or_else(|| {
+            SettingsError::Invalid(format!(
+                "{} is encrypted, set {PASSPHRASE_VAR} or pass --key-file",
+                encrypted.display()
+            ))
+        })?;
+        let content = fs::read(&encrypted).map_err(|e| SettingsError::Invalid(e.to_string()))?;
+        let content = String::from_utf8(key.decrypt(&content)?)
+            .map_err(|e| SettingsError::Invalid(e.to_string()))?;
+        Some((migrate::parse(&content, &encrypted)?, encrypted.as_path()))
+    } else {
+        None
+    };
+    let mut outdated = None;
+    if let Some(((table, from), path)) = file {
+        if from < SETTINGS_VERSION {
+            outdated = Some((path.to_path_buf(), from));
+        }
+        let mut ancestors = vec![fs::canonicalize(path).map_err(|e| invalid_file(path, e))?];
+        let included = includes(&table, path, &mut ancestors)?;
+        for table in std::iter::once(table).chain(included) {
+            let content =
+                toml::to_string(&table).map_err(|e| SettingsError::Invalid(e.to_string()))?;
+            builder = builder.add_source(File::from_str(&content, FileFormat::Toml));
+        }
     }
 
-    if let Ok(provider) = var("DEFAULT_PROVIDER") {
+    // `WEATHER_DEFAULT_PROVIDER`, `WEATHER_PROVIDERS__WEATHERAPI__API_KEY`, `WEATHER_DISPLAY__ASCII`...
+    builder = builder.add_source(
+        Environment::with_prefix(ENV_PREFIX)
+            .prefix_separator("_")
+            .separator("__")
+            .ignore_empty(true),
+    );
+
+    if var(format!("{ENV_PREFIX}_DEFAULT_PROVIDER")).is_err()
+        && let Ok(provider) = var("DEFAULT_PROVIDER")
+    {
         builder = builder.set_override("default_provider", provider)?;
     }
 
     let config = builder.build()?;
-    let settings = config
+    let mut settings = config
         .try_deserialize::<Settings>()
         .map_err(SettingsError::Load)?;
+    settings.outdated = outdated;
 
     Ok(settings)
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+/// `[aliases]` of the plaintext settings file at `config_path`, needed before the command line is
+/// parsed and so before the settings are loaded; empty without such a file or section.
+pub fn load_aliases(config_path: &Path) -> HashMap<String, String> {
+    let Ok((table, _)) = migrate::read(config_path) else {
+        return HashMap::new();
+    };
+    table
+        .get("aliases")
+        .cloned()
+        .and_then(|aliases| aliases.try_into().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// `language` the settings will have, needed before the command line is parsed to show the help
+/// in it: `WEATHER_LANGUAGE`, else the last one of the plaintext settings file at `config_path` and
+/// the files it includes. Encrypted settings are not unlocked for it.
+pub fn load_language(config_path: &Path) -> Option<String> {
+    if let Ok(language) = var(format!("{ENV_PREFIX}_LANGUAGE"))
+        && !language.is_empty()
+    {
+        return Some(language);
+    }
+    let (table, _) = migrate::read(config_path).ok()?;
+    let mut ancestors = fs::canonicalize(config_path).into_iter().collect();
+    let included = includes(&table, config_path, &mut ancestors).unwrap_or_default();
+    std::iter::once(table)
+        .chain(included)
+        .rev()
+        .find_map(|table| table.get("language")?.as_str().map(str::to_string))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn invalid_file(path: &Path, e: impl std::fmt::Display) -> SettingsError {
+    SettingsError::Invalid(format!("{}: {e}", path.display()))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Tables of the files listed in `include` of `table`, which was read from `origin`, each
+/// followed by the ones it includes in turn. Paths are relative to the including file.
+///
+/// `ancestors` are the files including `origin`, itself last, to reject cycles; two files may
+/// include the same third one.
+fn includes(
+    table: &Table,
+    origin: &Path,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<Vec<Table>, SettingsError> {
+    let Some(include) = table.get("include") else {
+        return Ok(Vec::new());
+    };
+    let not_a_list = || invalid_file(origin, "include must be a list of file names");
+    let dir = origin.parent().unwrap_or(Path::new(""));
+
+    let mut tables = Vec::new();
+    for name in include.as_array().ok_or_else(not_a_list)? {
+        let path = dir.join(name.as_str().ok_or_else(not_a_list)?);
+        let canonical = fs::canonicalize(&path).map_err(|e| invalid_file(&path, e))?;
+        if ancestors.contains(&canonical) {
+            return Err(invalid_file(&path, "includes itself"));
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| invalid_file(&path, e))?;
+        let mut included: Table = content.parse().map_err(|e| invalid_file(&path, e))?;
+        ancestors.push(canonical);
+        let nested = includes(&included, &path, ancestors);
+        ancestors.pop();
+        let nested = nested?;
+        // Only the top-level file's list ends up in `Settings::include`.
+        included.remove("include");
+        tables.push(included);
+        tables.extend(nested);
+    }
+    Ok(tables)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Refuse to write plaintext settings to `path` next to its encrypted version.
+fn ensure_not_encrypted(path: &Path) -> Result<(), SettingsError> {
+    if !path.exists() && encrypted_path(path).exists() {
+        return Err(SettingsError::Save(format!(
+            "{} is encrypted, run `weather config decrypt` first",
+            encrypted_path(path).display()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 pub fn save_settings(settings: &Settings, path: &PathBuf) -> Result<(), SettingsError> {
+    ensure_not_encrypted(path)?;
     let toml_settings =
         toml::to_string_pretty(settings).map_err(|e| SettingsError::Save(e.to_string()))?;
     fs::write(path, toml_settings).map_err(|e| SettingsError::Save(e.to_string()))?;
 
     Ok(())
 }
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Set `default_provider` in the settings file at `path`, leaving the rest of it as written, so
+/// values from included files or the environment are not copied into it.
+pub fn save_default_provider(path: &Path, provider: &str) -> Result<(), SettingsError> {
+    ensure_not_encrypted(path)?;
+    let (mut table, _) = migrate::read(path).map_err(|e| SettingsError::Save(e.to_string()))?;
+    table.insert("default_provider".into(), provider.into());
+    let content = toml::to_string_pretty(&table).map_err(|e| SettingsError::Save(e.to_string()))?;
+    fs::write(path, content).map_err(|e| SettingsError::Save(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,10 +708,15 @@ mod tests {
                         test_provider_name.to_string(),
                         ProviderSettings {
                             api_key: "dummy".to_string(),
+                            base_url: None,
+                            requests_per_minute: None,
+                            options: HashMap::new(),
+                            ensemble_model: None,
                         },
                     );
                     m
                 },
+                ..Default::default()
             };
 
             let tmp_path = Path::new("tests/tmp_no_file.toml");
@@ -144,11 +750,16 @@ mod tests {
                 test_provider_name.to_string(),
                 ProviderSettings {
                     api_key: "dummy_api_key".to_string(),
+                    base_url: None,
+                    requests_per_minute: None,
+                    options: HashMap::new(),
+                    ensemble_model: None,
                 },
             );
             let settings = Settings {
                 default_provider: test_provider_name.to_string(),
                 providers,
+                ..Default::default()
             };
             let toml_data = toml::to_string(&settings).unwrap();
             fs::write(tmp_path, toml_data).unwrap();
@@ -163,6 +774,110 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn test_load_settings_env_overrides() {
+        let settings_path = Path::new("tests/settings_env.toml");
+        fs::create_dir_all("tests").unwrap();
+        fs::write(
+            settings_path,
+            "default_provider = \"weatherapi\"\n[providers.weatherapi]\napi_key = \"from_file\"\n",
+        )
+        .unwrap();
+
+        temp_env::with_vars(
+            [
+                ("WEATHER_DEFAULT_PROVIDER", Some("openmeteo")),
+                ("DEFAULT_PROVIDER", Some("openweather")),
+                ("WEATHER_PROVIDERS__WEATHERAPI__API_KEY", Some("from_env")),
+                ("WEATHER_DISPLAY__ASCII", Some("true")),
+            ],
+            || {
+                let s = load_settings(settings_path).unwrap();
+                assert_eq!(s.default_provider, "openmeteo");
+                assert_eq!(s.providers["weatherapi"].api_key, "from_env");
+                assert!(s.display.ascii);
+            },
+        );
+
+        fs::remove_file(settings_path).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_settings_includes() {
+        let dir = Path::new("tests/settings_include");
+        fs::create_dir_all(dir.join("private")).unwrap();
+        fs::write(
+            dir.join("settings.toml"),
+            "include = [\"private/secrets.toml\"]\ndefault_provider = \"weatherapi\"\n\
+             language = \"en\"\n[providers.weatherapi]\napi_key = \"YourApiKey\"\nbase_url = \"http://localhost\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("private/secrets.toml"),
+            "include = [\"locations.toml\"]\n[providers.weatherapi]\napi_key = \"secret\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("private/locations.toml"),
+            "default_provider = \"openmeteo\"\nlanguage = \"de\"\n",
+        )
+        .unwrap();
+
+        let s = load_settings(&dir.join("settings.toml")).unwrap();
+        assert_eq!(s.include, [PathBuf::from("private/secrets.toml")]);
+        assert_eq!(s.default_provider, "openmeteo");
+        assert_eq!(s.language.as_deref(), Some("de"));
+        assert_eq!(
+            load_language(&dir.join("settings.toml")),
+            s.language,
+            "help has to be in the language the settings end up with"
+        );
+        assert_eq!(s.providers["weatherapi"].api_key, "secret");
+        assert_eq!(
+            s.providers["weatherapi"].base_url.as_deref(),
+            Some("http://localhost")
+        );
+
+        fs::write(
+            dir.join("private/locations.toml"),
+            "include = [\"secrets.toml\"]\n",
+        )
+        .unwrap();
+        let err = load_settings(&dir.join("settings.toml")).unwrap_err();
+        assert!(err.to_string().contains("includes itself"), "{err}");
+
+        // Both the top-level file and secrets.toml include locations.toml.
+        fs::write(
+            dir.join("private/locations.toml"),
+            "default_provider = \"openmeteo\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("settings.toml"),
+            "include = [\"private/secrets.toml\", \"private/locations.toml\"]\n",
+        )
+        .unwrap();
+        let s = load_settings(&dir.join("settings.toml")).unwrap();
+        assert_eq!(s.default_provider, "openmeteo");
+        assert_eq!(s.providers["weatherapi"].api_key, "secret");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn default_provider_not_saved_next_to_encrypted_settings() {
+        let dir = std::env::temp_dir().join(format!("weather-save-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("settings.toml.enc"), "").unwrap();
+
+        let err = save_default_provider(&dir.join("settings.toml"), "openmeteo").unwrap_err();
+        assert!(err.to_string().contains("is encrypted"), "{err}");
+        assert!(!dir.join("settings.toml").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
     #[test]
     #[serial]
     fn test_load_settings_from_file() {
@@ -187,4 +902,34 @@ mod tests {
 
         fs::remove_file(settings_path).unwrap();
     }
+
+    #[test]
+    #[serial]
+    fn test_load_display_settings() {
+        let settings_path = Path::new("tests/settings_display_test.toml");
+        fs::write(
+            settings_path,
+            r#"default_provider = "weatherapi"
+[providers.weatherapi]
+api_key = "dummy_api_key"
+[display]
+time_format = "12h"
+date_format = "%d.%m.%Y"
+[logging]
+format = "json"
+file = "logs/weather.log"
+rotation = "hourly"
+"#,
+        )
+        .unwrap();
+
+        let s = load_settings(settings_path).unwrap();
+        assert_eq!(s.display.time_format.as_deref(), Some("12h"));
+        assert_eq!(s.display.date_format.as_deref(), Some("%d.%m.%Y"));
+        assert_eq!(s.logging.format, LogFormat::Json);
+        assert_eq!(s.logging.file, Some(PathBuf::from("logs/weather.log")));
+        assert_eq!(s.logging.rotation, LogRotation::Hourly);
+
+        fs::remove_file(settings_path).unwrap();
+    }
 }
@@ -0,0 +1,148 @@
+use crate::i18n::tr;
+use crate::output::OutputOptions;
+use crate::weather_providers::DailyData;
+use chrono::Datelike;
+
+/// Heating and cooling degree days of one calendar month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthlyDegreeDays {
+    pub year: i32,
+    pub month: u32,
+    /// Sum of how far the daily mean fell below the base.
+    pub heating: f64,
+    /// Sum of how far the daily mean rose above the base.
+    pub cooling: f64,
+    /// Days without temperature records, skipped in the sums.
+    pub missing: usize,
+}
+
+/// Mean of the day's extremes, or `None` when either is missing.
+pub(crate) fn mean_temp(day: &DailyData) -> Option<f64> {
+    Some((day.temp_min_c? + day.temp_max_c?) / 2.0)
+}
+
+/// Growing degree days above `base` °C, by the averaging method.
+///
+/// Returns the sum and the number of days without temperature records, which are skipped.
+pub fn growing(days: &[DailyData], base: f64) -> (f64, usize) {
+    let mut missing = 0;
+    let total = days
+        .iter()
+        .filter_map(|day| {
+            let mean = mean_temp(day);
+            if mean.is_none() {
+                missing += 1;
+            }
+            mean
+        })
+        .map(|mean| (mean - base).max(0.0))
+        .sum();
+    (total, missing)
+}
+
+/// Heating and cooling degree days against `base` °C, per month in date order.
+pub fn heating_cooling(days: &[DailyData], base: f64) -> Vec<MonthlyDegreeDays> {
+    let mut months: Vec<MonthlyDegreeDays> = Vec::new();
+    for day in days {
+        let (year, month) = (day.date.year(), day.date.month());
+        let current = match months.last_mut() {
+            Some(last) if (last.year, last.month) == (year, month) => last,
+            _ => {
+                months.push(MonthlyDegreeDays {
+                    year,
+                    month,
+                    heating: 0.0,
+                    cooling: 0.0,
+                    missing: 0,
+                });
+                months.last_mut().expect("just pushed")
+            },
+        };
+
+        match mean_temp(day) {
+            Some(mean) => {
+                current.heating += (base - mean).max(0.0);
+                current.cooling += (mean - base).max(0.0);
+            },
+            None => current.missing += 1,
+        }
+    }
+    months
+}
+
+/// Table of monthly heating and cooling degree days with a total row.
+pub fn render_monthly(months: &[MonthlyDegreeDays], options: &OutputOptions) -> String {
+    let mut lines = vec![format!(
+        "{:<8} {:>8} {:>8}",
+        "",
+        tr("degree-days-heating"),
+        tr("degree-days-cooling")
+    )];
+    for m in months {
+        lines.push(format!(
+            "{:04}-{:02}  {:>8.1} {:>8.1}",
+            m.year, m.month, m.heating, m.cooling
+        ));
+    }
+    let heating: f64 = months.iter().map(|m| m.heating).sum();
+    let cooling: f64 = months.iter().map(|m| m.cooling).sum();
+    lines.push(format!(
+        "{:<8} {heating:>8.1} {cooling:>8.1}",
+        tr("degree-days-total")
+    ));
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn day(min: Option<f64>, max: Option<f64>) -> DailyData {
+        on(2024, 4, 1, min, max)
+    }
+
+    fn on(year: i32, month: u32, d: u32, min: Option<f64>, max: Option<f64>) -> DailyData {
+        DailyData {
+            date: NaiveDate::from_ymd_opt(year, month, d).unwrap(),
+            temp_min_c: min,
+            temp_max_c: max,
+            precipitation_mm: None,
+        }
+    }
+
+    #[test]
+    fn growing_degree_days() {
+        let days = [
+            day(Some(8.0), Some(20.0)),
+            day(Some(2.0), Some(12.0)),
+            day(None, Some(25.0)),
+            day(Some(15.0), Some(25.0)),
+        ];
+
+        assert_eq!(growing(&days, 10.0), (14.0, 1));
+    }
+
+    #[test]
+    fn monthly_heating_and_cooling() {
+        let days = [
+            on(2024, 1, 30, Some(0.0), Some(10.0)),
+            on(2024, 1, 31, None, None),
+            on(2024, 2, 1, Some(20.0), Some(30.0)),
+            on(2024, 2, 2, Some(10.0), Some(16.0)),
+        ];
+
+        let months = heating_cooling(&days, 18.0);
+
+        assert_eq!(months.len(), 2);
+        assert_eq!(
+            (months[0].month, months[0].heating, months[0].missing),
+            (1, 13.0, 1)
+        );
+        assert_eq!((months[1].heating, months[1].cooling), (5.0, 7.0));
+        assert!(
+            render_monthly(&months, &OutputOptions::default())
+                .ends_with("Total        18.0      7.0")
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::config::EmailSettings;
+use crate::output::json::to_json;
+use crate::output::template::render_template;
+use crate::weather_providers::WeatherData;
+use dotenvy::var;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde_json::{Value, json};
+
+const DEFAULT_SUBJECT: &str = "Weather alert: {location}";
+const DEFAULT_BODY: &str = "{location}: {condition}, {temp_c}°C\n\n{violations}";
+
+#[derive(Debug, thiserror::Error)]
+pub enum EmailError {
+    #[error("Invalid email address: {0}")]
+    Address(#[from] lettre::address::AddressError),
+
+    #[error("Failed to build email: {0}")]
+    Message(#[from] lettre::error::Error),
+
+    #[error("Failed to send email: {0}")]
+    Smtp(#[from] lettre::transport::smtp::Error),
+}
+
+/// Render the alert for `data` from the `[email]` subject and body templates.
+///
+/// Besides the JSON output fields, templates can use `{violations}`, one matched condition per line.
+fn build_message(
+    settings: &EmailSettings,
+    data: &WeatherData,
+    provider: &str,
+    violations: &[String],
+) -> Result<Message, EmailError> {
+    let mut values = to_json(data, provider);
+    if let Value::Object(map) = &mut values {
+        map.insert("violations".to_string(), json!(violations.join("\n")));
+    }
+
+    let subject = settings.subject.as_deref().unwrap_or(DEFAULT_SUBJECT);
+    let body = settings.body.as_deref().unwrap_or(DEFAULT_BODY);
+
+    let mut message = Message::builder()
+        .from(settings.from.parse::<Mailbox>()?)
+        .subject(render_template(subject, &values));
+    for to in &settings.to {
+        message = message.to(to.parse::<Mailbox>()?);
+    }
+
+    Ok(message.body(render_template(body, &values))?)
+}
+
+/// Send an alert email over SMTP with STARTTLS.
+///
+/// The password is read from `SMTP_PASSWORD` first, then from the settings.
+pub async fn send_alert(
+    settings: &EmailSettings,
+    data: &WeatherData,
+    provider: &str,
+    violations: &[String],
+) -> Result<(), EmailError> {
+    let message = build_message(settings, data, provider, violations)?;
+
+    let mut transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&settings.smtp_host)?;
+    if let Some(port) = settings.smtp_port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = &settings.username {
+        let password = var("SMTP_PASSWORD")
+            .ok()
+            .or_else(|| settings.password.clone())
+            .unwrap_or_default();
+        transport = transport.credentials(Credentials::new(username.clone(), password));
+    }
+
+    transport.build().send(message).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> EmailSettings {
+        EmailSettings {
+            smtp_host: "smtp.example.com".to_string(),
+            from: "Weather <weather@example.com>".to_string(),
+            to: vec!["me@example.com".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_templates() {
+        let data = WeatherData {
+            location: "Porto".to_string(),
+            condition: "Frost".to_string(),
+            temp_c: -2.0,
+            ..Default::default()
+        };
+        let violations = ["Temperature -2.0°C is below 0.0°C".to_string()];
+
+        let message = build_message(&settings(), &data, "weatherapi", &violations).unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+
+        assert!(formatted.contains("Subject: Weather alert: Porto"));
+        assert!(formatted.contains("To: me@example.com"));
+        assert!(formatted.contains("Temperature -2.0"));
+    }
+
+    #[test]
+    fn invalid_address() {
+        let settings = EmailSettings {
+            from: "not an address".to_string(),
+            ..settings()
+        };
+
+        assert!(matches!(
+            build_message(&settings, &WeatherData::default(), "weatherapi", &[]),
+            Err(EmailError::Address(_))
+        ));
+    }
+}
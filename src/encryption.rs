@@ -0,0 +1,148 @@
+use crate::config::SettingsError;
+use age::secrecy::SecretString;
+use age::{Decryptor, Encryptor, IdentityFile};
+use std::ffi::OsString;
+use std::fs;
+use std::io::{Read, Write};
+use std::iter;
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the passphrase, for when nobody is there to type it.
+pub const PASSPHRASE_VAR: &str = "WEATHER_PASSPHRASE";
+
+/// Where the encrypted copy of the settings file at `path` lives, e.g. `settings.toml.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+fn identity_file(path: &Path) -> Result<IdentityFile<age::NoCallbacks>, SettingsError> {
+    IdentityFile::from_file(path.display().to_string())
+        .map_err(|e| SettingsError::Invalid(format!("{}: {e}", path.display())))
+}
+
+/// What unlocks an encrypted settings file.
+pub enum Key {
+    /// Encrypted with scrypt, slow on purpose to resist guessing.
+    Passphrase(SecretString),
+    /// An age identity file, as made by `age-keygen -o key.txt`.
+    File(PathBuf),
+}
+
+impl Key {
+    /// The key file if one is given, else the passphrase from [`PASSPHRASE_VAR`] or typed in.
+    pub fn resolve(key_file: Option<PathBuf>, confirm: bool) -> Result<Self, SettingsError> {
+        if let Some(path) = key_file {
+            return Ok(Self::File(path));
+        }
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_VAR) {
+            return Ok(Self::Passphrase(passphrase.into()));
+        }
+
+        let read = |prompt: &str| {
+            rpassword::prompt_password(prompt).map_err(|e| {
+                SettingsError::Invalid(format!(
+                    "cannot read the settings passphrase ({e}), set {PASSPHRASE_VAR} or pass --key-file"
+                ))
+            })
+        };
+        let passphrase = read("Settings passphrase: ")?;
+        if confirm && read("Repeat passphrase: ")? != passphrase {
+            return Err(SettingsError::Invalid("passphrases do not match".into()));
+        }
+        Ok(Self::Passphrase(passphrase.into()))
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, SettingsError> {
+        let save = |e: &dyn std::fmt::Display| SettingsError::Save(e.to_string());
+        let encryptor = match self {
+            Self::Passphrase(passphrase) => Encryptor::with_user_passphrase(passphrase.clone()),
+            Self::File(path) => {
+                let recipients = identity_file(path)?.to_recipients().map_err(|e| save(&e))?;
+                Encryptor::with_recipients(recipients.iter().map(|r| r.as_ref() as _))
+                    .map_err(|e| save(&e))?
+            },
+        };
+
+        let mut ciphertext = Vec::new();
+        let mut writer = encryptor
+            .wrap_output(&mut ciphertext)
+            .map_err(|e| save(&e))?;
+        writer.write_all(plaintext).map_err(|e| save(&e))?;
+        writer.finish().map_err(|e| save(&e))?;
+        Ok(ciphertext)
+    }
+
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, SettingsError> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            SettingsError::Invalid(format!("cannot decrypt the settings: {e}"))
+        };
+        let decryptor = Decryptor::new(ciphertext).map_err(|e| invalid(&e))?;
+        let mut reader = match self {
+            Self::Passphrase(passphrase) => {
+                let identity = age::scrypt::Identity::new(passphrase.clone());
+                decryptor.decrypt(iter::once(&identity as _))
+            },
+            Self::File(path) => {
+                let identities = identity_file(path)?
+                    .into_identities()
+                    .map_err(|e| invalid(&e))?;
+                decryptor.decrypt(identities.iter().map(|i| i.as_ref() as _))
+            },
+        }
+        .map_err(|e| invalid(&e))?;
+
+        let mut plaintext = Vec::new();
+        reader
+            .read_to_end(&mut plaintext)
+            .map_err(|e| invalid(&e))?;
+        Ok(plaintext)
+    }
+}
+
+/// Encrypt the settings file `plain` to `encrypted`, then remove the plaintext.
+pub fn encrypt_settings(plain: &Path, encrypted: &Path, key: &Key) -> Result<(), SettingsError> {
+    let content =
+        fs::read(plain).map_err(|e| SettingsError::Save(format!("{}: {e}", plain.display())))?;
+    fs::write(encrypted, key.encrypt(&content)?).map_err(|e| SettingsError::Save(e.to_string()))?;
+    fs::remove_file(plain).map_err(|e| SettingsError::Save(e.to_string()))
+}
+
+/// Write the settings in `encrypted` back to the plaintext file `plain`, then remove the encrypted one.
+pub fn decrypt_settings(encrypted: &Path, plain: &Path, key: &Key) -> Result<(), SettingsError> {
+    let content = fs::read(encrypted)
+        .map_err(|e| SettingsError::Save(format!("{}: {e}", encrypted.display())))?;
+    fs::write(plain, key.decrypt(&content)?).map_err(|e| SettingsError::Save(e.to_string()))?;
+    fs::remove_file(encrypted).map_err(|e| SettingsError::Save(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::ExposeSecret;
+
+    #[test]
+    fn round_trip_with_key_file() {
+        let path = std::env::temp_dir().join(format!("weather-key-{}.txt", std::process::id()));
+        let identity = age::x25519::Identity::generate();
+        std::fs::write(&path, identity.to_string().expose_secret()).unwrap();
+        let key = Key::File(path.clone());
+
+        let ciphertext = key
+            .encrypt(b"[providers.weatherapi]\napi_key = \"secret\"\n")
+            .unwrap();
+        let plaintext = key.decrypt(&ciphertext);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(!String::from_utf8_lossy(&ciphertext).contains("secret"));
+        assert_eq!(
+            plaintext.unwrap(),
+            b"[providers.weatherapi]\napi_key = \"secret\"\n"
+        );
+        assert_eq!(
+            encrypted_path(Path::new("settings.toml")),
+            Path::new("settings.toml.enc")
+        );
+    }
+}
@@ -1,4 +1,8 @@
 use crate::config::SettingsError;
+use crate::mqtt::MqttError;
+use crate::store::StoreError;
+use crate::weather_providers::error::ProviderError;
+use std::path::PathBuf;
 
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
@@ -11,6 +15,128 @@ pub enum AppError {
     #[error("Invalid date: {0}")]
     InvalidDate(String),
 
+    #[error("Invalid output format: {0}")]
+    InvalidFormat(String),
+
+    #[error("Invalid timezone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("Failed to fetch weather: {0}")]
+    Provider(#[from] ProviderError),
+
     #[error("Missing API key: {0}")]
     MissingApiKey(String),
+
+    #[error("Failed to publish: {0}")]
+    Mqtt(Box<MqttError>),
+
+    #[error("Server error: {0}")]
+    Server(std::io::Error),
+
+    #[error("{0}")]
+    Store(#[from] StoreError),
+
+    #[error("Failed to write {path}: {source}", path = .0.display(), source = .1)]
+    Output(PathBuf, std::io::Error),
+
+    #[error("Threshold exceeded: {}", .0.join("; "))]
+    ThresholdExceeded(Vec<String>),
+
+    #[error("Data quality degraded: {}", .0.join("; "))]
+    Degraded(Vec<String>),
+
+    #[cfg(feature = "self-update")]
+    #[error("Update failed: {0}")]
+    Update(#[from] crate::self_update::UpdateError),
+
+    /// Answer of `weather umbrella`, already printed.
+    #[error("Umbrella needed")]
+    UmbrellaNeeded,
+}
+
+impl From<MqttError> for AppError {
+    fn from(e: MqttError) -> Self {
+        Self::Mqtt(Box::new(e))
+    }
+}
+
+/// Process exit codes, stable so scripts can branch on them.
+pub mod exit_code {
+    /// A `weather check` condition holds.
+    pub const THRESHOLD_EXCEEDED: u8 = 1;
+    /// Invalid command line input, same as clap's own usage errors.
+    pub const USAGE: u8 = 2;
+    /// Settings could not be loaded, saved or are incomplete.
+    pub const CONFIG: u8 = 3;
+    /// The provider rejected or failed the request.
+    pub const PROVIDER: u8 = 4;
+    /// The provider does not know the requested location.
+    pub const LOCATION_NOT_FOUND: u8 = 5;
+    /// The provider could not be reached.
+    pub const NETWORK: u8 = 6;
+    /// With `--strict`, the result came with a warning.
+    pub const DEGRADED: u8 = 7;
+    /// `weather umbrella` expects rain.
+    pub const UMBRELLA_NEEDED: u8 = 10;
+}
+
+impl AppError {
+    /// Exit code reported when this error ends the process.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::ThresholdExceeded(_) => exit_code::THRESHOLD_EXCEEDED,
+            Self::UmbrellaNeeded => exit_code::UMBRELLA_NEEDED,
+            Self::Degraded(_) => exit_code::DEGRADED,
+            Self::Config(_)
+            | Self::InvalidProvider(_)
+            | Self::MissingApiKey(_)
+            | Self::Store(_)
+            | Self::Output(..) => exit_code::CONFIG,
+            Self::InvalidDate(_) | Self::InvalidFormat(_) | Self::InvalidTimezone(_) => {
+                exit_code::USAGE
+            },
+            Self::Mqtt(_) | Self::Server(_) => exit_code::NETWORK,
+            #[cfg(feature = "self-update")]
+            Self::Update(e) => match e {
+                crate::self_update::UpdateError::Request(_) => exit_code::NETWORK,
+                _ => exit_code::CONFIG,
+            },
+            Self::Provider(e) => match e {
+                ProviderError::LocationNotFound { .. } | ProviderError::InvalidLocation(_) => {
+                    exit_code::LOCATION_NOT_FOUND
+                },
+                ProviderError::Timeout { .. }
+                | ProviderError::Network { .. }
+                | ProviderError::Request(_) => exit_code::NETWORK,
+                _ => exit_code::PROVIDER,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn provider_errors_have_distinct_exit_codes() {
+        let not_found = AppError::Provider(ProviderError::LocationNotFound {
+            provider: "p".to_string(),
+            location: "Nowhere".to_string(),
+        });
+        let timeout = AppError::Provider(ProviderError::Timeout {
+            provider: "p".to_string(),
+        });
+        let quota = AppError::Provider(ProviderError::QuotaExceeded {
+            provider: "p".to_string(),
+        });
+
+        assert_eq!(not_found.exit_code(), exit_code::LOCATION_NOT_FOUND);
+        assert_eq!(timeout.exit_code(), exit_code::NETWORK);
+        assert_eq!(quota.exit_code(), exit_code::PROVIDER);
+        assert_eq!(
+            AppError::MissingApiKey("p".to_string()).exit_code(),
+            exit_code::CONFIG
+        );
+    }
 }
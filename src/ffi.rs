@@ -0,0 +1,144 @@
+//! C API, built with `--features ffi`. The header is `include/weather.h`, regenerated with
+//! `cbindgen --config cbindgen.toml --output include/weather.h`.
+
+use crate::app::WeatherApp;
+use crate::commands::default_settings_path;
+use crate::config::load_settings_with_key;
+use crate::encryption::{Key, PASSPHRASE_VAR};
+use crate::errors::{AppError, exit_code};
+use crate::output::json::to_json;
+use crate::provider_registry::build_registry;
+use crate::weather_providers::error::ProviderError;
+use serde_json::json;
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::panic::{self, UnwindSafe};
+use std::ptr;
+
+/// Returned when the call panicked, the exit code of a panicking Rust program.
+const PANICKED: c_int = 101;
+
+fn fetch(provider: &str, location: &str) -> Result<String, AppError> {
+    // Never prompt for the passphrase of encrypted settings, the caller may have no terminal.
+    let key = std::env::var(PASSPHRASE_VAR)
+        .ok()
+        .map(|passphrase| Key::Passphrase(passphrase.into()));
+    let settings =
+        load_settings_with_key(&default_settings_path(), key.as_ref()).map_err(AppError::Config)?;
+    let app = WeatherApp::builder()
+        .with_registry(build_registry(&settings)?)
+        .build();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| ProviderError::Error(format!("Failed to start runtime: {e}")))?;
+
+    let data = runtime.block_on(app.run(provider, location, None))?;
+    Ok(to_json(&data, provider).to_string())
+}
+
+/// Error text for the caller. Settings errors can quote the settings file, API keys included, so
+/// only their kind is passed on.
+fn error_message(e: &AppError) -> String {
+    match e {
+        AppError::Config(_) => "Configuration error, run `weather` for details".to_string(),
+        e => e.to_string(),
+    }
+}
+
+/// `(json, code)` for `weather_fetch`, with a panic in `f` reported as an error instead of
+/// unwinding into the caller.
+fn guarded(f: impl FnOnce() -> Result<String, AppError> + UnwindSafe) -> (String, c_int) {
+    match panic::catch_unwind(f) {
+        Ok(Ok(json)) => (json, 0),
+        Ok(Err(e)) => (
+            json!({ "error": error_message(&e) }).to_string(),
+            c_int::from(e.exit_code()),
+        ),
+        Err(_) => (json!({ "error": "internal error" }).to_string(), PANICKED),
+    }
+}
+
+/// Fetch the current weather at `location` from `provider`, with the API keys from the settings
+/// file and environment the CLI would use.
+///
+/// On return `*out_json` holds the result as printed by `weather --format json`, or
+/// `{"error": "..."}`; release it with [`weather_string_free`]. Returns 0 on success, otherwise
+/// the exit code the CLI would end with, or 101 if the call panicked. The passphrase of encrypted
+/// settings is read from `WEATHER_PASSPHRASE`. Do not call it from a thread running a Tokio
+/// runtime.
+///
+/// # Safety
+///
+/// `provider` and `location` must be NUL-terminated strings or null, `out_json` a valid pointer
+/// or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn weather_fetch(
+    provider: *const c_char,
+    location: *const c_char,
+    out_json: *mut *mut c_char,
+) -> c_int {
+    let arg = |p: *const c_char| {
+        // SAFETY: non-null pointers are NUL-terminated strings, per the contract above.
+        (!p.is_null())
+            .then(|| unsafe { CStr::from_ptr(p) }.to_str().ok())
+            .flatten()
+    };
+    let (json, code) = match (arg(provider), arg(location)) {
+        (Some(provider), Some(location)) => guarded(|| fetch(provider, location)),
+        _ => (
+            json!({ "error": "provider and location must be UTF-8 strings" }).to_string(),
+            c_int::from(exit_code::USAGE),
+        ),
+    };
+
+    if !out_json.is_null() {
+        // SAFETY: `out_json` is valid to write, per the contract above.
+        unsafe { *out_json = CString::new(json).map_or(ptr::null_mut(), CString::into_raw) };
+    }
+    code
+}
+
+/// Release a string returned by [`weather_fetch`]; null is ignored.
+///
+/// # Safety
+///
+/// `s` must come from [`weather_fetch`] and not have been freed before.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn weather_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: `s` was made by `CString::into_raw`, per the contract above.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SettingsError;
+
+    #[test]
+    fn invalid_arguments_are_reported() {
+        let mut out = ptr::null_mut();
+        let code = unsafe { weather_fetch(c"openmeteo".as_ptr(), ptr::null(), &mut out) };
+        assert_eq!(code, c_int::from(exit_code::USAGE));
+
+        let json = unsafe { CStr::from_ptr(out) }.to_str().unwrap().to_string();
+        unsafe { weather_string_free(out) };
+        assert!(json.contains("\"error\""), "{json}");
+    }
+
+    #[test]
+    fn panics_and_settings_errors_are_reported() {
+        let (json, code) = guarded(|| panic!("block_on inside a runtime"));
+        assert_eq!(code, PANICKED);
+        assert_eq!(json, r#"{"error":"internal error"}"#);
+
+        let (json, code) = guarded(|| {
+            Err(AppError::Config(SettingsError::Invalid(
+                "api_key = \"secret\"".to_string(),
+            )))
+        });
+        assert_eq!(code, c_int::from(exit_code::CONFIG));
+        assert!(!json.contains("secret"), "{json}");
+    }
+}
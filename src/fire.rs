@@ -0,0 +1,136 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+
+/// Days of precipitation considered by the drought factor.
+pub const RAIN_WINDOW_DAYS: u64 = 7;
+
+/// Danger classes of the McArthur Forest Fire Danger Index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FireDanger {
+    Low,
+    High,
+    VeryHigh,
+    Severe,
+    Extreme,
+    Catastrophic,
+}
+
+impl FireDanger {
+    pub fn from_index(index: f64) -> Self {
+        match index {
+            i if i < 12.0 => Self::Low,
+            i if i < 25.0 => Self::High,
+            i if i < 50.0 => Self::VeryHigh,
+            i if i < 75.0 => Self::Severe,
+            i if i < 100.0 => Self::Extreme,
+            _ => Self::Catastrophic,
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Self::Low => "fire-low",
+            Self::High => "fire-high",
+            Self::VeryHigh => "fire-very-high",
+            Self::Severe => "fire-severe",
+            Self::Extreme => "fire-extreme",
+            Self::Catastrophic => "fire-catastrophic",
+        }
+    }
+}
+
+/// Drought factor (1–10) from precipitation over the last [`RAIN_WINDOW_DAYS`].
+///
+/// A simplification of the Keetch-Byram based factor: fuel is fully dry after a rainless week
+/// and every 10 mm of rain wets it by roughly two thirds.
+pub fn drought_factor(rain_mm: f64) -> f64 {
+    (10.0 * (-rain_mm.max(0.0) / 10.0).exp()).clamp(1.0, 10.0)
+}
+
+/// McArthur Forest Fire Danger Index (Mark 5).
+pub fn danger_index(temp_c: f64, humidity: f64, wind_kph: f64, rain_mm: f64) -> f64 {
+    2.0 * (-0.450 + 0.987 * drought_factor(rain_mm).ln() - 0.0345 * humidity
+        + 0.0338 * temp_c
+        + 0.0234 * wind_kph)
+        .exp()
+}
+
+/// Fire danger report for `weather fire`, led by a warning banner from `High` on.
+pub fn render_fire(data: &WeatherData, rain_mm: f64, options: &OutputOptions) -> String {
+    let index = danger_index(data.temp_c, data.humidity, data.wind_kph, rain_mm);
+    let danger = FireDanger::from_index(index);
+
+    let mut lines = Vec::new();
+    if danger >= FireDanger::High {
+        let warning = tr_args(
+            "fire-warning",
+            &[("danger", tr(danger.key()).to_uppercase())],
+        );
+        lines.push(if options.emoji {
+            format!("⚠ {warning}")
+        } else {
+            warning
+        });
+    }
+    lines.push(tr_args(
+        "fire-index",
+        &[
+            ("location", data.location.clone()),
+            ("index", format!("{index:.0}")),
+            ("danger", tr(danger.key())),
+        ],
+    ));
+    lines.push(tr_args(
+        "fire-inputs",
+        &[
+            ("temp", options.temperature(data.temp_c)),
+            ("humidity", format!("{:.0}", data.humidity)),
+            ("wind", options.wind_speed(data.wind_kph)),
+            ("rain", format!("{rain_mm:.1}")),
+            ("days", RAIN_WINDOW_DAYS.to_string()),
+        ],
+    ));
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_follows_conditions() {
+        // Hot, dry and windy after a rainless week.
+        let extreme = danger_index(40.0, 10.0, 50.0, 0.0);
+        assert_eq!(FireDanger::from_index(extreme), FireDanger::Catastrophic);
+
+        // Mild and humid after rain.
+        let mild = danger_index(18.0, 80.0, 10.0, 25.0);
+        assert_eq!(FireDanger::from_index(mild), FireDanger::Low);
+
+        assert_eq!(drought_factor(0.0), 10.0);
+        assert_eq!(drought_factor(100.0), 1.0);
+    }
+
+    #[test]
+    fn banner_only_when_high() {
+        let options = OutputOptions {
+            emoji: false,
+            ..Default::default()
+        };
+        let hot = WeatherData {
+            temp_c: 38.0,
+            humidity: 15.0,
+            wind_kph: 35.0,
+            ..Default::default()
+        };
+        let cool = WeatherData {
+            temp_c: 12.0,
+            humidity: 90.0,
+            ..Default::default()
+        };
+
+        assert!(render_fire(&hot, 0.0, &options).starts_with("Fire danger"));
+        assert!(render_fire(&cool, 20.0, &options).starts_with("Fire index"));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+use crate::weather_providers::condition::ConditionKind;
+
+/// Relative humidity from which poor visibility is put down to fog rather than haze or smoke.
+const FOG_HUMIDITY: f64 = 90.0;
+
+/// Visibility poor enough to mention.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FogAdvisory {
+    /// `None` when the provider reports fog but no visibility.
+    pub visibility_km: Option<f64>,
+    /// Humid enough for fog, rather than dry haze or smoke.
+    pub fog: bool,
+}
+
+impl FogAdvisory {
+    pub fn title(&self) -> String {
+        tr(if self.fog {
+            "fog-advisory"
+        } else {
+            "visibility-advisory"
+        })
+    }
+}
+
+/// An advisory when visibility in `data` is below `threshold_km`, or, without a visibility reading,
+/// when the condition is fog.
+pub fn advisory(data: &WeatherData, threshold_km: f64) -> Option<FogAdvisory> {
+    match data.visibility_km {
+        Some(km) if km < threshold_km => Some(FogAdvisory {
+            visibility_km: Some(km),
+            fog: data.humidity >= FOG_HUMIDITY || data.kind == ConditionKind::Fog,
+        }),
+        Some(_) => None,
+        None => (data.kind == ConditionKind::Fog).then_some(FogAdvisory {
+            visibility_km: None,
+            fog: true,
+        }),
+    }
+}
+
+/// "Fog advisory: visibility 0.3 km".
+pub fn render_advisory(advisory: &FogAdvisory, options: &OutputOptions) -> String {
+    let text = match advisory.visibility_km {
+        Some(km) => tr_args(
+            "advisory-visibility",
+            &[
+                ("advisory", advisory.title()),
+                (
+                    "visibility",
+                    options.measure(format!("{km:.1}"), " km", "spoken-km"),
+                ),
+            ],
+        ),
+        None => advisory.title(),
+    };
+    let banner = if options.emoji { "🌫 " } else { "" };
+    options.sanitize(&format!("{banner}{text}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn below_threshold() {
+        let mut data = WeatherData {
+            humidity: 97.0,
+            visibility_km: Some(0.3),
+            ..Default::default()
+        };
+        let fog = advisory(&data, 1.0).unwrap();
+        assert!(fog.fog);
+        let options = OutputOptions {
+            emoji: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_advisory(&fog, &options),
+            "Fog advisory: visibility 0.3 km"
+        );
+
+        data.humidity = 40.0;
+        assert!(!advisory(&data, 1.0).unwrap().fog);
+        assert_eq!(advisory(&data, 0.2), None);
+
+        data.visibility_km = None;
+        assert_eq!(advisory(&data, 1.0), None);
+        data.kind = ConditionKind::Fog;
+        assert_eq!(advisory(&data, 1.0).unwrap().visibility_km, None);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::i18n::tr_args;
+use crate::output::OutputOptions;
+use crate::weather_providers::DailyData;
+use chrono::NaiveDate;
+
+/// A night expected to fall below the frost threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrostNight {
+    /// Day whose morning brings the minimum.
+    pub date: NaiveDate,
+    pub min_c: f64,
+}
+
+/// Nights whose minimum temperature falls below `threshold` °C.
+///
+/// The daily minimum stands in for the overnight one, which it almost always is.
+pub fn frost_nights(days: &[DailyData], threshold: f64) -> Vec<FrostNight> {
+    days.iter()
+        .filter_map(|day| {
+            let min_c = day.temp_min_c?;
+            (min_c < threshold).then_some(FrostNight {
+                date: day.date,
+                min_c,
+            })
+        })
+        .collect()
+}
+
+/// One line per frosty night, e.g. "Frost in Porto: down to -1.5°C in the night to 2025-03-03".
+pub fn render_frost(location: &str, nights: &[FrostNight], options: &OutputOptions) -> String {
+    let lines: Vec<String> = nights
+        .iter()
+        .map(|night| {
+            tr_args(
+                "frost-night",
+                &[
+                    ("location", location.to_string()),
+                    ("temp", options.temperature(night.min_c)),
+                    ("date", night.date.format(&options.date_format).to_string()),
+                ],
+            )
+        })
+        .collect();
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32, min: Option<f64>) -> DailyData {
+        DailyData {
+            date: NaiveDate::from_ymd_opt(2025, 3, d).unwrap(),
+            temp_min_c: min,
+            temp_max_c: Some(12.0),
+            precipitation_mm: None,
+        }
+    }
+
+    #[test]
+    fn nights_below_threshold() {
+        let days = [day(1, Some(3.0)), day(2, Some(-1.5)), day(3, None)];
+
+        let nights = frost_nights(&days, 2.0);
+        assert_eq!(
+            nights,
+            vec![FrostNight {
+                date: NaiveDate::from_ymd_opt(2025, 3, 2).unwrap(),
+                min_c: -1.5,
+            }]
+        );
+        assert_eq!(
+            render_frost("Porto", &nights, &OutputOptions::default()),
+            "Frost in Porto: down to -1.5°C in the night to 2025-03-02"
+        );
+        assert!(frost_nights(&days, -5.0).is_empty());
+    }
+}
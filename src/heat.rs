@@ -0,0 +1,122 @@
+use crate::config::HeatSettings;
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+
+/// Temperature of a wet thermometer cooled by evaporation, in °C, by Stull's formula; good to
+/// about 1 °C for relative humidity from 5 to 99% and air from -20 to 50 °C.
+pub fn wet_bulb(temp_c: f64, humidity: f64) -> f64 {
+    let rh = humidity.clamp(0.0, 100.0);
+    temp_c * (0.151_977 * (rh + 8.313_659).sqrt()).atan() + (temp_c + rh).atan()
+        - (rh - 1.676_331).atan()
+        + 0.003_918_38 * rh.powf(1.5) * (0.023_101 * rh).atan()
+        - 4.686_035
+}
+
+/// Wet-bulb globe temperature in the shade, in °C. Without a globe thermometer the globe is taken
+/// to be as warm as the air, which underestimates it in the sun.
+pub fn wbgt(temp_c: f64, humidity: f64) -> f64 {
+    0.7 * wet_bulb(temp_c, humidity) + 0.3 * temp_c
+}
+
+/// How much heat stress the body is under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatLevel {
+    Caution,
+    Danger,
+}
+
+/// Heat stress worth warning about.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeatWarning {
+    pub level: HeatLevel,
+    pub wet_bulb_c: f64,
+    pub wbgt_c: f64,
+}
+
+impl HeatWarning {
+    pub fn title(&self) -> String {
+        tr(match self.level {
+            HeatLevel::Caution => "heat-caution",
+            HeatLevel::Danger => "heat-danger",
+        })
+    }
+}
+
+/// A warning when the WBGT of `data` reaches a threshold of `settings`.
+pub fn warning(data: &WeatherData, settings: &HeatSettings) -> Option<HeatWarning> {
+    if data.humidity <= 0.0 {
+        return None;
+    }
+    let wbgt_c = wbgt(data.temp_c, data.humidity);
+    let level = if wbgt_c >= settings.danger {
+        HeatLevel::Danger
+    } else if wbgt_c >= settings.caution {
+        HeatLevel::Caution
+    } else {
+        return None;
+    };
+    Some(HeatWarning {
+        level,
+        wet_bulb_c: wet_bulb(data.temp_c, data.humidity),
+        wbgt_c,
+    })
+}
+
+/// "Heat caution: WBGT 30.4°C, wet-bulb 28.5°C. Take breaks in the shade and drink regularly."
+pub fn render_warning(warning: &HeatWarning, options: &OutputOptions) -> String {
+    let advice = tr(match warning.level {
+        HeatLevel::Caution => "heat-caution-advice",
+        HeatLevel::Danger => "heat-danger-advice",
+    });
+    let text = tr_args(
+        "heat-warning",
+        &[
+            ("warning", warning.title()),
+            ("wbgt", options.temperature(warning.wbgt_c)),
+            ("wet_bulb", options.temperature(warning.wet_bulb_c)),
+            ("advice", advice),
+        ],
+    );
+    let banner = if options.emoji { "🥵 " } else { "" };
+    options.sanitize(&format!("{banner}{text}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warnings_by_wbgt() {
+        assert!((wet_bulb(20.0, 50.0) - 13.7).abs() < 0.05);
+
+        let mut data = WeatherData {
+            temp_c: 35.0,
+            humidity: 60.0,
+            ..Default::default()
+        };
+        let settings = HeatSettings::default();
+        let warning = warning(&data, &settings).unwrap();
+        assert_eq!(warning.level, HeatLevel::Caution);
+        let options = OutputOptions {
+            emoji: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_warning(&warning, &options),
+            "Heat caution: WBGT 30.4°C, wet-bulb 28.5°C. Take breaks in the shade and drink \
+             regularly."
+        );
+
+        let strict = HeatSettings {
+            danger: 30.0,
+            ..settings
+        };
+        assert_eq!(
+            super::warning(&data, &strict).unwrap().level,
+            HeatLevel::Danger
+        );
+        data.humidity = 20.0;
+        assert_eq!(super::warning(&data, &settings), None);
+    }
+}
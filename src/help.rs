@@ -0,0 +1,80 @@
+//! Command line help in the settings' `language`. The doc comments clap takes the help from are the
+//! English text; `help-*` messages of other locales replace them, e.g. `help-weather-get` for the
+//! about of `weather get` and `help-weather-get--date` for its `--date`.
+
+use crate::i18n::try_lookup;
+use clap::Command;
+use fluent_templates::LanguageIdentifier;
+
+/// `command` with its help in `language`, where translated; English for `None` or an invalid
+/// language, which is reported once the settings are loaded.
+///
+/// The language is passed in rather than taken from [`crate::i18n::init`], which only the loaded
+/// settings may call.
+pub fn localize(command: Command, language: Option<&str>) -> Command {
+    match language.and_then(|language| language.parse().ok()) {
+        Some(language) => localize_in(command, &language),
+        None => command,
+    }
+}
+
+fn localize_in(command: Command, language: &LanguageIdentifier) -> Command {
+    let key = format!("help-{}", command.get_name());
+    localize_at(command, &key, language)
+}
+
+/// Translate the about of `command`, messages `key`, and of its arguments and subcommands.
+fn localize_at(mut command: Command, key: &str, language: &LanguageIdentifier) -> Command {
+    if let Some(about) = try_lookup(language, key) {
+        command = command.about(about).long_about(None);
+    }
+    let ids: Vec<String> = command
+        .get_arguments()
+        .map(|arg| arg.get_id().to_string())
+        .collect();
+    for id in ids {
+        if let Some(help) = try_lookup(language, &format!("{key}--{}", id.replace('_', "-"))) {
+            command = command.mut_arg(id, |arg| arg.help(help).long_help(None));
+        }
+    }
+    let names: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in names {
+        let key = format!("{key}-{name}");
+        command = command.mut_subcommand(name, |sub| localize_at(sub, &key, language));
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn german_help_with_english_fallback() {
+        let de = localize_in(Cli::command(), &"de".parse().unwrap());
+        let about = |command: &Command| command.get_about().map(ToString::to_string);
+        assert_eq!(
+            about(de.find_subcommand("batch").unwrap()).as_deref(),
+            Some("Wetter für mehrere Orte, gefolgt von einer Übersicht der Extreme und Fehler")
+        );
+        let quiet = de.get_arguments().find(|arg| arg.get_id() == "quiet");
+        assert!(
+            quiet
+                .and_then(|arg| arg.get_help())
+                .is_some_and(|help| help.to_string().starts_with("Nur das Ergebnis"))
+        );
+
+        let en = Cli::command();
+        let fr = localize_in(Cli::command(), &"fr".parse().unwrap());
+        assert_eq!(about(&fr), about(&en));
+        assert_eq!(
+            about(fr.find_subcommand("batch").unwrap()),
+            about(en.find_subcommand("batch").unwrap())
+        );
+    }
+}
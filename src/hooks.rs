@@ -0,0 +1,124 @@
+use crate::config::HookSettings;
+use crate::output::json::to_json;
+use crate::weather_providers::WeatherData;
+use std::io;
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tracing::{debug, warn};
+
+/// Upper bound for a hook to finish; it is killed after that.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `command` run by the platform's shell.
+fn shell(command: &str) -> Command {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command).kill_on_drop(true);
+    shell
+}
+
+/// Run `command` with `env` set and `input` on stdin, waiting at most [`HOOK_TIMEOUT`].
+///
+/// What the hook prints goes to stderr, so it cannot corrupt the command's own output.
+async fn run(command: &str, env: &[(&str, &str)], input: &[u8]) -> io::Result<ExitStatus> {
+    let mut child = shell(command)
+        .envs(env.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(io::stderr())
+        .spawn()?;
+    // Writing counts against the timeout too: a hook that neither reads nor exits would
+    // otherwise block on a full pipe forever.
+    let finished = async {
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that does not read its input closes the pipe early, which is fine.
+            let _ = stdin.write_all(input).await;
+        }
+        child.wait().await
+    };
+    tokio::time::timeout(HOOK_TIMEOUT, finished)
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "timed out"))?
+}
+
+/// Run `command`, logging instead of failing when it does.
+async fn run_logged(hook: &str, command: &str, env: &[(&str, &str)], input: &[u8]) {
+    debug!("Running {hook} hook `{command}`");
+    match run(command, env, input).await {
+        Ok(status) if status.success() => {},
+        Ok(status) => warn!("{hook} hook `{command}` failed: {status}"),
+        Err(e) => warn!("{hook} hook `{command}` failed: {e}"),
+    }
+}
+
+/// Run the `pre_fetch` hook before asking `provider` about `location`, which the hook gets in
+/// `WEATHER_PROVIDER` and `WEATHER_LOCATION`. `provider` lists them all, comma-separated, when
+/// several are raced.
+pub async fn pre_fetch(settings: &HookSettings, provider: &str, location: &str) {
+    if let Some(command) = &settings.pre_fetch {
+        let env = [
+            ("WEATHER_PROVIDER", provider),
+            ("WEATHER_LOCATION", location),
+        ];
+        run_logged("pre_fetch", command, &env, &[]).await;
+    }
+}
+
+/// Run the `post_fetch` hook with the result, as in `--format json`, on stdin.
+///
+/// A failing hook is logged and does not affect the command's result.
+pub async fn post_fetch(settings: &HookSettings, data: &WeatherData, provider: &str) {
+    if let Some(command) = &settings.post_fetch {
+        let json = to_json(data, provider).to_string();
+        let env = [
+            ("WEATHER_PROVIDER", provider),
+            ("WEATHER_LOCATION", &data.location),
+        ];
+        run_logged("post_fetch", command, &env, json.as_bytes()).await;
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn result_on_stdin() {
+        let dir = std::env::temp_dir().join(format!("weather-hooks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let out = dir.join("result.json");
+        let settings = HookSettings {
+            pre_fetch: None,
+            post_fetch: Some(format!("cat > '{}'", out.display())),
+        };
+        let data = WeatherData {
+            location: "Porto".to_string(),
+            temp_c: 16.1,
+            ..Default::default()
+        };
+
+        post_fetch(&settings, &data, "demo").await;
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(written["location"], "Porto");
+        assert_eq!(written["provider"], "demo");
+        assert!(
+            run("exit 3", &[], &[])
+                .await
+                .is_ok_and(|s| s.code() == Some(3))
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use crate::config::SettingsError;
+use fluent_templates::fluent_bundle::FluentValue;
+use fluent_templates::{LanguageIdentifier, Loader, static_loader};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+static_loader! {
+    static LOCALES = {
+        locales: "./locales",
+        fallback_language: "en",
+        customise: |bundle| bundle.set_use_isolating(false),
+    };
+}
+
+static LANGUAGE: OnceLock<LanguageIdentifier> = OnceLock::new();
+
+/// Select the language for user-facing strings. Only the first call has effect.
+pub fn init(language: Option<&str>) -> Result<(), SettingsError> {
+    let language = language
+        .unwrap_or("en")
+        .parse::<LanguageIdentifier>()
+        .map_err(|e| SettingsError::Invalid(format!("language: {e}")))?;
+    let _ = LANGUAGE.set(language);
+
+    Ok(())
+}
+
+fn language() -> &'static LanguageIdentifier {
+    LANGUAGE.get_or_init(|| "en".parse().expect("valid language identifier"))
+}
+
+/// Translate a message without arguments.
+pub fn tr(key: &str) -> String {
+    lookup(language(), key, &[])
+}
+
+/// Translate a message with named arguments.
+pub fn tr_args(key: &str, args: &[(&'static str, String)]) -> String {
+    lookup(language(), key, args)
+}
+
+/// Translate a message without arguments in `language`, `None` when no locale has it.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn try_lookup(language: &LanguageIdentifier, key: &str) -> Option<String> {
+    LOCALES.try_lookup(language, key)
+}
+
+fn lookup(language: &LanguageIdentifier, key: &str, args: &[(&'static str, String)]) -> String {
+    if args.is_empty() {
+        return LOCALES.lookup(language, key);
+    }
+
+    let args: HashMap<Cow<'static, str>, FluentValue> = args
+        .iter()
+        .map(|(name, value)| (Cow::Borrowed(*name), FluentValue::from(value.clone())))
+        .collect();
+    LOCALES.lookup_with_args(language, key, &args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_english_and_german() {
+        let en: LanguageIdentifier = "en".parse().unwrap();
+        let de: LanguageIdentifier = "de".parse().unwrap();
+
+        assert_eq!(lookup(&en, "label-temperature", &[]), "Temperature");
+        assert_eq!(lookup(&de, "label-temperature", &[]), "Temperatur");
+
+        let args = [("provider", "weatherapi".to_string())];
+        assert_eq!(
+            lookup(&de, "default-provider", &args),
+            "Standardanbieter: weatherapi"
+        );
+    }
+
+    #[test]
+    fn unknown_language_falls_back_to_english() {
+        let fr: LanguageIdentifier = "fr".parse().unwrap();
+        assert_eq!(lookup(&fr, "label-pressure", &[]), "Pressure");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::HourlyData;
+use crate::weather_providers::condition::ConditionKind;
+use chrono::Timelike;
+
+/// Hours a load needs on the line.
+pub const WINDOW_HOURS: usize = 3;
+/// Hours of the day worth hanging laundry out, local time.
+const DAYTIME: std::ops::Range<u32> = 7..20;
+
+/// Drying score of an hour from 0 (keep it inside) to 100 (dry in no time).
+///
+/// Dry air, a breeze and sun help; rain ruins the load.
+pub fn score(hour: &HourlyData) -> u8 {
+    if hour.kind.is_rain() || hour.kind.is_snow() || hour.precipitation_mm.unwrap_or(0.0) > 0.1 {
+        return 0;
+    }
+
+    let mut penalty = match hour.kind {
+        ConditionKind::Clear | ConditionKind::Unknown => 0.0,
+        ConditionKind::PartlyCloudy => 10.0,
+        ConditionKind::Cloudy => 25.0,
+        _ => 40.0,
+    };
+    if let Some(humidity) = hour.humidity {
+        penalty += ((humidity - 40.0).max(0.0) * 1.2).min(60.0);
+    }
+    penalty += match hour.wind_kph {
+        Some(wind) if wind < 5.0 => 15.0,
+        Some(wind) if wind > 40.0 => 20.0,
+        _ => 0.0,
+    };
+    penalty += hour.precipitation_probability.unwrap_or(0.0);
+
+    (100.0 - penalty).clamp(0.0, 100.0).round() as u8
+}
+
+/// Daytime window of [`WINDOW_HOURS`] consecutive hours with the best mean score.
+///
+/// Returns the index of its first hour in `hours` and the mean score.
+pub fn best_window(hours: &[HourlyData]) -> Option<(usize, u8)> {
+    hours
+        .windows(WINDOW_HOURS)
+        .enumerate()
+        .filter(|(_, window)| {
+            window
+                .iter()
+                .all(|hour| DAYTIME.contains(&hour.time.hour()))
+                && window
+                    .windows(2)
+                    .all(|pair| pair[1].time - pair[0].time == chrono::Duration::hours(1))
+        })
+        .map(|(i, window)| {
+            let total: u32 = window.iter().map(|hour| u32::from(score(hour))).sum();
+            (i, (total / WINDOW_HOURS as u32) as u8)
+        })
+        .max_by_key(|&(i, mean)| (mean, std::cmp::Reverse(i)))
+}
+
+/// Answer of `weather laundry`.
+pub fn render_laundry(location: &str, hours: &[HourlyData], options: &OutputOptions) -> String {
+    let Some((start, mean)) = best_window(hours) else {
+        return options.sanitize(&tr_args(
+            "laundry-no-window",
+            &[("location", location.to_string())],
+        ));
+    };
+    let rating = match mean {
+        m if m < 40 => "laundry-poor",
+        m if m < 70 => "laundry-fair",
+        _ => "laundry-good",
+    };
+    let end = hours[start + WINDOW_HOURS - 1].time + chrono::Duration::hours(1);
+
+    options.sanitize(&tr_args(
+        "laundry-best",
+        &[
+            ("location", location.to_string()),
+            (
+                "start",
+                hours[start].time.format(&options.time_format).to_string(),
+            ),
+            ("end", end.format(&options.time_format).to_string()),
+            ("score", mean.to_string()),
+            ("rating", tr(rating)),
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32, kind: ConditionKind, humidity: f64) -> HourlyData {
+        HourlyData {
+            time: NaiveDate::from_ymd_opt(2025, 6, 1)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap(),
+            kind,
+            humidity: Some(humidity),
+            wind_kph: Some(12.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rain_ruins_drying() {
+        assert_eq!(score(&at(12, ConditionKind::Rain, 40.0)), 0);
+        assert_eq!(score(&at(12, ConditionKind::Clear, 40.0)), 100);
+        assert_eq!(score(&at(12, ConditionKind::Cloudy, 90.0)), 15);
+    }
+
+    #[test]
+    fn best_daytime_window() {
+        let hours: Vec<_> = [
+            (5, ConditionKind::Clear),
+            (6, ConditionKind::Clear),
+            (7, ConditionKind::Cloudy),
+            (8, ConditionKind::Clear),
+            (9, ConditionKind::Clear),
+            (10, ConditionKind::Clear),
+            (11, ConditionKind::Rain),
+        ]
+        .into_iter()
+        .map(|(hour, kind)| at(hour, kind, 50.0))
+        .collect();
+
+        // 05:00 and 06:00 are too early, 11:00 is rainy.
+        assert_eq!(best_window(&hours), Some((3, 88)));
+        assert!(best_window(&hours[..3]).is_none());
+    }
+}
@@ -0,0 +1,75 @@
+pub mod activity;
+pub mod advice;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod alias;
+pub mod allergy;
+pub mod anomaly;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod app;
+pub mod astro;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod backfill;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod batch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
+pub mod check;
+pub mod climate;
+pub mod comfort;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod commands;
+pub mod condition_map;
+pub mod config;
+pub mod degree_days;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod email;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod encryption;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod errors;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fire;
+pub mod fog;
+pub mod frost;
+pub mod heat;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod help;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod hooks;
+pub mod i18n;
+pub mod laundry;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod logger;
+pub mod metrics;
+pub mod migrate;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mqtt;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod notify;
+pub mod output;
+pub mod pressure;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod provider_registry;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod reload;
+pub mod report;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod route;
+#[cfg(all(feature = "self-update", not(target_arch = "wasm32")))]
+pub mod self_update;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod singleflight;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod store;
+pub mod suggest;
+pub mod surf;
+pub mod trend;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+pub mod weather_providers;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod webhook;
+pub mod windrose;
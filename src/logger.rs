@@ -1,32 +1,186 @@
+use crate::config::{LogFormat, LogRotation, LoggingSettings, SettingsError};
+use crate::output::console;
+use crate::weather_providers::QUALITY;
 use dotenvy::var;
 use std::io;
-use tracing::{info, trace};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tracing::{Event, Subscriber, info, trace};
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::Context;
 use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
+impl From<LogRotation> for Rotation {
+    fn from(rotation: LogRotation) -> Self {
+        match rotation {
+            LogRotation::Minutely => Rotation::MINUTELY,
+            LogRotation::Hourly => Rotation::HOURLY,
+            LogRotation::Daily => Rotation::DAILY,
+            LogRotation::Never => Rotation::NEVER,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct LoggerGuard {
-    _std_out_guard: WorkerGuard,
+    _writer_guard: WorkerGuard,
+    warnings: WarningCounter,
+}
+
+impl LoggerGuard {
+    /// Data-quality warnings logged so far, whether or not they were shown.
+    pub fn warnings(&self) -> usize {
+        self.warnings.count()
+    }
+}
+
+/// Counts the [`QUALITY`] events it sees, for `--strict` to fail on degraded data.
+#[derive(Debug, Clone, Default)]
+struct WarningCounter(Arc<AtomicUsize>);
+
+impl WarningCounter {
+    fn count(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for WarningCounter {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() == QUALITY {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Rolling appender writing to `path`, rotated files get a date suffix.
+fn file_appender(
+    path: &Path,
+    settings: &LoggingSettings,
+) -> Result<RollingFileAppender, SettingsError> {
+    let directory = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let prefix = path
+        .file_name()
+        .ok_or_else(|| SettingsError::Invalid(format!("log file '{}'", path.display())))?;
+
+    let mut builder = RollingFileAppender::builder()
+        .rotation(settings.rotation.into())
+        .filename_prefix(prefix.to_string_lossy());
+    if let Some(max_files) = settings.max_files {
+        builder = builder.max_log_files(max_files);
+    }
+
+    builder
+        .build(directory)
+        .map_err(|e| SettingsError::Invalid(format!("log file '{}': {e}", path.display())))
+}
+
+/// Log level for the number of `-v` flags given.
+fn level_for_verbosity(verbose: u8) -> &'static str {
+    match verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    }
 }
-pub fn init_logger() -> LoggerGuard {
-    let (std_out_writer, std_out_guard) = tracing_appender::non_blocking(io::stdout());
 
-    let enable_color = var("ENABLE_COLOR").map(|v| v == "true").unwrap_or(false);
+/// Set up logging to stderr, keeping stdout to the command's output, or to a rotating file when
+/// `[logging] file` is set.
+///
+/// `quiet` turns all log output off; otherwise `RUST_LOG` takes precedence over `verbose`.
+pub fn init_logger(
+    quiet: bool,
+    verbose: u8,
+    settings: &LoggingSettings,
+) -> Result<LoggerGuard, SettingsError> {
+    let (writer, writer_guard) = match &settings.file {
+        Some(path) => tracing_appender::non_blocking(file_appender(path, settings)?),
+        None => tracing_appender::non_blocking(io::stderr()),
+    };
+
+    let enable_color = var("ENABLE_COLOR").is_ok_and(|v| v == "true") && console::ansi_supported();
+
+    let filter = if quiet {
+        EnvFilter::new("off")
+    } else {
+        EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new(level_for_verbosity(verbose)))
+    };
 
-    let std_out_layer = fmt::layer()
-        .with_writer(std_out_writer)
-        .with_ansi(enable_color)
-        .with_target(false)
-        .with_level(true)
-        .without_time()
-        .with_filter(EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new("warn")));
+    let layer = match settings.format {
+        LogFormat::Text if settings.file.is_some() => fmt::layer()
+            .with_writer(writer)
+            .with_ansi(false)
+            .with_target(false)
+            .with_level(true)
+            .boxed(),
+        LogFormat::Text => fmt::layer()
+            .with_writer(writer)
+            .with_ansi(enable_color)
+            .with_target(false)
+            .with_level(true)
+            .without_time()
+            .boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_writer(writer)
+            .with_target(true)
+            .with_current_span(false)
+            .boxed(),
+    };
 
-    tracing_subscriber::registry().with(std_out_layer).init();
+    // Counted even when `quiet` or `RUST_LOG` hides them.
+    let warnings = WarningCounter::default();
+    tracing_subscriber::registry()
+        .with(layer.with_filter(filter))
+        .with(warnings.clone().with_filter(LevelFilter::WARN))
+        .init();
 
     trace!("Logging successfully initialized!");
     info!("Enabling ANSI: {}", enable_color);
 
-    LoggerGuard {
-        _std_out_guard: std_out_guard,
+    Ok(LoggerGuard {
+        _writer_guard: writer_guard,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_levels() {
+        assert_eq!(level_for_verbosity(0), "warn");
+        assert_eq!(level_for_verbosity(1), "info");
+        assert_eq!(level_for_verbosity(2), "debug");
+        assert_eq!(level_for_verbosity(3), "trace");
+        assert_eq!(level_for_verbosity(9), "trace");
+    }
+
+    #[test]
+    fn only_quality_warnings_are_counted() {
+        let warnings = WarningCounter::default();
+        let subscriber =
+            tracing_subscriber::registry().with(warnings.clone().with_filter(LevelFilter::WARN));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: QUALITY, "fine");
+            tracing::warn!(target: QUALITY, "drifted");
+            tracing::error!(target: QUALITY, "failed");
+            tracing::warn!("Failed to save metrics");
+        });
+        assert_eq!(warnings.count(), 2);
+    }
+
+    #[test]
+    fn file_appender_rejects_path_without_file_name() {
+        let settings = LoggingSettings::default();
+        assert!(file_appender(Path::new("/"), &settings).is_err());
     }
 }
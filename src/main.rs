@@ -1,39 +1,149 @@
-mod commands;
-mod config;
-mod errors;
-
-mod provider_registry;
-
-mod app;
-mod logger;
-mod weather_providers;
-
-use crate::app::WeatherApp;
-use crate::commands::{default_settings_path, run};
-use crate::config::init_settings_file;
-use crate::logger::init_logger;
-use crate::provider_registry::build_registry;
-use crate::{config::load_settings, errors::AppError};
-use clap::Parser;
-use commands::Cli;
-use tracing::{info, trace};
+use clap::{CommandFactory, FromArgMatches};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, trace, warn};
+use weather::alias;
+use weather::app::WeatherApp;
+use weather::cache::disk::DiskCache;
+use weather::cache::lru::LruCache;
+use weather::cache::tiered::TieredCache;
+use weather::commands::{Cli, default_settings_path, run};
+use weather::condition_map::ConditionMap;
+use weather::config::LogFormat;
+use weather::config::{SettingsError, init_settings_file, load_aliases, load_language};
+use weather::help;
+use weather::i18n;
+use weather::logger::init_logger;
+use weather::metrics::Metrics;
+use weather::provider_registry::build_registry;
+use weather::weather_providers::deprecation;
+use weather::weather_providers::parse::ParseMode;
+use weather::{config::load_settings_with_key, errors::AppError};
 
 #[tokio::main]
-async fn main() -> Result<(), AppError> {
+async fn main() -> ExitCode {
+    match try_main().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e @ AppError::UmbrellaNeeded) => ExitCode::from(e.exit_code()),
+        Err(e) => {
+            error!("{e:?}");
+            eprintln!("Error: {e}");
+            ExitCode::from(e.exit_code())
+        },
+    }
+}
+
+async fn try_main() -> Result<(), AppError> {
     // add ratatui TUI
-    let _logger_guard = init_logger();
-    let _ = dotenvy::dotenv().ok();
-    info!("App started");
-    let cli = Cli::parse();
+    let args: Vec<_> = std::env::args_os().collect();
+    let config_path = alias::config_path(&args).unwrap_or_else(default_settings_path);
+    let matches = help::localize(Cli::command(), load_language(&config_path).as_deref())
+        .get_matches_from(alias::expand(args, &load_aliases(&config_path)));
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    match &cli.env_file {
+        // Asked for explicitly, so it has to be there.
+        Some(path) => {
+            dotenvy::from_path(path).map_err(|e| AppError::Config(SettingsError::Env(e)))?;
+        },
+        None => {
+            let _ = dotenvy::dotenv().ok();
+        },
+    }
 
     let _ = init_settings_file(&default_settings_path());
 
-    let settings = load_settings(cli.config_path.clone().as_path()).map_err(AppError::Config)?;
+    let key = cli.settings_key().map_err(AppError::Config)?;
+    let mut settings =
+        load_settings_with_key(&cli.config_path, key.as_ref()).map_err(AppError::Config)?;
+    if cli.log_json {
+        settings.logging.format = LogFormat::Json;
+    }
+    if cli.strict {
+        settings.parse_mode = ParseMode::Strict;
+    }
+    if cli.no_cache {
+        settings.cache.enabled = false;
+    }
+
+    let logger_guard =
+        init_logger(cli.quiet, cli.verbose, &settings.logging).map_err(AppError::Config)?;
+    info!("App started");
+    settings.warn_if_outdated();
 
     trace!("Settings {:?}", settings);
 
+    i18n::init(settings.language.as_deref()).map_err(AppError::Config)?;
+
+    #[cfg(feature = "demo")]
+    let demo = cli.demo;
+    #[cfg(not(feature = "demo"))]
+    let demo = false;
+
+    #[cfg(feature = "demo")]
+    let registry = if demo {
+        // Made-up results must not end up in the history ...
+        settings.storage.enabled = false;
+        settings.default_provider = "demo".to_string();
+        weather::provider_registry::demo_registry(&settings)
+    } else {
+        build_registry(&settings)?
+    };
+    #[cfg(not(feature = "demo"))]
     let registry = build_registry(&settings)?;
-    let app = WeatherApp::new(registry);
+    // Calls add up across runs for `weather providers stats`, made-up ones aside.
+    let metrics_path = (!demo).then(Metrics::default_path);
+    let metrics = Arc::new(
+        metrics_path
+            .as_deref()
+            .map(Metrics::load)
+            .unwrap_or_default(),
+    );
+    let mut builder = WeatherApp::builder()
+        .with_registry(registry)
+        .with_metrics(metrics.clone());
+    // ... or in the cache, next to real ones.
+    if settings.cache.enabled && !demo {
+        let dir = settings
+            .cache
+            .dir
+            .clone()
+            .unwrap_or_else(DiskCache::default_dir);
+        let disk = DiskCache::new(dir, Duration::from_secs(settings.cache.ttl_secs));
+        builder = if cli.serves() {
+            // Hot locations are answered without touching the disk.
+            let memory = LruCache::new(
+                settings.cache.memory_capacity,
+                Duration::from_secs(settings.cache.memory_ttl_secs),
+            );
+            builder.with_cache(TieredCache::new(memory, disk))
+        } else {
+            builder.with_cache(disk)
+        };
+    }
+    if let Some(path) = &settings.display.condition_map {
+        builder = builder.with_conditions(ConditionMap::load(path).map_err(AppError::Config)?);
+    }
+    let app = builder.build();
 
-    run(cli, app, settings).await
+    let strict = cli.strict;
+    let result = run(cli, app, settings, key).await;
+    if !demo
+        && let Err(e) = deprecation::warn_once(&deprecation::default_path(), deprecation::notices())
+    {
+        warn!("Failed to remember deprecation notices: {e}");
+    }
+    if let Some(path) = metrics_path
+        && let Err(e) = metrics.save(&path)
+    {
+        warn!("Failed to save metrics to {}: {e}", path.display());
+    }
+    let warnings = logger_guard.warnings();
+    if strict && warnings > 0 && result.is_ok() {
+        // A result that came with degraded data is not good enough for automation.
+        return Err(AppError::Degraded(vec![format!(
+            "{warnings} data-quality warning(s) logged"
+        )]));
+    }
+    result
 }
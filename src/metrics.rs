@@ -0,0 +1,247 @@
+use crate::weather_providers::error::ProviderError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use tracing::warn;
+
+/// Upper bounds of the latency histogram buckets, in seconds.
+pub const BUCKETS: [f64; 8] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Calls of one provider with one outcome.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Series {
+    pub count: u64,
+    /// Total latency, in seconds.
+    pub sum: f64,
+    /// Calls per bucket of [`BUCKETS`], not cumulative; the last one counts slower calls.
+    pub buckets: [u64; BUCKETS.len() + 1],
+}
+
+impl Series {
+    fn observe(&mut self, latency: Duration) {
+        let secs = latency.as_secs_f64();
+        self.count += 1;
+        self.sum += secs;
+        let bucket = BUCKETS.iter().position(|le| secs <= *le);
+        self.buckets[bucket.unwrap_or(BUCKETS.len())] += 1;
+    }
+
+    pub fn mean(&self) -> Duration {
+        Duration::from_secs_f64(self.sum / self.count.max(1) as f64)
+    }
+
+    /// Upper bound of the bucket holding the `q` quantile, `None` when it falls past the last one.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let rank = (q * self.count as f64).ceil() as u64;
+        let mut seen = 0;
+        for (le, n) in BUCKETS.iter().zip(self.buckets) {
+            seen += n;
+            if seen >= rank {
+                return Some(*le);
+            }
+        }
+        None
+    }
+}
+
+/// `(provider, outcome)` of a series.
+pub type SeriesKey = (String, String);
+
+/// Counters and latency histograms of provider calls, per provider and outcome.
+///
+/// The CLI keeps them in a file so `weather providers stats` covers past runs, the server exposes
+/// them on `/metrics`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    series: Mutex<BTreeMap<SeriesKey, Series>>,
+}
+
+/// Outcome label of a call: `ok` or the kind of error.
+pub fn outcome<T>(result: &Result<T, ProviderError>) -> &'static str {
+    match result {
+        Ok(_) => "ok",
+        Err(e) => e.kind(),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    provider: String,
+    outcome: String,
+    #[serde(flatten)]
+    series: Series,
+}
+
+impl Metrics {
+    /// Record one call of `provider`.
+    pub fn observe(&self, provider: &str, outcome: &str, latency: Duration) {
+        self.series
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((provider.to_string(), outcome.to_string()))
+            .or_default()
+            .observe(latency);
+    }
+
+    /// Copy of every series, sorted by provider and outcome.
+    pub fn snapshot(&self) -> BTreeMap<SeriesKey, Series> {
+        self.series
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Platform data directory, e.g. `~/.local/share/weather/metrics.json` on Linux.
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from(".local/share"))
+            .join("weather")
+            .join("metrics.json")
+    }
+
+    /// Metrics saved by earlier runs; empty when there are none or they cannot be read.
+    pub fn load(path: &Path) -> Self {
+        let entries: Vec<Entry> = match std::fs::read(path) {
+            Ok(content) => serde_json::from_slice(&content)
+                .inspect_err(|e| warn!("Ignoring corrupt metrics {}: {e}", path.display()))
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+        let series = entries
+            .into_iter()
+            .map(|e| ((e.provider, e.outcome), e.series))
+            .collect();
+        Self {
+            series: Mutex::new(series),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let entries: Vec<Entry> = self
+            .snapshot()
+            .into_iter()
+            .map(|((provider, outcome), series)| Entry {
+                provider,
+                outcome,
+                series,
+            })
+            .collect();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Written aside and renamed over the old file, so that a run killed while saving, or
+        // another one saving at the same time, cannot leave half a file behind.
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(format!(".{}.tmp", std::process::id()));
+        let temp = PathBuf::from(temp);
+        std::fs::write(&temp, serde_json::to_vec(&entries)?)?;
+        std::fs::rename(&temp, path).inspect_err(|_| {
+            let _ = std::fs::remove_file(&temp);
+        })
+    }
+
+    /// Prometheus text exposition format.
+    pub fn prometheus(&self) -> String {
+        let series = self.snapshot();
+        let mut out = String::from(
+            "# HELP weather_provider_requests_total Provider calls by outcome.\n\
+             # TYPE weather_provider_requests_total counter\n",
+        );
+        for ((provider, outcome), s) in &series {
+            let _ = writeln!(
+                out,
+                "weather_provider_requests_total{{provider=\"{provider}\",outcome=\"{outcome}\"}} {}",
+                s.count
+            );
+        }
+
+        out.push_str(
+            "# HELP weather_provider_request_duration_seconds Provider call latency.\n\
+             # TYPE weather_provider_request_duration_seconds histogram\n",
+        );
+        for ((provider, outcome), s) in &series {
+            let labels = format!("provider=\"{provider}\",outcome=\"{outcome}\"");
+            let mut cumulative = 0;
+            for (le, n) in BUCKETS.iter().zip(s.buckets) {
+                cumulative += n;
+                let _ = writeln!(
+                    out,
+                    "weather_provider_request_duration_seconds_bucket{{{labels},le=\"{le}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "weather_provider_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}\n\
+                 weather_provider_request_duration_seconds_sum{{{labels}}} {}\n\
+                 weather_provider_request_duration_seconds_count{{{labels}}} {}",
+                s.count, s.sum, s.count
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_and_quantiles() {
+        let metrics = Metrics::default();
+        for ms in [40, 80, 90, 300] {
+            metrics.observe("openmeteo", "ok", Duration::from_millis(ms));
+        }
+        metrics.observe("openmeteo", "timeout", Duration::from_secs(30));
+
+        let series = metrics.snapshot();
+        let ok = &series[&("openmeteo".to_string(), "ok".to_string())];
+        assert_eq!(ok.count, 4);
+        assert_eq!(ok.buckets[..4], [1, 2, 0, 1]);
+        assert_eq!(ok.quantile(0.5), Some(0.1));
+        assert_eq!(ok.quantile(0.95), Some(0.5));
+        assert_eq!(ok.mean().as_millis(), 127);
+        assert_eq!(
+            series[&("openmeteo".to_string(), "timeout".to_string())].quantile(0.5),
+            None
+        );
+
+        let text = metrics.prometheus();
+        assert!(text.contains(
+            "weather_provider_requests_total{provider=\"openmeteo\",outcome=\"ok\"} 4\n"
+        ));
+        assert!(text.contains(
+            "weather_provider_request_duration_seconds_bucket{provider=\"openmeteo\",outcome=\"ok\",le=\"0.1\"} 3\n"
+        ));
+        assert!(text.contains(
+            "weather_provider_request_duration_seconds_bucket{provider=\"openmeteo\",outcome=\"timeout\",le=\"+Inf\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn saved_and_loaded() {
+        let path =
+            std::env::temp_dir().join(format!("weather-metrics-{}.json", std::process::id()));
+        let metrics = Metrics::default();
+        metrics.observe("weatherapi", "not_found", Duration::from_millis(20));
+        metrics.save(&path).unwrap();
+        metrics.save(&path).unwrap();
+
+        let leftovers = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.starts_with(&format!("weather-metrics-{}", std::process::id()))
+                    && name.ends_with(".tmp")
+            })
+            .count();
+        assert_eq!(leftovers, 0);
+        let loaded = Metrics::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.snapshot(), metrics.snapshot());
+        assert!(Metrics::load(&path).snapshot().is_empty());
+    }
+}
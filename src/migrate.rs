@@ -0,0 +1,99 @@
+use crate::config::SettingsError;
+use std::fs;
+use std::path::Path;
+use toml::{Table, Value};
+
+/// Layout version of settings files written by this build.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Upgrade steps, `MIGRATIONS[n]` turns a version `n` table into version `n + 1`.
+const MIGRATIONS: [fn(&mut Table); SETTINGS_VERSION as usize] = [v0_to_v1];
+
+/// Files from before versioning share the version 1 layout, they only lack the field.
+fn v0_to_v1(_table: &mut Table) {}
+
+/// Layout version of a settings table, 0 when it predates the `version` field.
+pub fn version(table: &Table) -> Result<u32, SettingsError> {
+    match table.get("version") {
+        None => Ok(0),
+        Some(Value::Integer(v)) => u32::try_from(*v)
+            .map_err(|_| SettingsError::Invalid(format!("version must be positive, got {v}"))),
+        Some(other) => Err(SettingsError::Invalid(format!(
+            "version must be a number, got {other}"
+        ))),
+    }
+}
+
+/// Bring `table` up to [`SETTINGS_VERSION`], returning the version it had.
+///
+/// Files written by a newer build are refused rather than half understood.
+pub fn migrate(table: &mut Table) -> Result<u32, SettingsError> {
+    let from = version(table)?;
+    if from > SETTINGS_VERSION {
+        return Err(SettingsError::Invalid(format!(
+            "settings version {from} is newer than the supported {SETTINGS_VERSION}, \
+             please update weather"
+        )));
+    }
+    for step in &MIGRATIONS[from as usize..] {
+        step(table);
+    }
+    table.insert("version".into(), Value::Integer(SETTINGS_VERSION.into()));
+    Ok(from)
+}
+
+/// Parse the settings file at `path` and migrate it in memory.
+pub fn read(path: &Path) -> Result<(Table, u32), SettingsError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| SettingsError::Invalid(format!("{}: {e}", path.display())))?;
+    parse(&content, path)
+}
+
+/// Parse settings read from `origin` and migrate them in memory.
+pub fn parse(content: &str, origin: &Path) -> Result<(Table, u32), SettingsError> {
+    let mut table: Table = content
+        .parse()
+        .map_err(|e| SettingsError::Invalid(format!("{}: {e}", origin.display())))?;
+    let from = migrate(&mut table)?;
+    Ok((table, from))
+}
+
+/// Rewrite the settings file at `path` in the current layout, keeping the original next to it
+/// as `<name>.v<old version>.bak`. Returns the old version, `None` when it was up to date.
+pub fn migrate_file(path: &Path) -> Result<Option<u32>, SettingsError> {
+    let (table, from) = read(path)?;
+    if from == SETTINGS_VERSION {
+        return Ok(None);
+    }
+
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(format!(".v{from}.bak"));
+    fs::copy(path, &backup).map_err(|e| SettingsError::Save(e.to_string()))?;
+    let content = toml::to_string_pretty(&table).map_err(|e| SettingsError::Save(e.to_string()))?;
+    fs::write(path, content).map_err(|e| SettingsError::Save(e.to_string()))?;
+    Ok(Some(from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unversioned_file_is_stamped() {
+        let mut table: Table = "default_provider = \"openmeteo\"\n".parse().unwrap();
+        assert_eq!(migrate(&mut table).unwrap(), 0);
+        assert_eq!(version(&table).unwrap(), SETTINGS_VERSION);
+        assert_eq!(table["default_provider"].as_str(), Some("openmeteo"));
+    }
+
+    #[test]
+    fn newer_version_is_refused() {
+        let mut table: Table = format!("version = {}\n", SETTINGS_VERSION + 1)
+            .parse()
+            .unwrap();
+        assert!(matches!(
+            migrate(&mut table),
+            Err(SettingsError::Invalid(_))
+        ));
+    }
+}
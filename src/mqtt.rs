@@ -0,0 +1,188 @@
+use crate::config::MqttSettings;
+use crate::output::json::to_json;
+use crate::output::slug;
+use crate::weather_providers::WeatherData;
+use dotenvy::var;
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use serde_json::{Value, json};
+use std::time::Duration;
+use tracing::debug;
+
+/// Upper bound for connecting and getting every message acknowledged.
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum MqttError {
+    #[error("Failed to queue MQTT message: {0}")]
+    Client(#[from] rumqttc::ClientError),
+
+    #[error("MQTT connection failed: {0}")]
+    Connection(#[from] rumqttc::ConnectionError),
+
+    #[error("MQTT broker did not acknowledge within {0:?}")]
+    Timeout(Duration),
+}
+
+/// A reading exposed as a Home Assistant sensor entity.
+struct Sensor {
+    /// Field of the JSON state payload.
+    key: &'static str,
+    name: &'static str,
+    unit: Option<&'static str>,
+    device_class: Option<&'static str>,
+}
+
+const SENSORS: &[Sensor] = &[
+    Sensor {
+        key: "temp_c",
+        name: "Temperature",
+        unit: Some("°C"),
+        device_class: Some("temperature"),
+    },
+    Sensor {
+        key: "humidity",
+        name: "Humidity",
+        unit: Some("%"),
+        device_class: Some("humidity"),
+    },
+    Sensor {
+        key: "pressure",
+        name: "Pressure",
+        unit: Some("hPa"),
+        device_class: Some("atmospheric_pressure"),
+    },
+    Sensor {
+        key: "wind_kph",
+        name: "Wind speed",
+        unit: Some("km/h"),
+        device_class: Some("wind_speed"),
+    },
+    Sensor {
+        key: "wind_deg",
+        name: "Wind direction",
+        unit: Some("°"),
+        device_class: None,
+    },
+    Sensor {
+        key: "condition",
+        name: "Condition",
+        unit: None,
+        device_class: None,
+    },
+];
+
+/// Topic and payload of every message for `data`: one retained discovery config per sensor, then the state.
+fn messages(settings: &MqttSettings, data: &WeatherData, provider: &str) -> Vec<(String, Value)> {
+    let node = slug(&data.location);
+    let state_topic = format!("{}/{node}/state", settings.topic_prefix);
+    let device = json!({
+        "identifiers": [format!("weather_{node}")],
+        "name": format!("Weather {}", data.location),
+        "manufacturer": provider,
+    });
+
+    let mut messages: Vec<(String, Value)> = SENSORS
+        .iter()
+        .map(|sensor| {
+            let mut config = json!({
+                "name": sensor.name,
+                "unique_id": format!("weather_{node}_{}", sensor.key),
+                "state_topic": state_topic,
+                "value_template": format!("{{{{ value_json.{} }}}}", sensor.key),
+                "device": device,
+            });
+            if let Some(unit) = sensor.unit {
+                config["unit_of_measurement"] = json!(unit);
+                config["state_class"] = json!("measurement");
+            }
+            if let Some(device_class) = sensor.device_class {
+                config["device_class"] = json!(device_class);
+            }
+            let topic = format!(
+                "{}/sensor/weather_{node}/{}/config",
+                settings.discovery_prefix, sensor.key
+            );
+            (topic, config)
+        })
+        .collect();
+
+    messages.push((state_topic, to_json(data, provider)));
+    messages
+}
+
+/// Publish `data` to the broker from the `[mqtt]` settings, announcing its sensors to Home Assistant.
+///
+/// The password is read from `MQTT_PASSWORD` first, then from the settings.
+pub async fn publish(
+    settings: &MqttSettings,
+    data: &WeatherData,
+    provider: &str,
+) -> Result<(), MqttError> {
+    let mut options = MqttOptions::new(&settings.client_id, &settings.host, settings.port);
+    options.set_keep_alive(Duration::from_secs(5));
+    if let Some(username) = &settings.username {
+        let password = var("MQTT_PASSWORD")
+            .ok()
+            .or_else(|| settings.password.clone())
+            .unwrap_or_default();
+        options.set_credentials(username, password);
+    }
+
+    let messages = messages(settings, data, provider);
+    let (client, mut eventloop) = AsyncClient::new(options, messages.len() + 1);
+    for (topic, payload) in &messages {
+        client
+            .publish(topic, QoS::AtLeastOnce, true, payload.to_string())
+            .await?;
+    }
+
+    let mut pending = messages.len();
+    let acknowledged = tokio::time::timeout(PUBLISH_TIMEOUT, async {
+        while pending > 0 {
+            if let Event::Incoming(Packet::PubAck(ack)) = eventloop.poll().await? {
+                debug!("MQTT message {} acknowledged", ack.pkid);
+                pending -= 1;
+            }
+        }
+        Ok::<_, MqttError>(())
+    })
+    .await;
+
+    match acknowledged {
+        Ok(result) => result?,
+        Err(_) => return Err(MqttError::Timeout(PUBLISH_TIMEOUT)),
+    }
+
+    client.disconnect().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery_and_state_messages() {
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            temp_c: 16.1,
+            ..Default::default()
+        };
+
+        let messages = messages(&MqttSettings::default(), &data, "weatherapi");
+        assert_eq!(messages.len(), SENSORS.len() + 1);
+
+        let (topic, config) = &messages[0];
+        assert_eq!(
+            topic,
+            "homeassistant/sensor/weather_porto_portugal/temp_c/config"
+        );
+        assert_eq!(config["state_topic"], "weather/porto_portugal/state");
+        assert_eq!(config["value_template"], "{{ value_json.temp_c }}");
+        assert_eq!(config["unit_of_measurement"], "°C");
+
+        let (topic, state) = messages.last().unwrap();
+        assert_eq!(topic, "weather/porto_portugal/state");
+        assert_eq!(state["temp_c"], 16.1);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+use notify_rust::{Notification, Urgency};
+use tracing::warn;
+
+/// Summary and body of the desktop notification for `data`.
+fn content(data: &WeatherData, violations: &[String], options: &OutputOptions) -> (String, String) {
+    let summary = options.sanitize(&data.location);
+
+    let mut body = format!("{}, {}", data.condition, options.temperature(data.temp_c));
+    for violation in violations {
+        body.push('\n');
+        body.push_str(violation);
+    }
+
+    (summary, options.sanitize(&body))
+}
+
+/// Pop a desktop notification for a fetched result; `violations` from `weather check` raise its urgency.
+///
+/// Failing to reach the notification daemon is logged, not treated as an error.
+pub async fn notify(data: &WeatherData, violations: &[String], options: &OutputOptions) {
+    let (summary, body) = content(data, violations, options);
+    let urgency = if violations.is_empty() {
+        Urgency::Normal
+    } else {
+        Urgency::Critical
+    };
+    show(summary, body, urgency).await;
+}
+
+/// Pop a critical desktop notification for a warning, e.g. frost.
+pub async fn alert(summary: &str, body: &str) {
+    show(summary.to_string(), body.to_string(), Urgency::Critical).await;
+}
+
+async fn show(summary: String, body: String, urgency: Urgency) {
+    let shown = tokio::task::spawn_blocking(move || {
+        Notification::new()
+            .appname("weather")
+            .summary(&summary)
+            .body(&body)
+            .urgency(urgency)
+            .show()
+            .map(|_| ())
+    })
+    .await;
+
+    match shown {
+        Ok(Ok(())) => {},
+        Ok(Err(e)) => warn!("Failed to show notification: {e}"),
+        Err(e) => warn!("Failed to show notification: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notification_content() {
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            condition: "Light rain".to_string(),
+            temp_c: 16.1,
+            ..Default::default()
+        };
+
+        let (summary, body) = content(&data, &[], &OutputOptions::default());
+        assert_eq!(summary, "Porto, Portugal");
+        assert_eq!(body, "Light rain, 16.1°C");
+
+        let (_, body) = content(
+            &data,
+            &["Rain: Light rain".to_string()],
+            &OutputOptions::default(),
+        );
+        assert_eq!(body, "Light rain, 16.1°C\nRain: Light rain");
+    }
+}
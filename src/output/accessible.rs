@@ -0,0 +1,193 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::pressure::Tendency;
+use crate::weather_providers::WeatherData;
+
+/// The 16 compass points from north clockwise, as i18n keys.
+const COMPASS_KEYS: [&str; 16] = [
+    "compass-n",
+    "compass-nne",
+    "compass-ne",
+    "compass-ene",
+    "compass-e",
+    "compass-ese",
+    "compass-se",
+    "compass-sse",
+    "compass-s",
+    "compass-ssw",
+    "compass-sw",
+    "compass-wsw",
+    "compass-w",
+    "compass-wnw",
+    "compass-nw",
+    "compass-nnw",
+];
+
+/// Compass point the wind blows from, spelled out, e.g. "west-southwest" for 245°.
+pub fn compass_point(deg: f64) -> String {
+    let index = (deg.rem_euclid(360.0) / 22.5).round() as usize % COMPASS_KEYS.len();
+    tr(COMPASS_KEYS[index])
+}
+
+/// `value` followed by the unit named by `key`, e.g. "16.1 degrees Celsius".
+fn spoken(key: &str, value: String) -> String {
+    tr_args(key, &[("value", value)])
+}
+
+/// The report of the text output for screen readers: one fact per line, units and directions
+/// spelled out and no emoji, arrows or box drawing. `options.accessible` is expected to be set,
+/// which spells out units in the sections that follow too.
+pub fn render_accessible(data: &WeatherData, provider: &str, options: &OutputOptions) -> String {
+    let datetime = data.local_datetime();
+    let line = |label: &str, value: String| format!("{}: {value}", tr(label));
+
+    let mut lines = vec![
+        tr_args(
+            "weather-in",
+            &[
+                ("location", data.location.clone()),
+                ("condition", data.condition.clone()),
+            ],
+        ),
+        line(
+            "label-date",
+            format!(
+                "{} {}",
+                datetime.format(&options.date_format),
+                datetime.format(&options.time_format)
+            ),
+        ),
+        line(
+            "label-temperature",
+            spoken("spoken-celsius", format!("{:.1}", data.temp_c)),
+        ),
+        line(
+            "label-humidity",
+            spoken("spoken-percent", format!("{:.0}", data.humidity)),
+        ),
+        line(
+            "label-pressure",
+            spoken("spoken-hpa", format!("{:.0}", data.pressure)),
+        ),
+    ];
+    if let Some(change) = data.pressure_change_3h {
+        lines.push(line(
+            "label-pressure-tendency",
+            tr_args(
+                "spoken-pressure-change",
+                &[
+                    ("tendency", tr(Tendency::of(change).key())),
+                    ("change", format!("{:.1}", change.abs())),
+                ],
+            ),
+        ));
+    }
+    lines.push(line(
+        "label-wind",
+        tr_args(
+            "spoken-wind",
+            &[
+                ("speed", options.wind_speed(data.wind_kph)),
+                ("direction", compass_point(data.wind_deg)),
+            ],
+        ),
+    ));
+    if let Some(snow) = &data.snow {
+        if let Some(cm) = snow.fresh_cm.filter(|cm| *cm > 0.0) {
+            lines.push(line(
+                "label-fresh-snow",
+                spoken("spoken-cm", format!("{cm:.1}")),
+            ));
+        }
+        if let Some(m) = snow.freezing_level_m {
+            lines.push(line(
+                "label-freezing-level",
+                spoken("spoken-m", format!("{m:.0}")),
+            ));
+        }
+    }
+    lines.push(line("label-provider", provider.to_string()));
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Formatter;
+    use crate::output::text::TextFormatter;
+    use crate::weather_providers::PollenData;
+    use chrono::{FixedOffset, TimeZone, Utc};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn one_spelled_out_fact_per_line() {
+        assert_eq!(compass_point(0.0), "north");
+        assert_eq!(compass_point(350.0), "north");
+        assert_eq!(compass_point(245.0), "west-southwest");
+
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            datetime: Utc.with_ymd_and_hms(2025, 12, 5, 17, 15, 0).unwrap(),
+            utc_offset: FixedOffset::east_opt(0),
+            condition: "Partly cloudy".to_string(),
+            temp_c: 16.1,
+            humidity: 94.0,
+            pressure: 1018.0,
+            pressure_change_3h: Some(-3.4),
+            wind_kph: 22.0,
+            wind_deg: 245.0,
+            ..Default::default()
+        };
+        let options = OutputOptions {
+            accessible: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            render_accessible(&data, "weatherapi", &options),
+            "Weather in Porto, Portugal: Partly cloudy\n\
+             Date: 2025-12-05 17:15\n\
+             Temperature: 16.1 degrees Celsius\n\
+             Humidity: 94 percent\n\
+             Pressure: 1018 hectopascals\n\
+             Pressure tendency: falling by 3.4 hectopascals in 3 hours\n\
+             Wind: 22.0 kilometers per hour from the west-southwest\n\
+             Provider: weatherapi"
+        );
+    }
+
+    #[test]
+    fn sections_after_the_report_spell_out_units() {
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            temp_c: 16.0,
+            humidity: 97.0,
+            wind_kph: 30.0,
+            visibility_km: Some(0.3),
+            pollen: Some(PollenData {
+                grass: Some(120.0),
+                ..Default::default()
+            }),
+            sources: BTreeMap::from([("uv_index".to_string(), "openmeteo".to_string())]),
+            ..Default::default()
+        };
+        let options = OutputOptions {
+            accessible: true,
+            emoji: false,
+            allergy: true,
+            sources: true,
+            ..Default::default()
+        };
+
+        let text = TextFormatter.format(&data, "weatherapi", &options);
+        assert!(
+            text.contains("Filled in from openmeteo: UV index"),
+            "{text}"
+        );
+        assert!(text.contains("120 grains per cubic meter"), "{text}");
+        assert!(text.contains("wind of 30.0 kilometers per hour"), "{text}");
+        assert!(text.contains("humid air (97 percent)"), "{text}");
+        assert!(text.contains("visibility 0.3 kilometers"), "{text}");
+        assert!(!text.contains("km"), "{text}");
+    }
+}
@@ -0,0 +1,98 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::output::color::Rgb;
+use crate::weather_providers::alert::{Severity, WeatherAlert};
+
+/// Color of a severity, the same whatever the provider called it.
+fn color(severity: Severity) -> Rgb {
+    match severity {
+        Severity::Advisory => Rgb(250, 204, 21),
+        Severity::Watch => Rgb(249, 115, 22),
+        Severity::Warning => Rgb(239, 68, 68),
+        Severity::Emergency => Rgb(192, 38, 211),
+    }
+}
+
+fn name(severity: Severity) -> String {
+    tr(match severity {
+        Severity::Advisory => "severity-advisory",
+        Severity::Watch => "severity-watch",
+        Severity::Warning => "severity-warning",
+        Severity::Emergency => "severity-emergency",
+    })
+}
+
+/// Alerts for `weather alerts`, one per line with the colored severity first and the headline below.
+pub fn render_alerts(location: &str, alerts: &[WeatherAlert], options: &OutputOptions) -> String {
+    if alerts.is_empty() {
+        return options.sanitize(&tr_args(
+            "alerts-none",
+            &[("location", location.to_string())],
+        ));
+    }
+    let names: Vec<String> = alerts.iter().map(|alert| name(alert.severity)).collect();
+    let width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+
+    let mut lines = Vec::new();
+    for (alert, name) in alerts.iter().zip(&names) {
+        let padded = format!("{:<width$}", name.to_uppercase());
+        let mut line = format!(
+            "{}  {}",
+            options.paint(&padded, Some(color(alert.severity))),
+            alert.event
+        );
+        if let Some(expires) = alert.expires {
+            let time = expires
+                .format(&format!("{} {}", options.date_format, options.time_format))
+                .to_string();
+            line.push_str(&format!(
+                " ({})",
+                tr_args("alerts-until", &[("time", format!("{time} UTC"))])
+            ));
+        }
+        lines.push(line);
+        if let Some(headline) = alert.headline.as_ref().filter(|h| **h != alert.event) {
+            lines.push(format!("{:width$}  {headline}", ""));
+        }
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn aligned_by_severity() {
+        let alerts = [
+            WeatherAlert {
+                event: "Orange Wind Warning".to_string(),
+                severity: Severity::Warning,
+                headline: Some("Gusts up to 90 km/h".to_string()),
+                description: None,
+                effective: None,
+                expires: Some(Utc.with_ymd_and_hms(2025, 12, 5, 21, 0, 0).unwrap()),
+            },
+            WeatherAlert {
+                event: "Fog Advisory".to_string(),
+                severity: Severity::Advisory,
+                headline: None,
+                description: None,
+                effective: None,
+                expires: None,
+            },
+        ];
+
+        assert_eq!(
+            render_alerts("Porto", &alerts, &OutputOptions::default()),
+            "WARNING   Orange Wind Warning (until 2025-12-05 21:00 UTC)\n\
+             \x20         Gusts up to 90 km/h\n\
+             ADVISORY  Fog Advisory"
+        );
+        assert_eq!(
+            render_alerts("Porto", &[], &OutputOptions::default()),
+            "No alerts in effect for Porto."
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::color::ColorChoice;
+use crate::output::{Formatter, OutputOptions, render_oneline};
+use crate::weather_providers::WeatherData;
+use serde_json::{Value, json};
+
+/// Plain-text options for chat messages: never colorized.
+fn plain(options: &OutputOptions) -> OutputOptions {
+    OutputOptions {
+        color: ColorChoice::Never,
+        ..options.clone()
+    }
+}
+
+fn title(data: &WeatherData, options: &OutputOptions) -> String {
+    let title = tr_args(
+        "weather-in",
+        &[
+            ("location", data.location.clone()),
+            ("condition", data.condition.clone()),
+        ],
+    );
+    let title = match options.icon(data.kind) {
+        Some(icon) => format!("{title} {icon}"),
+        None => title,
+    };
+    options.sanitize(&title)
+}
+
+/// Label/value pairs shown as fields of the message.
+fn fields(data: &WeatherData, options: &OutputOptions) -> Vec<(String, String)> {
+    [
+        ("label-temperature", options.temperature(data.temp_c)),
+        ("label-humidity", format!("{:.0}%", data.humidity)),
+        ("label-pressure", format!("{:.0} hPa", data.pressure)),
+        (
+            "label-wind",
+            tr_args(
+                "wind-value",
+                &[
+                    ("speed", options.wind_described(data.wind_kph)),
+                    (
+                        "degree",
+                        format!("{:.0}{}", data.wind_deg, options.degree()),
+                    ),
+                ],
+            ),
+        ),
+    ]
+    .into_iter()
+    .map(|(label, value)| (options.sanitize(&tr(label)), options.sanitize(&value)))
+    .collect()
+}
+
+/// Slack Block Kit message, as accepted by incoming webhooks.
+pub fn slack_message(data: &WeatherData, provider: &str, options: &OutputOptions) -> Value {
+    let options = plain(options);
+    let fields: Vec<Value> = fields(data, &options)
+        .into_iter()
+        .map(|(label, value)| json!({"type": "mrkdwn", "text": format!("*{label}*\n{value}")}))
+        .collect();
+    let context = format!(
+        "{} · {}",
+        provider,
+        data.local_datetime()
+            .format(&format!("{} {}", options.date_format, options.time_format))
+    );
+
+    json!({
+        "text": render_oneline(data, &options),
+        "blocks": [
+            {"type": "header", "text": {"type": "plain_text", "text": title(data, &options)}},
+            {"type": "section", "fields": fields},
+            {"type": "context", "elements": [{"type": "mrkdwn", "text": context}]},
+        ],
+    })
+}
+
+/// Discord embed message, as accepted by channel webhooks.
+pub fn discord_message(data: &WeatherData, provider: &str, options: &OutputOptions) -> Value {
+    let options = plain(options);
+    let fields: Vec<Value> = fields(data, &options)
+        .into_iter()
+        .map(|(name, value)| json!({"name": name, "value": value, "inline": true}))
+        .collect();
+    let color = options.theme.temperature(data.temp_c);
+
+    json!({
+        "embeds": [{
+            "title": title(data, &options),
+            "color": (u32::from(color.0) << 16) | (u32::from(color.1) << 8) | u32::from(color.2),
+            "fields": fields,
+            "footer": {"text": provider},
+            "timestamp": data.datetime.to_rfc3339(),
+        }],
+    })
+}
+
+/// Slack Block Kit JSON.
+pub struct SlackFormatter;
+
+impl Formatter for SlackFormatter {
+    fn format(&self, data: &WeatherData, provider: &str, options: &OutputOptions) -> String {
+        serde_json::to_string_pretty(&slack_message(data, provider, options)).unwrap_or_default()
+    }
+}
+
+/// Discord embed JSON.
+pub struct DiscordFormatter;
+
+impl Formatter for DiscordFormatter {
+    fn format(&self, data: &WeatherData, provider: &str, options: &OutputOptions) -> String {
+        serde_json::to_string_pretty(&discord_message(data, provider, options)).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::icons::IconSet;
+    use crate::weather_providers::condition::ConditionKind;
+
+    fn sample() -> WeatherData {
+        WeatherData {
+            location: "Porto, Portugal".to_string(),
+            condition: "Light rain".to_string(),
+            kind: ConditionKind::Rain,
+            temp_c: 16.1,
+            humidity: 94.0,
+            ..Default::default()
+        }
+    }
+
+    fn options() -> OutputOptions {
+        OutputOptions {
+            icons: IconSet::Text,
+            color: ColorChoice::Always,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn slack_blocks() {
+        let message = slack_message(&sample(), "weatherapi", &options());
+
+        assert_eq!(
+            message["blocks"][0]["text"]["text"],
+            "Weather in Porto, Portugal: Light rain [rain]"
+        );
+        assert_eq!(
+            message["blocks"][1]["fields"][0]["text"],
+            "*Temperature*\n16.1°C"
+        );
+        assert!(!message["text"].as_str().unwrap().contains('\x1b'));
+    }
+
+    #[test]
+    fn discord_embed() {
+        let message = discord_message(&sample(), "weatherapi", &options());
+        let embed = &message["embeds"][0];
+
+        assert_eq!(embed["footer"]["text"], "weatherapi");
+        assert_eq!(embed["fields"][1]["name"], "Humidity");
+        assert_eq!(embed["fields"][1]["value"], "94%");
+        assert!(embed["color"].is_u64());
+    }
+}
@@ -0,0 +1,192 @@
+use crate::config::SettingsError;
+use crate::output::console;
+use crate::weather_providers::condition::ConditionKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+/// Temperatures at or below this get the theme's `cold` color.
+const GRADIENT_MIN_C: f64 = -10.0;
+/// Temperatures at or above this get the theme's `hot` color.
+const GRADIENT_MAX_C: f64 = 35.0;
+
+/// When to colorize output.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Colorize when stdout is a terminal understanding ANSI codes and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(self) -> bool {
+        match self {
+            Self::Always => {
+                console::ansi_supported();
+                true
+            },
+            Self::Never => false,
+            Self::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && std::io::stdout().is_terminal()
+                    && console::ansi_supported()
+            },
+        }
+    }
+}
+
+/// 24-bit color parsed from "#rrggbb".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl FromStr for Rgb {
+    type Err = SettingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SettingsError::Invalid(format!("color '{s}' is not in #rrggbb format"));
+        let hex = s.strip_prefix('#').ok_or_else(invalid)?;
+        if hex.len() != 6 {
+            return Err(invalid());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+
+        Ok(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl Rgb {
+    fn lerp(self, other: Self, t: f64) -> Self {
+        let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Self(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+/// A theme as written in a `[themes.<name>]` section, every color is optional.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ThemeSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cold: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hot: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Per-condition colors, e.g. `rain = "#3b82f6"`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub conditions: HashMap<ConditionKind, String>,
+}
+
+/// Resolved colors used while rendering.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub cold: Rgb,
+    pub hot: Rgb,
+    pub label: Rgb,
+    pub conditions: HashMap<ConditionKind, Rgb>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let conditions = HashMap::from([
+            (ConditionKind::Clear, Rgb(250, 204, 21)),
+            (ConditionKind::PartlyCloudy, Rgb(253, 230, 138)),
+            (ConditionKind::Cloudy, Rgb(156, 163, 175)),
+            (ConditionKind::Fog, Rgb(148, 163, 184)),
+            (ConditionKind::Drizzle, Rgb(125, 211, 252)),
+            (ConditionKind::Rain, Rgb(59, 130, 246)),
+            (ConditionKind::Sleet, Rgb(165, 180, 252)),
+            (ConditionKind::Snow, Rgb(241, 245, 249)),
+            (ConditionKind::Thunderstorm, Rgb(168, 85, 247)),
+        ]);
+
+        Self {
+            cold: Rgb(59, 130, 246),
+            hot: Rgb(239, 68, 68),
+            label: Rgb(148, 163, 184),
+            conditions,
+        }
+    }
+}
+
+impl Theme {
+    /// Resolve a theme definition on top of the default colors.
+    pub fn from_settings(settings: &ThemeSettings) -> Result<Self, SettingsError> {
+        let mut theme = Self::default();
+        if let Some(cold) = &settings.cold {
+            theme.cold = cold.parse()?;
+        }
+        if let Some(hot) = &settings.hot {
+            theme.hot = hot.parse()?;
+        }
+        if let Some(label) = &settings.label {
+            theme.label = label.parse()?;
+        }
+        for (kind, color) in &settings.conditions {
+            theme.conditions.insert(*kind, color.parse()?);
+        }
+
+        Ok(theme)
+    }
+
+    /// Color for a temperature on the cold → hot gradient.
+    pub fn temperature(&self, temp_c: f64) -> Rgb {
+        let t = ((temp_c - GRADIENT_MIN_C) / (GRADIENT_MAX_C - GRADIENT_MIN_C)).clamp(0.0, 1.0);
+        self.cold.lerp(self.hot, t)
+    }
+}
+
+/// Wrap `text` in a 24-bit ANSI foreground color.
+pub fn paint(text: &str, color: Rgb) -> String {
+    format!(
+        "\x1b[38;2;{};{};{}m{text}\x1b[0m",
+        color.0, color.1, color.2
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rgb() {
+        assert_eq!("#ff8000".parse::<Rgb>().unwrap(), Rgb(255, 128, 0));
+        assert!("ff8000".parse::<Rgb>().is_err());
+        assert!("#ff80".parse::<Rgb>().is_err());
+        assert!("#gg0000".parse::<Rgb>().is_err());
+    }
+
+    #[test]
+    fn temperature_gradient() {
+        let theme = Theme::default();
+        assert_eq!(theme.temperature(-20.0), theme.cold);
+        assert_eq!(theme.temperature(40.0), theme.hot);
+        assert_ne!(theme.temperature(12.5), theme.cold);
+        assert_ne!(theme.temperature(12.5), theme.hot);
+    }
+
+    #[test]
+    fn theme_overrides() {
+        let settings = ThemeSettings {
+            hot: Some("#ffffff".to_string()),
+            conditions: HashMap::from([(ConditionKind::Rain, "#000000".to_string())]),
+            ..Default::default()
+        };
+
+        let theme = Theme::from_settings(&settings).unwrap();
+        assert_eq!(theme.hot, Rgb(255, 255, 255));
+        assert_eq!(theme.cold, Theme::default().cold);
+        assert_eq!(theme.conditions[&ConditionKind::Rain], Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn paint_wraps_in_escape_codes() {
+        assert_eq!(paint("x", Rgb(1, 2, 3)), "\x1b[38;2;1;2;3mx\x1b[0m");
+    }
+}
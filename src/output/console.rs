@@ -0,0 +1,27 @@
+//! What the terminal can display. Unix terminals handle ANSI colors and emoji, the Windows
+//! console only when asked to or, for emoji, when it is a modern host.
+
+use std::sync::OnceLock;
+
+/// Whether ANSI escape codes are understood, turning on their processing on Windows consoles.
+pub fn ansi_supported() -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        #[cfg(windows)]
+        return enable_ansi_support::enable_ansi_support().is_ok();
+        #[cfg(not(windows))]
+        true
+    })
+}
+
+/// Whether emoji are likely to be drawn rather than shown as boxes or question marks.
+///
+/// The legacy Windows console (cmd.exe, older PowerShell) cannot, Windows Terminal and the
+/// VS Code terminal can.
+pub fn emoji_supported() -> bool {
+    if cfg!(windows) {
+        std::env::var_os("WT_SESSION").is_some() || std::env::var_os("TERM_PROGRAM").is_some()
+    } else {
+        true
+    }
+}
@@ -0,0 +1,106 @@
+use crate::i18n::tr_args;
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+
+/// Side-by-side report for `weather diff`: each field of `data` against `against`, with the change.
+pub fn render_diff(data: &WeatherData, against: &WeatherData, options: &OutputOptions) -> String {
+    let when = |data: &WeatherData| {
+        let datetime = data.local_datetime();
+        format!(
+            "{} {}",
+            datetime.format(&options.date_format),
+            datetime.format(&options.time_format)
+        )
+    };
+    let line = |label: &str, value: String, other: String, delta: Option<String>| {
+        let values = tr_args("diff-values", &[("value", value), ("against", other)]);
+        match delta {
+            Some(delta) => format!("> {}: {values} ({delta})", options.label(label)),
+            None => format!("> {}: {values}", options.label(label)),
+        }
+    };
+    let unit = |value: f64, unit: &str| format!("{value:.1}{unit}");
+    let delta = |a: f64, b: f64, unit: &str| Some(format!("{:+.1}{unit}", a - b));
+    let degree = if options.ascii { " C" } else { "°C" };
+
+    let lines = [
+        tr_args(
+            "diff-title",
+            &[
+                ("location", data.location.clone()),
+                ("date", when(data)),
+                ("against", when(against)),
+            ],
+        ),
+        line(
+            "label-temperature",
+            options.temperature(data.temp_c),
+            options.temperature(against.temp_c),
+            delta(data.temp_c, against.temp_c, degree),
+        ),
+        line(
+            "label-humidity",
+            unit(data.humidity, "%"),
+            unit(against.humidity, "%"),
+            delta(data.humidity, against.humidity, "%"),
+        ),
+        line(
+            "label-pressure",
+            unit(data.pressure, " hPa"),
+            unit(against.pressure, " hPa"),
+            delta(data.pressure, against.pressure, " hPa"),
+        ),
+        line(
+            "label-wind",
+            options.wind_speed(data.wind_kph),
+            options.wind_speed(against.wind_kph),
+            Some(options.wind_change(data.wind_kph, against.wind_kph)),
+        ),
+        line(
+            "label-condition",
+            data.condition.clone(),
+            against.condition.clone(),
+            None,
+        ),
+    ];
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    #[test]
+    fn year_over_year() {
+        let reading = |year: i32, temp_c: f64, condition: &str| WeatherData {
+            location: "Porto".to_string(),
+            datetime: Utc.with_ymd_and_hms(year, 7, 1, 12, 0, 0).unwrap(),
+            utc_offset: FixedOffset::east_opt(3600),
+            temp_c,
+            humidity: 60.0,
+            pressure: 1015.0,
+            wind_kph: 12.0,
+            condition: condition.to_string(),
+            ..Default::default()
+        };
+        let options = OutputOptions::default();
+
+        let text = render_diff(
+            &reading(2024, 24.1, "Sunny"),
+            &reading(2023, 21.3, "Cloudy"),
+            &options,
+        );
+        assert!(
+            text.starts_with("Porto: 2024-07-01 13:00 vs 2023-07-01 13:00"),
+            "{text}"
+        );
+        assert!(text.contains("24.1°C vs 21.3°C (+2.8°C)"), "{text}");
+        assert!(text.contains("60.0% vs 60.0% (+0.0%)"), "{text}");
+        assert!(
+            text.contains("12.0 km/h vs 12.0 km/h (+0.0 km/h)"),
+            "{text}"
+        );
+        assert!(text.contains("Sunny vs Cloudy"), "{text}");
+    }
+}
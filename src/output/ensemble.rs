@@ -0,0 +1,110 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::{EnsembleHour, Spread};
+
+/// Width of the temperature band, in characters.
+const BAND_WIDTH: usize = 20;
+
+/// The temperature range of one hour drawn on the scale `min..=max` of the whole forecast: a bar
+/// from the 10th to the 90th percentile with a mark at the median.
+fn band(spread: &Spread, min: f64, max: f64, options: &OutputOptions) -> String {
+    let (bar, mark) = if options.ascii {
+        ('=', '|')
+    } else {
+        ('━', '●')
+    };
+    let column = |t: f64| {
+        let share = if max > min {
+            (t - min) / (max - min)
+        } else {
+            0.5
+        };
+        (share * (BAND_WIDTH - 1) as f64).round() as usize
+    };
+    let (from, to, median) = (
+        column(spread.p10),
+        column(spread.p90),
+        column(spread.median),
+    );
+    (0..BAND_WIDTH)
+        .map(|i| match i {
+            _ if i == median => mark,
+            _ if (from..=to).contains(&i) => bar,
+            _ => ' ',
+        })
+        .collect()
+}
+
+/// Ensemble forecast for `weather ensemble`: per hour the median temperature and precipitation,
+/// the range of the middle 80% of the runs, and the temperature range as a band.
+pub fn render_ensemble(location: &str, hours: &[EnsembleHour], options: &OutputOptions) -> String {
+    let temps = || hours.iter().filter_map(|hour| hour.temp_c);
+    let min = temps().map(|t| t.p10).fold(f64::INFINITY, f64::min);
+    let max = temps().map(|t| t.p90).fold(f64::NEG_INFINITY, f64::max);
+
+    let mut lines = vec![tr_args(
+        "ensemble-title",
+        &[("location", location.to_string())],
+    )];
+    let time_format = format!("{} {}", options.date_format, options.time_format);
+    for hour in hours {
+        let time = hour.time.format(&time_format);
+        let temp = match &hour.temp_c {
+            Some(t) => format!(
+                "{} ({}..{})  {}",
+                options.temperature(t.median),
+                options.temperature(t.p10),
+                options.temperature(t.p90),
+                band(t, min, max, options),
+            ),
+            None => tr("not-available"),
+        };
+        let rain = match &hour.precipitation_mm {
+            Some(p) => format!("{:.1} mm ({:.1}..{:.1})", p.median, p.p10, p.p90),
+            None => tr("not-available"),
+        };
+        lines.push(format!("{time}  {temp}  {rain}"));
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn ranges_and_band() {
+        let spread = |p10, median, p90| Spread { p10, median, p90 };
+        let hours = vec![
+            EnsembleHour {
+                time: NaiveDate::from_ymd_opt(2025, 6, 1)
+                    .unwrap()
+                    .and_hms_opt(7, 0, 0)
+                    .unwrap(),
+                temp_c: Some(spread(10.0, 12.0, 13.0)),
+                precipitation_mm: Some(spread(0.0, 0.2, 1.5)),
+            },
+            EnsembleHour {
+                time: NaiveDate::from_ymd_opt(2025, 6, 1)
+                    .unwrap()
+                    .and_hms_opt(8, 0, 0)
+                    .unwrap(),
+                temp_c: Some(spread(13.0, 15.0, 29.0)),
+                precipitation_mm: None,
+            },
+        ];
+        let options = OutputOptions {
+            ascii: true,
+            ..Default::default()
+        };
+
+        let text = render_ensemble("Porto", &hours, &options);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[1],
+            "2025-06-01 07:00  12.0 C (10.0 C..13.0 C)  ==|=                  0.2 mm (0.0..1.5)"
+        );
+        assert!(lines[2].ends_with("   ==|==============  n/a"), "{text}");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::weather_providers::condition::ConditionKind;
+use serde::{Deserialize, Serialize};
+
+/// Glyphs used to illustrate the weather condition.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconSet {
+    #[default]
+    Emoji,
+    /// Weather icons from a patched Nerd Font.
+    NerdFont,
+    /// Plain ASCII labels.
+    Text,
+}
+
+impl IconSet {
+    pub fn icon(self, kind: ConditionKind) -> &'static str {
+        match self {
+            Self::Emoji => emoji(kind),
+            Self::NerdFont => nerd_font(kind),
+            Self::Text => text(kind),
+        }
+    }
+}
+
+fn emoji(kind: ConditionKind) -> &'static str {
+    match kind {
+        ConditionKind::Clear => "☀️",
+        ConditionKind::PartlyCloudy => "⛅",
+        ConditionKind::Cloudy => "☁️",
+        ConditionKind::Fog => "🌫️",
+        ConditionKind::Drizzle => "🌦️",
+        ConditionKind::Rain => "🌧️",
+        ConditionKind::Sleet => "🌨️",
+        ConditionKind::Snow => "❄️",
+        ConditionKind::Thunderstorm => "⛈️",
+        ConditionKind::Unknown => "🌡️",
+    }
+}
+
+fn nerd_font(kind: ConditionKind) -> &'static str {
+    match kind {
+        ConditionKind::Clear => "\u{e30d}",
+        ConditionKind::PartlyCloudy => "\u{e302}",
+        ConditionKind::Cloudy => "\u{e312}",
+        ConditionKind::Fog => "\u{e313}",
+        ConditionKind::Drizzle => "\u{e31c}",
+        ConditionKind::Rain => "\u{e318}",
+        ConditionKind::Sleet => "\u{e3ad}",
+        ConditionKind::Snow => "\u{e31a}",
+        ConditionKind::Thunderstorm => "\u{e31d}",
+        ConditionKind::Unknown => "\u{e374}",
+    }
+}
+
+fn text(kind: ConditionKind) -> &'static str {
+    match kind {
+        ConditionKind::Clear => "[clear]",
+        ConditionKind::PartlyCloudy => "[partly cloudy]",
+        ConditionKind::Cloudy => "[cloudy]",
+        ConditionKind::Fog => "[fog]",
+        ConditionKind::Drizzle => "[drizzle]",
+        ConditionKind::Rain => "[rain]",
+        ConditionKind::Sleet => "[sleet]",
+        ConditionKind::Snow => "[snow]",
+        ConditionKind::Thunderstorm => "[thunderstorm]",
+        ConditionKind::Unknown => "[?]",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icon_sets() {
+        assert_eq!(IconSet::Emoji.icon(ConditionKind::Rain), "🌧️");
+        assert_eq!(IconSet::NerdFont.icon(ConditionKind::Clear), "\u{e30d}");
+        assert_eq!(IconSet::Text.icon(ConditionKind::Snow), "[snow]");
+    }
+}
@@ -0,0 +1,85 @@
+use crate::output::color::ColorChoice;
+use crate::output::{Formatter, OutputOptions, render_text};
+use crate::weather_providers::WeatherData;
+use serde_json::{Value, json};
+
+/// The result as a JSON value, with the provider and the local date/time added.
+pub fn to_json(data: &WeatherData, provider: &str) -> Value {
+    let mut value = serde_json::to_value(data).unwrap_or_else(|_| json!({}));
+    if let Value::Object(map) = &mut value {
+        map.insert("provider".to_string(), json!(provider));
+        map.insert(
+            "local_datetime".to_string(),
+            json!(data.local_datetime().to_rfc3339()),
+        );
+    }
+    value
+}
+
+/// Pretty-printed JSON.
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn format(&self, data: &WeatherData, provider: &str, _options: &OutputOptions) -> String {
+        serde_json::to_string_pretty(&to_json(data, provider)).unwrap_or_default()
+    }
+}
+
+/// Custom module output for Waybar: `text`, `tooltip` and a CSS `class`.
+pub struct WaybarFormatter;
+
+impl Formatter for WaybarFormatter {
+    fn format(&self, data: &WeatherData, _provider: &str, options: &OutputOptions) -> String {
+        let plain = OutputOptions {
+            color: ColorChoice::Never,
+            ..options.clone()
+        };
+        let text = match plain.icon(data.kind) {
+            Some(icon) => format!("{icon} {}", plain.temperature(data.temp_c)),
+            None => plain.temperature(data.temp_c),
+        };
+
+        json!({
+            "text": plain.sanitize(&text),
+            "tooltip": render_text(data, &plain),
+            "class": data.kind,
+            "alt": data.kind,
+        })
+        .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::condition::ConditionKind;
+
+    fn sample() -> WeatherData {
+        WeatherData {
+            location: "Porto, Portugal".to_string(),
+            temp_c: 16.1,
+            kind: ConditionKind::Rain,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn json_includes_provider() {
+        let text = JsonFormatter.format(&sample(), "weatherapi", &OutputOptions::default());
+        let value: Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(value["provider"], "weatherapi");
+        assert_eq!(value["location"], "Porto, Portugal");
+        assert_eq!(value["temp_c"], 16.1);
+    }
+
+    #[test]
+    fn waybar_output() {
+        let text = WaybarFormatter.format(&sample(), "weatherapi", &OutputOptions::default());
+        let value: Value = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(value["text"], "🌧️ 16.1°C");
+        assert_eq!(value["class"], "rain");
+        assert!(!text.contains('\n'));
+    }
+}
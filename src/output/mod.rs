@@ -0,0 +1,547 @@
+use crate::comfort;
+use crate::config::{FogSettings, HeatSettings, Settings, SettingsError};
+use crate::i18n::{tr, tr_args};
+use crate::weather_providers::WeatherData;
+use crate::weather_providers::condition::ConditionKind;
+use chrono::format::StrftimeItems;
+use color::{ColorChoice, Rgb, Theme, paint};
+use icons::IconSet;
+use unicode_normalization::UnicodeNormalization;
+use wind::WindUnit;
+
+pub mod accessible;
+pub mod alerts;
+pub mod chat;
+pub mod color;
+pub mod console;
+pub mod diff;
+pub mod ensemble;
+pub mod icons;
+pub mod json;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod plan;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod query;
+pub mod registry;
+pub mod snow;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod status;
+pub mod table;
+pub mod template;
+pub mod text;
+pub mod tsv;
+pub mod wind;
+
+/// Renders a fetched result for display; see [`registry::FormatterRegistry`].
+pub trait Formatter: Send + Sync {
+    fn format(&self, data: &WeatherData, provider: &str, options: &OutputOptions) -> String;
+}
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_TIME_FORMAT: &str = "%H:%M";
+
+/// Options controlling how weather data is rendered.
+#[derive(Debug, Clone)]
+pub struct OutputOptions {
+    /// strftime pattern for the date part.
+    pub date_format: String,
+    /// strftime pattern for the time part.
+    pub time_format: String,
+    /// Decorate output with emoji.
+    pub emoji: bool,
+    /// Restrict output to pure ASCII, implies no emoji.
+    pub ascii: bool,
+    /// Glyphs used for the condition icon.
+    pub icons: IconSet,
+    /// When to colorize output.
+    pub color: ColorChoice,
+    /// Colors used when colorizing.
+    pub theme: Theme,
+    /// Layout for the `template` formatter.
+    pub template: Option<String>,
+    /// Add a "what to wear" line to the text output.
+    pub advice: bool,
+    /// Add an allergy risk section to the text output.
+    pub allergy: bool,
+    /// Visibility in km below which the text output carries a fog advisory.
+    pub fog_visibility_km: f64,
+    /// WBGT thresholds from which the text output carries a heat warning.
+    pub heat: HeatSettings,
+    /// Unit wind speeds are primarily shown in.
+    pub wind_unit: WindUnit,
+    /// Name the provider of fields filled in from a secondary one.
+    pub sources: bool,
+    /// Start tabular output with a line of column names.
+    pub header: bool,
+    /// Render the text output for screen readers, implies no emoji.
+    pub accessible: bool,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            time_format: DEFAULT_TIME_FORMAT.to_string(),
+            emoji: true,
+            ascii: false,
+            icons: IconSet::default(),
+            color: ColorChoice::default(),
+            theme: Theme::default(),
+            template: None,
+            advice: false,
+            allergy: false,
+            fog_visibility_km: FogSettings::default().visibility_km,
+            heat: HeatSettings::default(),
+            wind_unit: WindUnit::default(),
+            sources: false,
+            header: true,
+            accessible: false,
+        }
+    }
+}
+
+impl OutputOptions {
+    /// Build options from the `[display]` settings, validating patterns and theme.
+    pub fn from_settings(settings: &Settings) -> Result<Self, SettingsError> {
+        let display = &settings.display;
+
+        let time_format = match display.time_format.as_deref() {
+            None | Some("24h") => DEFAULT_TIME_FORMAT.to_string(),
+            Some("12h") => "%I:%M %p".to_string(),
+            Some(pattern) => validate_pattern("time_format", pattern)?,
+        };
+
+        let date_format = match display.date_format.as_deref() {
+            None => DEFAULT_DATE_FORMAT.to_string(),
+            Some(pattern) => validate_pattern("date_format", pattern)?,
+        };
+
+        let theme = match display.theme.as_deref() {
+            None | Some("default") => Theme::default(),
+            Some(name) => {
+                let theme = settings.themes.get(name).ok_or_else(|| {
+                    SettingsError::Invalid(format!("theme '{name}' is not defined"))
+                })?;
+                Theme::from_settings(theme)?
+            },
+        };
+
+        let emoji = console::emoji_supported();
+        Ok(Self {
+            date_format,
+            time_format,
+            emoji: display.emoji.unwrap_or(emoji) && !display.ascii && !display.accessible,
+            ascii: display.ascii,
+            // Text labels rather than nothing where emoji would come out garbled.
+            icons: display
+                .icons
+                .unwrap_or(if emoji { IconSet::Emoji } else { IconSet::Text }),
+            color: display.color.unwrap_or_default(),
+            theme,
+            template: display.template.clone(),
+            advice: display.advice,
+            allergy: display.allergy,
+            fog_visibility_km: settings.fog.visibility_km,
+            heat: settings.heat,
+            wind_unit: display.wind_unit.unwrap_or_default(),
+            sources: false,
+            header: true,
+            accessible: display.accessible,
+        })
+    }
+
+    /// Apply the ASCII restriction to already rendered text.
+    pub fn sanitize(&self, text: &str) -> String {
+        if self.ascii {
+            to_ascii(text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Condition icon, or `None` when the icon set is ruled out by the emoji/ASCII options.
+    pub(crate) fn icon(&self, kind: ConditionKind) -> Option<&'static str> {
+        match self.icons {
+            IconSet::Emoji if !self.emoji || self.ascii => None,
+            IconSet::NerdFont if self.ascii => None,
+            icons => Some(icons.icon(kind)),
+        }
+    }
+
+    fn paint(&self, text: &str, color: Option<Rgb>) -> String {
+        match color {
+            Some(color) if self.color.enabled() => paint(text, color),
+            _ => text.to_string(),
+        }
+    }
+
+    fn label(&self, key: &str) -> String {
+        self.paint(&tr(key), Some(self.theme.label))
+    }
+
+    pub(crate) fn temperature(&self, temp_c: f64) -> String {
+        let symbol = if self.ascii { " C" } else { "°C" };
+        self.measure(format!("{temp_c:.1}"), symbol, "spoken-celsius")
+    }
+
+    /// `value` followed by its unit `symbol`, e.g. "94%", or by the unit spelled out with
+    /// `spoken_key` when rendering for screen readers.
+    pub(crate) fn measure(&self, value: String, symbol: &str, spoken_key: &str) -> String {
+        if self.accessible {
+            tr_args(spoken_key, &[("value", value)])
+        } else {
+            format!("{value}{symbol}")
+        }
+    }
+
+    pub(crate) fn degree(&self) -> &'static str {
+        if self.ascii { " deg" } else { "°" }
+    }
+}
+
+/// Fold accented letters to their base form and drop everything else outside ASCII.
+fn to_ascii(text: &str) -> String {
+    text.nfkd().filter(char::is_ascii).collect()
+}
+
+/// Values as a row of bars from lowest to highest.
+pub(crate) fn sparkline(values: &[f64], options: &OutputOptions) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    const ASCII_BARS: [char; 8] = ['_', '.', ',', '-', '~', '=', '*', '#'];
+    let bars = if options.ascii { ASCII_BARS } else { BARS };
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            let share = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.5
+            };
+            bars[(share * (bars.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+/// Lowercase location folded to ASCII, with everything but letters and digits turned into `_`,
+/// usable in topics, ids and file names.
+pub fn slug(location: &str) -> String {
+    to_ascii(location)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Render weather data as a single compact line.
+pub fn render_oneline(data: &WeatherData, options: &OutputOptions) -> String {
+    let condition = match options.icon(data.kind) {
+        Some(icon) => format!("{icon} {}", data.condition),
+        None => data.condition.clone(),
+    };
+    let condition = options.paint(
+        &condition,
+        options.theme.conditions.get(&data.kind).copied(),
+    );
+
+    let text = format!(
+        "{}: {}, {}, {:.0}%, {:.0} hPa, {} {:.0}{}",
+        data.location,
+        condition,
+        options.paint(
+            &options.temperature(data.temp_c),
+            Some(options.theme.temperature(data.temp_c))
+        ),
+        data.humidity,
+        data.pressure,
+        options.wind_speed(data.wind_kph),
+        data.wind_deg,
+        options.degree(),
+    );
+
+    options.sanitize(&text)
+}
+
+fn validate_pattern(name: &str, pattern: &str) -> Result<String, SettingsError> {
+    StrftimeItems::new(pattern).parse().map_err(|_| {
+        SettingsError::Invalid(format!("{name} '{pattern}' is not a valid pattern"))
+    })?;
+    Ok(pattern.to_string())
+}
+
+/// Humidity with how it feels, e.g. "78.0% (muggy, dew point 17.2°C)".
+fn humidity(data: &WeatherData, options: &OutputOptions) -> String {
+    match comfort::render_comfort(data.temp_c, data.humidity, options) {
+        Some(comfort) => format!("{:.1}% ({comfort})", data.humidity),
+        None => format!("{:.1}%", data.humidity),
+    }
+}
+
+/// Pressure with its tendency over 3 hours when known, e.g. "1004.0 hPa ↘ falling, -3.4 hPa/3h".
+pub(crate) fn pressure(data: &WeatherData, options: &OutputOptions) -> String {
+    match data.pressure_change_3h {
+        Some(change) => format!(
+            "{:.1} hPa {}",
+            data.pressure,
+            crate::pressure::render_change(change, options)
+        ),
+        None => format!("{:.1} hPa", data.pressure),
+    }
+}
+
+/// Render weather data as human-readable text.
+pub fn render_text(data: &WeatherData, options: &OutputOptions) -> String {
+    let datetime = data.local_datetime();
+
+    let condition = options.paint(
+        &data.condition,
+        options.theme.conditions.get(&data.kind).copied(),
+    );
+    let title = tr_args(
+        "weather-in",
+        &[
+            ("location", data.location.clone()),
+            ("condition", condition),
+        ],
+    );
+    let title = match options.icon(data.kind) {
+        Some(icon) => format!("{title} {icon}"),
+        None => title,
+    };
+
+    let temperature = options.temperature(data.temp_c);
+
+    let mut text = format!(
+        "{}\n> {}: {} {}\n> {}: {}\n> {}: {}\n> {}: {}\n> {}: {}",
+        title,
+        options.label("label-date"),
+        datetime.format(&options.date_format),
+        datetime.format(&options.time_format),
+        options.label("label-temperature"),
+        options.paint(&temperature, Some(options.theme.temperature(data.temp_c))),
+        options.label("label-humidity"),
+        humidity(data, options),
+        options.label("label-pressure"),
+        pressure(data, options),
+        options.label("label-wind"),
+        tr_args(
+            "wind-value",
+            &[
+                ("speed", options.wind_described(data.wind_kph)),
+                (
+                    "degree",
+                    format!("{:.1}{}", data.wind_deg, options.degree())
+                ),
+            ]
+        ),
+    );
+    // Snow is otherwise told apart from rain only by the condition.
+    if let Some(snow) = &data.snow {
+        if let Some(cm) = snow.fresh_cm.filter(|cm| *cm > 0.0) {
+            text.push_str(&format!(
+                "\n> {}: {cm:.1} cm",
+                options.label("label-fresh-snow")
+            ));
+        }
+        if let Some(m) = snow.freezing_level_m {
+            text.push_str(&format!(
+                "\n> {}: {m:.0} m",
+                options.label("label-freezing-level")
+            ));
+        }
+    }
+
+    options.sanitize(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DisplaySettings;
+    use crate::weather_providers::SnowData;
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    fn sample() -> WeatherData {
+        WeatherData {
+            location: "Porto, Portugal".to_string(),
+            datetime: Utc.with_ymd_and_hms(2025, 12, 5, 17, 15, 0).unwrap(),
+            utc_offset: FixedOffset::east_opt(0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn default_format() {
+        let text = render_text(&sample(), &OutputOptions::default());
+        assert!(text.contains("> Date: 2025-12-05 17:15"));
+    }
+
+    #[test]
+    fn pressure_tendency_when_known() {
+        let mut data = sample();
+        data.pressure = 1004.0;
+        assert!(render_text(&data, &OutputOptions::default()).contains("> Pressure: 1004.0 hPa\n"));
+
+        data.pressure_change_3h = Some(-3.4);
+        let text = render_text(&data, &OutputOptions::default());
+        assert!(
+            text.contains("> Pressure: 1004.0 hPa ↘ falling, -3.4 hPa/3h\n"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn snow_lines_when_reported() {
+        let mut data = sample();
+        assert!(!render_text(&data, &OutputOptions::default()).contains("snow"));
+
+        data.snow = Some(SnowData {
+            fresh_cm: Some(1.4),
+            freezing_level_m: Some(850.0),
+            ..Default::default()
+        });
+        let text = render_text(&data, &OutputOptions::default());
+        assert!(
+            text.ends_with("> Fresh snow: 1.4 cm\n> Freezing level: 850 m"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn twelve_hour_format() {
+        let settings = Settings {
+            display: DisplaySettings {
+                time_format: Some("12h".to_string()),
+                date_format: Some("%d.%m.%Y".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let options = OutputOptions::from_settings(&settings).unwrap();
+
+        let text = render_text(&sample(), &options);
+        assert!(text.contains("> Date: 05.12.2025 05:15 PM"));
+    }
+
+    #[test]
+    fn ascii_output() {
+        let mut data = sample();
+        data.condition = "Leichter Regenschauer über Köln".to_string();
+        let options = OutputOptions {
+            ascii: true,
+            ..Default::default()
+        };
+
+        let text = render_text(&data, &options);
+        assert!(text.is_ascii(), "{text}");
+        assert!(text.contains("uber Koln"));
+    }
+
+    #[test]
+    fn without_emoji() {
+        let options = OutputOptions {
+            emoji: false,
+            ..Default::default()
+        };
+
+        let text = render_text(&sample(), &options);
+        assert!(text.starts_with("Weather in Porto, Portugal: \n"));
+    }
+
+    #[test]
+    fn text_icons_survive_ascii() {
+        let mut data = sample();
+        data.kind = ConditionKind::Rain;
+        let options = OutputOptions {
+            ascii: true,
+            icons: IconSet::Text,
+            ..Default::default()
+        };
+
+        let text = render_text(&data, &options);
+        assert!(
+            text.starts_with("Weather in Porto, Portugal:  [rain]\n"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn oneline_format() {
+        let mut data = sample();
+        data.condition = "Partly cloudy".to_string();
+        data.kind = ConditionKind::PartlyCloudy;
+        data.temp_c = 16.1;
+        data.humidity = 94.0;
+        data.pressure = 1018.0;
+        data.wind_kph = 22.0;
+        data.wind_deg = 245.0;
+        let options = OutputOptions {
+            icons: IconSet::Text,
+            color: ColorChoice::Never,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            render_oneline(&data, &options),
+            "Porto, Portugal: [partly cloudy] Partly cloudy, 16.1°C, 94%, 1018 hPa, 22.0 km/h 245°"
+        );
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let settings = Settings {
+            display: DisplaySettings {
+                time_format: Some("%Q".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(OutputOptions::from_settings(&settings).is_err());
+    }
+
+    #[test]
+    fn colored_output() {
+        let options = OutputOptions {
+            color: ColorChoice::Always,
+            ..Default::default()
+        };
+        let text = render_text(&sample(), &options);
+        assert!(text.contains("\x1b[38;2;"));
+
+        let options = OutputOptions {
+            color: ColorChoice::Never,
+            ..Default::default()
+        };
+        let text = render_text(&sample(), &options);
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn location_slug() {
+        assert_eq!(slug("Porto, Portugal"), "porto_portugal");
+        assert_eq!(slug("São Paulo"), "sao_paulo");
+        assert_eq!(slug("Zürich (ZH)"), "zurich_zh");
+    }
+
+    #[test]
+    fn undefined_theme() {
+        let settings = Settings {
+            display: DisplaySettings {
+                theme: Some("missing".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(OutputOptions::from_settings(&settings).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use crate::app::RequestPlan;
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+
+/// Line of `--dry-run` naming the providers `get` would ask in turn: `providers` raced when
+/// `race`, otherwise the only one, followed by `fill` for the fields they leave out.
+pub fn render_order(
+    providers: &[String],
+    race: bool,
+    fill: Option<&str>,
+    options: &OutputOptions,
+) -> String {
+    let names: Vec<String> = providers.iter().map(|p| p.to_uppercase()).collect();
+    let mut order = if race {
+        tr_args("plan-order-race", &[("providers", names.join(", "))])
+    } else {
+        names.join(", ")
+    };
+    if let Some(fill) = fill {
+        order = tr_args(
+            "plan-order-fill",
+            &[("order", order), ("fill", fill.to_uppercase())],
+        );
+    }
+    options.sanitize(&format!("{}: {order}", options.label("label-order")))
+}
+
+/// Report of `--dry-run`: provider, cache state and the URLs that would be requested.
+pub fn render_plan(plan: &RequestPlan, options: &OutputOptions) -> String {
+    let cache = match plan.cached {
+        Some(true) => tr("plan-cache-hit"),
+        Some(false) => tr("plan-cache-miss"),
+        None => tr("plan-cache-disabled"),
+    };
+    let mut lines = vec![
+        format!(
+            "{}: {}",
+            options.label("label-provider"),
+            plan.provider.to_uppercase()
+        ),
+        format!("{}: {cache}", options.label("label-cache")),
+    ];
+    if plan.requests.is_empty() {
+        lines.push(format!(
+            "{}: {}",
+            options.label("label-request"),
+            tr("plan-offline")
+        ));
+    }
+    for url in &plan.requests {
+        lines.push(format!("{}: {url}", options.label("label-request")));
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_hit_without_request() {
+        let plan = RequestPlan {
+            provider: "weatherapi".to_string(),
+            cached: Some(true),
+            requests: vec![
+                "https://api.weatherapi.com/v1/current.json?key=***&q=Porto".to_string(),
+            ],
+        };
+
+        assert_eq!(
+            render_plan(&plan, &OutputOptions::default()),
+            "Provider: WEATHERAPI\n\
+             Cache: hit, the provider would not be asked\n\
+             Request: https://api.weatherapi.com/v1/current.json?key=***&q=Porto"
+        );
+    }
+
+    #[test]
+    fn order_with_fallback() {
+        let options = OutputOptions::default();
+        let providers = ["weatherapi".to_string(), "openmeteo".to_string()];
+
+        assert_eq!(
+            render_order(&providers[..1], false, Some("openmeteo"), &options),
+            "Order: WEATHERAPI, then OPENMETEO for the fields it leaves out"
+        );
+        assert_eq!(
+            render_order(&providers, true, None, &options),
+            "Order: WEATHERAPI, OPENMETEO at once, the first answer wins"
+        );
+    }
+}
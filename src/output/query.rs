@@ -0,0 +1,193 @@
+use crate::output::OutputOptions;
+use crate::output::tsv::tsv_field;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::Value;
+
+/// How `weather store query` prints its rows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum QueryFormat {
+    /// Columns aligned under a header.
+    #[default]
+    Table,
+    Csv,
+    /// Tab-separated, tabs and line breaks in cells replaced by spaces.
+    Tsv,
+    /// Array of objects keyed by column name.
+    Json,
+}
+
+/// Rows returned by a query, with the names of their columns.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// A cell as text; strings without quotes, NULL as an empty cell.
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn render_table(result: &QueryResult, options: &OutputOptions) -> String {
+    let cells: Vec<Vec<String>> = result
+        .rows
+        .iter()
+        .map(|row| row.iter().map(cell).collect())
+        .collect();
+    let widths: Vec<usize> = result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            cells
+                .iter()
+                .filter_map(|row| row.get(i))
+                .chain([name])
+                .map(|s| s.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(s, width)| format!("{s:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut lines = vec![line(&result.columns), line(&rule)];
+    lines.extend(cells.iter().map(|row| line(row)));
+    options.sanitize(&lines.join("\n"))
+}
+
+/// RFC 4180 field, quoted when it contains a separator, quote or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn render_tsv(result: &QueryResult, header: bool) -> String {
+    let line = |fields: Vec<String>| {
+        fields
+            .iter()
+            .map(|s| tsv_field(s))
+            .collect::<Vec<_>>()
+            .join("\t")
+    };
+    let header = header.then(|| line(result.columns.clone()));
+    let rows = result
+        .rows
+        .iter()
+        .map(|row| line(row.iter().map(cell).collect()));
+    header
+        .into_iter()
+        .chain(rows)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_csv(result: &QueryResult, header: bool) -> String {
+    let line = |fields: Vec<String>| {
+        fields
+            .iter()
+            .map(|s| csv_field(s))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let mut lines: Vec<String> = header
+        .then(|| line(result.columns.clone()))
+        .into_iter()
+        .collect();
+    lines.extend(
+        result
+            .rows
+            .iter()
+            .map(|row| line(row.iter().map(cell).collect())),
+    );
+    lines.join("\n")
+}
+
+/// A row as a JSON object, keeping the columns in query order.
+struct Row<'a>(&'a [String], &'a [Value]);
+
+impl Serialize for Row<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (column, value) in self.0.iter().zip(self.1) {
+            map.serialize_entry(column, value)?;
+        }
+        map.end()
+    }
+}
+
+fn render_json(result: &QueryResult) -> String {
+    let rows: Vec<Row> = result
+        .rows
+        .iter()
+        .map(|row| Row(&result.columns, row))
+        .collect();
+    serde_json::to_string_pretty(&rows).unwrap_or_default()
+}
+
+/// Rows of `weather store query` in `format`.
+pub fn render_query(result: &QueryResult, format: QueryFormat, options: &OutputOptions) -> String {
+    match format {
+        QueryFormat::Table => render_table(result, options),
+        QueryFormat::Csv => render_csv(result, options.header),
+        QueryFormat::Tsv => render_tsv(result, options.header),
+        QueryFormat::Json => render_json(result),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn formats() {
+        let result = QueryResult {
+            columns: vec!["location".to_string(), "temp_c".to_string()],
+            rows: vec![
+                vec![json!("kyiv"), json!(-3.5)],
+                vec![json!("porto, pt"), Value::Null],
+            ],
+        };
+        let options = OutputOptions::default();
+
+        assert_eq!(
+            render_query(&result, QueryFormat::Table, &options),
+            "location   temp_c\n---------  ------\nkyiv       -3.5\nporto, pt"
+        );
+        assert_eq!(
+            render_query(&result, QueryFormat::Csv, &options),
+            "location,temp_c\nkyiv,-3.5\n\"porto, pt\","
+        );
+        assert_eq!(
+            render_query(&result, QueryFormat::Tsv, &options),
+            "location\ttemp_c\nkyiv\t-3.5\nporto, pt\t"
+        );
+        let headless = OutputOptions {
+            header: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_query(&result, QueryFormat::Csv, &headless),
+            "kyiv,-3.5\n\"porto, pt\","
+        );
+        let json: Value =
+            serde_json::from_str(&render_query(&result, QueryFormat::Json, &options)).unwrap();
+        assert_eq!(json[1], json!({"location": "porto, pt", "temp_c": null}));
+    }
+}
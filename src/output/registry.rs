@@ -0,0 +1,106 @@
+use crate::output::Formatter;
+use crate::output::chat::{DiscordFormatter, SlackFormatter};
+use crate::output::json::{JsonFormatter, WaybarFormatter};
+use crate::output::table::TableFormatter;
+use crate::output::template::TemplateFormatter;
+use crate::output::text::{OnelineFormatter, TextFormatter};
+use crate::output::tsv::TsvFormatter;
+use std::{collections::HashMap, sync::Arc};
+use tracing::error;
+
+/// Holds registered output formatters.
+#[derive(Default)]
+pub struct FormatterRegistry {
+    formatters: HashMap<String, Arc<dyn Formatter>>,
+}
+
+impl FormatterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with all built-in formatters.
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register("text", TextFormatter);
+        registry.register("oneline", OnelineFormatter);
+        registry.register("json", JsonFormatter);
+        registry.register("table", TableFormatter);
+        registry.register("tsv", TsvFormatter);
+        registry.register("template", TemplateFormatter);
+        registry.register("waybar", WaybarFormatter);
+        registry.register("slack", SlackFormatter);
+        registry.register("discord", DiscordFormatter);
+        registry
+    }
+
+    /// Register a formatter by name.
+    pub fn register<F>(&mut self, name: &str, formatter: F)
+    where
+        F: Formatter + 'static,
+    {
+        if self.formatters.contains_key(name) {
+            error!(
+                "Warning: Formatter '{}' is already registered and will be overwritten",
+                name
+            );
+        }
+        self.formatters
+            .insert(name.to_string(), Arc::new(formatter));
+    }
+
+    /// Get a formatter by name.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Formatter>> {
+        self.formatters.get(name).cloned()
+    }
+
+    /// List all registered formatter names.
+    pub fn list_formatters(&self) -> Vec<String> {
+        let mut keys: Vec<_> = self.formatters.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputOptions;
+    use crate::weather_providers::WeatherData;
+
+    struct Upper;
+
+    impl Formatter for Upper {
+        fn format(&self, data: &WeatherData, _provider: &str, _options: &OutputOptions) -> String {
+            data.location.to_uppercase()
+        }
+    }
+
+    #[test]
+    fn builtin_formatters() {
+        let registry = FormatterRegistry::with_builtin();
+        assert_eq!(
+            registry.list_formatters(),
+            vec![
+                "discord", "json", "oneline", "slack", "table", "template", "text", "tsv", "waybar"
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_formatter() {
+        let mut registry = FormatterRegistry::new();
+        registry.register("upper", Upper);
+
+        let data = WeatherData {
+            location: "porto".to_string(),
+            ..Default::default()
+        };
+        let formatter = registry.get("upper").unwrap();
+        assert_eq!(
+            formatter.format(&data, "", &OutputOptions::default()),
+            "PORTO"
+        );
+        assert!(registry.get("missing").is_none());
+    }
+}
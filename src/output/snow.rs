@@ -0,0 +1,63 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::WeatherData;
+
+/// Snow report for `weather snow`: depth, fresh snow and freezing level.
+pub fn render_snow(data: &WeatherData, options: &OutputOptions) -> String {
+    let snow = data.snow.clone().unwrap_or_default();
+    let value = |value: Option<f64>, unit: &str| match value {
+        Some(value) => format!("{value:.0} {unit}"),
+        None => tr("not-available"),
+    };
+
+    let title = tr_args("snow-in", &[("location", data.location.clone())]);
+    let title = match options.icon(data.kind) {
+        Some(icon) => format!("{title} {icon}"),
+        None => title,
+    };
+
+    let text = format!(
+        "{}\n> {}: {}\n> {}: {}\n> {}: {}\n> {}: {}, {}",
+        title,
+        options.label("label-snow-depth"),
+        value(snow.depth_cm, "cm"),
+        options.label("label-fresh-snow"),
+        value(snow.fresh_cm, "cm"),
+        options.label("label-freezing-level"),
+        value(snow.freezing_level_m, "m"),
+        options.label("label-condition"),
+        data.condition,
+        options.temperature(data.temp_c),
+    );
+    options.sanitize(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::SnowData;
+
+    #[test]
+    fn missing_values() {
+        let data = WeatherData {
+            location: "Zermatt".to_string(),
+            condition: "Snowfall".to_string(),
+            temp_c: -4.0,
+            snow: Some(SnowData {
+                depth_cm: Some(85.0),
+                fresh_cm: None,
+                freezing_level_m: Some(1850.0),
+            }),
+            ..Default::default()
+        };
+        let options = OutputOptions {
+            emoji: false,
+            ..Default::default()
+        };
+
+        let text = render_snow(&data, &options);
+        assert!(text.contains("85 cm"), "{text}");
+        assert!(text.contains("1850 m"), "{text}");
+        assert!(text.contains("n/a"), "{text}");
+    }
+}
@@ -0,0 +1,150 @@
+use crate::app::{ApiStatus, ProviderStatus};
+use crate::i18n::tr;
+use crate::metrics::{Series, SeriesKey};
+use crate::output::OutputOptions;
+use std::collections::BTreeMap;
+
+/// One line per provider for `weather providers status`: name, outcome, latency and error.
+pub fn render_status(statuses: &[ProviderStatus], options: &OutputOptions) -> String {
+    let width = statuses.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let lines: Vec<String> = statuses
+        .iter()
+        .map(|status| {
+            let outcome = match &status.error {
+                None => tr("provider-ok"),
+                Some(_) => tr("provider-error"),
+            };
+            let line = format!(
+                "{:<width$}  {:<6} {:>6} ms",
+                status.name,
+                outcome,
+                status.latency.as_millis()
+            );
+            match &status.error {
+                Some(error) => format!("{line}  {error}"),
+                None => line,
+            }
+        })
+        .collect();
+    options.sanitize(&lines.join("\n"))
+}
+
+/// One line per provider for `weather providers check-versions`: name, API version and whether it
+/// is deprecated, followed by the notices.
+pub fn render_versions(statuses: &[ApiStatus], options: &OutputOptions) -> String {
+    let width = statuses.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let mut lines = Vec::new();
+    for status in statuses {
+        let outcome = if !status.notices.is_empty() {
+            tr("api-deprecated")
+        } else if status.error.is_some() {
+            tr("provider-error")
+        } else {
+            tr("api-current")
+        };
+        let line = format!(
+            "{:<width$}  {:<5} {outcome}",
+            status.name,
+            status.version.unwrap_or("-"),
+        );
+        lines.push(match &status.error {
+            Some(error) => format!("{line}  {error}"),
+            None => line,
+        });
+        lines.extend(status.notices.iter().map(|notice| format!("  {notice}")));
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+/// Table for `weather providers stats`: calls, mean and 95th percentile latency per provider and outcome.
+pub fn render_stats(series: &BTreeMap<SeriesKey, Series>, options: &OutputOptions) -> String {
+    if series.is_empty() {
+        return tr("stats-empty");
+    }
+    let width = series
+        .keys()
+        .map(|(provider, _)| provider.len())
+        .chain([tr("stats-provider").len()])
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![format!(
+        "{:<width$}  {:<12} {:>6} {:>8} {:>8}",
+        tr("stats-provider"),
+        tr("stats-outcome"),
+        tr("stats-calls"),
+        tr("stats-mean"),
+        "p95",
+    )];
+    for ((provider, outcome), s) in series {
+        let p95 = match s.quantile(0.95) {
+            Some(le) => format!("{} ms", (le * 1000.0).round()),
+            None => "> 10 s".to_string(),
+        };
+        lines.push(format!(
+            "{provider:<width$}  {outcome:<12} {:>6} {:>5} ms {p95:>8}",
+            s.count,
+            s.mean().as_millis(),
+        ));
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::error::ProviderError;
+    use std::time::Duration;
+
+    #[test]
+    fn aligned_lines() {
+        let statuses = [
+            ProviderStatus {
+                name: "openmeteo".to_string(),
+                latency: Duration::from_millis(132),
+                error: None,
+            },
+            ProviderStatus {
+                name: "weatherapi".to_string(),
+                latency: Duration::from_millis(48),
+                error: Some(ProviderError::Unsupported("Marine")),
+            },
+        ];
+
+        assert_eq!(
+            render_status(&statuses, &OutputOptions::default()),
+            "openmeteo   OK        132 ms\n\
+             weatherapi  error      48 ms  Marine data is not available from this provider"
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_ping_hides_api_key() {
+        let error = reqwest::get("http://127.0.0.1:1/v1?key=secret")
+            .await
+            .unwrap_err();
+        let statuses = [ProviderStatus {
+            name: "weatherapi".to_string(),
+            latency: Duration::from_millis(1),
+            error: Some(ProviderError::from_reqwest("weatherapi", error)),
+        }];
+
+        let status = render_status(&statuses, &OutputOptions::default());
+        assert!(status.starts_with("weatherapi  error"), "{status}");
+        assert!(!status.contains("secret"), "{status}");
+    }
+
+    #[test]
+    fn stats_table() {
+        let metrics = crate::metrics::Metrics::default();
+        metrics.observe("openmeteo", "ok", Duration::from_millis(120));
+        metrics.observe("openmeteo", "ok", Duration::from_millis(140));
+        metrics.observe("weatherapi", "timeout", Duration::from_secs(30));
+
+        assert_eq!(
+            render_stats(&metrics.snapshot(), &OutputOptions::default()),
+            "Provider    Outcome       Calls     Mean      p95\n\
+             openmeteo   ok                2   130 ms   250 ms\n\
+             weatherapi  timeout           1 30000 ms   > 10 s"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::i18n::tr;
+use crate::output::{Formatter, OutputOptions};
+use crate::weather_providers::WeatherData;
+
+/// Two-column table of fields and values.
+pub struct TableFormatter;
+
+impl Formatter for TableFormatter {
+    fn format(&self, data: &WeatherData, provider: &str, options: &OutputOptions) -> String {
+        let datetime = data.local_datetime();
+        let rows = [
+            (tr("label-location"), data.location.clone()),
+            (tr("label-condition"), data.condition.clone()),
+            (
+                tr("label-date"),
+                format!(
+                    "{} {}",
+                    datetime.format(&options.date_format),
+                    datetime.format(&options.time_format)
+                ),
+            ),
+            (tr("label-temperature"), options.temperature(data.temp_c)),
+            (tr("label-humidity"), format!("{:.1}%", data.humidity)),
+            (tr("label-pressure"), super::pressure(data, options)),
+            (
+                tr("label-wind"),
+                format!(
+                    "{} {:.0}{}",
+                    options.wind_described(data.wind_kph),
+                    data.wind_deg,
+                    options.degree()
+                ),
+            ),
+            (tr("label-provider"), provider.to_uppercase()),
+        ];
+
+        let rows: Vec<(String, String)> = rows
+            .into_iter()
+            .map(|(label, value)| (options.sanitize(&label), options.sanitize(&value)))
+            .collect();
+        render_table(&rows, options.ascii)
+    }
+}
+
+/// Draw rows in a bordered table, with ASCII borders when `ascii` is set.
+pub fn render_table(rows: &[(String, String)], ascii: bool) -> String {
+    let (h, v, top, middle, bottom) = if ascii {
+        ('-', '|', ['+', '+', '+'], ['+', '+', '+'], ['+', '+', '+'])
+    } else {
+        ('─', '│', ['┌', '┬', '┐'], ['├', '┼', '┤'], ['└', '┴', '┘'])
+    };
+
+    let width = |s: &String| s.chars().count();
+    let left = rows.iter().map(|(l, _)| width(l)).max().unwrap_or(0);
+    let right = rows.iter().map(|(_, r)| width(r)).max().unwrap_or(0);
+
+    let border = |[l, m, r]: [char; 3]| {
+        format!(
+            "{l}{}{m}{}{r}",
+            h.to_string().repeat(left + 2),
+            h.to_string().repeat(right + 2)
+        )
+    };
+
+    let mut lines = vec![border(top)];
+    for (i, (label, value)) in rows.iter().enumerate() {
+        if i > 0 {
+            lines.push(border(middle));
+        }
+        lines.push(format!("{v} {label:<left$} {v} {value:<right$} {v}"));
+    }
+    lines.push(border(bottom));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_table() {
+        let rows = vec![
+            ("a".to_string(), "1".to_string()),
+            ("long".to_string(), "22".to_string()),
+        ];
+
+        assert_eq!(
+            render_table(&rows, true),
+            "+------+----+\n| a    | 1  |\n+------+----+\n| long | 22 |\n+------+----+"
+        );
+    }
+
+    #[test]
+    fn table_has_all_fields() {
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            ..Default::default()
+        };
+        let text = TableFormatter.format(&data, "weatherapi", &OutputOptions::default());
+
+        assert!(text.contains("│ Location"));
+        assert!(text.contains("Porto, Portugal"));
+        assert!(text.contains("WEATHERAPI"));
+    }
+}
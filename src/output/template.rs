@@ -0,0 +1,84 @@
+use crate::output::json::to_json;
+use crate::output::{Formatter, OutputOptions};
+use crate::weather_providers::WeatherData;
+use serde_json::Value;
+
+const DEFAULT_TEMPLATE: &str = "{location}: {condition}, {temp_c}°C";
+
+/// User-defined layout from `[display] template`, e.g. "{location}: {temp_c}°C".
+///
+/// Every field of the JSON output can be used as a `{placeholder}`;
+/// unknown placeholders are kept as written.
+pub struct TemplateFormatter;
+
+impl Formatter for TemplateFormatter {
+    fn format(&self, data: &WeatherData, provider: &str, options: &OutputOptions) -> String {
+        let template = options.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+        let values = to_json(data, provider);
+        options.sanitize(&render_template(template, &values))
+    }
+}
+
+/// Replace `{placeholder}`s with fields of `values`, keeping unknown ones as written.
+pub(crate) fn render_template(template: &str, values: &Value) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let key = &after[..end];
+        match values.get(key) {
+            Some(Value::String(s)) => out.push_str(s),
+            Some(Value::Null) => {},
+            Some(value) => out.push_str(&value.to_string()),
+            None => {
+                out.push('{');
+                out.push_str(key);
+                out.push('}');
+            },
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn placeholders() {
+        let values = json!({"location": "Porto", "temp_c": 16.1, "kind": "rain"});
+
+        assert_eq!(
+            render_template("{location} {temp_c}°C ({kind}) {unknown} {", &values),
+            "Porto 16.1°C (rain) {unknown} {"
+        );
+    }
+
+    #[test]
+    fn template_from_options() {
+        let data = WeatherData {
+            location: "Porto".to_string(),
+            ..Default::default()
+        };
+        let options = OutputOptions {
+            template: Some("{provider}: {location}".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            TemplateFormatter.format(&data, "weatherapi", &options),
+            "weatherapi: Porto"
+        );
+    }
+}
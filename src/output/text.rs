@@ -0,0 +1,94 @@
+use crate::advice::render_clothing;
+use crate::allergy;
+use crate::anomaly::render_anomalies;
+use crate::fog;
+use crate::heat;
+use crate::i18n::{tr, tr_args};
+use crate::output::accessible::render_accessible;
+use crate::output::{Formatter, OutputOptions, render_oneline, render_text};
+use crate::weather_providers::WeatherData;
+use std::collections::BTreeMap;
+
+/// Multi-line human-readable report, the default.
+pub struct TextFormatter;
+
+impl Formatter for TextFormatter {
+    fn format(&self, data: &WeatherData, provider: &str, options: &OutputOptions) -> String {
+        let mut text = if options.accessible {
+            render_accessible(data, provider, options)
+        } else {
+            format!(
+                "{}\n{}: {}",
+                render_text(data, options),
+                tr("label-provider"),
+                provider.to_uppercase()
+            )
+        };
+        if options.sources && !data.sources.is_empty() {
+            text.push('\n');
+            text.push_str(&render_sources(&data.sources, options));
+        }
+        if options.advice {
+            text.push('\n');
+            text.push_str(&render_clothing(data));
+        }
+        if options.allergy {
+            text.push('\n');
+            text.push_str(&allergy::render_risk(
+                &allergy::risk(data, options),
+                options,
+            ));
+        }
+        if !data.anomalies.is_empty() {
+            text.push('\n');
+            text.push_str(&render_anomalies(&data.anomalies, options));
+        }
+        if let Some(advisory) = fog::advisory(data, options.fog_visibility_km) {
+            text.push('\n');
+            text.push_str(&fog::render_advisory(&advisory, options));
+        }
+        if let Some(warning) = heat::warning(data, &options.heat) {
+            text.push('\n');
+            text.push_str(&heat::render_warning(&warning, options));
+        }
+        options.sanitize(&text)
+    }
+}
+
+/// "Filled in from OPENMETEO: uv_index, visibility_km", a line per secondary provider. For
+/// screen readers the fields are named in words, e.g. "UV index".
+fn render_sources(sources: &BTreeMap<String, String>, options: &OutputOptions) -> String {
+    let mut fields: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (field, provider) in sources {
+        let field = if options.accessible {
+            tr(&format!("field-{}", field.replace('_', "-")))
+        } else {
+            field.clone()
+        };
+        fields.entry(provider).or_default().push(field);
+    }
+    fields
+        .into_iter()
+        .map(|(provider, fields)| {
+            let provider = if options.accessible {
+                provider.to_string()
+            } else {
+                provider.to_uppercase()
+            };
+            tr_args(
+                "filled-from",
+                &[("provider", provider), ("fields", fields.join(", "))],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compact single line for pipelines and status bars.
+pub struct OnelineFormatter;
+
+impl Formatter for OnelineFormatter {
+    fn format(&self, data: &WeatherData, _provider: &str, options: &OutputOptions) -> String {
+        render_oneline(data, options)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::output::{Formatter, OutputOptions};
+use crate::weather_providers::WeatherData;
+
+/// Columns of the TSV output, in order. Only ever appended to, so scripts can rely on positions.
+pub const COLUMNS: [&str; 13] = [
+    "location",
+    "datetime",
+    "condition",
+    "temp_c",
+    "feels_like_c",
+    "humidity",
+    "pressure_hpa",
+    "wind_kph",
+    "wind_deg",
+    "precipitation_probability",
+    "uv_index",
+    "visibility_km",
+    "provider",
+];
+
+/// A field with the tabs and line breaks that would shift columns replaced by spaces.
+pub fn tsv_field(s: &str) -> String {
+    s.replace(['\t', '\n', '\r'], " ")
+}
+
+fn optional(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Tab-separated values in the fixed [`COLUMNS`], units left out and unknown values empty, under
+/// a header line unless `--no-header` is given.
+pub struct TsvFormatter;
+
+impl Formatter for TsvFormatter {
+    fn format(&self, data: &WeatherData, provider: &str, options: &OutputOptions) -> String {
+        let row = [
+            data.location.clone(),
+            data.local_datetime().to_rfc3339(),
+            data.condition.clone(),
+            data.temp_c.to_string(),
+            optional(data.feels_like_c),
+            data.humidity.to_string(),
+            data.pressure.to_string(),
+            data.wind_kph.to_string(),
+            data.wind_deg.to_string(),
+            optional(data.precipitation_probability),
+            optional(data.uv_index),
+            optional(data.visibility_km),
+            provider.to_string(),
+        ];
+        let row = row.map(|field| tsv_field(&field)).join("\t");
+        if options.header {
+            format!("{}\n{row}", COLUMNS.join("\t"))
+        } else {
+            row
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    #[test]
+    fn fixed_columns() {
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            datetime: Utc.with_ymd_and_hms(2025, 12, 5, 17, 15, 0).unwrap(),
+            utc_offset: FixedOffset::east_opt(0),
+            condition: "Partly\tcloudy".to_string(),
+            temp_c: 16.1,
+            humidity: 94.0,
+            pressure: 1018.0,
+            wind_kph: 22.0,
+            wind_deg: 245.0,
+            uv_index: Some(0.5),
+            ..Default::default()
+        };
+        let options = OutputOptions {
+            header: false,
+            ..Default::default()
+        };
+
+        let row = TsvFormatter.format(&data, "weatherapi", &options);
+        assert_eq!(
+            row,
+            "Porto, Portugal\t2025-12-05T17:15:00+00:00\tPartly cloudy\t16.1\t\t94\t1018\t22\t245\t\t\
+             0.5\t\tweatherapi"
+        );
+        assert_eq!(row.split('\t').count(), COLUMNS.len());
+
+        let with_header = TsvFormatter.format(&data, "weatherapi", &OutputOptions::default());
+        assert!(with_header.starts_with("location\tdatetime\tcondition\t"));
+        assert!(with_header.ends_with(&row));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use serde::{Deserialize, Serialize};
+
+/// Upper bounds in km/h of Beaufort forces 0 to 11; anything faster is force 12.
+const BEAUFORT_KPH: [f64; 12] = [
+    1.0, 6.0, 12.0, 20.0, 29.0, 39.0, 50.0, 62.0, 75.0, 89.0, 103.0, 118.0,
+];
+
+/// Descriptive terms of the Beaufort forces, as i18n keys.
+const BEAUFORT_KEYS: [&str; 13] = [
+    "beaufort-calm",
+    "beaufort-light-air",
+    "beaufort-light-breeze",
+    "beaufort-gentle-breeze",
+    "beaufort-moderate-breeze",
+    "beaufort-fresh-breeze",
+    "beaufort-strong-breeze",
+    "beaufort-near-gale",
+    "beaufort-gale",
+    "beaufort-strong-gale",
+    "beaufort-storm",
+    "beaufort-violent-storm",
+    "beaufort-hurricane",
+];
+
+/// Unit wind speeds are primarily shown in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum WindUnit {
+    #[default]
+    #[serde(alias = "km/h")]
+    #[value(alias = "km/h")]
+    Kmh,
+    #[serde(alias = "m/s")]
+    #[value(alias = "m/s")]
+    Ms,
+    Knots,
+    Mph,
+    Beaufort,
+}
+
+/// Beaufort force of a wind speed in km/h, from 0 (calm) to 12 (hurricane force).
+pub fn beaufort(kph: f64) -> usize {
+    BEAUFORT_KPH
+        .iter()
+        .position(|bound| kph < *bound)
+        .unwrap_or(BEAUFORT_KPH.len())
+}
+
+/// Descriptive term of the Beaufort force of `kph`, e.g. "Fresh breeze".
+pub fn beaufort_term(kph: f64) -> String {
+    tr(BEAUFORT_KEYS[beaufort(kph)])
+}
+
+/// Wind speed in `unit` spelled out, e.g. "22.0 kilometers per hour".
+fn spoken_wind_speed(kph: f64, unit: WindUnit) -> String {
+    let spoken = |key, value: f64| tr_args(key, &[("value", format!("{value:.1}"))]);
+    match unit {
+        WindUnit::Kmh => spoken("spoken-kmh", kph),
+        WindUnit::Ms => spoken("spoken-ms", kph / 3.6),
+        WindUnit::Knots => spoken("spoken-knots", kph / 1.852),
+        WindUnit::Mph => spoken("spoken-mph", kph / 1.609_344),
+        WindUnit::Beaufort => tr_args(
+            "spoken-beaufort",
+            &[
+                ("force", beaufort(kph).to_string()),
+                ("term", beaufort_term(kph)),
+            ],
+        ),
+    }
+}
+
+impl OutputOptions {
+    /// `kph` converted to the configured unit and its symbol, `None` for Beaufort.
+    fn in_wind_unit(&self, kph: f64) -> Option<(f64, &'static str)> {
+        match self.wind_unit {
+            WindUnit::Kmh => Some((kph, "km/h")),
+            WindUnit::Ms => Some((kph / 3.6, "m/s")),
+            WindUnit::Knots => Some((kph / 1.852, "kn")),
+            WindUnit::Mph => Some((kph / 1.609_344, "mph")),
+            WindUnit::Beaufort => None,
+        }
+    }
+
+    /// Wind speed in the configured unit, e.g. "22.0 km/h" or "Bft 4", spelled out for screen
+    /// readers, e.g. "22.0 kilometers per hour".
+    pub(crate) fn wind_speed(&self, kph: f64) -> String {
+        if self.accessible {
+            return spoken_wind_speed(kph, self.wind_unit);
+        }
+        match self.in_wind_unit(kph) {
+            Some((speed, unit)) => format!("{speed:.1} {unit}"),
+            None => format!("Bft {}", beaufort(kph)),
+        }
+    }
+
+    /// Signed difference of two wind speeds in the configured unit, e.g. "+3.0 km/h" or "Bft -1".
+    pub(crate) fn wind_change(&self, kph: f64, against_kph: f64) -> String {
+        match self.in_wind_unit(kph - against_kph) {
+            Some((change, unit)) => format!("{change:+.1} {unit}"),
+            None => format!(
+                "Bft {:+}",
+                beaufort(kph) as i64 - beaufort(against_kph) as i64
+            ),
+        }
+    }
+
+    /// Wind speed with its Beaufort force and term alongside, e.g.
+    /// "22.0 km/h (Bft 4, Moderate breeze)"; km/h go alongside when Beaufort is the unit.
+    pub(crate) fn wind_described(&self, kph: f64) -> String {
+        match self.wind_unit {
+            WindUnit::Beaufort => format!(
+                "Bft {} ({}, {kph:.1} km/h)",
+                beaufort(kph),
+                beaufort_term(kph)
+            ),
+            _ => format!(
+                "{} (Bft {}, {})",
+                self.wind_speed(kph),
+                beaufort(kph),
+                beaufort_term(kph)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speeds_in_each_unit() {
+        assert_eq!(beaufort(0.5), 0);
+        assert_eq!(beaufort(35.0), 5);
+        assert_eq!(beaufort(150.0), 12);
+
+        let options = |wind_unit| OutputOptions {
+            wind_unit,
+            ..Default::default()
+        };
+        assert_eq!(
+            options(WindUnit::Kmh).wind_described(35.0),
+            "35.0 km/h (Bft 5, Fresh breeze)"
+        );
+        assert_eq!(options(WindUnit::Ms).wind_speed(36.0), "10.0 m/s");
+        assert_eq!(options(WindUnit::Knots).wind_speed(18.52), "10.0 kn");
+        assert_eq!(options(WindUnit::Mph).wind_speed(16.09344), "10.0 mph");
+        assert_eq!(options(WindUnit::Ms).wind_change(18.0, 36.0), "-5.0 m/s");
+        assert_eq!(
+            options(WindUnit::Beaufort).wind_change(35.0, 15.0),
+            "Bft +2"
+        );
+        assert_eq!(
+            options(WindUnit::Beaufort).wind_described(35.0),
+            "Bft 5 (Fresh breeze, 35.0 km/h)"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::{OutputOptions, sparkline};
+use chrono::{DateTime, Duration, Utc};
+
+/// Changes within this many hPa per 3 hours count as steady.
+const STEADY_HPA: f64 = 1.0;
+/// Fall in hPa per 3 hours from which `weather pressure` warns, unless told otherwise.
+pub const RAPID_DROP_HPA: f64 = 3.0;
+/// Hours of history drawn under the current pressure.
+pub const HISTORY_HOURS: i64 = 24;
+
+/// Which way the pressure is heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tendency {
+    Rising,
+    Steady,
+    Falling,
+}
+
+impl Tendency {
+    pub fn of(change_3h: f64) -> Self {
+        if change_3h >= STEADY_HPA {
+            Self::Rising
+        } else if change_3h <= -STEADY_HPA {
+            Self::Falling
+        } else {
+            Self::Steady
+        }
+    }
+
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            Self::Rising => "pressure-rising",
+            Self::Steady => "pressure-steady",
+            Self::Falling => "pressure-falling",
+        }
+    }
+
+    fn arrow(self, options: &OutputOptions) -> &'static str {
+        match (self, options.ascii) {
+            (Self::Rising, false) => "↗",
+            (Self::Steady, false) => "→",
+            (Self::Falling, false) => "↘",
+            (Self::Rising, true) => "^",
+            (Self::Steady, true) => "-",
+            (Self::Falling, true) => "v",
+        }
+    }
+}
+
+/// Change of the latest of `readings`, pressure in hPa by time, scaled to 3 hours. It is measured
+/// against the reading closest to 3 hours before, which must be between 1 and 6 hours old.
+pub fn change_per_3h(readings: &[(DateTime<Utc>, f64)]) -> Option<f64> {
+    let (latest_at, latest) = readings.iter().max_by_key(|(at, _)| *at)?;
+    let target = *latest_at - Duration::hours(3);
+    let (before_at, before) = readings
+        .iter()
+        .filter(|(at, _)| {
+            let age = *latest_at - *at;
+            age >= Duration::hours(1) && age <= Duration::hours(6)
+        })
+        .min_by_key(|(at, _)| (*at - target).abs())?;
+    let hours = (*latest_at - *before_at).num_seconds() as f64 / 3600.0;
+    Some((latest - before) / hours * 3.0)
+}
+
+/// Arrow and word for which way the pressure is heading, with its change, e.g.
+/// "↘ falling, -3.4 hPa/3h".
+pub fn render_change(change_3h: f64, options: &OutputOptions) -> String {
+    let tendency = Tendency::of(change_3h);
+    let text = format!(
+        "{} {}, {change_3h:+.1} hPa/3h",
+        tendency.arrow(options),
+        tr(tendency.key())
+    );
+    options.sanitize(&text)
+}
+
+/// Current pressure with its tendency over 3 hours and the last day of readings, for
+/// `weather pressure`.
+pub fn render_pressure(
+    location: &str,
+    readings: &[(DateTime<Utc>, f64)],
+    change_3h: Option<f64>,
+    options: &OutputOptions,
+) -> String {
+    let Some((_, now)) = readings.iter().max_by_key(|(at, _)| *at) else {
+        return String::new();
+    };
+    let mut lines = vec![tr_args(
+        "pressure-now",
+        &[
+            ("location", location.to_string()),
+            ("pressure", format!("{now:.0}")),
+        ],
+    )];
+    match change_3h {
+        Some(change) => lines.push(render_change(change, options)),
+        None => lines.push(tr("pressure-no-history")),
+    }
+    if readings.len() > 1 {
+        let pressures: Vec<f64> = readings.iter().map(|(_, p)| *p).collect();
+        let low = pressures.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = pressures.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        lines.push(format!(
+            "{HISTORY_HOURS}h: {} ({low:.0}..{high:.0} hPa)",
+            sparkline(&pressures, options)
+        ));
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn tendency_over_three_hours() {
+        let at = |hour| Utc.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap();
+        let readings = [
+            (at(6), 1016.0),
+            (at(8), 1015.0),
+            (at(9), 1014.0),
+            (at(12), 1010.0),
+        ];
+
+        assert_eq!(change_per_3h(&readings), Some(-4.0));
+        assert_eq!(Tendency::of(-4.0), Tendency::Falling);
+        assert_eq!(Tendency::of(0.4), Tendency::Steady);
+        assert_eq!(change_per_3h(&readings[3..]), None);
+
+        let options = OutputOptions {
+            ascii: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_pressure("Kyiv", &readings, change_per_3h(&readings), &options),
+            "Pressure in Kyiv: 1010 hPa\nv falling, -4.0 hPa/3h\n24h: #*=_ (1010..1016 hPa)"
+        );
+    }
+}
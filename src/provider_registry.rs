@@ -1,12 +1,19 @@
-use crate::config::Settings;
+use crate::cache::disk::DiskCache;
+use crate::config::{ProviderSettings, Settings, SettingsError};
 use crate::errors::AppError;
 use crate::weather_providers::WeatherProvider;
+use crate::weather_providers::geocode::GeocodeCache;
+use crate::weather_providers::openmeteo::OpenMeteo;
 use crate::weather_providers::openweather::OpenWeather;
+use crate::weather_providers::rate_limit::RateLimited;
 use crate::weather_providers::weatherapi::WeatherApi;
+use reqwest::Url;
+use std::time::Duration;
 use std::{collections::HashMap, sync::Arc};
 use tracing::{error, info, warn};
 
 /// Holds registered weather providers.
+#[derive(Default)]
 pub struct ProviderRegistry {
     providers: HashMap<String, Arc<dyn WeatherProvider>>,
 }
@@ -32,11 +39,30 @@ impl ProviderRegistry {
         self.providers.insert(name.to_string(), Arc::new(provider));
     }
 
+    /// Chainable form of [`ProviderRegistry::register`].
+    pub fn with_provider<P>(mut self, name: &str, provider: P) -> Self
+    where
+        P: WeatherProvider + 'static,
+    {
+        self.register(name, provider);
+        self
+    }
+
     /// Get a provider by name.
     pub fn get(&self, name: &str) -> Option<Arc<dyn WeatherProvider>> {
         self.providers.get(name).cloned()
     }
 
+    /// Make the requests to `name` wait for a token of a `per_minute` bucket.
+    pub fn limit(&mut self, name: &str, per_minute: u32) {
+        if let Some(provider) = self.providers.remove(name) {
+            self.providers.insert(
+                name.to_string(),
+                Arc::new(RateLimited::new(provider, per_minute)),
+            );
+        }
+    }
+
     /// List all registered provider names.
     pub fn list_providers(&self) -> Vec<String> {
         let mut keys: Vec<_> = self.providers.keys().cloned().collect();
@@ -45,6 +71,36 @@ impl ProviderRegistry {
     }
 }
 
+/// `options` of `[providers.<name>]` as query parameters.
+fn options(settings: &Settings, name: &str) -> Vec<(String, String)> {
+    settings
+        .providers
+        .get(name)
+        .map(ProviderSettings::query_options)
+        .unwrap_or_default()
+}
+
+/// `base_url` of `[providers.<name>]`, with a trailing slash so endpoint paths append to it.
+fn base_url(settings: &Settings, name: &str) -> Result<Option<Url>, AppError> {
+    let Some(url) = settings
+        .providers
+        .get(name)
+        .and_then(|p| p.base_url.as_deref())
+    else {
+        return Ok(None);
+    };
+
+    let mut url = Url::parse(url).map_err(|e| {
+        AppError::Config(SettingsError::Invalid(format!(
+            "base_url of provider `{name}`: {e}"
+        )))
+    })?;
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    Ok(Some(url))
+}
+
 /// Build a registry from settings.
 pub fn build_registry(settings: &Settings) -> Result<ProviderRegistry, AppError> {
     let mut registry = ProviderRegistry::new();
@@ -52,28 +108,91 @@ pub fn build_registry(settings: &Settings) -> Result<ProviderRegistry, AppError>
     for name in settings.providers.keys() {
         match name.as_str() {
             "openweather" => {
-                registry.register(
-                    name,
-                    OpenWeather::new(settings.get_api_key(name))
-                        .map_err(|e| AppError::MissingApiKey(e.to_string()))?,
-                );
+                let mut provider = OpenWeather::new(settings.get_api_key(name))
+                    .map_err(|e| AppError::MissingApiKey(e.to_string()))?
+                    .with_language(settings.language.clone())
+                    .with_parse_mode(settings.parse_mode)
+                    .with_options(options(settings, name));
+                if let Some(url) = base_url(settings, name)? {
+                    provider = provider.with_base_url(url);
+                }
+                registry.register(name, provider);
                 info!("OpenWeather registered");
             },
             "weatherapi" => {
-                registry.register(
-                    name,
-                    WeatherApi::new(settings.get_api_key(name))
-                        .map_err(|e| AppError::MissingApiKey(e.to_string()))?,
-                );
+                let mut provider = WeatherApi::new(settings.get_api_key(name))
+                    .map_err(|e| AppError::MissingApiKey(e.to_string()))?
+                    .with_language(settings.language.clone())
+                    .with_parse_mode(settings.parse_mode)
+                    .with_options(options(settings, name));
+                if let Some(url) = base_url(settings, name)? {
+                    provider = provider.with_base_url(url);
+                }
+                registry.register(name, provider);
                 info!("WeatherApi registered");
             },
+            // Needs no API key and is registered below either way.
+            "openmeteo" => {},
             _ => warn!("Provider `{}` in config is not implemented", name),
         }
     }
 
+    let mut openmeteo = OpenMeteo::new()?
+        .with_language(settings.language.clone())
+        .with_parse_mode(settings.parse_mode)
+        .with_options(options(settings, "openmeteo"));
+    if let Some(url) = base_url(settings, "openmeteo")? {
+        openmeteo = openmeteo.with_base_url(url);
+    }
+    if let Some(model) = settings
+        .providers
+        .get("openmeteo")
+        .and_then(|p| p.ensemble_model.clone())
+    {
+        openmeteo = openmeteo.with_ensemble_model(model);
+    }
+    if settings.cache.enabled {
+        let dir = settings
+            .cache
+            .dir
+            .clone()
+            .unwrap_or_else(DiskCache::default_dir);
+        openmeteo = openmeteo.with_geocode_cache(Arc::new(GeocodeCache::in_dir(
+            &dir,
+            Duration::from_secs(settings.cache.geocode_ttl_secs),
+        )));
+    }
+    registry.register("openmeteo", openmeteo);
+    info!("OpenMeteo registered");
+
+    for (name, provider) in &settings.providers {
+        if let Some(per_minute) = provider.requests_per_minute {
+            registry.limit(name, per_minute);
+        }
+    }
+
     Ok(registry)
 }
 
+/// Registry for `--demo`: the offline demo provider, also standing in for every provider named
+/// in the settings and for Open-Meteo so commands asking for those keep working without a network.
+#[cfg(feature = "demo")]
+pub fn demo_registry(settings: &Settings) -> ProviderRegistry {
+    use crate::weather_providers::demo::Demo;
+
+    let mut registry = ProviderRegistry::new().with_provider("demo", Demo);
+    for name in settings
+        .providers
+        .keys()
+        .map(String::as_str)
+        .chain(["openmeteo"])
+    {
+        registry.register(name, Demo);
+    }
+    info!("Demo provider registered");
+    registry
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,8 +222,21 @@ mod tests {
                 humidity: 0.0,
                 pressure: 0.0,
                 condition: "".to_string(),
+                kind: Default::default(),
+                code: None,
                 wind_kph: 0.0,
                 wind_deg: 0.0,
+                feels_like_c: None,
+                uv_index: None,
+                precipitation_probability: None,
+                visibility_km: None,
+                pressure_change_3h: None,
+                utc_offset: None,
+                coordinates: None,
+                snow: None,
+                pollen: None,
+                anomalies: Vec::new(),
+                sources: Default::default(),
             })
         }
     }
@@ -140,6 +272,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn chained_registration() {
+        let reg = ProviderRegistry::new()
+            .with_provider("p1", MockProvider {})
+            .with_provider("p2", MockProvider {});
+
+        assert_eq!(reg.list_providers(), vec!["p1", "p2"]);
+    }
+
     #[test]
     fn registry_handles_multiple_providers() {
         let mut reg = ProviderRegistry::new();
@@ -149,4 +290,32 @@ mod tests {
         assert!(reg.get("p1").is_some());
         assert!(reg.get("p2").is_some());
     }
+
+    #[test]
+    fn configured_base_url_and_options() {
+        let settings = Settings {
+            providers: HashMap::from([(
+                "weatherapi".to_string(),
+                crate::config::ProviderSettings {
+                    api_key: "key".to_string(),
+                    base_url: Some("http://localhost:8080/weather".to_string()),
+                    requests_per_minute: None,
+                    options: HashMap::from([("aqi".to_string(), toml::Value::from("yes"))]),
+                    ensemble_model: None,
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let registry = build_registry(&settings).unwrap();
+        let plan = registry
+            .get("weatherapi")
+            .unwrap()
+            .plan("Porto", None)
+            .unwrap();
+        assert_eq!(
+            plan,
+            vec!["http://localhost:8080/weather/v1/current.json?key=***&q=Porto&aqi=yes"]
+        );
+    }
 }
@@ -0,0 +1,97 @@
+use crate::app::WeatherApp;
+use crate::config::{Settings, load_settings};
+use crate::errors::AppError;
+use crate::provider_registry::build_registry;
+use ::notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// Pause after a change before reloading, as editors save in several steps.
+const SETTLE: Duration = Duration::from_millis(250);
+
+/// Load the settings at `path` and swap in the providers they configure.
+///
+/// `prepare` applies the command line overrides again. On error the current providers stay.
+pub fn reload(
+    path: &Path,
+    app: &WeatherApp,
+    prepare: impl Fn(&mut Settings),
+) -> Result<(), AppError> {
+    let mut settings = load_settings(path).map_err(AppError::Config)?;
+    settings.warn_if_outdated();
+    prepare(&mut settings);
+    app.set_registry(build_registry(&settings)?);
+    Ok(())
+}
+
+/// Reload the providers whenever the settings file at `path` changes, until the watcher is dropped.
+pub fn watch(
+    path: PathBuf,
+    app: Arc<WeatherApp>,
+    prepare: impl Fn(&mut Settings) + Send + 'static,
+) -> Result<RecommendedWatcher, ::notify::Error> {
+    let file_name = path.file_name().map(ToOwned::to_owned);
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = ::notify::recommended_watcher(move |event: ::notify::Result<Event>| {
+        if let Ok(event) = event
+            && (event.kind.is_modify() || event.kind.is_create())
+            && event
+                .paths
+                .iter()
+                .any(|p| p.file_name() == file_name.as_deref())
+        {
+            let _ = tx.send(());
+        }
+    })?;
+    // Editors often replace the file instead of writing to it, so watch its directory.
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    tokio::spawn(async move {
+        while rx.recv().await.is_some() {
+            tokio::time::sleep(SETTLE).await;
+            while rx.try_recv().is_ok() {}
+
+            match reload(&path, &app, &prepare) {
+                Ok(()) => info!("Reloaded providers from {}", path.display()),
+                Err(e) => warn!("Keeping current providers, {}: {e}", path.display()),
+            }
+        }
+    });
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn new_provider_after_change() {
+        let dir = std::env::temp_dir().join(format!("weather-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.toml");
+        std::fs::write(&path, "default_provider = \"openmeteo\"\n").unwrap();
+
+        let app = Arc::new(WeatherApp::builder().build());
+        let _watcher = watch(path.clone(), app.clone(), |_| {}).unwrap();
+        std::fs::write(
+            &path,
+            "default_provider = \"openmeteo\"\n[providers.openmeteo]\n",
+        )
+        .unwrap();
+
+        let mut waited = Duration::ZERO;
+        while !app.provider_exist("openmeteo") && waited < Duration::from_secs(5) {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            waited += Duration::from_millis(50);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(app.provider_exist("openmeteo"));
+    }
+}
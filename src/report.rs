@@ -0,0 +1,423 @@
+use crate::anomaly::Anomaly;
+use crate::degree_days::mean_temp;
+use crate::i18n::{tr, tr_args};
+use crate::output::{OutputOptions, sparkline};
+use crate::weather_providers::condition::ConditionKind;
+use crate::weather_providers::{DailyData, WeatherData};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+
+/// Stretch of history a report covers, ending today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportPeriod {
+    Week,
+    Month,
+}
+
+impl ReportPeriod {
+    pub fn days(self) -> u64 {
+        match self {
+            Self::Week => 7,
+            Self::Month => 30,
+        }
+    }
+}
+
+/// The highest or lowest value of a measure and when it was seen, in local time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extreme {
+    pub value: f64,
+    pub at: NaiveDateTime,
+    /// Taken from a daily summary, so the time of day is unknown.
+    pub whole_day: bool,
+}
+
+/// A reading worth a mention: unusual for the season or a thunderstorm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notable {
+    pub at: NaiveDateTime,
+    pub anomaly: Option<Anomaly>,
+    pub condition: String,
+}
+
+/// What the stored history of one location says about a period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocationReport {
+    pub location: String,
+    pub readings: usize,
+    pub temp_mean_c: Option<f64>,
+    pub highest: Option<Extreme>,
+    pub lowest: Option<Extreme>,
+    pub windiest: Option<Extreme>,
+    pub humidity_mean: Option<f64>,
+    pub pressure_range: Option<(f64, f64)>,
+    /// Sum over the days with a daily summary.
+    pub precipitation_mm: Option<f64>,
+    /// Mean temperature of each day, from its daily summary or else its readings.
+    pub daily_means: Vec<(NaiveDate, f64)>,
+    pub notable: Vec<Notable>,
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, n) = values.fold((0.0, 0), |(sum, n), v| (sum + v, n + 1));
+    (n > 0).then(|| sum / n as f64)
+}
+
+/// Keep whichever of `current` and `candidate` is further in the direction of `better`.
+fn pick(current: &mut Option<Extreme>, candidate: Extreme, better: fn(f64, f64) -> bool) {
+    if current.is_none_or(|c| better(candidate.value, c.value)) {
+        *current = Some(candidate);
+    }
+}
+
+/// Summarize the `readings` and daily summaries `days` of `location`, both oldest first.
+pub fn summarize(location: &str, readings: &[WeatherData], days: &[DailyData]) -> LocationReport {
+    let higher = |a: f64, b: f64| a > b;
+    let lower = |a: f64, b: f64| a < b;
+    let (mut highest, mut lowest, mut windiest) = (None, None, None);
+    let mut reading_days: BTreeMap<NaiveDate, Vec<f64>> = BTreeMap::new();
+    let mut notable = Vec::new();
+    for data in readings {
+        let at = data.local_datetime().naive_local();
+        let extreme = |value| Extreme {
+            value,
+            at,
+            whole_day: false,
+        };
+        pick(&mut highest, extreme(data.temp_c), higher);
+        pick(&mut lowest, extreme(data.temp_c), lower);
+        pick(&mut windiest, extreme(data.wind_kph), higher);
+        reading_days.entry(at.date()).or_default().push(data.temp_c);
+
+        notable.extend(data.anomalies.iter().map(|anomaly| Notable {
+            at,
+            anomaly: Some(*anomaly),
+            condition: data.condition.clone(),
+        }));
+        if data.kind == ConditionKind::Thunderstorm && data.anomalies.is_empty() {
+            notable.push(Notable {
+                at,
+                anomaly: None,
+                condition: data.condition.clone(),
+            });
+        }
+    }
+
+    let mut daily_means: BTreeMap<NaiveDate, f64> = reading_days
+        .into_iter()
+        .filter_map(|(date, temps)| Some((date, average(temps.into_iter())?)))
+        .collect();
+    for day in days {
+        let at = day.date.and_time(Default::default());
+        let extreme = |value| Extreme {
+            value,
+            at,
+            whole_day: true,
+        };
+        if let Some(max) = day.temp_max_c {
+            pick(&mut highest, extreme(max), higher);
+        }
+        if let Some(min) = day.temp_min_c {
+            pick(&mut lowest, extreme(min), lower);
+        }
+        // A day's own summary beats a handful of readings.
+        if let Some(mean) = mean_temp(day) {
+            daily_means.insert(day.date, mean);
+        }
+    }
+
+    let pressures = || readings.iter().map(|data| data.pressure);
+    let precipitation: Vec<f64> = days.iter().filter_map(|day| day.precipitation_mm).collect();
+    LocationReport {
+        location: location.to_string(),
+        readings: readings.len(),
+        temp_mean_c: average(daily_means.values().copied()),
+        highest,
+        lowest,
+        windiest,
+        humidity_mean: average(readings.iter().map(|data| data.humidity)),
+        pressure_range: (!readings.is_empty()).then(|| {
+            (
+                pressures().fold(f64::INFINITY, f64::min),
+                pressures().fold(f64::NEG_INFINITY, f64::max),
+            )
+        }),
+        precipitation_mm: (!precipitation.is_empty()).then(|| precipitation.iter().sum()),
+        daily_means: daily_means.into_iter().collect(),
+        notable,
+    }
+}
+
+/// Output of `weather report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+}
+
+/// The lines of the report as label and value, with `n/a` for what the history lacks.
+fn rows(report: &LocationReport, options: &OutputOptions) -> Vec<(String, String)> {
+    let na = || tr("not-available");
+    let when = |e: &Extreme| {
+        let pattern = if e.whole_day {
+            options.date_format.clone()
+        } else {
+            format!("{} {}", options.date_format, options.time_format)
+        };
+        e.at.format(&pattern).to_string()
+    };
+    let temp = |e: &Option<Extreme>| {
+        e.as_ref().map_or_else(na, |e| {
+            format!("{} ({})", options.temperature(e.value), when(e))
+        })
+    };
+    vec![
+        (tr("report-readings"), report.readings.to_string()),
+        (
+            tr("report-mean"),
+            report
+                .temp_mean_c
+                .map_or_else(na, |t| options.temperature(t)),
+        ),
+        (tr("report-highest"), temp(&report.highest)),
+        (tr("report-lowest"), temp(&report.lowest)),
+        (
+            tr("report-wind"),
+            report.windiest.as_ref().map_or_else(na, |e| {
+                format!("{} ({})", options.wind_speed(e.value), when(e))
+            }),
+        ),
+        (
+            tr("report-humidity"),
+            report.humidity_mean.map_or_else(na, |h| format!("{h:.0}%")),
+        ),
+        (
+            tr("report-pressure"),
+            report
+                .pressure_range
+                .map_or_else(na, |(low, high)| format!("{low:.0}..{high:.0} hPa")),
+        ),
+        (
+            tr("report-precipitation"),
+            report
+                .precipitation_mm
+                .map_or_else(na, |mm| format!("{mm:.1} mm")),
+        ),
+    ]
+}
+
+fn notable_text(notable: &Notable, options: &OutputOptions) -> String {
+    let pattern = format!("{} {}", options.date_format, options.time_format);
+    let at = notable.at.format(&pattern);
+    match notable.anomaly {
+        Some(anomaly) => format!("{at}: {} ({})", tr(anomaly.key()), notable.condition),
+        None => format!("{at}: {}", notable.condition),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_markdown(title: &str, reports: &[LocationReport], options: &OutputOptions) -> String {
+    let mut lines = vec![format!("# {title}")];
+    if reports.is_empty() {
+        lines.extend([String::new(), tr("report-empty")]);
+    }
+    for report in reports {
+        lines.extend([
+            String::new(),
+            format!("## {}", report.location),
+            String::new(),
+            "| | |".to_string(),
+            "|---|---|".to_string(),
+        ]);
+        lines.extend(
+            rows(report, options)
+                .into_iter()
+                .map(|(label, value)| format!("| {label} | {value} |")),
+        );
+        if let (Some((first, _)), Some((last, _))) =
+            (report.daily_means.first(), report.daily_means.last())
+        {
+            let means: Vec<f64> = report.daily_means.iter().map(|(_, t)| *t).collect();
+            lines.extend([
+                String::new(),
+                format!("### {}", tr("report-chart")),
+                String::new(),
+                "```text".to_string(),
+                sparkline(&means, options),
+                format!("{first} .. {last}"),
+                "```".to_string(),
+            ]);
+        }
+        if !report.notable.is_empty() {
+            lines.extend([
+                String::new(),
+                format!("### {}", tr("report-notable")),
+                String::new(),
+            ]);
+            lines.extend(
+                report
+                    .notable
+                    .iter()
+                    .map(|n| format!("- {}", notable_text(n, options))),
+            );
+        }
+    }
+    options.sanitize(&lines.join("\n"))
+}
+
+fn render_html(title: &str, reports: &[LocationReport], options: &OutputOptions) -> String {
+    let title = escape_html(title);
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        format!("<title>{title}</title>"),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        format!("<h1>{title}</h1>"),
+    ];
+    if reports.is_empty() {
+        lines.push(format!("<p>{}</p>", escape_html(&tr("report-empty"))));
+    }
+    for report in reports {
+        lines.push(format!("<h2>{}</h2>", escape_html(&report.location)));
+        lines.push("<table>".to_string());
+        lines.extend(rows(report, options).into_iter().map(|(label, value)| {
+            format!(
+                "<tr><th>{}</th><td>{}</td></tr>",
+                escape_html(&label),
+                escape_html(&value)
+            )
+        }));
+        lines.push("</table>".to_string());
+        if let (Some((first, _)), Some((last, _))) =
+            (report.daily_means.first(), report.daily_means.last())
+        {
+            let means: Vec<f64> = report.daily_means.iter().map(|(_, t)| *t).collect();
+            lines.push(format!("<h3>{}</h3>", escape_html(&tr("report-chart"))));
+            lines.push(format!(
+                "<pre>{}\n{first} .. {last}</pre>",
+                sparkline(&means, options)
+            ));
+        }
+        if !report.notable.is_empty() {
+            lines.push(format!("<h3>{}</h3>", escape_html(&tr("report-notable"))));
+            lines.push("<ul>".to_string());
+            lines.extend(
+                report
+                    .notable
+                    .iter()
+                    .map(|n| format!("<li>{}</li>", escape_html(&notable_text(n, options)))),
+            );
+            lines.push("</ul>".to_string());
+        }
+    }
+    lines.extend(["</body>".to_string(), "</html>".to_string()]);
+    options.sanitize(&lines.join("\n"))
+}
+
+/// Report on `reports` from `start` to `end` for `weather report`.
+pub fn render_report(
+    (start, end): (NaiveDate, NaiveDate),
+    reports: &[LocationReport],
+    format: ReportFormat,
+    options: &OutputOptions,
+) -> String {
+    let title = tr_args(
+        "report-title",
+        &[("start", start.to_string()), ("end", end.to_string())],
+    );
+    match format {
+        ReportFormat::Markdown => render_markdown(&title, reports, options),
+        ReportFormat::Html => render_html(&title, reports, options),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    fn reading(day: u32, hour: u32, temp_c: f64, wind_kph: f64) -> WeatherData {
+        WeatherData {
+            location: "Porto".to_string(),
+            datetime: Utc.with_ymd_and_hms(2026, 9, day, hour, 0, 0).unwrap(),
+            utc_offset: FixedOffset::east_opt(0),
+            temp_c,
+            wind_kph,
+            humidity: 70.0,
+            pressure: 1010.0 + f64::from(day),
+            condition: "Clear".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn summary_and_markdown() {
+        let mut storm = reading(2, 18, 19.0, 55.0);
+        storm.kind = ConditionKind::Thunderstorm;
+        storm.condition = "Thunderstorm".to_string();
+        let readings = [reading(1, 12, 22.0, 10.0), storm, reading(3, 6, 12.0, 5.0)];
+        let days = [DailyData {
+            date: NaiveDate::from_ymd_opt(2026, 9, 3).unwrap(),
+            temp_min_c: Some(11.0),
+            temp_max_c: Some(25.0),
+            precipitation_mm: Some(4.5),
+        }];
+
+        let report = summarize("porto", &readings, &days);
+
+        assert_eq!(
+            report.highest.map(|e| (e.value, e.whole_day)),
+            Some((25.0, true))
+        );
+        assert_eq!(
+            report.lowest.map(|e| (e.value, e.whole_day)),
+            Some((11.0, true))
+        );
+        assert_eq!(report.windiest.map(|e| e.value), Some(55.0));
+        assert_eq!(report.pressure_range, Some((1011.0, 1013.0)));
+        assert_eq!(
+            report
+                .daily_means
+                .iter()
+                .map(|(_, t)| *t)
+                .collect::<Vec<_>>(),
+            [22.0, 19.0, 18.0]
+        );
+        assert_eq!(report.notable.len(), 1);
+
+        let period = (
+            NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(),
+        );
+        let options = OutputOptions::default();
+        let markdown = render_report(
+            period,
+            std::slice::from_ref(&report),
+            ReportFormat::Markdown,
+            &options,
+        );
+        assert!(
+            markdown.contains("| Highest | 25.0°C (2026-09-03) |"),
+            "{markdown}"
+        );
+        assert!(markdown.contains("\n█▃▁\n"), "{markdown}");
+        assert!(
+            markdown.contains("- 2026-09-02 18:00: Thunderstorm"),
+            "{markdown}"
+        );
+        let html = render_report(period, &[report], ReportFormat::Html, &options);
+        assert!(
+            html.contains("<th>Precipitation</th><td>4.5 mm</td>"),
+            "{html}"
+        );
+    }
+}
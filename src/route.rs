@@ -0,0 +1,226 @@
+use crate::app::WeatherApp;
+use crate::errors::AppError;
+use crate::output::OutputOptions;
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::{Coordinates, WeatherData};
+use chrono::{Duration, Local, NaiveDateTime};
+
+/// Mean Earth radius used for distances.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// How a trip is driven.
+#[derive(Debug, Clone)]
+pub struct Trip {
+    /// Origin, optional stops in between, and destination.
+    pub stops: Vec<String>,
+    pub depart: NaiveDateTime,
+    /// Average travel speed in km/h.
+    pub speed_kph: f64,
+    /// Distance between sampled points in km.
+    pub step_km: f64,
+}
+
+/// A sampled point of the route with the weather expected on arrival.
+#[derive(Debug, Clone)]
+pub struct RoutePoint {
+    /// Stop name, or `None` for points sampled between stops.
+    pub stop: Option<String>,
+    pub coordinates: Coordinates,
+    /// Straight-line distance from the origin.
+    pub distance_km: f64,
+    pub eta: NaiveDateTime,
+    pub weather: WeatherData,
+}
+
+/// Great-circle distance between two points.
+pub fn haversine_km(a: Coordinates, b: Coordinates) -> f64 {
+    let (lat1, lat2) = (a.lat.to_radians(), b.lat.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b.lon - a.lon).to_radians();
+
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
+/// Points at most `step_km` apart on the straight line from `a` to `b`, excluding both ends.
+fn interpolate(a: Coordinates, b: Coordinates, step_km: f64) -> Vec<Coordinates> {
+    let segments = (haversine_km(a, b) / step_km).ceil().max(1.0) as usize;
+    (1..segments)
+        .map(|i| {
+            let t = i as f64 / segments as f64;
+            Coordinates {
+                lat: a.lat + (b.lat - a.lat) * t,
+                lon: a.lon + (b.lon - a.lon) * t,
+            }
+        })
+        .collect()
+}
+
+/// Sample the trip and fetch the weather expected at every point on arrival.
+///
+/// Stops are geocoded through the provider itself, and the route between them is a straight line
+/// travelled at a constant speed, so distances and arrival times are estimates.
+pub async fn plan(
+    app: &WeatherApp,
+    provider: &str,
+    trip: &Trip,
+) -> Result<Vec<RoutePoint>, AppError> {
+    let mut waypoints: Vec<(Option<String>, Coordinates)> = Vec::new();
+    for stop in &trip.stops {
+        let coordinates = app
+            .run(provider, stop, None)
+            .await?
+            .coordinates
+            .ok_or_else(|| {
+                ProviderError::Error(format!(
+                    "{provider} did not return coordinates for '{stop}'"
+                ))
+            })?;
+
+        if let Some((_, previous)) = waypoints.last() {
+            let previous = *previous;
+            waypoints.extend(
+                interpolate(previous, coordinates, trip.step_km)
+                    .into_iter()
+                    .map(|point| (None, point)),
+            );
+        }
+        waypoints.push((Some(stop.clone()), coordinates));
+    }
+
+    let now = Local::now().naive_local();
+    let mut points = Vec::with_capacity(waypoints.len());
+    let mut distance_km = 0.0;
+    let mut previous: Option<Coordinates> = None;
+    for (stop, coordinates) in waypoints {
+        if let Some(previous) = previous {
+            distance_km += haversine_km(previous, coordinates);
+        }
+        previous = Some(coordinates);
+
+        let eta = trip.depart + Duration::seconds((distance_km / trip.speed_kph * 3600.0) as i64);
+        let date = (eta > now).then_some(eta);
+        let weather = app.run(provider, &coordinates.to_string(), date).await?;
+
+        points.push(RoutePoint {
+            stop,
+            coordinates,
+            distance_km,
+            eta,
+            weather,
+        });
+    }
+
+    Ok(points)
+}
+
+/// One line per point: arrival time, distance, place and expected conditions.
+pub fn render_route(points: &[RoutePoint], options: &OutputOptions) -> String {
+    points
+        .iter()
+        .map(|point| {
+            let place = point
+                .stop
+                .clone()
+                .unwrap_or_else(|| format!("  {}", point.weather.location));
+            let condition = match options.icon(point.weather.kind) {
+                Some(icon) => format!("{icon} {}", point.weather.condition),
+                None => point.weather.condition.clone(),
+            };
+            options.sanitize(&format!(
+                "{} {:>5.0} km  {place}: {condition}, {}, {}",
+                point.eta.format(&options.time_format),
+                point.distance_km,
+                options.temperature(point.weather.temp_c),
+                options.wind_speed(point.weather.wind_kph),
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::WeatherProvider;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    const PORTO: Coordinates = Coordinates {
+        lat: 41.1496,
+        lon: -8.611,
+    };
+    const LISBON: Coordinates = Coordinates {
+        lat: 38.7223,
+        lon: -9.1393,
+    };
+
+    #[test]
+    fn distance() {
+        let km = haversine_km(PORTO, LISBON);
+        assert!((km - 274.0).abs() < 2.0, "{km}");
+        assert_eq!(haversine_km(PORTO, PORTO), 0.0);
+    }
+
+    #[test]
+    fn sampled_points() {
+        let points = interpolate(PORTO, LISBON, 100.0);
+        assert_eq!(points.len(), 2);
+        assert!(points[0].lat < PORTO.lat && points[0].lat > points[1].lat);
+        assert!(interpolate(PORTO, LISBON, 500.0).is_empty());
+    }
+
+    /// Knows Porto and Lisbon by name, records the dates asked for.
+    struct MockProvider(Mutex<Vec<Option<NaiveDateTime>>>);
+
+    #[async_trait]
+    impl WeatherProvider for MockProvider {
+        async fn fetch(
+            &self,
+            location: &str,
+            date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            let coordinates = match location {
+                "Porto" => PORTO,
+                "Lisbon" => LISBON,
+                _ => {
+                    self.0.lock().unwrap().push(date);
+                    let (lat, lon) = location.split_once(',').unwrap();
+                    Coordinates {
+                        lat: lat.parse().unwrap(),
+                        lon: lon.parse().unwrap(),
+                    }
+                },
+            };
+            Ok(WeatherData {
+                location: location.to_string(),
+                coordinates: Some(coordinates),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn plan_trip() {
+        let app = WeatherApp::builder()
+            .with_provider("mock", MockProvider(Mutex::new(Vec::new())))
+            .build();
+        let depart = Local::now().naive_local() + Duration::days(1);
+        let trip = Trip {
+            stops: vec!["Porto".to_string(), "Lisbon".to_string()],
+            depart,
+            speed_kph: 100.0,
+            step_km: 100.0,
+        };
+
+        let points = plan(&app, "mock", &trip).await.unwrap();
+
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[0].stop.as_deref(), Some("Porto"));
+        assert_eq!(points[0].eta, depart);
+        assert!(points[1].stop.is_none());
+        assert_eq!(points[3].stop.as_deref(), Some("Lisbon"));
+        let hours = (points[3].eta - depart).num_minutes() as f64 / 60.0;
+        assert!((hours - 2.74).abs() < 0.05, "{hours}");
+    }
+}
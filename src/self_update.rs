@@ -0,0 +1,212 @@
+//! `weather self-update`: replace the running binary with the latest GitHub release, for installs
+//! outside a package manager.
+
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, info};
+
+/// Latest release of the upstream repository.
+pub const RELEASES_URL: &str = "https://api.github.com/repos/gofmanaa/weather/releases/latest";
+
+/// Release asset listing `<sha256>  <asset name>` for every binary, as written by `sha256sum`.
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Upper bound for each request, downloads included.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Error)]
+pub enum UpdateError {
+    #[error("Failed to reach GitHub: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Release {tag} has no version number")]
+    InvalidTag { tag: String },
+    #[error("Release {tag} has no {asset}")]
+    MissingAsset { tag: String, asset: String },
+    #[error("Checksum of {asset} does not match {CHECKSUMS_ASSET}, not installing it")]
+    ChecksumMismatch { asset: String },
+    #[error("No release binary is built for {os}/{arch}")]
+    UnsupportedPlatform {
+        os: &'static str,
+        arch: &'static str,
+    },
+    #[error("Failed to replace {path}: {source}", path = .0.display(), source = .1)]
+    Install(PathBuf, std::io::Error),
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// A GitHub release, as far as updating needs it.
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    tag_name: String,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+impl Release {
+    /// Version of the release, its tag without a leading "v".
+    pub fn version(&self) -> Result<Version, UpdateError> {
+        Version::parse(self.tag_name.trim_start_matches('v')).map_err(|_| UpdateError::InvalidTag {
+            tag: self.tag_name.clone(),
+        })
+    }
+
+    fn asset(&self, name: &str) -> Result<&Asset, UpdateError> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| UpdateError::MissingAsset {
+                tag: self.tag_name.clone(),
+                asset: name.to_string(),
+            })
+    }
+}
+
+/// Name of the release binary for this platform, as published by CI, e.g. "weather-linux-amd64".
+pub fn asset_name() -> Result<String, UpdateError> {
+    let (os, arch) = (std::env::consts::OS, std::env::consts::ARCH);
+    let unsupported = || UpdateError::UnsupportedPlatform { os, arch };
+    if !matches!(os, "linux" | "macos" | "windows") {
+        return Err(unsupported());
+    }
+    let arch_name = match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        _ => return Err(unsupported()),
+    };
+    let extension = if os == "windows" { ".exe" } else { "" };
+    Ok(format!("weather-{os}-{arch_name}{extension}"))
+}
+
+/// Checksum listed for `asset` in the contents of a `SHA256SUMS` file.
+fn expected_checksum<'a>(sums: &'a str, asset: &str) -> Option<&'a str> {
+    sums.lines().find_map(|line| {
+        let (checksum, name) = line.split_once(char::is_whitespace)?;
+        // `sha256sum -b` marks names with '*'.
+        let name = name.trim_start().trim_start_matches('*');
+        (name == asset).then_some(checksum)
+    })
+}
+
+/// Check that the SHA-256 of `bytes` is the one listed for `asset` in `sums`.
+pub fn verify(bytes: &[u8], sums: &str, asset: &str) -> Result<(), UpdateError> {
+    let actual = hex::encode(Sha256::digest(bytes));
+    match expected_checksum(sums, asset) {
+        Some(expected) if expected.eq_ignore_ascii_case(&actual) => Ok(()),
+        _ => Err(UpdateError::ChecksumMismatch {
+            asset: asset.to_string(),
+        }),
+    }
+}
+
+/// Client for the GitHub API, which rejects requests without a user agent.
+fn client() -> Result<reqwest::Client, UpdateError> {
+    Ok(reqwest::Client::builder()
+        .user_agent(concat!("weather/", env!("CARGO_PKG_VERSION")))
+        .timeout(REQUEST_TIMEOUT)
+        .build()?)
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, UpdateError> {
+    debug!("Downloading {url}");
+    let response = client.get(url).send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// The release at `url`, [`RELEASES_URL`] outside of tests.
+pub async fn latest(url: &str) -> Result<Release, UpdateError> {
+    let response = client()?.get(url).send().await?.error_for_status()?;
+    Ok(response.json().await?)
+}
+
+/// Download this platform's binary of `release` and check it against the release's checksums.
+pub async fn fetch_binary(release: &Release) -> Result<Vec<u8>, UpdateError> {
+    let client = client()?;
+    let name = asset_name()?;
+    let binary = download(&client, &release.asset(&name)?.browser_download_url).await?;
+    let sums = download(
+        &client,
+        &release.asset(CHECKSUMS_ASSET)?.browser_download_url,
+    )
+    .await?;
+    verify(&binary, &String::from_utf8_lossy(&sums), &name)?;
+    Ok(binary)
+}
+
+/// Put `binary` in place of the executable at `exe`.
+///
+/// The new file is written next to it and renamed over it, so an interrupted update leaves the old
+/// binary working. Windows does not allow replacing a running executable, so there it is moved
+/// aside to `<exe>.old` first.
+pub fn install(binary: &[u8], exe: &Path) -> Result<(), UpdateError> {
+    let failed = |e| UpdateError::Install(exe.to_path_buf(), e);
+    let new = exe.with_extension("new");
+    std::fs::write(&new, binary).map_err(failed)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&new, std::fs::Permissions::from_mode(0o755)).map_err(failed)?;
+    }
+    #[cfg(windows)]
+    std::fs::rename(exe, exe.with_extension("old")).map_err(failed)?;
+    std::fs::rename(&new, exe).map_err(failed)?;
+    info!("Installed new binary at {}", exe.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn verified_binary_from_latest_release() {
+        let server = MockServer::start().await;
+        let name = asset_name().unwrap();
+        let binary = b"new weather".to_vec();
+        let sums = format!(
+            "{}  weather-other\n{} *{name}\n",
+            "0".repeat(64),
+            hex::encode(Sha256::digest(&binary))
+        );
+        Mock::given(path("/releases/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tag_name": "v1.2.0",
+                "assets": [
+                    {"name": name, "browser_download_url": format!("{}/bin", server.uri())},
+                    {"name": "SHA256SUMS", "browser_download_url": format!("{}/sums", server.uri())},
+                ],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(path("/bin"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(binary.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(path("/sums"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(sums.clone()))
+            .mount(&server)
+            .await;
+
+        let release = latest(&format!("{}/releases/latest", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(release.version().unwrap(), Version::new(1, 2, 0));
+        assert_eq!(fetch_binary(&release).await.unwrap(), binary);
+        assert!(matches!(
+            verify(b"tampered", &sums, &name),
+            Err(UpdateError::ChecksumMismatch { .. })
+        ));
+        assert!(verify(&binary, &sums, "weather-other").is_err());
+    }
+}
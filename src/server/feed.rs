@@ -0,0 +1,179 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::{OutputOptions, render_oneline, render_text, slug};
+use crate::weather_providers::alert::WeatherAlert;
+use crate::weather_providers::{DailyData, WeatherData};
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn entry(id: &str, title: &str, updated: &str, content: &str) -> String {
+    format!(
+        r#"  <entry>
+    <id>{id}</id>
+    <title>{title}</title>
+    <updated>{updated}</updated>
+    <content type="text">{content}</content>
+  </entry>
+"#,
+        id = escape(id),
+        title = escape(title),
+        content = escape(content),
+    )
+}
+
+/// "Forecast for 2025-12-06: 8.0°C to 15.0°C" and the precipitation below, when known.
+fn day_entry(feed_id: &str, day: &DailyData, updated: &str, options: &OutputOptions) -> String {
+    let temperature = |temp_c: Option<f64>| {
+        temp_c.map_or_else(|| tr("not-available"), |t| options.temperature(t))
+    };
+    let title = tr_args(
+        "feed-day",
+        &[
+            ("date", day.date.format(&options.date_format).to_string()),
+            ("min", temperature(day.temp_min_c)),
+            ("max", temperature(day.temp_max_c)),
+        ],
+    );
+    let content = match day.precipitation_mm {
+        Some(mm) => format!(
+            "{title}\n{}",
+            tr_args("feed-day-precipitation", &[("mm", format!("{mm:.1}"))])
+        ),
+        None => title.clone(),
+    };
+    entry(
+        &format!("{feed_id}:day:{}", day.date),
+        &options.sanitize(&title),
+        updated,
+        &options.sanitize(&content),
+    )
+}
+
+fn alert_entry(
+    feed_id: &str,
+    alert: &WeatherAlert,
+    updated: &str,
+    options: &OutputOptions,
+) -> String {
+    let since = alert.effective.map_or(0, |at| at.timestamp());
+    let content: Vec<&str> = [alert.headline.as_deref(), alert.description.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect();
+    entry(
+        &format!("{feed_id}:alert:{}:{since}", slug(&alert.event)),
+        &options.sanitize(&alert.event),
+        &alert
+            .effective
+            .map_or_else(|| updated.to_string(), |at| at.to_rfc3339()),
+        &options.sanitize(&content.join("\n")),
+    )
+}
+
+/// Atom feed for a location: the alerts in effect, the latest reading and the daily forecast.
+///
+/// Entry ids include the reading's time, the forecast day and the alert's start, so feed readers
+/// show every new reading and alert once and each day's forecast as it is updated.
+pub fn atom_feed(
+    data: &WeatherData,
+    days: &[DailyData],
+    alerts: &[WeatherAlert],
+    provider: &str,
+    options: &OutputOptions,
+) -> String {
+    let id = format!("urn:weather:{}", slug(&data.location));
+    let updated = data.datetime.to_rfc3339();
+
+    let mut entries: Vec<String> = alerts
+        .iter()
+        .map(|alert| alert_entry(&id, alert, &updated, options))
+        .collect();
+    entries.push(entry(
+        &format!("{id}:{}", data.datetime.timestamp()),
+        &render_oneline(data, options),
+        &updated,
+        &render_text(data, options),
+    ));
+    entries.extend(
+        days.iter()
+            .map(|day| day_entry(&id, day, &updated, options)),
+    );
+
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>{id}</id>
+  <title>{location}</title>
+  <updated>{updated}</updated>
+  <author><name>{provider}</name></author>
+{entries}</feed>
+"#,
+        location = escape(&data.location),
+        provider = escape(provider),
+        entries = entries.concat(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::color::ColorChoice;
+    use crate::weather_providers::alert::Severity;
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    #[test]
+    fn feed_entry() {
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            condition: "Sun & clouds".to_string(),
+            datetime: Utc.with_ymd_and_hms(2025, 12, 5, 17, 15, 0).unwrap(),
+            ..Default::default()
+        };
+        let options = OutputOptions {
+            color: ColorChoice::Never,
+            ..Default::default()
+        };
+
+        let days = [DailyData {
+            date: NaiveDate::from_ymd_opt(2025, 12, 6).unwrap(),
+            temp_min_c: Some(8.0),
+            temp_max_c: Some(15.0),
+            precipitation_mm: Some(2.0),
+        }];
+        let alerts = [WeatherAlert {
+            event: "Wind Warning".to_string(),
+            severity: Severity::Warning,
+            headline: Some("Gusts up to 90 km/h".to_string()),
+            description: None,
+            effective: Some(Utc.with_ymd_and_hms(2025, 12, 5, 12, 0, 0).unwrap()),
+            expires: None,
+        }];
+
+        let feed = atom_feed(&data, &days, &alerts, "weatherapi", &options);
+        assert!(feed.contains("<id>urn:weather:porto_portugal</id>"));
+        assert!(feed.contains("<id>urn:weather:porto_portugal:1764954900</id>"));
+        assert!(feed.contains("<updated>2025-12-05T17:15:00+00:00</updated>"));
+        assert!(feed.contains("Sun &amp; clouds"));
+        assert!(!feed.contains("Sun & clouds"));
+
+        assert!(feed.contains("<id>urn:weather:porto_portugal:day:2025-12-06</id>"));
+        assert!(feed.contains("<title>Forecast for 2025-12-06: 8.0°C to 15.0°C</title>"));
+        assert!(feed.contains("2.0 mm precipitation"));
+        assert!(feed.contains("<id>urn:weather:porto_portugal:alert:wind_warning:1764936000</id>"));
+        assert!(feed.contains("<title>Wind Warning</title>"));
+        assert!(feed.contains("<content type=\"text\">Gusts up to 90 km/h</content>"));
+        assert_eq!(feed.matches("<entry>").count(), 3);
+    }
+}
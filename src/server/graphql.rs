@@ -0,0 +1,219 @@
+use super::client_message;
+use crate::app::WeatherApp;
+use crate::commands::parse_datetime;
+use crate::weather_providers::WeatherData;
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Error, Object, Result, Schema};
+use std::sync::Arc;
+
+pub type WeatherSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Deepest nesting a query may use, enough for the introspection queries of GraphQL clients.
+const MAX_DEPTH: usize = 16;
+
+/// What a `weather` field counts towards [`MAX_COMPLEXITY`], on top of its selection: every
+/// one is a provider request.
+const WEATHER_COMPLEXITY: usize = 100;
+
+/// Upper bound of a query's complexity, every field counting one; allows about nine `weather`
+/// fields per query.
+const MAX_COMPLEXITY: usize = 1000;
+
+/// Build the schema served at `/graphql`.
+///
+/// Several locations or providers are fetched in one request by aliasing `weather`:
+///
+/// ```graphql
+/// { porto: weather(location: "Porto") { tempC }
+///   lisbon: weather(location: "Lisbon", provider: "openweather") { tempC condition } }
+/// ```
+pub fn schema(app: Arc<WeatherApp>, default_provider: String) -> WeatherSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(app)
+        .data(DefaultProvider(default_provider))
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+struct DefaultProvider(String);
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Weather at a location; `date` takes the same formats as `weather get --date`.
+    #[graphql(complexity = "WEATHER_COMPLEXITY + child_complexity")]
+    async fn weather(
+        &self,
+        ctx: &Context<'_>,
+        location: String,
+        provider: Option<String>,
+        date: Option<String>,
+    ) -> Result<Weather> {
+        let app = ctx.data::<Arc<WeatherApp>>()?;
+        let provider = match provider {
+            Some(provider) => provider,
+            None => ctx.data::<DefaultProvider>()?.0.clone(),
+        };
+        let data = async {
+            let date = date.as_deref().map(parse_datetime).transpose()?;
+            app.run(&provider, &location, date).await
+        }
+        .await
+        .map_err(|e| Error::new(client_message(&e)))?;
+        Ok(Weather { data, provider })
+    }
+
+    /// Names of the registered providers.
+    async fn providers(&self, ctx: &Context<'_>) -> Result<Vec<String>> {
+        Ok(ctx.data::<Arc<WeatherApp>>()?.list())
+    }
+}
+
+pub struct Weather {
+    data: WeatherData,
+    provider: String,
+}
+
+#[Object]
+impl Weather {
+    async fn location(&self) -> &str {
+        &self.data.location
+    }
+
+    async fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// RFC 3339 time of the reading, in UTC.
+    async fn datetime(&self) -> String {
+        self.data.datetime.to_rfc3339()
+    }
+
+    /// RFC 3339 time of the reading, in the location's timezone.
+    async fn local_datetime(&self) -> String {
+        self.data.local_datetime().to_rfc3339()
+    }
+
+    async fn temp_c(&self) -> f64 {
+        self.data.temp_c
+    }
+
+    async fn humidity(&self) -> f64 {
+        self.data.humidity
+    }
+
+    async fn pressure(&self) -> f64 {
+        self.data.pressure
+    }
+
+    async fn condition(&self) -> &str {
+        &self.data.condition
+    }
+
+    /// Provider-independent condition, e.g. "partly_cloudy".
+    async fn kind(&self) -> String {
+        serde_json::to_value(self.data.kind)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    async fn wind_kph(&self) -> f64 {
+        self.data.wind_kph
+    }
+
+    async fn wind_deg(&self) -> f64 {
+        self.data.wind_deg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::WeatherProvider;
+    use crate::weather_providers::condition::ConditionKind;
+    use crate::weather_providers::error::ProviderError;
+    use async_trait::async_trait;
+    use chrono::NaiveDateTime;
+    use serde_json::json;
+
+    struct MockProvider;
+
+    #[async_trait]
+    impl WeatherProvider for MockProvider {
+        async fn fetch(
+            &self,
+            location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            Ok(WeatherData {
+                location: location.to_string(),
+                temp_c: 16.1,
+                kind: ConditionKind::PartlyCloudy,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn query_several_locations() {
+        let app = WeatherApp::builder()
+            .with_provider("mock", MockProvider)
+            .build();
+        let schema = schema(Arc::new(app), "mock".to_string());
+
+        let res = schema
+            .execute(
+                r#"{
+                    porto: weather(location: "Porto") { location tempC kind }
+                    lisbon: weather(location: "Lisbon", provider: "mock") { provider }
+                    providers
+                }"#,
+            )
+            .await;
+
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({
+                "porto": {"location": "Porto", "tempC": 16.1, "kind": "partly_cloudy"},
+                "lisbon": {"provider": "mock"},
+                "providers": ["mock"],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn complex_queries_are_rejected() {
+        let schema = schema(Arc::new(WeatherApp::builder().build()), "mock".to_string());
+
+        let aliases: String = (0..10)
+            .map(|i| format!(r#"l{i}: weather(location: "Porto") {{ tempC }} "#))
+            .collect();
+        let res = schema.execute(format!("{{ {aliases} }}")).await;
+        assert!(
+            res.errors[0].message.contains("too complex"),
+            "{:?}",
+            res.errors
+        );
+
+        let res = schema
+            .execute(
+                "{ __schema { types { name fields { name type { name kind ofType { name kind \
+                 ofType { name kind ofType { name kind ofType { name } } } } } } } } }",
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+    }
+
+    #[tokio::test]
+    async fn unknown_provider_is_an_error() {
+        let schema = schema(Arc::new(WeatherApp::builder().build()), "mock".to_string());
+
+        let res = schema
+            .execute(r#"{ weather(location: "Porto") { tempC } }"#)
+            .await;
+        assert_eq!(res.errors.len(), 1);
+    }
+}
@@ -0,0 +1,376 @@
+use crate::app::WeatherApp;
+use crate::commands::parse_datetime;
+use crate::errors::AppError;
+use crate::output::OutputOptions;
+use crate::output::color::ColorChoice;
+use crate::output::json::to_json;
+use crate::weather_providers::alert::Severity;
+use crate::weather_providers::error::ProviderError;
+use async_graphql_axum::GraphQL;
+use axum::extract::{Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::Days;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tracing::{debug, info, warn};
+
+pub mod feed;
+pub mod graphql;
+
+/// Days of forecast in a feed, today included.
+const FEED_DAYS: u64 = 3;
+
+/// State shared by the request handlers.
+#[derive(Clone)]
+struct ServerState {
+    app: Arc<WeatherApp>,
+    default_provider: String,
+    /// Rendering of text in feeds.
+    options: OutputOptions,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderQuery {
+    provider: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WeatherQuery {
+    location: String,
+    /// Defaults to the configured provider.
+    provider: Option<String>,
+    /// Same formats as `weather get --date`.
+    date: Option<String>,
+}
+
+/// An [`AppError`] rendered as `{"error": "..."}` with a matching status code.
+struct ApiError(AppError);
+
+impl From<AppError> for ApiError {
+    fn from(e: AppError) -> Self {
+        Self(e)
+    }
+}
+
+/// Status code for `error`.
+fn status(error: &AppError) -> StatusCode {
+    match error {
+        AppError::InvalidProvider(_) => StatusCode::NOT_FOUND,
+        AppError::InvalidDate(_) => StatusCode::BAD_REQUEST,
+        AppError::Provider(e) => match e {
+            ProviderError::LocationNotFound { .. } | ProviderError::InvalidLocation(_) => {
+                StatusCode::NOT_FOUND
+            },
+            ProviderError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            ProviderError::QuotaExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::BAD_GATEWAY,
+        },
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Message for `error` that is safe to send to a client.
+///
+/// Errors about the request itself are passed on. Others may include upstream URLs, paths or
+/// configuration, so they are logged and replaced by the status reason.
+pub(crate) fn client_message(error: &AppError) -> String {
+    let status = status(error);
+    if status.is_client_error() || status == StatusCode::GATEWAY_TIMEOUT {
+        return error.to_string();
+    }
+    warn!("Request failed: {error}");
+    status
+        .canonical_reason()
+        .unwrap_or("Request failed")
+        .to_string()
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({ "error": client_message(&self.0) }));
+        (status(&self.0), body).into_response()
+    }
+}
+
+/// Routes of the REST API, see [`serve`].
+pub fn router(app: Arc<WeatherApp>, default_provider: String, options: OutputOptions) -> Router {
+    let schema = graphql::schema(app.clone(), default_provider.clone());
+
+    Router::new()
+        .route("/v1/current", get(current))
+        .route("/v1/forecast", get(forecast))
+        .route("/v1/providers", get(providers))
+        .route("/feed/{file}", get(feed))
+        .route("/metrics", get(metrics))
+        .route_service("/graphql", GraphQL::new(schema))
+        .with_state(ServerState {
+            app,
+            default_provider,
+            options: OutputOptions {
+                color: ColorChoice::Never,
+                ..options
+            },
+        })
+}
+
+/// Serve the REST API on `addr` until the process is stopped.
+///
+/// - `GET /v1/current?location=..&provider=..`
+/// - `GET /v1/forecast?location=..&date=..&provider=..`
+/// - `GET /v1/providers`
+/// - `GET /feed/<location>.xml?provider=..`, an Atom feed
+/// - `GET /metrics`, provider call metrics for Prometheus
+/// - `POST /graphql`, see [`graphql::schema`]
+pub async fn serve(
+    app: Arc<WeatherApp>,
+    default_provider: String,
+    options: OutputOptions,
+    addr: SocketAddr,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!("Listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, router(app, default_provider, options)).await
+}
+
+async fn fetch(state: &ServerState, query: WeatherQuery) -> Result<Json<Value>, ApiError> {
+    let provider = query
+        .provider
+        .unwrap_or_else(|| state.default_provider.clone());
+    let date = query.date.as_deref().map(parse_datetime).transpose()?;
+
+    let data = state.app.run(&provider, &query.location, date).await?;
+    Ok(Json(to_json(&data, &provider)))
+}
+
+async fn current(
+    State(state): State<ServerState>,
+    Query(query): Query<WeatherQuery>,
+) -> Result<Json<Value>, ApiError> {
+    fetch(
+        &state,
+        WeatherQuery {
+            date: None,
+            ..query
+        },
+    )
+    .await
+}
+
+async fn forecast(
+    State(state): State<ServerState>,
+    Query(query): Query<WeatherQuery>,
+) -> Result<Json<Value>, ApiError> {
+    if query.date.is_none() {
+        return Err(AppError::InvalidDate("a forecast needs a `date`".to_string()).into());
+    }
+    fetch(&state, query).await
+}
+
+async fn feed(
+    State(state): State<ServerState>,
+    Path(file): Path<String>,
+    Query(query): Query<ProviderQuery>,
+) -> Result<Response, ApiError> {
+    let Some(location) = file.strip_suffix(".xml") else {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    };
+    let provider = query
+        .provider
+        .unwrap_or_else(|| state.default_provider.clone());
+
+    let data = state.app.run(&provider, location, None).await?;
+    let today = data.local_datetime().date_naive();
+    let until = today + Days::new(FEED_DAYS - 1);
+    // Not every provider forecasts days or reports alerts; the feed then goes without.
+    let (days, alerts) = tokio::join!(
+        state.app.daily(&provider, location, today, until),
+        state.app.alerts(&provider, location, Severity::Advisory),
+    );
+    let days = days
+        .inspect_err(|e| debug!("No daily forecast in feed: {e}"))
+        .unwrap_or_default();
+    let alerts = alerts
+        .inspect_err(|e| debug!("No alerts in feed: {e}"))
+        .unwrap_or_default();
+    Ok((
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        feed::atom_feed(&data, &days, &alerts, &provider, &state.options),
+    )
+        .into_response())
+}
+
+async fn providers(State(state): State<ServerState>) -> Json<Value> {
+    Json(json!({
+        "default": state.default_provider,
+        "providers": state.app.list(),
+    }))
+}
+
+async fn metrics(State(state): State<ServerState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.app.metrics().prometheus(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::{WeatherData, WeatherProvider};
+    use async_trait::async_trait;
+    use chrono::NaiveDateTime;
+
+    struct MockProvider;
+
+    #[async_trait]
+    impl WeatherProvider for MockProvider {
+        async fn fetch(
+            &self,
+            location: &str,
+            _date: Option<NaiveDateTime>,
+        ) -> Result<WeatherData, ProviderError> {
+            if location == "Nowhere" {
+                return Err(ProviderError::LocationNotFound {
+                    provider: "mock".to_string(),
+                    location: location.to_string(),
+                });
+            }
+            if location == "Broken" {
+                return Err(ProviderError::Network {
+                    provider: "mock".to_string(),
+                    message: "connection refused for https://example.com/?key=secret".to_string(),
+                });
+            }
+            Ok(WeatherData {
+                location: location.to_string(),
+                ..Default::default()
+            })
+        }
+    }
+
+    async fn start() -> String {
+        let app = WeatherApp::builder()
+            .with_provider("mock", MockProvider)
+            .build();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let router = router(Arc::new(app), "mock".to_string(), OutputOptions::default());
+            axum::serve(listener, router).await.unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn current_weather() {
+        let base = start().await;
+
+        let res = reqwest::get(format!("{base}/v1/current?location=Porto"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let body: Value = res.json().await.unwrap();
+        assert_eq!(body["location"], "Porto");
+        assert_eq!(body["provider"], "mock");
+    }
+
+    #[tokio::test]
+    async fn errors_map_to_status_codes() {
+        let base = start().await;
+
+        let res = reqwest::get(format!("{base}/v1/current?location=Nowhere"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 404);
+
+        let res = reqwest::get(format!("{base}/v1/current?location=Porto&provider=missing"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 404);
+
+        let res = reqwest::get(format!("{base}/v1/forecast?location=Porto"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 400);
+
+        let res = reqwest::get(format!("{base}/v1/current?location=Broken"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 502);
+        let body: Value = res.json().await.unwrap();
+        assert_eq!(body["error"], "Bad Gateway");
+    }
+
+    #[tokio::test]
+    async fn graphql_endpoint() {
+        let base = start().await;
+
+        let body: Value = reqwest::Client::new()
+            .post(format!("{base}/graphql"))
+            .json(&json!({"query": r#"{ weather(location: "Porto") { location } }"#}))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["data"]["weather"]["location"], "Porto");
+    }
+
+    #[tokio::test]
+    async fn atom_feed() {
+        let base = start().await;
+
+        let res = reqwest::get(format!("{base}/feed/Porto.xml"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "application/atom+xml; charset=utf-8"
+        );
+        assert!(res.text().await.unwrap().contains("<title>Porto</title>"));
+
+        let res = reqwest::get(format!("{base}/feed/Porto.json"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn prometheus_metrics() {
+        let base = start().await;
+        reqwest::get(format!("{base}/v1/current?location=Nowhere"))
+            .await
+            .unwrap();
+
+        let text = reqwest::get(format!("{base}/metrics"))
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert!(text.contains(
+            "weather_provider_requests_total{provider=\"mock\",outcome=\"not_found\"} 1\n"
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_providers() {
+        let base = start().await;
+
+        let body: Value = reqwest::get(format!("{base}/v1/providers"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body, json!({"default": "mock", "providers": ["mock"]}));
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use tokio::sync::{Mutex, OnceCell};
+
+/// Coalesces concurrent calls for the same key: the first caller does the work and every caller
+/// arriving while it is in flight gets a clone of its result.
+///
+/// Nothing is kept once the call completes, later calls start a new one; caching is left to
+/// [`crate::cache::WeatherCache`].
+pub struct Group<K, V> {
+    calls: Mutex<HashMap<K, Arc<OnceCell<V>>>>,
+}
+
+impl<K, V> Default for Group<K, V> {
+    fn default() -> Self {
+        Self {
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, V> Group<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// Run `work` for `key`, unless a call for it is already in flight.
+    ///
+    /// Should the caller doing the work be cancelled, one of the waiting ones takes over.
+    pub async fn run<F, Fut>(&self, key: K, work: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let call = self
+            .calls
+            .lock()
+            .await
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        let value = call.get_or_init(work).await.clone();
+
+        let mut calls = self.calls.lock().await;
+        if calls.get(&key).is_some_and(|c| Arc::ptr_eq(c, &call)) {
+            calls.remove(&key);
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn concurrent_calls_share_one_run() {
+        let group: Group<&str, usize> = Group::default();
+        let runs = AtomicUsize::new(0);
+        let work = || async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            runs.fetch_add(1, Ordering::SeqCst) + 1
+        };
+
+        let (a, b, c) = tokio::join!(
+            group.run("porto", work),
+            group.run("porto", work),
+            group.run("oslo", work),
+        );
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // Finished calls are not remembered.
+        group.run("porto", work).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,618 @@
+use crate::anomaly::Reading;
+use crate::config::{SettingsError, StorageSettings};
+use crate::output::query::QueryResult;
+use crate::weather_providers::{DailyData, WeatherData};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags, params};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("Failed to create history directory: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("History database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error(transparent)]
+    Config(#[from] SettingsError),
+    #[error("Only queries reading the history are allowed")]
+    NotReadOnly,
+}
+
+/// How long the history is kept, from `[storage] retention`. Months count as 30 days, years as 365.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    pub days: u32,
+}
+
+impl FromStr for Retention {
+    type Err = SettingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            SettingsError::Invalid(format!(
+                "retention '{s}' is not a number followed by d, w, m or y"
+            ))
+        };
+        let (split, _) = s.char_indices().next_back().ok_or_else(invalid)?;
+        let (amount, unit) = s.split_at(split);
+        let amount: u32 = amount.trim().parse().map_err(|_| invalid())?;
+        let per_unit = match unit {
+            "d" => 1,
+            "w" => 7,
+            "m" => 30,
+            "y" => 365,
+            _ => return Err(invalid()),
+        };
+        let days = amount.checked_mul(per_unit).ok_or_else(invalid)?;
+        Ok(Self { days })
+    }
+}
+
+impl Retention {
+    /// Oldest moment still kept at `now`.
+    pub fn cutoff(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now.checked_sub_days(chrono::Days::new(u64::from(self.days)))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC)
+    }
+}
+
+/// Every fetched result, kept in a SQLite database so later runs can compare against it.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> Result<Self, StoreError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        Self::init(Connection::open(path)?)
+    }
+
+    /// The configured store, `None` unless `[storage]` is enabled. Rows past the retention are
+    /// dropped on opening.
+    pub fn from_settings(settings: &StorageSettings) -> Result<Option<Self>, StoreError> {
+        if !settings.enabled {
+            return Ok(None);
+        }
+        let path = settings.path.clone().unwrap_or_else(Self::default_path);
+        let store = Self::open(&path)?;
+        if let Some(retention) = &settings.retention {
+            let retention: Retention = retention.parse()?;
+            store.prune(retention.cutoff(Utc::now()))?;
+        }
+        Ok(Some(store))
+    }
+
+    /// Open an existing database without the right to change it.
+    pub fn open_read_only(path: &Path) -> Result<Self, StoreError> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        Ok(Self { conn })
+    }
+
+    pub fn in_memory() -> Result<Self, StoreError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    /// Platform data directory, e.g. `~/.local/share/weather/history.db` on Linux.
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from(".local/share"))
+            .join("weather")
+            .join("history.db")
+    }
+
+    fn init(conn: Connection) -> Result<Self, StoreError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS readings (
+                provider    TEXT NOT NULL,
+                location    TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL,
+                temp_c      REAL NOT NULL,
+                humidity    REAL NOT NULL,
+                pressure    REAL NOT NULL,
+                wind_kph    REAL NOT NULL,
+                wind_deg    REAL NOT NULL,
+                condition   TEXT NOT NULL,
+                kind        TEXT NOT NULL,
+                data        TEXT NOT NULL,
+                UNIQUE (provider, location, recorded_at)
+            );
+            CREATE INDEX IF NOT EXISTS readings_location ON readings (location, recorded_at);
+            CREATE TABLE IF NOT EXISTS days (
+                provider         TEXT NOT NULL,
+                location         TEXT NOT NULL,
+                date             TEXT NOT NULL,
+                temp_min_c       REAL,
+                temp_max_c       REAL,
+                precipitation_mm REAL,
+                UNIQUE (provider, location, date)
+            );
+            CREATE TABLE IF NOT EXISTS names (
+                location TEXT PRIMARY KEY,
+                name     TEXT NOT NULL
+            );",
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Delete readings and days from before `cutoff`, returning how many rows went.
+    pub fn prune(&self, cutoff: DateTime<Utc>) -> Result<usize, StoreError> {
+        let readings = self.conn.execute(
+            "DELETE FROM readings WHERE recorded_at < ?1",
+            params![cutoff.timestamp()],
+        )?;
+        let days = self.conn.execute(
+            "DELETE FROM days WHERE date < ?1",
+            params![cutoff.date_naive().to_string()],
+        )?;
+        Ok(readings + days)
+    }
+
+    /// Give the space of deleted rows back to the file system.
+    pub fn vacuum(&self) -> Result<(), StoreError> {
+        self.conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    /// Run a single statement that only reads, e.g. a `SELECT`, and return all its rows.
+    pub fn query(&self, sql: &str) -> Result<QueryResult, StoreError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        if !stmt.readonly() {
+            return Err(StoreError::NotReadOnly);
+        }
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let mut rows = stmt.query([])?;
+        let mut result = QueryResult {
+            columns,
+            rows: Vec::new(),
+        };
+        while let Some(row) = rows.next()? {
+            let values = (0..result.columns.len())
+                .map(|i| {
+                    Ok(match row.get_ref(i)? {
+                        ValueRef::Null => serde_json::Value::Null,
+                        ValueRef::Integer(n) => n.into(),
+                        ValueRef::Real(x) => x.into(),
+                        ValueRef::Text(text) => String::from_utf8_lossy(text).into(),
+                        ValueRef::Blob(blob) => format!("<{} bytes>", blob.len()).into(),
+                    })
+                })
+                .collect::<Result<_, rusqlite::Error>>()?;
+            result.rows.push(values);
+        }
+        Ok(result)
+    }
+
+    /// Locations are matched case-insensitively on the provider's resolved name.
+    fn key(location: &str) -> String {
+        location.trim().to_lowercase()
+    }
+
+    /// Remember `location` as written last, to show in place of its key.
+    fn record_name(conn: &Connection, location: &str) -> Result<(), StoreError> {
+        conn.execute(
+            "INSERT OR REPLACE INTO names (location, name) VALUES (?1, ?2)",
+            params![Self::key(location), location.trim()],
+        )?;
+        Ok(())
+    }
+
+    /// Store `data`; a reading already recorded, e.g. served from the cache, is ignored.
+    pub fn record(&self, provider: &str, data: &WeatherData) -> Result<(), StoreError> {
+        let kind = serde_json::to_value(data.kind)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let json = serde_json::to_string(data).unwrap_or_default();
+        self.conn.execute(
+            "INSERT OR IGNORE INTO readings
+                (provider, location, recorded_at, temp_c, humidity, pressure,
+                 wind_kph, wind_deg, condition, kind, data)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                provider,
+                Self::key(&data.location),
+                data.datetime.timestamp(),
+                data.temp_c,
+                data.humidity,
+                data.pressure,
+                data.wind_kph,
+                data.wind_deg,
+                data.condition,
+                kind,
+                json,
+            ],
+        )?;
+        Self::record_name(&self.conn, &data.location)
+    }
+
+    /// Store daily summaries of `location`, replacing those already recorded for the same days.
+    pub fn record_days(
+        &mut self,
+        provider: &str,
+        location: &str,
+        days: &[DailyData],
+    ) -> Result<(), StoreError> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO days
+                    (provider, location, date, temp_min_c, temp_max_c, precipitation_mm)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for day in days {
+                stmt.execute(params![
+                    provider,
+                    Self::key(location),
+                    day.date.to_string(),
+                    day.temp_min_c,
+                    day.temp_max_c,
+                    day.precipitation_mm,
+                ])?;
+            }
+        }
+        Self::record_name(&tx, location)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Daily summaries of `location` from `start` to `end`, both included, by date. Without a
+    /// `provider`, a day backfilled from several keeps the summary of the first by name.
+    pub fn days(
+        &self,
+        provider: Option<&str>,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, StoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT date, temp_min_c, temp_max_c, precipitation_mm FROM days
+             WHERE (?1 IS NULL OR provider = ?1) AND location = ?2 AND date BETWEEN ?3 AND ?4
+             ORDER BY date, provider",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                provider,
+                Self::key(location),
+                start.to_string(),
+                end.to_string()
+            ],
+            |row| {
+                Ok(DailyData {
+                    date: row.get(0)?,
+                    temp_min_c: row.get(1)?,
+                    temp_max_c: row.get(2)?,
+                    precipitation_mm: row.get(3)?,
+                })
+            },
+        )?;
+        let mut days = rows.collect::<Result<Vec<_>, _>>()?;
+        days.dedup_by_key(|day| day.date);
+        Ok(days)
+    }
+
+    /// Locations with readings or days recorded from `since` to `until`, by name. Those
+    /// recorded before names were kept go by their lowercase key.
+    pub fn locations(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<String>, StoreError> {
+        // A database only opened read-only since may not have the table yet.
+        let has_names = self
+            .conn
+            .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'names'")?
+            .exists([])?;
+        let name = if has_names {
+            "(SELECT name FROM names WHERE names.location = recorded.location)"
+        } else {
+            "NULL"
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT coalesce({name}, location) AS shown FROM (
+                 SELECT location FROM readings WHERE recorded_at BETWEEN ?1 AND ?2
+                 UNION
+                 SELECT location FROM days WHERE date BETWEEN ?3 AND ?4
+             ) AS recorded
+             ORDER BY shown",
+        ))?;
+        let rows = stmt.query_map(
+            params![
+                since.timestamp(),
+                until.timestamp(),
+                since.date_naive().to_string(),
+                until.date_naive().to_string(),
+            ],
+            |row| row.get(0),
+        )?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Readings of `location` recorded from `since` to `until`, oldest first, from `provider` or
+    /// from every provider.
+    pub fn readings(
+        &self,
+        provider: Option<&str>,
+        location: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<WeatherData>, StoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT data FROM readings
+             WHERE (?1 IS NULL OR provider = ?1) AND location = ?2 AND recorded_at BETWEEN ?3 AND ?4
+             ORDER BY recorded_at",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                provider,
+                Self::key(location),
+                since.timestamp(),
+                until.timestamp()
+            ],
+            |row| row.get::<_, String>(0),
+        )?;
+        let mut readings = Vec::new();
+        for json in rows {
+            // Rows written by an older version may not parse; the report does without them.
+            if let Ok(data) = serde_json::from_str(&json?) {
+                readings.push(data);
+            }
+        }
+        Ok(readings)
+    }
+
+    /// Pressure in hPa of `location` as reported by `provider` from `since` to `until`, oldest
+    /// first; providers are kept apart as their values differ by a few hPa.
+    pub fn pressures(
+        &self,
+        provider: &str,
+        location: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, f64)>, StoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT recorded_at, pressure FROM readings
+             WHERE provider = ?1 AND location = ?2 AND recorded_at BETWEEN ?3 AND ?4
+             ORDER BY recorded_at",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                provider,
+                Self::key(location),
+                since.timestamp(),
+                until.timestamp()
+            ],
+            |row| {
+                Ok((
+                    DateTime::from_timestamp(row.get(0)?, 0).unwrap_or_default(),
+                    row.get(1)?,
+                ))
+            },
+        )?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Wind speed in km/h and the direction it comes from in degrees, of every reading of
+    /// `location` from `since` on.
+    pub fn winds(
+        &self,
+        location: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(f64, f64)>, StoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT wind_kph, wind_deg FROM readings WHERE location = ?1 AND recorded_at >= ?2
+             ORDER BY recorded_at",
+        )?;
+        let rows = stmt.query_map(params![Self::key(location), since.timestamp()], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Readings of `location` from any year within `days` calendar days of `around`,
+    /// excluding `around` itself.
+    pub fn seasonal(
+        &self,
+        location: &str,
+        around: DateTime<Utc>,
+        days: u32,
+    ) -> Result<Vec<Reading>, StoreError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT recorded_at, temp_c, humidity, pressure, wind_kph
+             FROM readings WHERE location = ?1 AND recorded_at != ?2",
+        )?;
+        let rows = stmt.query_map(params![Self::key(location), around.timestamp()], |row| {
+            Ok(Reading {
+                recorded_at: DateTime::from_timestamp(row.get(0)?, 0).unwrap_or_default(),
+                temp_c: row.get(1)?,
+                humidity: row.get(2)?,
+                pressure: row.get(3)?,
+                wind_kph: row.get(4)?,
+            })
+        })?;
+
+        let day = around.ordinal0();
+        let mut readings = Vec::new();
+        for reading in rows {
+            let reading = reading?;
+            let diff = reading.recorded_at.ordinal0().abs_diff(day);
+            if diff.min(365 - diff) <= days {
+                readings.push(reading);
+            }
+        }
+        Ok(readings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn reading(location: &str, datetime: DateTime<Utc>, temp_c: f64) -> WeatherData {
+        WeatherData {
+            location: location.to_string(),
+            datetime,
+            temp_c,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn seasonal_readings_across_years() {
+        let store = Store::in_memory().unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+        for (date, temp) in [
+            (Utc.with_ymd_and_hms(2024, 12, 28, 12, 0, 0).unwrap(), 1.0),
+            (Utc.with_ymd_and_hms(2025, 1, 10, 12, 0, 0).unwrap(), 2.0),
+            (Utc.with_ymd_and_hms(2025, 7, 1, 12, 0, 0).unwrap(), 25.0),
+        ] {
+            store
+                .record("openmeteo", &reading("Kyiv", date, temp))
+                .unwrap();
+        }
+        store
+            .record("openmeteo", &reading("Lviv", now, 3.0))
+            .unwrap();
+        store
+            .record("openmeteo", &reading("Kyiv", now, 4.0))
+            .unwrap();
+        // Recording the same observation twice keeps one row.
+        store
+            .record("openmeteo", &reading("Kyiv", now, 4.0))
+            .unwrap();
+
+        let temps: Vec<f64> = store
+            .seasonal("kyiv", now, 15)
+            .unwrap()
+            .iter()
+            .map(|r| r.temp_c)
+            .collect();
+        assert_eq!(temps, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn pruned_after_retention() {
+        let mut store = Store::in_memory().unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+        let old = Utc.with_ymd_and_hms(2023, 12, 1, 12, 0, 0).unwrap();
+        store
+            .record("openmeteo", &reading("Kyiv", old, 1.0))
+            .unwrap();
+        store
+            .record("openmeteo", &reading("Kyiv", now, 2.0))
+            .unwrap();
+        store
+            .record_days(
+                "openmeteo",
+                "Kyiv",
+                &[DailyData {
+                    date: old.date_naive(),
+                    temp_min_c: None,
+                    temp_max_c: None,
+                    precipitation_mm: None,
+                }],
+            )
+            .unwrap();
+
+        let retention: Retention = "2y".parse().unwrap();
+        assert_eq!(retention.days, 730);
+        assert!("2 years".parse::<Retention>().is_err());
+        assert!("5é".parse::<Retention>().is_err());
+        assert!("é".parse::<Retention>().is_err());
+        let forever: Retention = "4000000y".parse().unwrap();
+        assert_eq!(forever.cutoff(now), DateTime::<Utc>::MIN_UTC);
+        assert_eq!(store.prune(retention.cutoff(now)).unwrap(), 2);
+        store.vacuum().unwrap();
+        assert_eq!(store.seasonal("Kyiv", old, 183).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn readings_within_period() {
+        let store = Store::in_memory().unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+        for (location, days_ago, temp) in [("Kyiv", 1, 2.0), ("Kyiv", 10, 5.0), ("Porto", 2, 14.0)]
+        {
+            let at = now - chrono::Duration::days(days_ago);
+            store
+                .record("openmeteo", &reading(location, at, temp))
+                .unwrap();
+        }
+        let since = now - chrono::Duration::days(7);
+
+        assert_eq!(store.locations(since, now).unwrap(), ["Kyiv", "Porto"]);
+        let readings = store.readings(None, "KYIV", since, now).unwrap();
+        assert_eq!(readings.len(), 1);
+        assert_eq!(readings[0].temp_c, 2.0);
+        assert_eq!(store.winds("Porto", since).unwrap(), [(0.0, 0.0)]);
+    }
+
+    #[test]
+    fn only_reading_queries() {
+        let store = Store::in_memory().unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+        store
+            .record("openmeteo", &reading("Kyiv", now, 2.5))
+            .unwrap();
+
+        let result = store
+            .query("SELECT location, temp_c, NULL AS note FROM readings")
+            .unwrap();
+        assert_eq!(result.columns, ["location", "temp_c", "note"]);
+        assert_eq!(
+            result.rows,
+            [vec!["kyiv".into(), 2.5.into(), serde_json::Value::Null]]
+        );
+        assert!(matches!(
+            store.query("DELETE FROM readings"),
+            Err(StoreError::NotReadOnly)
+        ));
+    }
+
+    #[test]
+    fn days_replaced_on_backfill() {
+        let mut store = Store::in_memory().unwrap();
+        let date = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let day = |d, max| DailyData {
+            date: date(d),
+            temp_min_c: Some(1.0),
+            temp_max_c: max,
+            precipitation_mm: None,
+        };
+        store
+            .record_days("openmeteo", "Kyiv", &[day(1, None), day(2, Some(8.0))])
+            .unwrap();
+        store
+            .record_days("openmeteo", "Kyiv", &[day(1, Some(6.0))])
+            .unwrap();
+
+        let days = store.days(None, "kyiv", date(1), date(31)).unwrap();
+        assert_eq!(days, [day(1, Some(6.0)), day(2, Some(8.0))]);
+
+        store
+            .record_days(
+                "weatherapi",
+                "Kyiv",
+                &[day(1, Some(7.0)), day(3, Some(9.0))],
+            )
+            .unwrap();
+        let days = store.days(None, "kyiv", date(1), date(31)).unwrap();
+        assert_eq!(
+            days,
+            [day(1, Some(6.0)), day(2, Some(8.0)), day(3, Some(9.0))]
+        );
+        let days = store
+            .days(Some("weatherapi"), "kyiv", date(1), date(31))
+            .unwrap();
+        assert_eq!(days, [day(1, Some(7.0)), day(3, Some(9.0))]);
+        let march = |d| Utc.with_ymd_and_hms(2024, 3, d, 0, 0, 0).unwrap();
+        assert_eq!(store.locations(march(1), march(31)).unwrap(), ["Kyiv"]);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::{MarineData, WeatherData};
+
+/// How worthwhile paddling out is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SurfRating {
+    Flat,
+    Poor,
+    Fair,
+    Good,
+    Epic,
+}
+
+impl SurfRating {
+    fn key(self) -> &'static str {
+        match self {
+            Self::Flat => "surf-flat",
+            Self::Poor => "surf-poor",
+            Self::Fair => "surf-fair",
+            Self::Good => "surf-good",
+            Self::Epic => "surf-epic",
+        }
+    }
+}
+
+/// Rate the sea state: waves of 1–2.5 m with a long period and little wind are best.
+///
+/// Returns `None` when the wave height is unknown, e.g. for a spot inland.
+pub fn rate(marine: &MarineData, wind_kph: f64) -> Option<SurfRating> {
+    let height = marine.wave_height_m?;
+    let mut score: i32 = match height {
+        h if h < 0.3 => return Some(SurfRating::Flat),
+        h if h < 0.6 => 1,
+        h if h < 1.0 => 2,
+        h if h <= 2.5 => 3,
+        h if h <= 4.0 => 2,
+        _ => 1,
+    };
+    score += match marine.wave_period_s {
+        Some(p) if p >= 12.0 => 2,
+        Some(p) if p >= 9.0 => 1,
+        Some(p) if p < 6.0 => -1,
+        _ => 0,
+    };
+    score += match wind_kph {
+        w if w < 12.0 => 1,
+        w if w < 25.0 => 0,
+        w if w < 40.0 => -1,
+        _ => -2,
+    };
+
+    Some(match score {
+        ..=1 => SurfRating::Poor,
+        2..=3 => SurfRating::Fair,
+        4 => SurfRating::Good,
+        _ => SurfRating::Epic,
+    })
+}
+
+/// Surf report for `weather surf`: rating, waves, swell and wind.
+pub fn render_surf(weather: &WeatherData, marine: &MarineData, options: &OutputOptions) -> String {
+    let rating = match rate(marine, weather.wind_kph) {
+        Some(rating) => tr(rating.key()),
+        None => tr("not-available"),
+    };
+    let waves = match (marine.wave_height_m, marine.wave_period_s) {
+        (Some(height), Some(period)) => tr_args(
+            "waves-value",
+            &[
+                ("height", format!("{height:.1}")),
+                ("period", format!("{period:.0}")),
+            ],
+        ),
+        (Some(height), None) => format!("{height:.1} m"),
+        _ => tr("not-available"),
+    };
+    let swell = match marine.swell_direction_deg {
+        Some(deg) => tr_args(
+            "swell-value",
+            &[("degree", format!("{deg:.0}{}", options.degree()))],
+        ),
+        None => tr("not-available"),
+    };
+
+    let text = format!(
+        "{}\n> {}: {}\n> {}: {}\n> {}: {}",
+        tr_args(
+            "surf-at",
+            &[("location", marine.location.clone()), ("rating", rating)]
+        ),
+        tr("label-waves"),
+        waves,
+        tr("label-swell"),
+        swell,
+        tr("label-wind"),
+        tr_args(
+            "wind-value",
+            &[
+                ("speed", options.wind_described(weather.wind_kph)),
+                (
+                    "degree",
+                    format!("{:.0}{}", weather.wind_deg, options.degree())
+                ),
+            ],
+        ),
+    );
+    options.sanitize(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sea(height: f64, period: f64) -> MarineData {
+        MarineData {
+            wave_height_m: Some(height),
+            wave_period_s: Some(period),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn ratings() {
+        assert_eq!(rate(&sea(0.2, 14.0), 5.0), Some(SurfRating::Flat));
+        assert_eq!(rate(&sea(1.8, 13.0), 8.0), Some(SurfRating::Epic));
+        assert_eq!(rate(&sea(1.8, 10.0), 20.0), Some(SurfRating::Good));
+        assert_eq!(rate(&sea(0.8, 7.0), 30.0), Some(SurfRating::Poor));
+        assert_eq!(rate(&MarineData::default(), 5.0), None);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::i18n::{tr, tr_args};
+use crate::output::OutputOptions;
+use crate::weather_providers::{DailyData, HourlyData};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+/// Changes in the daily mean below this many degrees count as steady.
+const STEADY_C: f64 = 1.0;
+/// Chance of precipitation in percent from which an hour counts as wet.
+const WET_PROBABILITY: f64 = 50.0;
+
+/// Where the weather is heading over the coming days.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rain {
+    Dry,
+    /// Dry now, wet from this hour on.
+    Arriving(NaiveDateTime),
+    /// Wet now, dry from this hour on.
+    Clearing(NaiveDateTime),
+    Lasting,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    /// Change of the daily mean temperature from the first to the last day.
+    pub temp_change_c: Option<f64>,
+    /// Mean temperature of the first day.
+    pub temp_now_c: Option<f64>,
+    pub rain: Rain,
+}
+
+fn mean(day: &DailyData) -> Option<f64> {
+    Some((day.temp_min_c? + day.temp_max_c?) / 2.0)
+}
+
+fn is_wet(hour: &HourlyData) -> bool {
+    hour.kind.is_rain() || hour.precipitation_probability.unwrap_or(0.0) >= WET_PROBABILITY
+}
+
+/// Digest of daily forecasts for the temperature and hourly ones for rain.
+pub fn trend(days: &[DailyData], hours: &[HourlyData]) -> Trend {
+    let temp_now_c = days.first().and_then(mean);
+    let temp_change_c = temp_now_c
+        .zip(days.last().and_then(mean))
+        .map(|(first, last)| last - first);
+
+    let rain = match hours.first() {
+        None => Rain::Dry,
+        Some(first) if is_wet(first) => hours
+            .iter()
+            .find(|hour| !is_wet(hour))
+            .map_or(Rain::Lasting, |hour| Rain::Clearing(hour.time)),
+        Some(_) => hours
+            .iter()
+            .find(|hour| is_wet(hour))
+            .map_or(Rain::Dry, |hour| Rain::Arriving(hour.time)),
+    };
+
+    Trend {
+        temp_change_c,
+        temp_now_c,
+        rain,
+    }
+}
+
+/// "Thursday evening", or "today"/"tomorrow" relative to `today`.
+fn when(time: NaiveDateTime, today: NaiveDate) -> String {
+    let day = match (time.date() - today).num_days() {
+        0 => tr("day-today"),
+        1 => tr("day-tomorrow"),
+        _ => tr(match time.weekday().num_days_from_monday() {
+            0 => "day-monday",
+            1 => "day-tuesday",
+            2 => "day-wednesday",
+            3 => "day-thursday",
+            4 => "day-friday",
+            5 => "day-saturday",
+            _ => "day-sunday",
+        }),
+    };
+    let part = tr(match time.hour() {
+        5..12 => "part-morning",
+        12..17 => "part-afternoon",
+        17..22 => "part-evening",
+        _ => "part-night",
+    });
+    tr_args("trend-when", &[("day", day), ("part", part)])
+}
+
+/// One paragraph, e.g. "Porto: cooling 6.0°C over the next 3 days, rain arriving Thursday evening."
+pub fn render_trend(
+    location: &str,
+    trend: &Trend,
+    days: u64,
+    today: NaiveDate,
+    options: &OutputOptions,
+) -> String {
+    let temp = match (trend.temp_change_c, trend.temp_now_c) {
+        (Some(change), _) if change >= STEADY_C => tr_args(
+            "trend-warming",
+            &[
+                ("change", options.temperature(change)),
+                ("days", days.to_string()),
+            ],
+        ),
+        (Some(change), _) if change <= -STEADY_C => tr_args(
+            "trend-cooling",
+            &[
+                ("change", options.temperature(-change)),
+                ("days", days.to_string()),
+            ],
+        ),
+        (Some(_), Some(now)) => tr_args(
+            "trend-steady",
+            &[
+                ("temp", options.temperature(now)),
+                ("days", days.to_string()),
+            ],
+        ),
+        _ => tr("trend-unknown"),
+    };
+    let rain = match &trend.rain {
+        Rain::Dry => tr("trend-dry"),
+        Rain::Arriving(time) => tr_args("trend-rain-arriving", &[("when", when(*time, today))]),
+        Rain::Clearing(time) => tr_args("trend-rain-clearing", &[("when", when(*time, today))]),
+        Rain::Lasting => tr("trend-rain-lasting"),
+    };
+    options.sanitize(&tr_args(
+        "trend-summary",
+        &[
+            ("location", location.to_string()),
+            ("temp", temp),
+            ("rain", rain),
+        ],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::condition::ConditionKind;
+
+    fn day(d: u32, min: f64, max: f64) -> DailyData {
+        DailyData {
+            date: NaiveDate::from_ymd_opt(2025, 3, d).unwrap(),
+            temp_min_c: Some(min),
+            temp_max_c: Some(max),
+            precipitation_mm: None,
+        }
+    }
+
+    fn hour(d: u32, h: u32, kind: ConditionKind) -> HourlyData {
+        HourlyData {
+            time: NaiveDate::from_ymd_opt(2025, 3, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap(),
+            kind,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cooling_with_rain_arriving() {
+        let days = [day(3, 8.0, 16.0), day(4, 6.0, 12.0), day(6, 2.0, 10.0)];
+        let hours = [
+            hour(3, 12, ConditionKind::Clear),
+            hour(4, 9, ConditionKind::Cloudy),
+            hour(6, 19, ConditionKind::Rain),
+        ];
+        let trend = trend(&days, &hours);
+
+        assert_eq!(trend.temp_change_c, Some(-6.0));
+        assert_eq!(trend.rain, Rain::Arriving(hours[2].time));
+        assert_eq!(
+            render_trend(
+                "Porto",
+                &trend,
+                3,
+                NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+                &OutputOptions::default()
+            ),
+            "Porto: cooling 6.0°C over the next 3 days, rain arriving Thursday evening."
+        );
+    }
+
+    #[test]
+    fn rain_clearing() {
+        let hours = [
+            hour(3, 8, ConditionKind::Drizzle),
+            hour(3, 13, ConditionKind::PartlyCloudy),
+        ];
+
+        assert_eq!(trend(&[], &hours).rain, Rain::Clearing(hours[1].time));
+    }
+}
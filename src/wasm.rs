@@ -0,0 +1,38 @@
+//! Entry points for JavaScript, built with `--target wasm32-unknown-unknown`.
+//!
+//! Requests go through the browser's `fetch`, so the provider has to allow cross-origin calls;
+//! Open-Meteo and WeatherAPI do.
+
+use crate::output::json::to_json;
+use crate::weather_providers::WeatherProvider;
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::openmeteo::OpenMeteo;
+use crate::weather_providers::openweather::OpenWeather;
+use crate::weather_providers::weatherapi::WeatherApi;
+use wasm_bindgen::prelude::*;
+
+fn provider(
+    name: &str,
+    api_key: Option<String>,
+) -> Result<Box<dyn WeatherProvider>, ProviderError> {
+    Ok(match name {
+        "openmeteo" => Box::new(OpenMeteo::new()?),
+        "openweather" => Box::new(OpenWeather::new(api_key)?),
+        "weatherapi" => Box::new(WeatherApi::new(api_key)?),
+        other => return Err(ProviderError::Error(format!("unknown provider '{other}'"))),
+    })
+}
+
+/// Current weather at `location` as the JSON of `weather --format json`,
+/// e.g. `await weather_fetch("openmeteo", "Porto", undefined)`.
+#[wasm_bindgen(js_name = weather_fetch)]
+pub async fn fetch(
+    provider_name: String,
+    location: String,
+    api_key: Option<String>,
+) -> Result<String, JsError> {
+    let data = provider(&provider_name, api_key)?
+        .fetch(&location, None)
+        .await?;
+    Ok(to_json(&data, &provider_name).to_string())
+}
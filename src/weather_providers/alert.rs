@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Provider-independent alert level, from least to most severe.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Be aware: conditions may cause inconvenience.
+    #[default]
+    Advisory,
+    /// Be prepared: hazardous conditions are possible.
+    Watch,
+    /// Take action: hazardous conditions are occurring or imminent.
+    Warning,
+    /// Threat to life and property.
+    Emergency,
+}
+
+impl Severity {
+    /// Map a CAP severity as used by NWS and WeatherAPI: `Minor`, `Moderate`, `Severe`, `Extreme`.
+    pub fn from_cap(severity: &str) -> Option<Self> {
+        match severity.trim().to_lowercase().as_str() {
+            "minor" => Some(Self::Advisory),
+            "moderate" => Some(Self::Watch),
+            "severe" => Some(Self::Warning),
+            "extreme" => Some(Self::Emergency),
+            _ => None,
+        }
+    }
+
+    /// Map a MeteoAlarm awareness level, e.g. "3; orange" or just "orange".
+    pub fn from_meteoalarm(level: &str) -> Option<Self> {
+        let level = level.to_lowercase();
+        let level = level.rsplit(';').next().unwrap_or(&level).trim();
+        match level {
+            "1" | "green" => Some(Self::Advisory),
+            "2" | "yellow" => Some(Self::Watch),
+            "3" | "orange" => Some(Self::Warning),
+            "4" | "red" => Some(Self::Emergency),
+            _ => None,
+        }
+    }
+
+    /// MeteoAlarm colour an event starts with, e.g. "Yellow Wind Warning".
+    fn from_colour(event: &str) -> Option<Self> {
+        let first = event.split_whitespace().next()?.to_lowercase();
+        match first.as_str() {
+            "green" | "yellow" | "orange" | "red" => Self::from_meteoalarm(&first),
+            _ => None,
+        }
+    }
+
+    /// Level named in an NWS-style event, e.g. "Flood Watch" or "Heat Advisory".
+    pub fn from_event(event: &str) -> Option<Self> {
+        let event = event.to_lowercase();
+        [
+            ("emergency", Self::Emergency),
+            ("warning", Self::Warning),
+            ("watch", Self::Watch),
+            ("advisory", Self::Advisory),
+            ("statement", Self::Advisory),
+        ]
+        .into_iter()
+        .find_map(|(word, severity)| event.contains(word).then_some(severity))
+    }
+
+    /// Level of an alert reporting `severity` for `event`. A MeteoAlarm colour, in either, comes
+    /// first, as every MeteoAlarm event is called a warning: "Yellow Wind Warning" is a watch.
+    /// Otherwise the event's own wording wins, as "Flood Watch" stays a watch whatever severity it
+    /// was filed under.
+    pub fn classify(severity: Option<&str>, event: &str) -> Self {
+        severity
+            .and_then(Self::from_meteoalarm)
+            .or_else(|| Self::from_colour(event))
+            .or_else(|| Self::from_event(event))
+            .or_else(|| severity.and_then(Self::from_cap))
+            .unwrap_or_default()
+    }
+}
+
+/// Official warning issued for a location.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeatherAlert {
+    /// Kind of hazard, e.g. "Excessive Heat Warning".
+    pub event: String,
+    pub severity: Severity,
+    /// One-line summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headline: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_wording_wins() {
+        assert_eq!(
+            Severity::classify(Some("Severe"), "Flood Watch"),
+            Severity::Watch
+        );
+        assert_eq!(
+            Severity::classify(Some("Extreme"), "Thunderstorm"),
+            Severity::Emergency
+        );
+        assert_eq!(
+            Severity::classify(Some("2; yellow"), "Wind Warning"),
+            Severity::Watch
+        );
+        assert_eq!(
+            Severity::classify(Some("Moderate"), "Red Rain Warning"),
+            Severity::Emergency
+        );
+        assert_eq!(
+            Severity::classify(Some("3; orange"), "Wind"),
+            Severity::Warning
+        );
+        assert_eq!(Severity::classify(None, "Fog"), Severity::Advisory);
+        assert!(Severity::Advisory < Severity::Emergency);
+    }
+}
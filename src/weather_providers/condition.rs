@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+
+/// Provider-independent category of the weather condition.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionKind {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Fog,
+    Drizzle,
+    Rain,
+    Sleet,
+    Snow,
+    Thunderstorm,
+    #[default]
+    Unknown,
+}
+
+impl ConditionKind {
+    /// Liquid precipitation of any strength, including thunderstorms and sleet.
+    pub fn is_rain(self) -> bool {
+        matches!(
+            self,
+            Self::Drizzle | Self::Rain | Self::Sleet | Self::Thunderstorm
+        )
+    }
+
+    /// Frozen precipitation.
+    pub fn is_snow(self) -> bool {
+        matches!(self, Self::Snow | Self::Sleet)
+    }
+
+    /// Map a WeatherAPI condition code, see <https://www.weatherapi.com/docs/weather_conditions.json>.
+    pub fn from_weatherapi_code(code: u32) -> Self {
+        WeatherCode::from_weatherapi(code).map_or(Self::Unknown, WeatherCode::kind)
+    }
+
+    /// Map a WMO weather interpretation code as used by Open-Meteo, see <https://open-meteo.com/en/docs>.
+    pub fn from_wmo_code(code: u8) -> Self {
+        WeatherCode::try_from(code).map_or(Self::Unknown, WeatherCode::kind)
+    }
+
+    /// Map an OpenWeather condition id, see <https://openweathermap.org/weather-conditions>.
+    pub fn from_openweather_id(id: u64) -> Self {
+        WeatherCode::from_openweather(id).map_or(Self::Unknown, WeatherCode::kind)
+    }
+}
+
+/// WMO weather interpretation code (WW), the shared vocabulary providers' conditions are mapped to.
+///
+/// Serialized as the numeric code, e.g. `61` for slight rain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+pub enum WeatherCode {
+    ClearSky = 0,
+    MainlyClear = 1,
+    PartlyCloudy = 2,
+    Overcast = 3,
+    Fog = 45,
+    RimeFog = 48,
+    LightDrizzle = 51,
+    Drizzle = 53,
+    DenseDrizzle = 55,
+    LightFreezingDrizzle = 56,
+    DenseFreezingDrizzle = 57,
+    SlightRain = 61,
+    Rain = 63,
+    HeavyRain = 65,
+    LightFreezingRain = 66,
+    HeavyFreezingRain = 67,
+    SlightSnow = 71,
+    Snow = 73,
+    HeavySnow = 75,
+    SnowGrains = 77,
+    SlightRainShowers = 80,
+    RainShowers = 81,
+    ViolentRainShowers = 82,
+    SlightSnowShowers = 85,
+    HeavySnowShowers = 86,
+    Thunderstorm = 95,
+    ThunderstormWithHail = 96,
+    ThunderstormWithHeavyHail = 99,
+}
+
+impl WeatherCode {
+    /// Category used for icons, colors and checks.
+    pub fn kind(self) -> ConditionKind {
+        use WeatherCode::*;
+        match self {
+            ClearSky | MainlyClear => ConditionKind::Clear,
+            PartlyCloudy => ConditionKind::PartlyCloudy,
+            Overcast => ConditionKind::Cloudy,
+            Fog | RimeFog => ConditionKind::Fog,
+            LightDrizzle | Drizzle | DenseDrizzle => ConditionKind::Drizzle,
+            LightFreezingDrizzle | DenseFreezingDrizzle | LightFreezingRain | HeavyFreezingRain => {
+                ConditionKind::Sleet
+            },
+            SlightRain | Rain | HeavyRain | SlightRainShowers | RainShowers
+            | ViolentRainShowers => ConditionKind::Rain,
+            SlightSnow | Snow | HeavySnow | SnowGrains | SlightSnowShowers | HeavySnowShowers => {
+                ConditionKind::Snow
+            },
+            Thunderstorm | ThunderstormWithHail | ThunderstormWithHeavyHail => {
+                ConditionKind::Thunderstorm
+            },
+        }
+    }
+
+    /// Map a WeatherAPI condition code; sleet and ice pellets count as freezing rain.
+    pub fn from_weatherapi(code: u32) -> Option<Self> {
+        use WeatherCode::*;
+        Some(match code {
+            1000 => ClearSky,
+            1003 => PartlyCloudy,
+            1006 | 1009 => Overcast,
+            1030 | 1135 => Fog,
+            1147 => RimeFog,
+            1150 | 1153 => LightDrizzle,
+            1072 | 1168 => LightFreezingDrizzle,
+            1171 => DenseFreezingDrizzle,
+            1180 | 1183 => SlightRain,
+            1186 | 1189 => Rain,
+            1192 | 1195 => HeavyRain,
+            1069 | 1198 | 1204 | 1237 | 1249 | 1261 => LightFreezingRain,
+            1201 | 1207 | 1252 | 1264 => HeavyFreezingRain,
+            1066 | 1210 | 1213 => SlightSnow,
+            1114 | 1216 | 1219 => Snow,
+            1117 | 1222 | 1225 => HeavySnow,
+            1063 | 1240 => SlightRainShowers,
+            1243 => RainShowers,
+            1246 => ViolentRainShowers,
+            1255 => SlightSnowShowers,
+            1258 => HeavySnowShowers,
+            1087 | 1273 | 1276 | 1279 | 1282 => Thunderstorm,
+            _ => return None,
+        })
+    }
+
+    /// Map an OpenWeather condition id; all atmosphere ids (mist, haze, dust, ...) count as fog.
+    pub fn from_openweather(id: u64) -> Option<Self> {
+        use WeatherCode::*;
+        Some(match id {
+            200..=299 => Thunderstorm,
+            300 | 310 => LightDrizzle,
+            301 | 311 | 313 | 321 => Drizzle,
+            302 | 312 | 314 => DenseDrizzle,
+            500 => SlightRain,
+            501 => Rain,
+            502..=504 => HeavyRain,
+            511 | 611 | 612 | 615 => LightFreezingRain,
+            613 | 616 => HeavyFreezingRain,
+            520 => SlightRainShowers,
+            521 => RainShowers,
+            522 | 531 => ViolentRainShowers,
+            600 => SlightSnow,
+            601 => Snow,
+            602 => HeavySnow,
+            620 | 621 => SlightSnowShowers,
+            622 => HeavySnowShowers,
+            700..=799 => Fog,
+            800 => ClearSky,
+            801 | 802 => PartlyCloudy,
+            803 | 804 => Overcast,
+            _ => return None,
+        })
+    }
+}
+
+impl From<WeatherCode> for u8 {
+    fn from(code: WeatherCode) -> Self {
+        code as u8
+    }
+}
+
+impl TryFrom<u8> for WeatherCode {
+    type Error = String;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        use WeatherCode::*;
+        Ok(match code {
+            0 => ClearSky,
+            1 => MainlyClear,
+            2 => PartlyCloudy,
+            3 => Overcast,
+            45 => Fog,
+            48 => RimeFog,
+            51 => LightDrizzle,
+            53 => Drizzle,
+            55 => DenseDrizzle,
+            56 => LightFreezingDrizzle,
+            57 => DenseFreezingDrizzle,
+            61 => SlightRain,
+            63 => Rain,
+            65 => HeavyRain,
+            66 => LightFreezingRain,
+            67 => HeavyFreezingRain,
+            71 => SlightSnow,
+            73 => Snow,
+            75 => HeavySnow,
+            77 => SnowGrains,
+            80 => SlightRainShowers,
+            81 => RainShowers,
+            82 => ViolentRainShowers,
+            85 => SlightSnowShowers,
+            86 => HeavySnowShowers,
+            95 => Thunderstorm,
+            96 => ThunderstormWithHail,
+            99 => ThunderstormWithHeavyHail,
+            _ => return Err(format!("unknown WMO weather code {code}")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wmo_codes() {
+        assert_eq!(ConditionKind::from_wmo_code(2), ConditionKind::PartlyCloudy);
+        assert_eq!(ConditionKind::from_wmo_code(66), ConditionKind::Sleet);
+        assert_eq!(ConditionKind::from_wmo_code(86), ConditionKind::Snow);
+        assert_eq!(ConditionKind::from_wmo_code(42), ConditionKind::Unknown);
+    }
+
+    #[test]
+    fn weatherapi_codes() {
+        assert_eq!(
+            ConditionKind::from_weatherapi_code(1000),
+            ConditionKind::Clear
+        );
+        assert_eq!(
+            ConditionKind::from_weatherapi_code(1003),
+            ConditionKind::PartlyCloudy
+        );
+        assert_eq!(
+            ConditionKind::from_weatherapi_code(1189),
+            ConditionKind::Rain
+        );
+        assert_eq!(
+            ConditionKind::from_weatherapi_code(1201),
+            ConditionKind::Sleet
+        );
+        assert_eq!(
+            ConditionKind::from_weatherapi_code(1282),
+            ConditionKind::Thunderstorm
+        );
+        assert_eq!(
+            ConditionKind::from_weatherapi_code(42),
+            ConditionKind::Unknown
+        );
+    }
+
+    #[test]
+    fn openweather_ids() {
+        assert_eq!(
+            ConditionKind::from_openweather_id(800),
+            ConditionKind::Clear
+        );
+        assert_eq!(
+            ConditionKind::from_openweather_id(511),
+            ConditionKind::Sleet
+        );
+        assert_eq!(ConditionKind::from_openweather_id(502), ConditionKind::Rain);
+        assert_eq!(ConditionKind::from_openweather_id(741), ConditionKind::Fog);
+        assert_eq!(
+            ConditionKind::from_openweather_id(804),
+            ConditionKind::Cloudy
+        );
+    }
+
+    #[test]
+    fn providers_agree_on_codes() {
+        assert_eq!(WeatherCode::from_weatherapi(1189), Some(WeatherCode::Rain));
+        assert_eq!(WeatherCode::from_openweather(501), Some(WeatherCode::Rain));
+        assert_eq!(WeatherCode::try_from(63), Ok(WeatherCode::Rain));
+        assert_eq!(
+            WeatherCode::from_weatherapi(1255).map(WeatherCode::kind),
+            Some(ConditionKind::Snow)
+        );
+        assert_eq!(serde_json::to_value(WeatherCode::Rain).unwrap(), 63);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::weather_providers::alert::{Severity, WeatherAlert};
+use crate::weather_providers::condition::{ConditionKind, WeatherCode};
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::{
+    Coordinates, DailyData, HourlyData, MarineData, PollenData, WeatherData, WeatherProvider,
+};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Timelike, Utc};
+use std::f64::consts::TAU;
+
+/// Offline provider inventing plausible weather, the same for the same location and hour.
+///
+/// Each location gets its own climate from a hash of its name; the hour adds seasonal and
+/// daily cycles plus some noise, so repeated queries and the different commands agree.
+#[derive(Debug, Default)]
+pub struct Demo;
+
+/// FNV-1a, stable across Rust versions unlike `DefaultHasher`.
+fn hash(text: &str) -> u64 {
+    text.trim()
+        .to_lowercase()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Uniform value in [0, 1) derived from `seed` and `salt` (splitmix64).
+fn uniform(seed: u64, salt: u64) -> f64 {
+    let mut z = seed.wrapping_add(salt.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A location's made-up climate.
+struct Climate {
+    seed: u64,
+    name: String,
+    coordinates: Coordinates,
+    offset: FixedOffset,
+    mean_c: f64,
+    seasonal_c: f64,
+}
+
+/// One invented hour.
+struct Hour {
+    time: NaiveDateTime,
+    temp_c: f64,
+    humidity: f64,
+    pressure: f64,
+    wind_kph: f64,
+    wind_deg: f64,
+    code: WeatherCode,
+    kind: ConditionKind,
+    precipitation_probability: f64,
+    precipitation_mm: f64,
+    uv_index: f64,
+}
+
+impl Climate {
+    fn of(location: &str) -> Result<Self, ProviderError> {
+        if location.trim().is_empty() {
+            return Err(ProviderError::InvalidLocation(location.to_string()));
+        }
+        let seed = hash(location);
+        let coordinates = location.parse().unwrap_or(Coordinates {
+            lat: -50.0 + 115.0 * uniform(seed, 1),
+            lon: -180.0 + 360.0 * uniform(seed, 2),
+        });
+        let offset = FixedOffset::east_opt((coordinates.lon / 15.0).round() as i32 * 3600)
+            .unwrap_or(FixedOffset::east_opt(0).unwrap());
+        Ok(Self {
+            seed,
+            name: location.trim().to_string(),
+            coordinates,
+            offset,
+            // Warmer and less seasonal towards the equator.
+            mean_c: 28.0 - 0.4 * coordinates.lat.abs() + 4.0 * uniform(seed, 3),
+            seasonal_c: (0.25 * coordinates.lat.abs()).min(15.0) * coordinates.lat.signum(),
+        })
+    }
+
+    /// The hour starting at `time`, in the location's local time.
+    fn hour(&self, time: NaiveDateTime) -> Hour {
+        let key = self.seed ^ (time.and_utc().timestamp() / 3600) as u64;
+        let day = f64::from(time.ordinal0());
+        let hour = f64::from(time.hour());
+
+        // Coldest in mid-January (north), warmest mid-afternoon.
+        let season = -(TAU * (day - 15.0) / 365.0).cos();
+        let daily = (TAU * (hour - 15.0) / 24.0).cos();
+        let temp_c = self.mean_c + self.seasonal_c * season + 4.0 * daily + 2.0 * uniform(key, 4);
+
+        let wetness = uniform(self.seed ^ (time.and_utc().timestamp() / 21_600) as u64, 5);
+        let code = match wetness {
+            w if w < 0.35 => WeatherCode::ClearSky,
+            w if w < 0.6 => WeatherCode::PartlyCloudy,
+            w if w < 0.75 => WeatherCode::Overcast,
+            w if w < 0.8 => WeatherCode::Fog,
+            w if w < 0.95 && temp_c < 0.0 => WeatherCode::SlightSnow,
+            w if w < 0.95 => WeatherCode::SlightRain,
+            _ if temp_c < 0.0 => WeatherCode::HeavySnow,
+            _ => WeatherCode::Thunderstorm,
+        };
+        let kind = code.kind();
+        let wet = kind.is_rain() || kind.is_snow();
+        let daylight = (TAU * (hour - 13.0) / 24.0).cos().max(0.0);
+
+        Hour {
+            time,
+            temp_c,
+            code,
+            humidity: (45.0 + 50.0 * wetness + 5.0 * uniform(key, 6)).min(100.0),
+            pressure: 1025.0 - 25.0 * wetness + 3.0 * uniform(key, 7),
+            wind_kph: 5.0 + 25.0 * wetness * uniform(key, 8) + 5.0 * uniform(self.seed, 9),
+            wind_deg: (360.0 * uniform(self.seed, 10) + 60.0 * uniform(key, 11)) % 360.0,
+            kind,
+            precipitation_probability: (100.0 * (wetness - 0.5) * 2.0).clamp(0.0, 100.0).round(),
+            precipitation_mm: if wet {
+                0.5 + 3.0 * uniform(key, 12)
+            } else {
+                0.0
+            },
+            uv_index: if kind == ConditionKind::Clear {
+                (daylight * (10.0 - 0.1 * self.coordinates.lat.abs())).max(0.0)
+            } else {
+                daylight * 2.0
+            },
+        }
+    }
+
+    fn local_now(&self) -> NaiveDateTime {
+        let now = Utc::now().with_timezone(&self.offset).naive_local();
+        now.with_minute(0)
+            .and_then(|t| t.with_second(0))
+            .unwrap_or(now)
+    }
+}
+
+fn describe(kind: ConditionKind) -> &'static str {
+    match kind {
+        ConditionKind::Clear => "Clear",
+        ConditionKind::PartlyCloudy => "Partly cloudy",
+        ConditionKind::Cloudy => "Overcast",
+        ConditionKind::Fog => "Fog",
+        ConditionKind::Drizzle => "Drizzle",
+        ConditionKind::Rain => "Rain",
+        ConditionKind::Sleet => "Sleet",
+        ConditionKind::Snow => "Snowfall",
+        ConditionKind::Thunderstorm => "Thunderstorm",
+        ConditionKind::Unknown => "Unknown",
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl WeatherProvider for Demo {
+    async fn fetch(
+        &self,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<WeatherData, ProviderError> {
+        let climate = Climate::of(location)?;
+        let local = date.unwrap_or_else(|| climate.local_now());
+        let hour = climate.hour(local);
+        let datetime = DateTime::<Utc>::from_naive_utc_and_offset(
+            local - chrono::Duration::seconds(i64::from(climate.offset.local_minus_utc())),
+            Utc,
+        );
+
+        Ok(WeatherData {
+            location: climate.name.clone(),
+            datetime,
+            temp_c: hour.temp_c,
+            humidity: hour.humidity,
+            pressure: hour.pressure,
+            condition: describe(hour.kind).to_string(),
+            kind: hour.kind,
+            code: Some(hour.code),
+            wind_kph: hour.wind_kph,
+            wind_deg: hour.wind_deg,
+            feels_like_c: Some(hour.temp_c - hour.wind_kph / 10.0),
+            uv_index: Some(hour.uv_index),
+            precipitation_probability: Some(hour.precipitation_probability),
+            visibility_km: Some(if hour.kind == ConditionKind::Fog {
+                0.4
+            } else {
+                20.0
+            }),
+            pressure_change_3h: None,
+            utc_offset: Some(climate.offset),
+            coordinates: Some(climate.coordinates),
+            snow: None,
+            pollen: None,
+            anomalies: Vec::new(),
+            sources: Default::default(),
+        })
+    }
+
+    async fn fetch_marine(&self, location: &str) -> Result<MarineData, ProviderError> {
+        let climate = Climate::of(location)?;
+        let hour = climate.hour(climate.local_now());
+        Ok(MarineData {
+            location: climate.name,
+            wave_height_m: Some(0.3 + hour.wind_kph / 12.0),
+            wave_period_s: Some(6.0 + 8.0 * uniform(climate.seed, 13)),
+            swell_direction_deg: Some(360.0 * uniform(climate.seed, 14)),
+        })
+    }
+
+    async fn fetch_pollen(&self, location: &str) -> Result<PollenData, ProviderError> {
+        let climate = Climate::of(location)?;
+        // Each group flowers in its own months: trees in spring, grass in early summer, weeds late.
+        let month = climate.local_now().month();
+        let season = |months: std::ops::RangeInclusive<u32>, peak: f64, salt| {
+            Some(if months.contains(&month) {
+                peak * uniform(climate.seed, salt)
+            } else {
+                0.0
+            })
+        };
+        Ok(PollenData {
+            tree: season(2..=5, 300.0, 15),
+            grass: season(5..=7, 150.0, 16),
+            weed: season(8..=9, 80.0, 17),
+        })
+    }
+
+    async fn fetch_daily(
+        &self,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        let climate = Climate::of(location)?;
+        Ok(start
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .map(|date| {
+                let hours: Vec<Hour> = (0..24)
+                    .filter_map(|h| date.and_hms_opt(h, 0, 0))
+                    .map(|time| climate.hour(time))
+                    .collect();
+                let temps = hours.iter().map(|h| h.temp_c);
+                DailyData {
+                    date,
+                    temp_min_c: temps.clone().reduce(f64::min),
+                    temp_max_c: temps.reduce(f64::max),
+                    precipitation_mm: Some(hours.iter().map(|h| h.precipitation_mm).sum()),
+                }
+            })
+            .collect())
+    }
+
+    async fn fetch_hourly(
+        &self,
+        location: &str,
+        hours: u32,
+    ) -> Result<Vec<HourlyData>, ProviderError> {
+        let climate = Climate::of(location)?;
+        let start = climate.local_now();
+        Ok((0..i64::from(hours))
+            .map(|i| climate.hour(start + chrono::Duration::hours(i)))
+            .map(|hour| HourlyData {
+                time: hour.time,
+                temp_c: Some(hour.temp_c),
+                feels_like_c: Some(hour.temp_c - hour.wind_kph / 10.0),
+                humidity: Some(hour.humidity),
+                precipitation_probability: Some(hour.precipitation_probability),
+                precipitation_mm: Some(hour.precipitation_mm),
+                // About a centimetre of snow per millimetre of water.
+                snowfall_cm: Some(if hour.kind.is_snow() {
+                    hour.precipitation_mm
+                } else {
+                    0.0
+                }),
+                freezing_level_m: None,
+                wind_kph: Some(hour.wind_kph),
+                uv_index: Some(hour.uv_index),
+                kind: hour.kind,
+            })
+            .collect())
+    }
+
+    async fn fetch_alerts(&self, location: &str) -> Result<Vec<WeatherAlert>, ProviderError> {
+        let climate = Climate::of(location)?;
+        let start = climate.local_now();
+        let hours: Vec<Hour> = (0..24)
+            .map(|i| climate.hour(start + chrono::Duration::hours(i)))
+            .collect();
+        let utc = |hour: &Hour| {
+            DateTime::<Utc>::from_naive_utc_and_offset(
+                hour.time - chrono::Duration::seconds(i64::from(climate.offset.local_minus_utc())),
+                Utc,
+            )
+        };
+
+        let mut alerts = Vec::new();
+        if let Some(storm) = hours.iter().find(|h| h.kind == ConditionKind::Thunderstorm) {
+            alerts.push(WeatherAlert {
+                event: "Thunderstorm Watch".to_string(),
+                severity: Severity::Watch,
+                headline: Some(format!("Thunderstorms possible in {}", climate.name)),
+                description: None,
+                effective: Some(utc(storm)),
+                expires: Some(utc(storm) + chrono::Duration::hours(6)),
+            });
+        }
+        if let Some(windy) = hours.iter().find(|h| h.wind_kph > 28.0) {
+            alerts.push(WeatherAlert {
+                event: "Wind Advisory".to_string(),
+                severity: Severity::Advisory,
+                headline: Some(format!("Gusts up to {:.0} km/h", windy.wind_kph * 1.5)),
+                description: None,
+                effective: Some(utc(windy)),
+                expires: Some(utc(windy) + chrono::Duration::hours(3)),
+            });
+        }
+        Ok(alerts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn same_location_same_weather() {
+        let date = NaiveDate::from_ymd_opt(2025, 7, 1)
+            .unwrap()
+            .and_hms_opt(15, 0, 0);
+        let porto = Demo.fetch("Porto", date).await.unwrap();
+        let again = Demo.fetch(" porto ", date).await.unwrap();
+        let oslo = Demo.fetch("Oslo", date).await.unwrap();
+
+        assert_eq!(porto.temp_c, again.temp_c);
+        assert_eq!(porto.kind, again.kind);
+        assert_ne!(porto.temp_c, oslo.temp_c);
+        assert!((-40.0..50.0).contains(&porto.temp_c), "{}", porto.temp_c);
+        assert!((0.0..=100.0).contains(&porto.humidity));
+    }
+
+    #[tokio::test]
+    async fn daily_matches_requested_range() {
+        let start = NaiveDate::from_ymd_opt(2025, 1, 30).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 2, 2).unwrap();
+        let days = Demo.fetch_daily("Porto", start, end).await.unwrap();
+
+        assert_eq!(days.len(), 4);
+        assert_eq!(days[3].date, end);
+        assert!(days[0].temp_min_c < days[0].temp_max_c);
+    }
+}
@@ -0,0 +1,161 @@
+//! Deprecation notices, announced by a provider through [`super::WeatherProvider::api_version`] or
+//! by the upstream API in response headers. Each is logged once per install and listed by
+//! `weather providers check-versions`.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use tracing::{debug, warn};
+
+/// Notices seen since the process started.
+static NOTICES: Mutex<BTreeSet<Notice>> = Mutex::new(BTreeSet::new());
+
+/// Something a provider said about retiring the API in use.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Notice {
+    pub provider: String,
+    pub message: String,
+}
+
+impl Display for Notice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.provider, self.message)
+    }
+}
+
+/// `Deprecation` value, `@<unix time>` as in RFC 9745 or `true` as in its drafts.
+fn deprecated_since(value: &str) -> Option<String> {
+    let since = value
+        .strip_prefix('@')
+        .and_then(|secs| secs.parse().ok())
+        .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0));
+    match since {
+        Some(since) => Some(format!("deprecated since {}", since.date_naive())),
+        None => (value != "false").then(|| "deprecated".to_string()),
+    }
+}
+
+/// Notice in the `Deprecation`, `Sunset` (RFC 8594) or `Warning: 299` headers of a response.
+pub fn from_headers(provider: &str, headers: &HeaderMap) -> Option<Notice> {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+    let mut parts = Vec::new();
+    parts.extend(header("deprecation").and_then(deprecated_since));
+    if let Some(sunset) = header("sunset") {
+        let date = DateTime::parse_from_rfc2822(sunset)
+            .map_or_else(|_| sunset.to_string(), |date| date.date_naive().to_string());
+        parts.push(format!("removed on {date}"));
+    }
+    // `299 <agent> "<text>"`, "miscellaneous persistent warning".
+    if let Some(warning) = header("warning").filter(|warning| warning.starts_with("299 ")) {
+        let text = warning.split('"').nth(1).unwrap_or(warning);
+        parts.push(text.to_string());
+    }
+    (!parts.is_empty()).then(|| Notice {
+        provider: provider.to_string(),
+        message: format!("API {}", parts.join("; ")),
+    })
+}
+
+/// Keep `notice` for [`notices`].
+pub fn record(notice: Notice) {
+    NOTICES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(notice);
+}
+
+/// Record a notice in the headers of a successful response, if there is one.
+pub(super) fn observe(provider: &str, headers: &HeaderMap) {
+    if let Some(notice) = from_headers(provider, headers) {
+        debug!("Deprecation notice in response: {notice}");
+        record(notice);
+    }
+}
+
+/// Notices seen since the process started, by provider.
+pub fn notices() -> Vec<Notice> {
+    NOTICES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// Platform data directory, e.g. `~/.local/share/weather/notices.json` on Linux.
+pub fn default_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from(".local/share"))
+        .join("weather")
+        .join("notices.json")
+}
+
+/// Log those of `notices` not logged by an earlier run, remembering them in `path`, and return
+/// them.
+pub fn warn_once(path: &Path, notices: Vec<Notice>) -> std::io::Result<Vec<Notice>> {
+    let mut seen: BTreeSet<Notice> = std::fs::read(path)
+        .ok()
+        .and_then(|content| serde_json::from_slice(&content).ok())
+        .unwrap_or_default();
+    let new: Vec<Notice> = notices
+        .into_iter()
+        .filter(|notice| !seen.contains(notice))
+        .collect();
+    if new.is_empty() {
+        return Ok(new);
+    }
+    for notice in &new {
+        warn!("{notice}; see `weather providers check-versions`");
+    }
+    seen.extend(new.iter().cloned());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_vec(&seen)?)?;
+    Ok(new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn notices_from_headers_warned_once() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(from_headers("p", &headers), None);
+        headers.insert("deprecation", HeaderValue::from_static("@1719792000"));
+        headers.insert(
+            "sunset",
+            HeaderValue::from_static("Tue, 31 Dec 2024 23:59:59 GMT"),
+        );
+        headers.insert(
+            "warning",
+            HeaderValue::from_static(r#"299 - "Use /v3 instead""#),
+        );
+        let notice = from_headers("p", &headers).unwrap();
+        assert_eq!(
+            notice.message,
+            "API deprecated since 2024-07-01; removed on 2024-12-31; Use /v3 instead"
+        );
+
+        let path = std::env::temp_dir()
+            .join(format!("weather-notices-{}", std::process::id()))
+            .join("notices.json");
+        assert_eq!(
+            warn_once(&path, vec![notice.clone()]).unwrap(),
+            vec![notice.clone()]
+        );
+        assert!(warn_once(&path, vec![notice]).unwrap().is_empty());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
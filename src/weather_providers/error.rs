@@ -1,15 +1,26 @@
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::sync::Arc;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+/// Clone, so a result can be handed to every caller waiting on the same request.
+#[derive(Debug, Clone, Error)]
 pub enum ProviderError {
     #[error("API request failed: {0}")]
-    Request(#[from] reqwest::Error),
+    Request(#[source] Arc<reqwest::Error>),
 
     #[error("API returned an error: {0}")]
     ApiRequest(String),
 
     #[error("Failed to parse API response: {0}")]
-    ParseResponse(#[from] serde_json::Error),
+    ParseResponse(#[source] Arc<serde_json::Error>),
+
+    #[error("{provider}: unexpected response at `{path}`: {message}")]
+    Schema {
+        provider: String,
+        path: String,
+        message: String,
+    },
 
     #[error("Failed to parse date/time: {0}")]
     ParseDateTime(String),
@@ -20,6 +31,238 @@ pub enum ProviderError {
     #[error("Location '{0}' is invalid or not found")]
     InvalidLocation(String),
 
+    #[error("{provider}: API key was rejected")]
+    Unauthorized { provider: String },
+
+    #[error("{provider}: request quota exceeded")]
+    QuotaExceeded { provider: String },
+
+    #[error("{provider}: location '{location}' not found")]
+    LocationNotFound { provider: String, location: String },
+
+    #[error("{provider}: request timed out")]
+    Timeout { provider: String },
+
+    #[error("{provider}: server error (HTTP {status})")]
+    ServerError { provider: String, status: u16 },
+
+    #[error("{provider}: network error: {message}")]
+    Network { provider: String, message: String },
+
+    #[error("{0} data is not available from this provider")]
+    Unsupported(&'static str),
+
     #[error("Provider error '{0}'")]
     Error(String),
 }
+
+/// Drops the request URL, which carries the API key.
+impl From<reqwest::Error> for ProviderError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Request(Arc::new(e.without_url()))
+    }
+}
+
+impl From<serde_json::Error> for ProviderError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::ParseResponse(Arc::new(e))
+    }
+}
+
+/// Error payload both WeatherAPI (`{"error": {"code", "message"}}`) and
+/// OpenWeather (`{"cod", "message"}`) send along with a failed status.
+#[derive(Debug, Default, Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    error: Option<ErrorDetail>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorDetail {
+    code: u32,
+    message: String,
+}
+
+/// WeatherAPI: "No matching location found."
+const WEATHERAPI_NO_LOCATION: u32 = 1006;
+/// WeatherAPI: "API key has exceeded calls per month quota."
+const WEATHERAPI_QUOTA_EXCEEDED: u32 = 2007;
+
+impl ProviderError {
+    /// Map a failed HTTP response to the matching variant.
+    pub fn from_status(provider: &str, location: &str, status: StatusCode, body: &str) -> Self {
+        let body: ErrorBody = serde_json::from_str(body).unwrap_or_default();
+        let code = body.error.as_ref().map(|e| e.code);
+
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized {
+                provider: provider.to_string(),
+            },
+            StatusCode::TOO_MANY_REQUESTS => Self::QuotaExceeded {
+                provider: provider.to_string(),
+            },
+            StatusCode::FORBIDDEN if code == Some(WEATHERAPI_QUOTA_EXCEEDED) => {
+                Self::QuotaExceeded {
+                    provider: provider.to_string(),
+                }
+            },
+            StatusCode::FORBIDDEN => Self::Unauthorized {
+                provider: provider.to_string(),
+            },
+            StatusCode::NOT_FOUND => Self::LocationNotFound {
+                provider: provider.to_string(),
+                location: location.to_string(),
+            },
+            StatusCode::BAD_REQUEST if code == Some(WEATHERAPI_NO_LOCATION) => {
+                Self::LocationNotFound {
+                    provider: provider.to_string(),
+                    location: location.to_string(),
+                }
+            },
+            status if status.is_server_error() => Self::ServerError {
+                provider: provider.to_string(),
+                status: status.as_u16(),
+            },
+            status => {
+                let message = body
+                    .error
+                    .map(|e| e.message)
+                    .or(body.message)
+                    .unwrap_or_else(|| status.to_string());
+                Self::ApiRequest(format!("{provider}: {message}"))
+            },
+        }
+    }
+
+    /// Map a transport-level failure, before any response was received, without the URL.
+    pub fn from_reqwest(provider: &str, error: reqwest::Error) -> Self {
+        let error = error.without_url();
+        if error.is_timeout() {
+            Self::Timeout {
+                provider: provider.to_string(),
+            }
+        } else if is_connect(&error) || error.is_request() {
+            Self::Network {
+                provider: provider.to_string(),
+                message: error.to_string(),
+            }
+        } else {
+            Self::from(error)
+        }
+    }
+
+    /// Short snake_case name of the failure, e.g. for metric labels.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Request(_) | Self::Network { .. } => "network",
+            Self::ApiRequest(_) | Self::Error(_) => "error",
+            Self::ParseResponse(_) | Self::Schema { .. } | Self::ParseDateTime(_) => "parse",
+            Self::InvalidApiKey(_) | Self::Unauthorized { .. } => "unauthorized",
+            Self::InvalidLocation(_) | Self::LocationNotFound { .. } => "not_found",
+            Self::QuotaExceeded { .. } => "quota",
+            Self::Timeout { .. } => "timeout",
+            Self::ServerError { .. } => "server_error",
+            Self::Unsupported(_) => "unsupported",
+        }
+    }
+
+    /// Whether trying again later may succeed.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::Timeout { .. }
+                | Self::ServerError { .. }
+                | Self::Network { .. }
+                | Self::Request(_)
+        )
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_connect(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+/// The browser does not tell connection failures apart.
+#[cfg(target_arch = "wasm32")]
+fn is_connect(_error: &reqwest::Error) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weatherapi_statuses() {
+        let not_found = r#"{"error":{"code":1006,"message":"No matching location found."}}"#;
+        assert!(matches!(
+            ProviderError::from_status("weatherapi", "Nowhere", StatusCode::BAD_REQUEST, not_found),
+            ProviderError::LocationNotFound { provider, location }
+                if provider == "weatherapi" && location == "Nowhere"
+        ));
+
+        let quota =
+            r#"{"error":{"code":2007,"message":"API key has exceeded calls per month quota."}}"#;
+        assert!(matches!(
+            ProviderError::from_status("weatherapi", "", StatusCode::FORBIDDEN, quota),
+            ProviderError::QuotaExceeded { .. }
+        ));
+
+        let bad_key = r#"{"error":{"code":2006,"message":"API key provided is invalid"}}"#;
+        assert!(matches!(
+            ProviderError::from_status("weatherapi", "", StatusCode::UNAUTHORIZED, bad_key),
+            ProviderError::Unauthorized { .. }
+        ));
+    }
+
+    #[test]
+    fn openweather_statuses() {
+        let not_found = r#"{"cod":"404","message":"city not found"}"#;
+        assert!(matches!(
+            ProviderError::from_status("openweather", "Nowhere", StatusCode::NOT_FOUND, not_found),
+            ProviderError::LocationNotFound { .. }
+        ));
+
+        assert!(matches!(
+            ProviderError::from_status("openweather", "", StatusCode::BAD_GATEWAY, ""),
+            ProviderError::ServerError { status: 502, .. }
+        ));
+
+        let other = r#"{"cod":"400","message":"wrong latitude"}"#;
+        let error = ProviderError::from_status("openweather", "", StatusCode::BAD_REQUEST, other);
+        assert_eq!(
+            error.to_string(),
+            "API returned an error: openweather: wrong latitude"
+        );
+    }
+
+    #[tokio::test]
+    async fn network_error_hides_api_key() {
+        // Nothing listens on port 1.
+        let error = reqwest::get("http://127.0.0.1:1/v1?key=secret&q=Porto")
+            .await
+            .unwrap_err();
+        let error = ProviderError::from_reqwest("weatherapi", error);
+        assert!(matches!(error, ProviderError::Network { .. }));
+        assert!(!error.to_string().contains("secret"), "{error}");
+    }
+
+    #[test]
+    fn transient_errors() {
+        assert!(
+            ProviderError::Timeout {
+                provider: "p".to_string()
+            }
+            .is_transient()
+        );
+        assert!(
+            !ProviderError::Unauthorized {
+                provider: "p".to_string()
+            }
+            .is_transient()
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Real provider responses kept in `tests/fixtures`, recorded by `weather dev record-fixture`
+//! and replayed through the providers' mappings to catch upstream schema drift.
+
+use crate::output::slug;
+use crate::weather_providers::raw::RawResponse;
+use std::path::{Path, PathBuf};
+
+/// Where fixtures are kept, relative to the repository root.
+pub const FIXTURE_DIR: &str = "tests/fixtures";
+
+/// Endpoint a response came from, the last segment of its URL path without an extension, e.g.
+/// "forecast" or "current" for `.../v1/current.json`.
+fn endpoint(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            let segment = url.path_segments()?.next_back()?;
+            let name = segment.split('.').next().unwrap_or(segment);
+            (!name.is_empty()).then(|| name.to_string())
+        })
+        .unwrap_or_else(|| "response".to_string())
+}
+
+/// `<dir>/<provider>/<endpoint>-<location>.json` for `response` to a request for `location`.
+pub fn fixture_path(dir: &Path, response: &RawResponse, location: &str) -> PathBuf {
+    dir.join(&response.provider).join(format!(
+        "{}-{}.json",
+        endpoint(&response.url),
+        slug(location)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::{openmeteo, openweather, weatherapi};
+    use serde_json::Value;
+    use std::fs;
+
+    #[test]
+    fn named_by_provider_endpoint_and_location() {
+        let response = RawResponse {
+            provider: "weatherapi".to_string(),
+            url: "https://api.weatherapi.com/v1/current.json?key=***&q=Porto".to_string(),
+            status: 200,
+            body: Value::Null,
+        };
+        assert_eq!(
+            fixture_path(Path::new(FIXTURE_DIR), &response, "Porto, PT"),
+            Path::new("tests/fixtures/weatherapi/current-porto_pt.json")
+        );
+    }
+
+    #[test]
+    fn every_fixture_maps() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURE_DIR);
+        let mut failures = Vec::new();
+        let mut checked = 0;
+        for dir in fs::read_dir(&root).unwrap().flatten() {
+            let provider = dir.file_name().to_string_lossy().into_owned();
+            for file in fs::read_dir(dir.path()).unwrap().flatten() {
+                let path = file.path();
+                let body = fs::read_to_string(&path).unwrap();
+                // Location slugs have no hyphens, endpoints such as "air-quality" may.
+                let stem = path.file_stem().unwrap().to_string_lossy();
+                let endpoint = stem
+                    .rsplit_once('-')
+                    .map_or(&*stem, |(endpoint, _)| endpoint);
+                let mapped = match provider.as_str() {
+                    "openmeteo" => openmeteo::map_fixture(endpoint, &body),
+                    "openweather" => openweather::map_fixture(endpoint, &body),
+                    "weatherapi" => weatherapi::map_fixture(endpoint, &body),
+                    _ => panic!("no mapping for provider {provider}"),
+                };
+                if let Err(e) = mapped {
+                    failures.push(format!("{}: {e}", path.display()));
+                }
+                checked += 1;
+            }
+        }
+        assert!(checked > 0, "no fixtures in {}", root.display());
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::weather_providers::Coordinates;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Where a location string led a provider: its display name, position and, for providers
+/// addressing places by their own ids, that id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Place {
+    pub name: String,
+    pub coordinates: Coordinates,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    #[serde(flatten)]
+    place: Place,
+    resolved_at: DateTime<Utc>,
+}
+
+/// Location lookups kept in a JSON file, so a place is looked up once a `ttl` rather than on
+/// every request.
+///
+/// Places rarely move, so the `ttl` is long; the file is rewritten after each new lookup.
+#[derive(Debug)]
+pub struct GeocodeCache {
+    path: PathBuf,
+    ttl: Duration,
+    entries: Mutex<Option<HashMap<String, Entry>>>,
+}
+
+impl GeocodeCache {
+    pub fn new(path: PathBuf, ttl: Duration) -> Self {
+        Self {
+            path,
+            ttl,
+            entries: Mutex::new(None),
+        }
+    }
+
+    /// `geocode.json` in the cache directory `dir`.
+    pub fn in_dir(dir: &Path, ttl: Duration) -> Self {
+        Self::new(dir.join("geocode.json"), ttl)
+    }
+
+    /// Key for `location` as looked up by `provider`; case and spacing do not matter.
+    pub fn key(provider: &str, language: Option<&str>, location: &str) -> String {
+        let location = location
+            .split(',')
+            .map(|part| part.trim().to_lowercase())
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{provider}:{}:{location}", language.unwrap_or(""))
+    }
+
+    /// Entries of the file, read on first use; a missing or corrupt file counts as empty.
+    fn with_entries<T>(&self, f: impl FnOnce(&mut HashMap<String, Entry>) -> T) -> T {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let entries = entries.get_or_insert_with(|| match std::fs::read(&self.path) {
+            Ok(content) => serde_json::from_slice(&content)
+                .inspect_err(|e| warn!("Ignoring corrupt {}: {e}", self.path.display()))
+                .unwrap_or_default(),
+            Err(_) => HashMap::new(),
+        });
+        f(entries)
+    }
+
+    pub fn get(&self, key: &str) -> Option<Place> {
+        let place = self.with_entries(|entries| {
+            entries
+                .get(key)
+                .filter(|e| {
+                    (Utc::now() - e.resolved_at)
+                        .to_std()
+                        .is_ok_and(|age| age < self.ttl)
+                })
+                .map(|e| e.place.clone())
+        });
+        debug!(
+            "Geocode cache {} for {key}",
+            if place.is_some() { "hit" } else { "miss" }
+        );
+        place
+    }
+
+    pub fn put(&self, key: &str, place: Place) {
+        let content = self.with_entries(|entries| {
+            entries.insert(
+                key.to_string(),
+                Entry {
+                    place,
+                    resolved_at: Utc::now(),
+                },
+            );
+            serde_json::to_vec(entries)
+        });
+        let saved = content.map_err(std::io::Error::from).and_then(|content| {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&self.path, content)
+        });
+        if let Err(e) = saved {
+            warn!("Failed to save {}: {e}", self.path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kept_across_instances_until_expired() {
+        let dir = std::env::temp_dir().join(format!("weather-geocode-{}", std::process::id()));
+        let porto = Place {
+            name: "Porto, Portugal".to_string(),
+            coordinates: Coordinates {
+                lat: 41.15,
+                lon: -8.61,
+            },
+            id: None,
+        };
+        let key = GeocodeCache::key("openmeteo", None, "Porto, PT");
+        assert_eq!(key, GeocodeCache::key("openmeteo", None, " porto,pt"));
+
+        GeocodeCache::in_dir(&dir, Duration::from_secs(60)).put(&key, porto.clone());
+        let hit = GeocodeCache::in_dir(&dir, Duration::from_secs(60)).get(&key);
+        let expired = GeocodeCache::in_dir(&dir, Duration::ZERO).get(&key);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(hit, Some(porto));
+        assert_eq!(expired, None);
+    }
+}
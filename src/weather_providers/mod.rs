@@ -1,15 +1,164 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
 
+pub mod alert;
+pub mod condition;
+#[cfg(feature = "demo")]
+pub mod demo;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod deprecation;
 pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fixtures;
+pub mod geocode;
+pub mod openmeteo;
 pub mod openweather;
+pub mod parse;
+pub mod radar;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rate_limit;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod raw;
+pub mod satellite;
 pub mod weatherapi;
 
+use crate::anomaly::Anomaly;
+use crate::output::{OutputOptions, render_text};
+use crate::weather_providers::alert::WeatherAlert;
+use crate::weather_providers::condition::{ConditionKind, WeatherCode};
 use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::parse::{ParseMode, parse};
+
+/// Target of the warnings that mean the data shown is degraded, e.g. defaults used for fields a
+/// provider left out: `warn!(target: QUALITY, ...)`. Only these fail `--strict`.
+pub const QUALITY: &str = "weather::quality";
+
+/// Location asked for by the default [`WeatherProvider::ping`], in a form every provider accepts.
+const PING_LOCATION: &str = "51.5074,-0.1278";
+
+/// Upper bound for a single provider request.
+#[cfg(not(target_arch = "wasm32"))]
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HTTP client shared by the providers' constructors.
+fn http_client() -> Result<reqwest::Client, ProviderError> {
+    let builder = reqwest::Client::builder();
+    // In the browser `fetch` applies its own limits.
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = builder.timeout(REQUEST_TIMEOUT);
+    builder
+        .build()
+        .map_err(|e| ProviderError::Error(format!("Failed to build HTTP client: {e}")))
+}
+
+/// Send `request`, mapping transport failures and non-success statuses to [`ProviderError`].
+async fn send(
+    provider: &str,
+    location: &str,
+    request: reqwest::RequestBuilder,
+) -> Result<reqwest::Response, ProviderError> {
+    let res = request
+        .send()
+        .await
+        .map_err(|e| ProviderError::from_reqwest(provider, e))?;
+
+    let status = res.status();
+    if status.is_success() {
+        #[cfg(not(target_arch = "wasm32"))]
+        deprecation::observe(provider, res.headers());
+        return Ok(res);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let url = res.url().clone();
+    let body = res.text().await.unwrap_or_default();
+    #[cfg(not(target_arch = "wasm32"))]
+    raw::record(provider, &url, status, &body);
+    Err(ProviderError::from_status(
+        provider, location, status, &body,
+    ))
+}
+
+/// Body of a successful response, deserialized according to `mode`.
+async fn json<T: serde::de::DeserializeOwned>(
+    provider: &str,
+    res: reqwest::Response,
+    mode: ParseMode,
+) -> Result<T, ProviderError> {
+    let body = text(provider, res).await?;
+    parse(provider, &body, mode)
+}
+
+/// Body of a successful response, kept for `--raw`.
+async fn text(provider: &str, res: reqwest::Response) -> Result<String, ProviderError> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let (url, status) = (res.url().clone(), res.status());
+    let body = res.text().await?;
+    #[cfg(not(target_arch = "wasm32"))]
+    raw::record(provider, &url, status, &body);
+    #[cfg(target_arch = "wasm32")]
+    let _ = provider;
+    Ok(body)
+}
+
+/// `url` with the value of query parameter `key` masked, for showing it without the secret.
+fn redact(url: &reqwest::Url, key: &str) -> String {
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            let v = if k == key {
+                "***".into()
+            } else {
+                v.into_owned()
+            };
+            (k.into_owned(), v)
+        })
+        .collect();
+    let mut url = url.clone();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+    url.to_string()
+}
+
+/// Set query parameters from `[providers.<name>] options` on `url`, replacing the provider's own.
+fn set_options(url: &mut reqwest::Url, options: &[(String, String)]) {
+    if options.is_empty() {
+        return;
+    }
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| !options.iter().any(|(key, _)| key == k))
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .chain(options.iter().cloned())
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(pairs);
+}
+
+/// `request` with [`set_options`] applied to its URL.
+fn with_options(
+    request: reqwest::RequestBuilder,
+    options: &[(String, String)],
+) -> reqwest::RequestBuilder {
+    if options.is_empty() {
+        return request;
+    }
+    match request.build_split() {
+        (client, Ok(mut request)) => {
+            set_options(request.url_mut(), options);
+            reqwest::RequestBuilder::from_parts(client, request)
+        },
+        // Only an invalid URL fails to build, and it fails again when sent.
+        (client, Err(e)) => client.get(e.url().map_or("", reqwest::Url::as_str)),
+    }
+}
 
 /// Represents the weather information for a specific location.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct WeatherData {
     /// The name of the city or location.
     pub location: String,
@@ -23,45 +172,464 @@ pub struct WeatherData {
     pub pressure: f64,
     /// A short textual description of the weather condition (e.g., "Sunny", "Cloudy").
     pub condition: String,
+    /// Provider-independent category of the condition.
+    pub kind: ConditionKind,
+    /// The condition as a WMO weather code, when the provider's code maps to one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<WeatherCode>,
     /// Wind speed in kilometers per hour.
     pub wind_kph: f64,
     /// Wind direction in degrees (meteorological standard, 0–360°).
     pub wind_deg: f64,
+    /// Apparent temperature in Celsius, accounting for wind and humidity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feels_like_c: Option<f64>,
+    /// UV index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv_index: Option<f64>,
+    /// Chance of precipitation (0–100%), from forecasts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precipitation_probability: Option<f64>,
+    /// How far one can see, in kilometers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility_km: Option<f64>,
+    /// Change of the pressure in hPa over the last 3 hours, reported by the provider or derived
+    /// from an earlier reading.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pressure_change_3h: Option<f64>,
+    /// UTC offset of the location at `datetime`; `None` falls back to the machine's local zone.
+    #[serde(default, with = "offset_seconds")]
+    pub utc_offset: Option<FixedOffset>,
+    /// Position of the location, when the provider reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    /// Snow conditions, from providers that report them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snow: Option<SnowData>,
+    /// Pollen in the air, when asked for and the provider reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pollen: Option<PollenData>,
+    /// Ways this reading stands out from the location's recorded history for the season.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub anomalies: Vec<Anomaly>,
+    /// Provider of each field filled in from a secondary provider, by field name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<String, String>,
 }
 
-impl Display for WeatherData {
+/// Snow conditions; each field is `None` when the provider does not report it.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnowData {
+    /// Snow on the ground.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth_cm: Option<f64>,
+    /// Snowfall over the last hour.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fresh_cm: Option<f64>,
+    /// Altitude of the 0 °C isotherm.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freezing_level_m: Option<f64>,
+}
+
+/// Pollen concentration in grains/m³ by plant group; each field is `None` when not reported.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PollenData {
+    /// Trees, e.g. alder, birch or olive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tree: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grass: Option<f64>,
+    /// Weeds, e.g. mugwort or ragweed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weed: Option<f64>,
+}
+
+impl PollenData {
+    /// Whether no group was reported, as for locations outside a pollen model's area.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_none() && self.grass.is_none() && self.weed.is_none()
+    }
+}
+
+/// Sea state at a coastal location; each field is `None` when not reported.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarineData {
+    /// Resolved name of the location.
+    pub location: String,
+    /// Significant height of wind waves and swell combined.
+    pub wave_height_m: Option<f64>,
+    /// Period between wave crests.
+    pub wave_period_s: Option<f64>,
+    /// Direction the swell comes from (0–360°).
+    pub swell_direction_deg: Option<f64>,
+}
+
+/// Summary of one day; each value is `None` when the provider has no record of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyData {
+    pub date: NaiveDate,
+    pub temp_min_c: Option<f64>,
+    pub temp_max_c: Option<f64>,
+    /// Total precipitation over the day.
+    pub precipitation_mm: Option<f64>,
+}
+
+/// Forecast for one hour; each value is `None` when the provider does not report it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HourlyData {
+    /// Start of the hour in the location's local time.
+    pub time: NaiveDateTime,
+    pub temp_c: Option<f64>,
+    /// Apparent temperature, accounting for wind and humidity.
+    pub feels_like_c: Option<f64>,
+    /// Relative humidity (0–100%).
+    pub humidity: Option<f64>,
+    /// Chance of precipitation (0–100%).
+    pub precipitation_probability: Option<f64>,
+    pub precipitation_mm: Option<f64>,
+    /// Snow falling over the hour, part of `precipitation_mm` as water.
+    #[serde(default)]
+    pub snowfall_cm: Option<f64>,
+    /// Altitude of the 0 °C isotherm.
+    #[serde(default)]
+    pub freezing_level_m: Option<f64>,
+    pub wind_kph: Option<f64>,
+    pub uv_index: Option<f64>,
+    pub kind: ConditionKind,
+}
+
+/// 10th percentile, median and 90th percentile of a set of values, e.g. the members of an
+/// ensemble forecast.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Spread {
+    pub p10: f64,
+    pub median: f64,
+    pub p90: f64,
+}
+
+impl Spread {
+    /// Spread of `values`, interpolating between neighbours; `None` when there are none.
+    pub fn of(values: &[f64]) -> Option<Self> {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let last = sorted.len().checked_sub(1)?;
+        let at = |q: f64| {
+            let i = q * last as f64;
+            let (lo, hi) = (sorted[i.floor() as usize], sorted[i.ceil() as usize]);
+            lo + (hi - lo) * i.fract()
+        };
+        Some(Self {
+            p10: at(0.1),
+            median: at(0.5),
+            p90: at(0.9),
+        })
+    }
+}
+
+/// Ensemble forecast for one hour: how far the runs of the forecast model disagree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnsembleHour {
+    /// Start of the hour in the location's local time.
+    pub time: NaiveDateTime,
+    pub temp_c: Option<Spread>,
+    pub precipitation_mm: Option<Spread>,
+}
+
+/// Geographic position in decimal degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Coordinates {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl Display for Coordinates {
+    /// "lat,lon", the form providers accept as a location.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Weather in {}: {} {}\n> Date: {}\n> Temperature: {:.1}°C\n> Humidity: {:.1}%\n> Pressure: {:.1} hPa\n> Wind: {:.1} km/h at {:.1}°",
-            self.location,
-            self.condition,
-            get_temperature_emoji(self.temp_c),
-            self.datetime.with_timezone(&Local),
-            self.temp_c,
-            self.humidity,
-            self.pressure,
-            self.wind_kph,
-            self.wind_deg
-        )
+        write!(f, "{:.4},{:.4}", self.lat, self.lon)
+    }
+}
+
+impl std::str::FromStr for Coordinates {
+    type Err = ();
+
+    /// Parse "lat,lon" as written by [`Display`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (lat, lon) = s.split_once(',').ok_or(())?;
+        Ok(Self {
+            lat: lat.trim().parse().map_err(|_| ())?,
+            lon: lon.trim().parse().map_err(|_| ())?,
+        })
+    }
+}
+
+/// (De)serialize a UTC offset as seconds east of UTC.
+mod offset_seconds {
+    use chrono::FixedOffset;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        offset: &Option<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match offset {
+            Some(offset) => serializer.serialize_some(&offset.local_minus_utc()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FixedOffset>, D::Error> {
+        Ok(Option::<i32>::deserialize(deserializer)?.and_then(FixedOffset::east_opt))
     }
 }
 
-fn get_temperature_emoji(temperature: f64) -> &'static str {
-    match temperature {
-        t if t < 0.0 => "❄️",
-        t if (0.0..10.0).contains(&t) => "☁️",
-        t if (10.0..20.0).contains(&t) => "⛅",
-        t if (20.0..30.0).contains(&t) => "🌤️",
-        _ => "🔥",
+/// Set `slot` to `value` when it is empty and `value` is not, telling whether it was.
+fn fill<T>(slot: &mut Option<T>, value: Option<T>) -> bool {
+    let filled = slot.is_none() && value.is_some();
+    if filled {
+        *slot = value;
     }
+    filled
 }
 
-#[async_trait]
+impl WeatherData {
+    /// Optional fields the provider left out, by name. Snow and the pressure tendency are not
+    /// among them: they are also absent without snow or earlier readings, not only for lack of data.
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        [
+            ("feels_like_c", self.feels_like_c.is_none()),
+            ("uv_index", self.uv_index.is_none()),
+            (
+                "precipitation_probability",
+                self.precipitation_probability.is_none(),
+            ),
+            ("visibility_km", self.visibility_km.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(name, missing)| missing.then_some(name))
+        .collect()
+    }
+
+    /// Take the [`WeatherData::missing_fields`] from `other`, a reading of the same place by
+    /// `provider`, and note it as their source.
+    pub fn fill_from(&mut self, other: WeatherData, provider: &str) {
+        let filled = [
+            (
+                "feels_like_c",
+                fill(&mut self.feels_like_c, other.feels_like_c),
+            ),
+            ("uv_index", fill(&mut self.uv_index, other.uv_index)),
+            (
+                "precipitation_probability",
+                fill(
+                    &mut self.precipitation_probability,
+                    other.precipitation_probability,
+                ),
+            ),
+            (
+                "visibility_km",
+                fill(&mut self.visibility_km, other.visibility_km),
+            ),
+        ];
+        for (name, _) in filled.into_iter().filter(|(_, filled)| *filled) {
+            self.sources.insert(name.to_string(), provider.to_string());
+        }
+    }
+
+    /// Show `datetime` in the given timezone instead of the location's own.
+    pub fn apply_timezone(&mut self, tz: Tz) {
+        self.utc_offset = Some(
+            tz.offset_from_utc_datetime(&self.datetime.naive_utc())
+                .fix(),
+        );
+    }
+
+    /// The recorded date and time in the location's timezone.
+    pub fn local_datetime(&self) -> DateTime<FixedOffset> {
+        let offset = self.utc_offset.unwrap_or_else(|| {
+            Local
+                .offset_from_utc_datetime(&self.datetime.naive_utc())
+                .fix()
+        });
+        self.datetime.with_timezone(&offset)
+    }
+}
+
+impl Display for WeatherData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&render_text(self, &OutputOptions::default()))
+    }
+}
+
+/// Version of the upstream API a provider talks to, for `weather providers check-versions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiVersion {
+    pub version: &'static str,
+    /// What the provider has announced about retiring this version, if anything.
+    pub deprecation: Option<&'static str>,
+}
+
+// Browser futures are not `Send`.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 pub trait WeatherProvider: Send + Sync {
     async fn fetch(
         &self,
         location: &str,
         date: Option<NaiveDateTime>,
     ) -> Result<WeatherData, ProviderError>;
+
+    /// Current sea state, for providers with marine data.
+    async fn fetch_marine(&self, _location: &str) -> Result<MarineData, ProviderError> {
+        Err(ProviderError::Unsupported("Marine"))
+    }
+
+    /// Current pollen concentration, for providers with air quality data.
+    async fn fetch_pollen(&self, _location: &str) -> Result<PollenData, ProviderError> {
+        Err(ProviderError::Unsupported("Pollen"))
+    }
+
+    /// Daily summaries from `start` to `end`, both included, for providers with history.
+    async fn fetch_daily(
+        &self,
+        _location: &str,
+        _start: NaiveDate,
+        _end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        Err(ProviderError::Unsupported("Daily history"))
+    }
+
+    /// Daily values projected by a climate model from `start` to `end`, both included, for
+    /// providers with climate projections.
+    async fn fetch_climate(
+        &self,
+        _location: &str,
+        _start: NaiveDate,
+        _end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        Err(ProviderError::Unsupported("Climate projection"))
+    }
+
+    /// Forecast for the next `hours` hours, starting with the current one.
+    async fn fetch_hourly(
+        &self,
+        _location: &str,
+        _hours: u32,
+    ) -> Result<Vec<HourlyData>, ProviderError> {
+        Err(ProviderError::Unsupported("Hourly forecast"))
+    }
+
+    /// Spread of the ensemble forecast for the next `hours` hours, for providers running ensembles.
+    async fn fetch_ensemble(
+        &self,
+        _location: &str,
+        _hours: u32,
+    ) -> Result<Vec<EnsembleHour>, ProviderError> {
+        Err(ProviderError::Unsupported("Ensemble forecast"))
+    }
+
+    /// Official warnings currently in effect, for providers with alert feeds.
+    async fn fetch_alerts(&self, _location: &str) -> Result<Vec<WeatherAlert>, ProviderError> {
+        Err(ProviderError::Unsupported("Alert"))
+    }
+
+    /// URLs [`WeatherProvider::fetch`] would request, with API keys masked; empty for offline providers.
+    fn plan(
+        &self,
+        _location: &str,
+        _date: Option<NaiveDateTime>,
+    ) -> Result<Vec<String>, ProviderError> {
+        Ok(Vec::new())
+    }
+
+    /// Check that the provider is reachable and accepts its credentials.
+    async fn ping(&self) -> Result<(), ProviderError> {
+        self.fetch(PING_LOCATION, None).await.map(|_| ())
+    }
+
+    /// Upstream API version in use; `None` for offline providers.
+    fn api_version(&self) -> Option<ApiVersion> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_timezone_overrides_location_offset() {
+        let mut data = WeatherData {
+            datetime: Utc.with_ymd_and_hms(2025, 12, 5, 8, 15, 0).unwrap(),
+            utc_offset: FixedOffset::east_opt(9 * 3600),
+            ..Default::default()
+        };
+        assert_eq!(
+            data.local_datetime().to_string(),
+            "2025-12-05 17:15:00 +09:00"
+        );
+
+        data.apply_timezone(chrono_tz::Europe::Kyiv);
+        assert_eq!(
+            data.local_datetime().to_string(),
+            "2025-12-05 10:15:00 +02:00"
+        );
+    }
+
+    #[test]
+    fn spread_interpolates_percentiles() {
+        let values: Vec<f64> = (0..=10).rev().map(f64::from).collect();
+        let spread = Spread::of(&values).unwrap();
+        assert_eq!((spread.p10, spread.median, spread.p90), (1.0, 5.0, 9.0));
+
+        let spread = Spread::of(&[2.0, 4.0]).unwrap();
+        assert!((spread.p10 - 2.2).abs() < 1e-9);
+        assert_eq!(spread.median, 3.0);
+        assert_eq!(Spread::of(&[]), None);
+    }
+
+    #[test]
+    fn redacts_api_key() {
+        let url = reqwest::Url::parse("https://example.com/v1?key=secret&q=Porto").unwrap();
+        assert_eq!(
+            redact(&url, "key"),
+            "https://example.com/v1?key=***&q=Porto"
+        );
+    }
+
+    #[test]
+    fn options_replace_parameters() {
+        let mut url = reqwest::Url::parse("https://example.com/v1?q=Porto&aqi=no").unwrap();
+        set_options(
+            &mut url,
+            &[
+                ("aqi".to_string(), "yes".to_string()),
+                ("units".to_string(), "metric".to_string()),
+            ],
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://example.com/v1?q=Porto&aqi=yes&units=metric"
+        );
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let data = WeatherData {
+            location: "Tokyo, Japan".to_string(),
+            datetime: Utc.with_ymd_and_hms(2025, 12, 5, 8, 15, 0).unwrap(),
+            kind: ConditionKind::Rain,
+            utc_offset: FixedOffset::east_opt(9 * 3600),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(&data).unwrap();
+        assert_eq!(json["utc_offset"], 32400);
+        assert_eq!(json["kind"], "rain");
+
+        let back: WeatherData = serde_json::from_value(json).unwrap();
+        assert_eq!(back.utc_offset, data.utc_offset);
+        assert_eq!(back.datetime, data.datetime);
+    }
 }
@@ -0,0 +1,1226 @@
+use crate::pressure;
+use crate::weather_providers::condition::{ConditionKind, WeatherCode};
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::geocode::{GeocodeCache, Place};
+use crate::weather_providers::parse::ParseMode;
+use crate::weather_providers::{
+    ApiVersion, Coordinates, DailyData, EnsembleHour, HourlyData, MarineData, PollenData, SnowData,
+    Spread, WeatherData, WeatherProvider, http_client, json, send, with_options,
+};
+use chrono::{DateTime, Days, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+use reqwest::Url;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::debug;
+
+const PROVIDER: &str = "openmeteo";
+
+/// Variables requested for current and hourly conditions.
+const VARIABLES: &str = "temperature_2m,apparent_temperature,relative_humidity_2m,surface_pressure,\
+weather_code,wind_speed_10m,wind_direction_10m,uv_index,precipitation_probability,snow_depth,\
+snowfall,freezing_level_height,visibility";
+
+/// Variables requested from the marine API.
+const MARINE_VARIABLES: &str = "wave_height,wave_period,swell_wave_direction";
+
+/// Pollen variables requested from the air quality API, which covers Europe only.
+const POLLEN_VARIABLES: &str =
+    "alder_pollen,birch_pollen,olive_pollen,grass_pollen,mugwort_pollen,ragweed_pollen";
+
+/// Variables requested for daily history.
+const DAILY_VARIABLES: &str = "temperature_2m_max,temperature_2m_min,precipitation_sum";
+
+/// Variables requested for the hourly forecast.
+const FORECAST_VARIABLES: &str = "temperature_2m,apparent_temperature,relative_humidity_2m,\
+precipitation_probability,precipitation,snowfall,freezing_level_height,weather_code,wind_speed_10m,\
+uv_index";
+
+/// The archive lags behind; more recent days are served by the forecast API.
+const ARCHIVE_DELAY_DAYS: u64 = 5;
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResponse {
+    #[serde(default)]
+    results: Vec<GeocodingResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    name: String,
+    #[serde(default)]
+    country: Option<String>,
+    latitude: f64,
+    longitude: f64,
+}
+
+/// Downscaled CMIP6 model asked by the climate API by default.
+const CLIMATE_MODEL: &str = "EC_Earth3P_HR";
+
+/// Variables requested from the ensemble API.
+const ENSEMBLE_VARIABLES: [&str; 2] = ["temperature_2m", "precipitation"];
+
+/// Ensemble model asked by default; a global one, so it covers every location.
+const ENSEMBLE_MODEL: &str = "gfs_seamless";
+
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    utc_offset_seconds: i32,
+    #[serde(default)]
+    current: Option<Conditions>,
+    #[serde(default)]
+    hourly: Option<HourlyConditions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MarineResponse {
+    current: MarineConditions,
+}
+
+/// Sea state; `null` away from the coast.
+#[derive(Debug, Deserialize)]
+struct MarineConditions {
+    wave_height: Option<f64>,
+    wave_period: Option<f64>,
+    swell_wave_direction: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AirQualityResponse {
+    current: PollenConditions,
+}
+
+/// Grains/m³; `null` outside Europe.
+#[derive(Debug, Deserialize)]
+struct PollenConditions {
+    alder_pollen: Option<f64>,
+    birch_pollen: Option<f64>,
+    olive_pollen: Option<f64>,
+    grass_pollen: Option<f64>,
+    mugwort_pollen: Option<f64>,
+    ragweed_pollen: Option<f64>,
+}
+
+impl PollenConditions {
+    /// The highest count of each group.
+    fn into_pollen(self) -> PollenData {
+        let highest = |counts: &[Option<f64>]| counts.iter().flatten().copied().reduce(f64::max);
+        PollenData {
+            tree: highest(&[self.alder_pollen, self.birch_pollen, self.olive_pollen]),
+            grass: self.grass_pollen,
+            weed: highest(&[self.mugwort_pollen, self.ragweed_pollen]),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyResponse {
+    daily: DailyColumns,
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyColumns {
+    time: Vec<NaiveDate>,
+    temperature_2m_max: Vec<Option<f64>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    precipitation_sum: Vec<Option<f64>>,
+}
+
+impl DailyColumns {
+    fn into_days(self) -> Vec<DailyData> {
+        let at = |column: &[Option<f64>], i: usize| column.get(i).copied().flatten();
+        self.time
+            .iter()
+            .enumerate()
+            .map(|(i, date)| DailyData {
+                date: *date,
+                temp_min_c: at(&self.temperature_2m_min, i),
+                temp_max_c: at(&self.temperature_2m_max, i),
+                precipitation_mm: at(&self.precipitation_sum, i),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyForecastResponse {
+    hourly: HourlyForecastColumns,
+}
+
+#[derive(Debug, Deserialize)]
+struct HourlyForecastColumns {
+    time: Vec<String>,
+    temperature_2m: Vec<Option<f64>>,
+    apparent_temperature: Vec<Option<f64>>,
+    #[serde(default)]
+    relative_humidity_2m: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<f64>>,
+    precipitation: Vec<Option<f64>>,
+    #[serde(default)]
+    snowfall: Vec<Option<f64>>,
+    #[serde(default)]
+    freezing_level_height: Vec<Option<f64>>,
+    weather_code: Vec<Option<u8>>,
+    wind_speed_10m: Vec<Option<f64>>,
+    uv_index: Vec<Option<f64>>,
+}
+
+impl HourlyForecastColumns {
+    fn into_hours(self) -> Result<Vec<HourlyData>, ProviderError> {
+        let at = |column: &[Option<f64>], i: usize| column.get(i).copied().flatten();
+        self.time
+            .iter()
+            .enumerate()
+            .map(|(i, time)| {
+                Ok(HourlyData {
+                    time: parse_time(time)?,
+                    temp_c: at(&self.temperature_2m, i),
+                    feels_like_c: at(&self.apparent_temperature, i),
+                    humidity: at(&self.relative_humidity_2m, i),
+                    precipitation_probability: at(&self.precipitation_probability, i),
+                    precipitation_mm: at(&self.precipitation, i),
+                    snowfall_cm: at(&self.snowfall, i),
+                    freezing_level_m: at(&self.freezing_level_height, i),
+                    wind_kph: at(&self.wind_speed_10m, i),
+                    uv_index: at(&self.uv_index, i),
+                    kind: ConditionKind::from_wmo_code(
+                        self.weather_code
+                            .get(i)
+                            .copied()
+                            .flatten()
+                            .unwrap_or(u8::MAX),
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EnsembleResponse {
+    hourly: EnsembleColumns,
+}
+
+/// Hourly columns of every ensemble member, named like `temperature_2m` for the control run and
+/// `temperature_2m_member01` onwards for the others.
+#[derive(Debug, Deserialize)]
+struct EnsembleColumns {
+    time: Vec<String>,
+    #[serde(flatten)]
+    members: HashMap<String, Vec<Option<f64>>>,
+}
+
+impl EnsembleColumns {
+    /// Spread of `variable` across the members at hour `i`.
+    fn spread(&self, variable: &str, i: usize) -> Option<Spread> {
+        let member = format!("{variable}_");
+        let values: Vec<f64> = self
+            .members
+            .iter()
+            .filter(|(name, _)| *name == variable || name.starts_with(&member))
+            .filter_map(|(_, column)| column.get(i).copied().flatten())
+            .collect();
+        Spread::of(&values)
+    }
+
+    fn into_hours(self) -> Result<Vec<EnsembleHour>, ProviderError> {
+        let [temperature, precipitation] = ENSEMBLE_VARIABLES;
+        self.time
+            .iter()
+            .enumerate()
+            .map(|(i, time)| {
+                Ok(EnsembleHour {
+                    time: parse_time(time)?,
+                    temp_c: self.spread(temperature, i),
+                    precipitation_mm: self.spread(precipitation, i),
+                })
+            })
+            .collect()
+    }
+}
+
+/// One reading; Open-Meteo reports `null` for values it does not have.
+#[derive(Debug, Default, Deserialize)]
+struct Conditions {
+    time: String,
+    temperature_2m: Option<f64>,
+    #[serde(default)]
+    apparent_temperature: Option<f64>,
+    relative_humidity_2m: Option<f64>,
+    surface_pressure: Option<f64>,
+    weather_code: Option<u8>,
+    wind_speed_10m: Option<f64>,
+    wind_direction_10m: Option<f64>,
+    #[serde(default)]
+    uv_index: Option<f64>,
+    #[serde(default)]
+    precipitation_probability: Option<f64>,
+    /// Meters.
+    snow_depth: Option<f64>,
+    /// Centimeters over the preceding hour.
+    snowfall: Option<f64>,
+    /// Meters above sea level.
+    freezing_level_height: Option<f64>,
+    /// Meters.
+    #[serde(default)]
+    visibility: Option<f64>,
+}
+
+/// Hourly readings as parallel columns.
+#[derive(Debug, Deserialize)]
+///
+/// Alongside current conditions only the pressure of the past hours comes as columns.
+#[derive(Default)]
+#[serde(default)]
+struct HourlyConditions {
+    time: Vec<String>,
+    temperature_2m: Vec<Option<f64>>,
+    apparent_temperature: Vec<Option<f64>>,
+    relative_humidity_2m: Vec<Option<f64>>,
+    surface_pressure: Vec<Option<f64>>,
+    weather_code: Vec<Option<u8>>,
+    wind_speed_10m: Vec<Option<f64>>,
+    wind_direction_10m: Vec<Option<f64>>,
+    uv_index: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<f64>>,
+    snow_depth: Vec<Option<f64>>,
+    snowfall: Vec<Option<f64>>,
+    freezing_level_height: Vec<Option<f64>>,
+    visibility: Vec<Option<f64>>,
+}
+
+impl HourlyConditions {
+    /// Change of the `current` pressure over 3 hours, measured against the past hours.
+    fn pressure_change_3h(&self, current: &Conditions) -> Option<f64> {
+        let now = parse_time(&current.time).ok()?;
+        // Local times serve as well as UTC ones, only their differences count.
+        let mut readings: Vec<_> = self
+            .time
+            .iter()
+            .zip(&self.surface_pressure)
+            .filter_map(|(time, pressure)| Some((parse_time(time).ok()?, (*pressure)?)))
+            .filter(|(time, _)| *time < now)
+            .map(|(time, pressure)| (time.and_utc(), pressure))
+            .collect();
+        readings.push((now.and_utc(), current.surface_pressure?));
+        pressure::change_per_3h(&readings)
+    }
+
+    /// The reading closest to `date`.
+    fn closest(self, date: NaiveDateTime) -> Option<Conditions> {
+        let (i, _) = self
+            .time
+            .iter()
+            .enumerate()
+            .filter_map(|(i, time)| Some((i, parse_time(time).ok()?)))
+            .min_by_key(|(_, time)| (*time - date).num_seconds().abs())?;
+        let at = |column: &[Option<f64>]| column.get(i).copied().flatten();
+
+        Some(Conditions {
+            time: self.time[i].clone(),
+            temperature_2m: at(&self.temperature_2m),
+            apparent_temperature: at(&self.apparent_temperature),
+            relative_humidity_2m: at(&self.relative_humidity_2m),
+            surface_pressure: at(&self.surface_pressure),
+            weather_code: self.weather_code.get(i).copied().flatten(),
+            wind_speed_10m: at(&self.wind_speed_10m),
+            wind_direction_10m: at(&self.wind_direction_10m),
+            uv_index: at(&self.uv_index),
+            precipitation_probability: at(&self.precipitation_probability),
+            snow_depth: at(&self.snow_depth),
+            snowfall: at(&self.snowfall),
+            freezing_level_height: at(&self.freezing_level_height),
+            visibility: at(&self.visibility),
+        })
+    }
+}
+
+/// English description of a WMO weather code.
+fn describe(code: u8) -> &'static str {
+    match code {
+        0 => "Clear sky",
+        1 => "Mainly clear",
+        2 => "Partly cloudy",
+        3 => "Overcast",
+        45 => "Fog",
+        48 => "Depositing rime fog",
+        51 | 53 | 55 => "Drizzle",
+        56 | 57 => "Freezing drizzle",
+        61 => "Slight rain",
+        63 => "Rain",
+        65 => "Heavy rain",
+        66 | 67 => "Freezing rain",
+        71 => "Slight snowfall",
+        73 => "Snowfall",
+        75 => "Heavy snowfall",
+        77 => "Snow grains",
+        80..=82 => "Rain showers",
+        85 | 86 => "Snow showers",
+        95 => "Thunderstorm",
+        96 | 99 => "Thunderstorm with hail",
+        _ => "Unknown",
+    }
+}
+
+fn parse_time(time: &str) -> Result<NaiveDateTime, ProviderError> {
+    NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M")
+        .map_err(|e| ProviderError::ParseDateTime(format!("Failed to parse datetime: {e}")))
+}
+
+impl Conditions {
+    fn into_weather_data(
+        self,
+        location: String,
+        coordinates: Coordinates,
+        utc_offset: FixedOffset,
+    ) -> Result<WeatherData, ProviderError> {
+        let local = parse_time(&self.time)?;
+        let datetime = DateTime::<Utc>::from_naive_utc_and_offset(
+            local - chrono::Duration::seconds(i64::from(utc_offset.local_minus_utc())),
+            Utc,
+        );
+        let code = self.weather_code.unwrap_or(u8::MAX);
+
+        let snow = SnowData {
+            depth_cm: self.snow_depth.map(|m| m * 100.0),
+            fresh_cm: self.snowfall,
+            freezing_level_m: self.freezing_level_height,
+        };
+
+        Ok(WeatherData {
+            location,
+            datetime,
+            temp_c: self.temperature_2m.unwrap_or_default(),
+            humidity: self.relative_humidity_2m.unwrap_or_default(),
+            pressure: self.surface_pressure.unwrap_or_default(),
+            condition: describe(code).to_string(),
+            kind: ConditionKind::from_wmo_code(code),
+            code: WeatherCode::try_from(code).ok(),
+            wind_kph: self.wind_speed_10m.unwrap_or_default(),
+            wind_deg: self.wind_direction_10m.unwrap_or_default(),
+            feels_like_c: self.apparent_temperature,
+            uv_index: self.uv_index,
+            precipitation_probability: self.precipitation_probability,
+            visibility_km: self.visibility.map(|m| m / 1000.0),
+            pressure_change_3h: None,
+            utc_offset: Some(utc_offset),
+            coordinates: Some(coordinates),
+            snow: (snow != SnowData::default()).then_some(snow),
+            pollen: None,
+            anomalies: Vec::new(),
+            sources: Default::default(),
+        })
+    }
+}
+
+/// Implementation of `Open-Meteo` '<https://open-meteo.com/>', free and without API key.
+pub struct OpenMeteo {
+    client: reqwest::Client,
+    base_url: Url,
+    geocoding_url: Url,
+    marine_url: Url,
+    air_quality_url: Url,
+    archive_url: Url,
+    ensemble_url: Url,
+    climate_url: Url,
+    ensemble_model: String,
+    language: Option<String>,
+    parse_mode: ParseMode,
+    options: Vec<(String, String)>,
+    places: Option<Arc<GeocodeCache>>,
+}
+
+impl OpenMeteo {
+    pub fn new() -> Result<Self, ProviderError> {
+        let parse = |url: &str| {
+            Url::parse(url).map_err(|e| ProviderError::Error(format!("Invalid API URL: {e}")))
+        };
+
+        Ok(Self {
+            client: http_client()?,
+            base_url: parse("https://api.open-meteo.com")?,
+            geocoding_url: parse("https://geocoding-api.open-meteo.com")?,
+            marine_url: parse("https://marine-api.open-meteo.com")?,
+            air_quality_url: parse("https://air-quality-api.open-meteo.com")?,
+            archive_url: parse("https://archive-api.open-meteo.com")?,
+            ensemble_url: parse("https://ensemble-api.open-meteo.com")?,
+            climate_url: parse("https://climate-api.open-meteo.com")?,
+            ensemble_model: ENSEMBLE_MODEL.to_string(),
+            language: None,
+            parse_mode: ParseMode::default(),
+            options: Vec::new(),
+            places: None,
+        })
+    }
+
+    /// Resolve place names in the given language, e.g. "de".
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = parse_mode;
+        self
+    }
+
+    /// Extra query parameters for every request but geocoding, air quality, ensembles and climate
+    /// projections, replacing those of the same name. The others have models and variables of
+    /// their own.
+    pub fn with_options(mut self, options: Vec<(String, String)>) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Ensemble model of [`WeatherProvider::fetch_ensemble`], e.g. "icon_seamless" instead of GFS.
+    pub fn with_ensemble_model(mut self, model: impl Into<String>) -> Self {
+        self.ensemble_model = model.into();
+        self
+    }
+
+    /// Remember resolved place names in `places` instead of asking the geocoder every time.
+    pub fn with_geocode_cache(mut self, places: Arc<GeocodeCache>) -> Self {
+        self.places = Some(places);
+        self
+    }
+
+    /// Serve all APIs, including geocoding, marine and air quality data, the archive, ensembles and climate
+    /// projections, from `base_url`.
+    pub fn with_base_url(mut self, base_url: impl Into<Url>) -> Self {
+        self.base_url = base_url.into();
+        self.geocoding_url = self.base_url.clone();
+        self.marine_url = self.base_url.clone();
+        self.air_quality_url = self.base_url.clone();
+        self.archive_url = self.base_url.clone();
+        self.ensemble_url = self.base_url.clone();
+        self.climate_url = self.base_url.clone();
+        self
+    }
+
+    /// Daily values from `request`, to an archive, forecast or climate endpoint, with options
+    /// already applied where they belong.
+    async fn daily(
+        &self,
+        request: reqwest::RequestBuilder,
+        location: &str,
+        coordinates: Coordinates,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        let request = request.query(&[
+            ("latitude", coordinates.lat.to_string()),
+            ("longitude", coordinates.lon.to_string()),
+            ("timezone", "auto".to_string()),
+            ("daily", DAILY_VARIABLES.to_string()),
+            ("start_date", start.to_string()),
+            ("end_date", end.to_string()),
+        ]);
+
+        let res: DailyResponse = json(
+            PROVIDER,
+            send(PROVIDER, location, request).await?,
+            self.parse_mode,
+        )
+        .await?;
+        Ok(res.daily.into_days())
+    }
+
+    fn geocoding_request(&self, location: &str) -> reqwest::RequestBuilder {
+        // The geocoder matches place names only, "Porto,PT" finds nothing.
+        let name = location.split(',').next().unwrap_or(location).trim();
+        let url = format!("{}v1/search", self.geocoding_url);
+        let request = self
+            .client
+            .get(&url)
+            .query(&[("name", name), ("count", "1")]);
+        match &self.language {
+            Some(language) => request.query(&[("language", language)]),
+            None => request,
+        }
+    }
+
+    /// Current conditions, or the hours of the day of `date`, at `lat`/`lon`.
+    fn forecast_request(
+        &self,
+        lat: &str,
+        lon: &str,
+        date: Option<NaiveDateTime>,
+    ) -> reqwest::RequestBuilder {
+        let url = format!("{}v1/forecast", self.base_url);
+        let request = self.client.get(&url).query(&[
+            ("latitude", lat),
+            ("longitude", lon),
+            ("timezone", "auto"),
+        ]);
+        let request = match date {
+            // The past hours' pressure gives its tendency.
+            None => request.query(&[
+                ("current", VARIABLES),
+                ("hourly", "surface_pressure"),
+                ("past_hours", "6"),
+                ("forecast_hours", "1"),
+            ]),
+            Some(date) => {
+                let day = date.date().to_string();
+                request.query(&[
+                    ("hourly", VARIABLES),
+                    ("start_date", &day),
+                    ("end_date", &day),
+                ])
+            },
+        };
+        with_options(request, &self.options)
+    }
+
+    /// Name and position of a location given as "lat,lon" or a place name.
+    async fn resolve(&self, location: &str) -> Result<(String, Coordinates), ProviderError> {
+        if let Ok(coordinates) = location.parse() {
+            return Ok((location.to_string(), coordinates));
+        }
+        if let Some(place) = self.cached(location) {
+            return Ok((place.name, place.coordinates));
+        }
+
+        let request = self.geocoding_request(location);
+        let res: GeocodingResponse = json(
+            PROVIDER,
+            send(PROVIDER, location, request).await?,
+            self.parse_mode,
+        )
+        .await?;
+        let place =
+            res.results
+                .into_iter()
+                .next()
+                .ok_or_else(|| ProviderError::LocationNotFound {
+                    provider: PROVIDER.to_string(),
+                    location: location.to_string(),
+                })?;
+
+        let name = match place.country {
+            Some(country) => format!("{}, {country}", place.name),
+            None => place.name,
+        };
+        let coordinates = Coordinates {
+            lat: place.latitude,
+            lon: place.longitude,
+        };
+        if let Some(places) = &self.places {
+            places.put(
+                &self.place_key(location),
+                Place {
+                    name: name.clone(),
+                    coordinates,
+                    id: None,
+                },
+            );
+        }
+        Ok((name, coordinates))
+    }
+
+    fn place_key(&self, location: &str) -> String {
+        GeocodeCache::key(PROVIDER, self.language.as_deref(), location)
+    }
+
+    fn cached(&self, location: &str) -> Option<Place> {
+        self.places.as_ref()?.get(&self.place_key(location))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl WeatherProvider for OpenMeteo {
+    async fn fetch(
+        &self,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<WeatherData, ProviderError> {
+        debug!("openmeteo location: {location}, date: {date:?}");
+
+        if location.is_empty() {
+            return Err(ProviderError::InvalidLocation(location.to_string()));
+        }
+
+        let (name, coordinates) = self.resolve(location).await?;
+        let request = self.forecast_request(
+            &coordinates.lat.to_string(),
+            &coordinates.lon.to_string(),
+            date,
+        );
+        let res: ForecastResponse = json(
+            PROVIDER,
+            send(PROVIDER, location, request).await?,
+            self.parse_mode,
+        )
+        .await?;
+        let utc_offset = FixedOffset::east_opt(res.utc_offset_seconds)
+            .ok_or_else(|| ProviderError::Error("invalid UTC offset".to_string()))?;
+        let (conditions, pressure_change_3h) = match (date, res.current, res.hourly) {
+            (None, Some(current), hourly) => {
+                let change = hourly.and_then(|hourly| hourly.pressure_change_3h(&current));
+                (current, change)
+            },
+            (Some(date), _, Some(hourly)) => {
+                let conditions = hourly.closest(date).ok_or_else(|| {
+                    ProviderError::Error("forecast contains no hours".to_string())
+                })?;
+                (conditions, None)
+            },
+            _ => {
+                return Err(ProviderError::Error(
+                    "response without conditions".to_string(),
+                ));
+            },
+        };
+
+        let mut data = conditions.into_weather_data(name, coordinates, utc_offset)?;
+        data.pressure_change_3h = pressure_change_3h;
+        Ok(data)
+    }
+
+    fn plan(
+        &self,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<Vec<String>, ProviderError> {
+        let known = location
+            .parse::<Coordinates>()
+            .ok()
+            .or_else(|| self.cached(location).map(|place| place.coordinates));
+        if let Some(coordinates) = known {
+            let request = self
+                .forecast_request(
+                    &coordinates.lat.to_string(),
+                    &coordinates.lon.to_string(),
+                    date,
+                )
+                .build()?;
+            return Ok(vec![request.url().to_string()]);
+        }
+
+        // The position comes from the geocoder's answer.
+        let geocoding = self.geocoding_request(location).build()?;
+        let forecast = self
+            .forecast_request("{latitude}", "{longitude}", date)
+            .build()?;
+        Ok(vec![
+            geocoding.url().to_string(),
+            forecast
+                .url()
+                .as_str()
+                .replace("%7B", "{")
+                .replace("%7D", "}"),
+        ])
+    }
+
+    fn api_version(&self) -> Option<ApiVersion> {
+        Some(ApiVersion {
+            version: "v1",
+            deprecation: None,
+        })
+    }
+
+    async fn fetch_marine(&self, location: &str) -> Result<MarineData, ProviderError> {
+        debug!("openmeteo marine location: {location}");
+
+        let (name, coordinates) = self.resolve(location).await?;
+        let url = format!("{}v1/marine", self.marine_url);
+        let request = self.client.get(&url).query(&[
+            ("latitude", coordinates.lat.to_string()),
+            ("longitude", coordinates.lon.to_string()),
+            ("current", MARINE_VARIABLES.to_string()),
+        ]);
+        let request = with_options(request, &self.options);
+
+        let res: MarineResponse = json(
+            PROVIDER,
+            send(PROVIDER, location, request).await?,
+            self.parse_mode,
+        )
+        .await?;
+        Ok(MarineData {
+            location: name,
+            wave_height_m: res.current.wave_height,
+            wave_period_s: res.current.wave_period,
+            swell_direction_deg: res.current.swell_wave_direction,
+        })
+    }
+
+    async fn fetch_pollen(&self, location: &str) -> Result<PollenData, ProviderError> {
+        debug!("openmeteo pollen location: {location}");
+
+        let (_, coordinates) = self.resolve(location).await?;
+        let url = format!("{}v1/air-quality", self.air_quality_url);
+        let request = self.client.get(&url).query(&[
+            ("latitude", coordinates.lat.to_string()),
+            ("longitude", coordinates.lon.to_string()),
+            ("current", POLLEN_VARIABLES.to_string()),
+        ]);
+
+        let res: AirQualityResponse = json(
+            PROVIDER,
+            send(PROVIDER, location, request).await?,
+            self.parse_mode,
+        )
+        .await?;
+        Ok(res.current.into_pollen())
+    }
+
+    async fn fetch_daily(
+        &self,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        debug!("openmeteo daily location: {location}, {start}..={end}");
+
+        let (_, coordinates) = self.resolve(location).await?;
+        let archived = Utc::now().date_naive() - Days::new(ARCHIVE_DELAY_DAYS);
+
+        let mut days = Vec::new();
+        if start <= archived {
+            let request = with_options(
+                self.client.get(format!("{}v1/archive", self.archive_url)),
+                &self.options,
+            );
+            let until = end.min(archived);
+            days.extend(
+                self.daily(request, location, coordinates, start, until)
+                    .await?,
+            );
+        }
+        if end > archived {
+            let request = with_options(
+                self.client.get(format!("{}v1/forecast", self.base_url)),
+                &self.options,
+            );
+            let from = start.max(archived + Days::new(1));
+            days.extend(
+                self.daily(request, location, coordinates, from, end)
+                    .await?,
+            );
+        }
+
+        Ok(days)
+    }
+
+    async fn fetch_hourly(
+        &self,
+        location: &str,
+        hours: u32,
+    ) -> Result<Vec<HourlyData>, ProviderError> {
+        debug!("openmeteo hourly location: {location}, hours: {hours}");
+
+        let (_, coordinates) = self.resolve(location).await?;
+        let url = format!("{}v1/forecast", self.base_url);
+        let request = self.client.get(&url).query(&[
+            ("latitude", coordinates.lat.to_string()),
+            ("longitude", coordinates.lon.to_string()),
+            ("timezone", "auto".to_string()),
+            ("hourly", FORECAST_VARIABLES.to_string()),
+            ("forecast_hours", hours.to_string()),
+        ]);
+        let request = with_options(request, &self.options);
+
+        let res: HourlyForecastResponse = json(
+            PROVIDER,
+            send(PROVIDER, location, request).await?,
+            self.parse_mode,
+        )
+        .await?;
+        res.hourly.into_hours()
+    }
+
+    async fn fetch_climate(
+        &self,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        debug!("openmeteo climate location: {location}, {start}..={end}");
+
+        let (_, coordinates) = self.resolve(location).await?;
+        let request = self
+            .client
+            .get(format!("{}v1/climate", self.climate_url))
+            .query(&[("models", CLIMATE_MODEL)]);
+        self.daily(request, location, coordinates, start, end).await
+    }
+
+    async fn fetch_ensemble(
+        &self,
+        location: &str,
+        hours: u32,
+    ) -> Result<Vec<EnsembleHour>, ProviderError> {
+        debug!("openmeteo ensemble location: {location}, hours: {hours}");
+
+        let (_, coordinates) = self.resolve(location).await?;
+        let url = format!("{}v1/ensemble", self.ensemble_url);
+        let request = self.client.get(&url).query(&[
+            ("latitude", coordinates.lat.to_string()),
+            ("longitude", coordinates.lon.to_string()),
+            ("timezone", "auto".to_string()),
+            ("hourly", ENSEMBLE_VARIABLES.join(",")),
+            ("models", self.ensemble_model.clone()),
+            ("forecast_hours", hours.to_string()),
+        ]);
+
+        let res: EnsembleResponse = json(
+            PROVIDER,
+            send(PROVIDER, location, request).await?,
+            self.parse_mode,
+        )
+        .await?;
+        res.hourly.into_hours()
+    }
+}
+
+/// Map a recorded response from `endpoint`, see [`crate::weather_providers::fixtures`].
+#[cfg(test)]
+pub(super) fn map_fixture(endpoint: &str, body: &str) -> Result<(), ProviderError> {
+    use crate::weather_providers::parse::parse;
+    match endpoint {
+        "search" => {
+            parse::<GeocodingResponse>(PROVIDER, body, ParseMode::Strict)?;
+        },
+        "forecast" => {
+            let res: ForecastResponse = parse(PROVIDER, body, ParseMode::Strict)?;
+            let utc_offset = FixedOffset::east_opt(res.utc_offset_seconds)
+                .ok_or_else(|| ProviderError::Error("invalid UTC offset".to_string()))?;
+            if let Some(current) = res.current {
+                let coordinates = Coordinates { lat: 0.0, lon: 0.0 };
+                current.into_weather_data(String::new(), coordinates, utc_offset)?;
+            }
+        },
+        "marine" => {
+            parse::<MarineResponse>(PROVIDER, body, ParseMode::Strict)?;
+        },
+        "air-quality" => {
+            let res: AirQualityResponse = parse(PROVIDER, body, ParseMode::Strict)?;
+            res.current.into_pollen();
+        },
+        _ => return Err(ProviderError::Error(format!("no mapping for {endpoint}"))),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn api(server: &MockServer) -> OpenMeteo {
+        OpenMeteo::new()
+            .unwrap()
+            .with_base_url(server.uri().parse::<Url>().unwrap())
+    }
+
+    #[tokio::test]
+    async fn current_snow_conditions() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/search"))
+            .and(query_param("name", "Zermatt"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": [{
+                    "name": "Zermatt", "country": "Switzerland",
+                    "latitude": 46.02, "longitude": 7.75
+                }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/forecast"))
+            .and(query_param("latitude", "46.02"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "utc_offset_seconds": 3600,
+                "current": {
+                    "time": "2025-12-05T10:00",
+                    "temperature_2m": -4.2,
+                    "relative_humidity_2m": 85,
+                    "surface_pressure": 780.5,
+                    "weather_code": 73,
+                    "wind_speed_10m": 12.0,
+                    "wind_direction_10m": 310,
+                    "snow_depth": 0.85,
+                    "snowfall": 1.4,
+                    "freezing_level_height": 1850.0
+                },
+                "hourly": {
+                    "time": ["2025-12-05T06:00", "2025-12-05T07:00", "2025-12-05T08:00",
+                             "2025-12-05T09:00", "2025-12-05T10:00", "2025-12-05T11:00"],
+                    "surface_pressure": [784.0, 783.5, 782.0, 781.5, 780.5, 779.0]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let data = api(&server).fetch("Zermatt,CH", None).await.unwrap();
+
+        assert_eq!(data.location, "Zermatt, Switzerland");
+        assert_eq!(data.temp_c, -4.2);
+        assert_eq!(data.kind, ConditionKind::Snow);
+        assert_eq!(data.condition, "Snowfall");
+        assert_eq!(data.datetime.to_rfc3339(), "2025-12-05T09:00:00+00:00");
+        assert_eq!(data.pressure_change_3h, Some(-3.0));
+        assert_eq!(
+            data.snow,
+            Some(SnowData {
+                depth_cm: Some(85.0),
+                fresh_cm: Some(1.4),
+                freezing_level_m: Some(1850.0),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_place() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .mount(&server)
+            .await;
+
+        assert!(matches!(
+            api(&server).fetch("Nowhere", None).await,
+            Err(ProviderError::LocationNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn marine_conditions() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/marine"))
+            .and(query_param("latitude", "43.48"))
+            .and(query_param("current", MARINE_VARIABLES))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "current": {
+                    "time": "2025-12-05T10:00",
+                    "wave_height": 1.8,
+                    "wave_period": 11.5,
+                    "swell_wave_direction": null
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let data = api(&server).fetch_marine("43.48,-1.56").await.unwrap();
+
+        assert_eq!(data.wave_height_m, Some(1.8));
+        assert_eq!(data.wave_period_s, Some(11.5));
+        assert_eq!(data.swell_direction_deg, None);
+    }
+
+    #[tokio::test]
+    async fn pollen_by_group() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/air-quality"))
+            .and(query_param("current", POLLEN_VARIABLES))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "current": {
+                    "time": "2026-05-05T10:00",
+                    "alder_pollen": 2.0,
+                    "birch_pollen": 140.5,
+                    "olive_pollen": null,
+                    "grass_pollen": 18.0,
+                    "mugwort_pollen": null,
+                    "ragweed_pollen": null
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let pollen = api(&server).fetch_pollen("48.21,16.37").await.unwrap();
+
+        assert_eq!(
+            pollen,
+            PollenData {
+                tree: Some(140.5),
+                grass: Some(18.0),
+                weed: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn daily_history_spans_archive_and_forecast() {
+        let server = MockServer::start().await;
+        let today = Utc::now().date_naive();
+        let start = today - Days::new(10);
+
+        Mock::given(method("GET"))
+            .and(path("/v1/archive"))
+            .and(query_param("start_date", start.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "daily": {
+                    "time": [start],
+                    "temperature_2m_max": [20.5],
+                    "temperature_2m_min": [8.0],
+                    "precipitation_sum": [1.2]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/forecast"))
+            .and(query_param("end_date", today.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "daily": {
+                    "time": [today],
+                    "temperature_2m_max": [null],
+                    "temperature_2m_min": [5.0],
+                    "precipitation_sum": [0.0]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let days = api(&server)
+            .fetch_daily("43.48,-1.56", start, today)
+            .await
+            .unwrap();
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, start);
+        assert_eq!(days[0].temp_max_c, Some(20.5));
+        assert_eq!(days[1].temp_max_c, None);
+        assert_eq!(days[1].temp_min_c, Some(5.0));
+    }
+
+    #[tokio::test]
+    async fn hourly_forecast() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/forecast"))
+            .and(query_param("forecast_hours", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "hourly": {
+                    "time": ["2025-06-01T07:00", "2025-06-01T08:00"],
+                    "temperature_2m": [14.0, 15.5],
+                    "apparent_temperature": [12.5, 14.8],
+                    "precipitation_probability": [10, 65],
+                    "precipitation": [0.0, 1.2],
+                    "snowfall": [0.0, 0.0],
+                    "freezing_level_height": [2900.0, 2950.0],
+                    "weather_code": [1, 61],
+                    "wind_speed_10m": [8.0, 14.0],
+                    "uv_index": [1.5, null]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let hours = api(&server).fetch_hourly("41.15,-8.61", 2).await.unwrap();
+
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].time, parse_time("2025-06-01T07:00").unwrap());
+        assert_eq!(hours[1].precipitation_probability, Some(65.0));
+        assert_eq!(hours[1].kind, ConditionKind::Rain);
+        assert_eq!(hours[1].uv_index, None);
+        assert_eq!(hours[1].snowfall_cm, Some(0.0));
+        assert_eq!(hours[1].freezing_level_m, Some(2950.0));
+    }
+
+    #[tokio::test]
+    async fn climate_projection() {
+        let server = MockServer::start().await;
+        let start = NaiveDate::from_ymd_opt(2040, 1, 1).unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/v1/climate"))
+            .and(query_param("models", CLIMATE_MODEL))
+            .and(query_param("start_date", "2040-01-01"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "daily": {
+                    "time": [start],
+                    "temperature_2m_max": [12.5],
+                    "temperature_2m_min": [6.0],
+                    "precipitation_sum": [3.1]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let days = api(&server)
+            .with_options(vec![("models".to_string(), "icon_seamless".to_string())])
+            .fetch_climate("41.15,-8.61", start, start)
+            .await
+            .unwrap();
+
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].temp_max_c, Some(12.5));
+        assert_eq!(days[0].precipitation_mm, Some(3.1));
+    }
+
+    #[tokio::test]
+    async fn ensemble_spread() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/ensemble"))
+            .and(query_param("models", "icon_seamless_eps"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "hourly": {
+                    "time": ["2025-06-01T07:00"],
+                    "temperature_2m": [14.0],
+                    "temperature_2m_member01": [12.0],
+                    "temperature_2m_member02": [16.0],
+                    "precipitation": [null],
+                    "precipitation_member01": [null],
+                    "precipitation_member02": [null]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let hours = api(&server)
+            .with_options(vec![("models".to_string(), "icon_seamless".to_string())])
+            .with_ensemble_model("icon_seamless_eps")
+            .fetch_ensemble("41.15,-8.61", 1)
+            .await
+            .unwrap();
+
+        assert_eq!(hours.len(), 1);
+        let temp = hours[0].temp_c.unwrap();
+        assert_eq!(temp.median, 14.0);
+        assert!((temp.p10 - 12.4).abs() < 1e-9);
+        assert_eq!(hours[0].precipitation_mm, None);
+    }
+
+    #[test]
+    fn closest_hour() {
+        let hourly: HourlyConditions = serde_json::from_value(json!({
+            "time": ["2025-12-06T08:00", "2025-12-06T09:00"],
+            "temperature_2m": [1.0, 2.0],
+            "relative_humidity_2m": [90, 91],
+            "surface_pressure": [1000.0, 1001.0],
+            "weather_code": [0, null],
+            "wind_speed_10m": [5.0, 6.0],
+            "wind_direction_10m": [180, 190],
+            "snow_depth": [null, null],
+            "snowfall": [0.0, 0.0],
+            "freezing_level_height": [2000.0, 2100.0]
+        }))
+        .unwrap();
+
+        let date = parse_time("2025-12-06T08:50").unwrap();
+        let conditions = hourly.closest(date).unwrap();
+
+        assert_eq!(conditions.time, "2025-12-06T09:00");
+        assert_eq!(conditions.temperature_2m, Some(2.0));
+        assert_eq!(conditions.weather_code, None);
+        assert_eq!(conditions.snow_depth, None);
+    }
+}
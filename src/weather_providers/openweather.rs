@@ -1,12 +1,70 @@
+use crate::weather_providers::condition::{ConditionKind, WeatherCode};
 use crate::weather_providers::error::ProviderError;
-use crate::weather_providers::{WeatherData, WeatherProvider};
-use chrono::{DateTime, NaiveDateTime, Utc};
-use openweathermap::CurrentWeather;
+use crate::weather_providers::parse::ParseMode;
+use crate::weather_providers::{
+    ApiVersion, Coordinates, SnowData, WeatherData, WeatherProvider, http_client, json, redact,
+    send, with_options,
+};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use reqwest::Url;
+use serde::Deserialize;
 use tracing::debug;
 
+/// Response of the current weather endpoint, `data/2.5/weather`, the fields in use.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurrentWeather {
+    pub coord: Coord,
+    pub weather: Vec<Condition>,
+    pub main: Main,
+    pub wind: Wind,
+    pub snow: Option<Volume>,
+    /// Meters, at most 10 km.
+    #[serde(default)]
+    pub visibility: Option<f64>,
+    /// Time of the observation, unix seconds.
+    pub dt: i64,
+    /// Offset from UTC in seconds.
+    pub timezone: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Coord {
+    pub lon: f64,
+    pub lat: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Condition {
+    pub id: u64,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Main {
+    pub temp: f64,
+    pub feels_like: f64,
+    pub pressure: f64,
+    pub humidity: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Wind {
+    /// Meters per second with metric units.
+    pub speed: f64,
+    pub deg: f64,
+}
+
+/// Precipitation in mm.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Volume {
+    #[serde(rename = "1h")]
+    pub h1: Option<f64>,
+}
+
 impl From<CurrentWeather> for WeatherData {
     fn from(w: CurrentWeather) -> Self {
-        let dt = DateTime::from_timestamp(w.dt, 0).map_or_else(Utc::now, |utc| utc);
+        let dt = DateTime::from_timestamp(w.dt, 0).unwrap_or_else(Utc::now);
 
         WeatherData {
             location: w.name,
@@ -18,42 +76,329 @@ impl From<CurrentWeather> for WeatherData {
                 .weather
                 .first()
                 .map_or("unknown".to_string(), |c| c.description.clone()),
+            kind: w.weather.first().map_or(ConditionKind::Unknown, |c| {
+                ConditionKind::from_openweather_id(c.id)
+            }),
+            code: w
+                .weather
+                .first()
+                .and_then(|c| WeatherCode::from_openweather(c.id)),
             wind_kph: w.wind.speed * 3.6,
             wind_deg: w.wind.deg,
+            feels_like_c: Some(w.main.feels_like),
+            uv_index: None,
+            precipitation_probability: None,
+            visibility_km: w.visibility.map(|m| m / 1000.0),
+            pressure_change_3h: None,
+            utc_offset: i32::try_from(w.timezone)
+                .ok()
+                .and_then(FixedOffset::east_opt),
+            coordinates: Some(Coordinates {
+                lat: w.coord.lat,
+                lon: w.coord.lon,
+            }),
+            snow: w.snow.and_then(|snow| snow.h1).map(|mm| SnowData {
+                fresh_cm: Some(mm / 10.0),
+                ..Default::default()
+            }),
+            pollen: None,
+            anomalies: Vec::new(),
+            sources: Default::default(),
         }
     }
 }
 
+const PROVIDER: &str = "openweather";
+
+/// Implementation of `OpenWeather` '<https://openweathermap.org/>'
 pub struct OpenWeather {
+    client: reqwest::Client,
     api_key: String,
+    base_url: Url,
+    language: String,
+    parse_mode: ParseMode,
+    options: Vec<(String, String)>,
 }
 
 impl OpenWeather {
     pub fn new(api_key: Option<String>) -> Result<Self, ProviderError> {
+        let base_url = Url::parse("https://api.openweathermap.org")
+            .map_err(|e| ProviderError::Error(format!("Invalid API URL: {e}")))?;
+
         let api_key = api_key.ok_or_else(|| {
             ProviderError::InvalidApiKey("OpenWeather requires API_KEY".to_string())
         })?;
 
-        Ok(Self { api_key })
+        Ok(Self {
+            client: http_client()?,
+            api_key,
+            base_url,
+            language: "en".to_string(),
+            parse_mode: ParseMode::default(),
+            options: Vec::new(),
+        })
+    }
+
+    /// Request condition texts in the given language, e.g. "de".
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        if let Some(language) = language {
+            self.language = language;
+        }
+        self
+    }
+
+    /// Extra query parameters, replacing those of the same name.
+    pub fn with_options(mut self, options: Vec<(String, String)>) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = parse_mode;
+        self
+    }
+
+    /// Send requests to `base_url` instead of the public API.
+    pub fn with_base_url(mut self, base_url: impl Into<Url>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Query for a location given as "lat,lon", a numeric city id, or a city name.
+    fn location_query(location: &str) -> Vec<(&'static str, String)> {
+        if let Some((lat, lon)) = location.split_once(',')
+            && let (Ok(lat), Ok(lon)) = (lat.trim().parse::<f64>(), lon.trim().parse::<f64>())
+        {
+            return vec![("lat", lat.to_string()), ("lon", lon.to_string())];
+        }
+        if !location.is_empty() && location.chars().all(|c| c.is_ascii_digit()) {
+            return vec![("id", location.to_string())];
+        }
+        vec![("q", location.to_string())]
     }
 
-    pub fn get_weather(&self, location: &str) -> Result<CurrentWeather, String> {
-        debug!("Api key: {}", self.api_key);
-        openweathermap::blocking::weather(location, "metric", "en", &self.api_key)
+    fn request(&self, location: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}data/2.5/weather", self.base_url);
+        let request = self
+            .client
+            .get(&url)
+            .query(&Self::location_query(location))
+            .query(&[
+                ("units", "metric"),
+                ("lang", self.language.as_str()),
+                ("appid", self.api_key.as_str()),
+            ]);
+        with_options(request, &self.options)
+    }
+
+    pub async fn get_weather(&self, location: &str) -> Result<CurrentWeather, ProviderError> {
+        debug!("openweather location: {location}");
+
+        if location.is_empty() {
+            return Err(ProviderError::InvalidLocation(location.to_string()));
+        }
+
+        let res = send(PROVIDER, location, self.request(location)).await?;
+        json(PROVIDER, res, self.parse_mode).await
     }
 }
 
-#[async_trait::async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl WeatherProvider for OpenWeather {
     async fn fetch(
         &self,
         location: &str,
         _date: Option<NaiveDateTime>,
     ) -> Result<WeatherData, ProviderError> {
-        let weather_response = self
-            .get_weather(location)
-            .map_err(ProviderError::ApiRequest)?;
+        let weather_response = self.get_weather(location).await?;
 
         Ok(WeatherData::from(weather_response))
     }
+
+    fn plan(
+        &self,
+        location: &str,
+        _date: Option<NaiveDateTime>,
+    ) -> Result<Vec<String>, ProviderError> {
+        let request = self.request(location).build()?;
+        Ok(vec![redact(request.url(), "appid")])
+    }
+
+    /// The current weather endpoint of 2.5, which unlike One Call 2.5 is not retired.
+    fn api_version(&self) -> Option<ApiVersion> {
+        Some(ApiVersion {
+            version: "2.5",
+            deprecation: None,
+        })
+    }
+}
+
+/// Map a recorded response from `endpoint`, see [`crate::weather_providers::fixtures`].
+#[cfg(test)]
+pub(super) fn map_fixture(endpoint: &str, body: &str) -> Result<(), ProviderError> {
+    use crate::weather_providers::parse::parse;
+    match endpoint {
+        "weather" => {
+            let res: CurrentWeather = parse(PROVIDER, body, ParseMode::Strict)?;
+            let _ = WeatherData::from(res);
+        },
+        _ => return Err(ProviderError::Error(format!("no mapping for {endpoint}"))),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_providers::raw;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const MOCK_RESPONSE: &str = r#"{
+        "coord": {"lon": -8.611, "lat": 41.1496},
+        "weather": [{"id": 803, "main": "Clouds", "description": "broken clouds", "icon": "04d"}],
+        "base": "stations",
+        "main": {
+            "temp": 16.1,
+            "feels_like": 15.9,
+            "temp_min": 15.0,
+            "temp_max": 17.0,
+            "pressure": 1018,
+            "humidity": 94
+        },
+        "visibility": 10000,
+        "wind": {"speed": 5.0, "deg": 245},
+        "clouds": {"all": 75},
+        "dt": 1764954900,
+        "sys": {"country": "PT", "sunrise": 1764920000, "sunset": 1764954000},
+        "timezone": 0,
+        "id": 2735943,
+        "name": "Porto",
+        "cod": 200
+    }"#;
+
+    async fn api(server: &MockServer) -> OpenWeather {
+        OpenWeather::new(Some("test_api_key".to_string()))
+            .unwrap()
+            .with_base_url(server.uri().parse::<Url>().unwrap())
+    }
+
+    #[tokio::test]
+    async fn fetch_provider() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/data/2.5/weather"))
+            .and(query_param("q", "Porto"))
+            .and(query_param("units", "metric"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(MOCK_RESPONSE, "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        let result = api(&server).await.fetch("Porto", None).await.unwrap();
+
+        assert_eq!(result.location, "Porto");
+        assert_eq!(result.temp_c, 16.1);
+        assert_eq!(result.wind_kph, 18.0);
+        assert_eq!(result.kind, ConditionKind::Cloudy);
+        assert_eq!(
+            result.coordinates,
+            Some(Coordinates {
+                lat: 41.1496,
+                lon: -8.611
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn raw_response_without_api_key() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/data/2.5/weather"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(MOCK_RESPONSE, "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        let api = api(&server).await;
+        let (result, responses) = raw::capture(api.fetch("Porto", None)).await;
+
+        assert!(result.is_ok());
+        assert_eq!(responses.len(), 1);
+        assert!(
+            responses[0].url.contains("appid=***"),
+            "{}",
+            responses[0].url
+        );
+        assert!(!responses[0].url.contains("test_api_key"));
+        assert_eq!(responses[0].body["main"]["temp"], 16.1);
+    }
+
+    #[tokio::test]
+    async fn rejected_api_key() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401).set_body_raw(
+                r#"{"cod":401,"message":"Invalid API key."}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        assert!(matches!(
+            api(&server).await.fetch("Porto", None).await,
+            Err(ProviderError::Unauthorized { provider }) if provider == "openweather"
+        ));
+    }
+
+    #[tokio::test]
+    async fn unknown_location() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_body_raw(
+                r#"{"cod":"404","message":"city not found"}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        assert!(matches!(
+            api(&server).await.fetch("Nowhere", None).await,
+            Err(ProviderError::LocationNotFound { location, .. }) if location == "Nowhere"
+        ));
+    }
+
+    #[test]
+    fn plan_hides_api_key() {
+        let api = OpenWeather::new(Some("secret".to_string())).unwrap();
+        assert_eq!(
+            api.plan("Porto", None).unwrap(),
+            vec![
+                "https://api.openweathermap.org/data/2.5/weather?q=Porto&units=metric&lang=en&appid=***"
+            ]
+        );
+    }
+
+    #[test]
+    fn coordinates_and_ids() {
+        assert_eq!(
+            OpenWeather::location_query("41.15, -8.61"),
+            vec![("lat", "41.15".to_string()), ("lon", "-8.61".to_string())]
+        );
+        assert_eq!(
+            OpenWeather::location_query("2735943"),
+            vec![("id", "2735943".to_string())]
+        );
+        assert_eq!(
+            OpenWeather::location_query("Porto,PT"),
+            vec![("q", "Porto,PT".to_string())]
+        );
+    }
 }
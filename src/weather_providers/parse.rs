@@ -0,0 +1,145 @@
+use crate::weather_providers::QUALITY;
+use crate::weather_providers::error::ProviderError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_path_to_error::{Path, Segment};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Stand-ins tried in turn for a missing or mistyped field.
+const FILLERS: [fn() -> Value; 5] = [
+    || Value::from(0),
+    || Value::from(""),
+    || Value::from(false),
+    || Value::Object(Map::new()),
+    || Value::Array(Vec::new()),
+];
+
+/// Upper bound on fields filled in for one response.
+const MAX_REPAIRS: usize = 32;
+
+/// How to treat provider responses that do not match the expected schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseMode {
+    /// Fill missing or mistyped fields with defaults and log a warning.
+    #[default]
+    Lenient,
+    /// Fail with the JSON path of the first offending field.
+    Strict,
+}
+
+/// The value at `path` in `value`, for a map key that may not exist yet.
+fn slot<'a>(value: &'a mut Value, path: &Path) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |value, segment| match segment {
+        Segment::Seq { index } => value.get_mut(*index),
+        Segment::Map { key } => value
+            .as_object_mut()
+            .map(|map| map.entry(key.clone()).or_insert(Value::Null)),
+        Segment::Enum { .. } | Segment::Unknown => Some(value),
+    })
+}
+
+/// Name of the field in serde's "missing field `name`" message.
+fn missing_field(message: &str) -> Option<&str> {
+    message.strip_prefix("missing field `")?.split('`').next()
+}
+
+/// Deserialize a provider response according to `mode`.
+pub fn parse<T: DeserializeOwned>(
+    provider: &str,
+    body: &str,
+    mode: ParseMode,
+) -> Result<T, ProviderError> {
+    let mut value: Value = serde_json::from_str(body)?;
+    let mut tried: HashMap<String, usize> = HashMap::new();
+
+    loop {
+        let error = match serde_path_to_error::deserialize::<_, T>(&value) {
+            Ok(parsed) => return Ok(parsed),
+            Err(error) => error,
+        };
+        let message = error.inner().to_string();
+        let path = error.path();
+        let schema_error = || ProviderError::Schema {
+            provider: provider.to_string(),
+            path: path.to_string(),
+            message: message.clone(),
+        };
+
+        if mode == ParseMode::Strict || tried.len() >= MAX_REPAIRS {
+            return Err(schema_error());
+        }
+        // A missing field is reported at its parent object.
+        let field = missing_field(&message).map(str::to_string);
+        let key = match &field {
+            Some(field) => format!("{path}.{field}")
+                .trim_start_matches('.')
+                .to_string(),
+            None => path.to_string(),
+        };
+        // Untagged enums report a mismatch of their own at the root, which no filler repairs.
+        if field.is_none() && path.iter().next().is_none() {
+            return Err(schema_error());
+        }
+        let attempt = tried.entry(key.clone()).or_default();
+        let Some(filler) = FILLERS.get(*attempt) else {
+            return Err(schema_error());
+        };
+        *attempt += 1;
+
+        let target = match (field, slot(&mut value, path)) {
+            (Some(field), Some(Value::Object(map))) => map.entry(field).or_insert(Value::Null),
+            (None, Some(target)) => target,
+            _ => return Err(schema_error()),
+        };
+        warn!(target: QUALITY, "{provider}: {message} at `{key}`, using a default");
+        *target = filler();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        current: Current,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Current {
+        temp: f64,
+        name: String,
+        #[allow(dead_code)]
+        wind: Option<f64>,
+    }
+
+    const DRIFTED: &str = r#"{"current": {"name": "Porto", "wind": 12.0}}"#;
+
+    #[test]
+    fn lenient_fills_missing_fields() {
+        let res: Response = parse("test", DRIFTED, ParseMode::Lenient).unwrap();
+        assert_eq!(res.current.temp, 0.0);
+        assert_eq!(res.current.name, "Porto");
+
+        let res: Response = parse(
+            "test",
+            r#"{"current": {"temp": "warm"}}"#,
+            ParseMode::Lenient,
+        )
+        .unwrap();
+        assert_eq!(res.current.temp, 0.0);
+        assert_eq!(res.current.name, "");
+    }
+
+    #[test]
+    fn strict_reports_path() {
+        let err = parse::<Response>("test", DRIFTED, ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "test: unexpected response at `current`: missing field `temp`"
+        );
+    }
+}
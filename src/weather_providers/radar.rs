@@ -0,0 +1,174 @@
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::parse::ParseMode;
+use crate::weather_providers::{Coordinates, http_client, json, send};
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::Deserialize;
+
+const PROVIDER: &str = "rainviewer";
+
+/// Colour scheme of the radar tiles, RainViewer's "Universal Blue".
+const COLOR_SCHEME: u8 = 2;
+
+#[derive(Debug, Deserialize)]
+struct WeatherMaps {
+    host: String,
+    radar: RadarFrames,
+}
+
+#[derive(Debug, Deserialize)]
+struct RadarFrames {
+    #[serde(default)]
+    past: Vec<Frame>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Frame {
+    time: i64,
+    path: String,
+}
+
+/// A precipitation radar image centred on a location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RadarImage {
+    /// When the radar scan was taken.
+    pub time: DateTime<Utc>,
+    pub png: Vec<u8>,
+}
+
+/// Client of the free RainViewer API, which composites the radars of many national weather
+/// services. Its tiles cover most of Europe, North America and parts of Asia and Oceania.
+#[derive(Debug, Clone)]
+pub struct RainViewer {
+    client: reqwest::Client,
+    maps_url: Url,
+}
+
+impl RainViewer {
+    pub fn new() -> Result<Self, ProviderError> {
+        Ok(Self {
+            client: http_client()?,
+            maps_url: Url::parse("https://api.rainviewer.com/public/weather-maps.json")
+                .expect("valid URL"),
+        })
+    }
+
+    /// Fetch the list of frames from `base_url` instead, e.g. a mock server in tests.
+    pub fn with_base_url(mut self, base_url: Url) -> Self {
+        self.maps_url = base_url
+            .join("public/weather-maps.json")
+            .expect("valid URL");
+        self
+    }
+
+    /// The latest radar scan around `coordinates`: a `size` pixels square PNG at map `zoom`,
+    /// from 1 for a continent to 7 for a region.
+    pub async fn latest(
+        &self,
+        coordinates: Coordinates,
+        zoom: u8,
+        size: u16,
+    ) -> Result<RadarImage, ProviderError> {
+        let location = coordinates.to_string();
+        let res = send(PROVIDER, &location, self.client.get(self.maps_url.clone())).await?;
+        let maps: WeatherMaps = json(PROVIDER, res, ParseMode::Strict).await?;
+        let frame = maps
+            .radar
+            .past
+            .iter()
+            .max_by_key(|frame| frame.time)
+            .ok_or_else(|| ProviderError::ApiRequest("No radar frames available".to_string()))?;
+
+        let url = format!(
+            "{}{}/{size}/{zoom}/{:.4}/{:.4}/{COLOR_SCHEME}/1_1.png",
+            maps.host.trim_end_matches('/'),
+            frame.path,
+            coordinates.lat,
+            coordinates.lon,
+        );
+        let res = send(PROVIDER, &location, self.client.get(url)).await?;
+        // Tiles out of coverage or past the zoom limit come back as text with a success status.
+        let is_png = res
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("image/png"));
+        if !is_png {
+            let body = res.text().await.unwrap_or_default();
+            return Err(ProviderError::ApiRequest(format!(
+                "No radar image for {location}: {}",
+                body.trim()
+            )));
+        }
+        Ok(RadarImage {
+            time: DateTime::from_timestamp(frame.time, 0).unwrap_or_default(),
+            png: res.bytes().await?.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn latest_frame() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/public/weather-maps.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "host": server.uri(),
+                "radar": {
+                    "past": [
+                        {"time": 1760520000, "path": "/v2/radar/1760520000"},
+                        {"time": 1760520600, "path": "/v2/radar/1760520600"}
+                    ],
+                    "nowcast": []
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/radar/1760520600/512/6/50.4500/30.5200/2/1_1.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(b"\x89PNG".to_vec(), "image/png"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/radar/1760520600/512/7/50.4500/30.5200/2/1_1.png"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw("Zoom Level Not Supported", "text/plain"),
+            )
+            .mount(&server)
+            .await;
+
+        let radar = RainViewer::new()
+            .unwrap()
+            .with_base_url(server.uri().parse().unwrap());
+        let image = radar
+            .latest(
+                Coordinates {
+                    lat: 50.45,
+                    lon: 30.52,
+                },
+                6,
+                512,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(image.time.timestamp(), 1760520600);
+        assert_eq!(image.png, b"\x89PNG");
+        let kyiv = Coordinates {
+            lat: 50.45,
+            lon: 30.52,
+        };
+        let error = radar.latest(kyiv, 7, 512).await.unwrap_err();
+        assert!(
+            error.to_string().contains("Zoom Level Not Supported"),
+            "{error}"
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::weather_providers::alert::WeatherAlert;
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::{
+    ApiVersion, DailyData, EnsembleHour, HourlyData, MarineData, PollenData, WeatherData,
+    WeatherProvider,
+};
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Token bucket allowing `per_minute` requests a minute, in bursts of up to as many.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    /// Tokens added per second.
+    rate: f64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    pub fn per_minute(per_minute: u32) -> Self {
+        let capacity = f64::from(per_minute.max(1));
+        Self {
+            capacity,
+            rate: capacity / 60.0,
+            state: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    /// Take a token, waiting for one to be added when the bucket is empty.
+    pub async fn acquire(&self) {
+        let mut state = self.state.lock().await;
+        let (tokens, refilled_at) = &mut *state;
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*refilled_at).as_secs_f64() * self.rate)
+            .min(self.capacity);
+        *refilled_at = now;
+
+        if *tokens < 1.0 {
+            let wait = Duration::from_secs_f64((1.0 - *tokens) / self.rate);
+            debug!("Rate limit reached, waiting {wait:?}");
+            // Holding the lock queues the other callers behind this one.
+            tokio::time::sleep(wait).await;
+            *tokens = 1.0;
+            *refilled_at = Instant::now();
+        }
+        *tokens -= 1.0;
+    }
+}
+
+/// Provider whose requests wait for a [`TokenBucket`], so free-tier limits are not exceeded
+/// however many commands, batch jobs or server requests ask at once.
+pub struct RateLimited {
+    inner: Arc<dyn WeatherProvider>,
+    bucket: TokenBucket,
+}
+
+impl RateLimited {
+    pub fn new(inner: Arc<dyn WeatherProvider>, per_minute: u32) -> Self {
+        Self {
+            inner,
+            bucket: TokenBucket::per_minute(per_minute),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for RateLimited {
+    async fn fetch(
+        &self,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<WeatherData, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch(location, date).await
+    }
+
+    async fn fetch_marine(&self, location: &str) -> Result<MarineData, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch_marine(location).await
+    }
+
+    async fn fetch_pollen(&self, location: &str) -> Result<PollenData, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch_pollen(location).await
+    }
+
+    async fn fetch_daily(
+        &self,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch_daily(location, start, end).await
+    }
+
+    async fn fetch_climate(
+        &self,
+        location: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<DailyData>, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch_climate(location, start, end).await
+    }
+
+    async fn fetch_hourly(
+        &self,
+        location: &str,
+        hours: u32,
+    ) -> Result<Vec<HourlyData>, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch_hourly(location, hours).await
+    }
+
+    async fn fetch_ensemble(
+        &self,
+        location: &str,
+        hours: u32,
+    ) -> Result<Vec<EnsembleHour>, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch_ensemble(location, hours).await
+    }
+
+    async fn fetch_alerts(&self, location: &str) -> Result<Vec<WeatherAlert>, ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.fetch_alerts(location).await
+    }
+
+    fn plan(
+        &self,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<Vec<String>, ProviderError> {
+        self.inner.plan(location, date)
+    }
+
+    async fn ping(&self) -> Result<(), ProviderError> {
+        self.bucket.acquire().await;
+        self.inner.ping().await
+    }
+
+    fn api_version(&self) -> Option<ApiVersion> {
+        self.inner.api_version()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn waits_once_the_burst_is_used() {
+        // A token every 50 ms.
+        let bucket = TokenBucket::per_minute(1200);
+        for _ in 0..1200 {
+            bucket.acquire().await;
+        }
+        let start = Instant::now();
+        bucket.acquire().await;
+        bucket.acquire().await;
+        assert!(
+            start.elapsed() >= Duration::from_millis(90),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Provider responses as received, for `weather get --raw`.
+
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde_json::Value;
+use std::cell::RefCell;
+
+/// Query parameters and JSON fields holding credentials.
+const SECRETS: [&str; 5] = ["key", "appid", "apikey", "api_key", "token"];
+
+tokio::task_local! {
+    static RESPONSES: RefCell<Vec<RawResponse>>;
+}
+
+/// Where `--raw` puts the responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum RawMode {
+    /// Followed by the parsed output.
+    #[default]
+    Also,
+    /// Instead of the parsed output.
+    Only,
+}
+
+/// Body of one provider response, with the URL it came from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RawResponse {
+    pub provider: String,
+    /// Request URL with credentials masked.
+    pub url: String,
+    /// HTTP status code.
+    pub status: u16,
+    /// The body as JSON, or as a string when it is not JSON.
+    pub body: Value,
+}
+
+fn is_secret(name: &str) -> bool {
+    SECRETS.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Mask the values of credential fields anywhere in `value`.
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (name, value) in map {
+                if is_secret(name) {
+                    *value = Value::from("***");
+                } else {
+                    redact_value(value);
+                }
+            }
+        },
+        Value::Array(values) => values.iter_mut().for_each(redact_value),
+        _ => {},
+    }
+}
+
+/// `url` with credential parameters masked.
+fn redact_url(url: &reqwest::Url) -> String {
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            let v = if is_secret(&k) {
+                "***".into()
+            } else {
+                v.into_owned()
+            };
+            (k.into_owned(), v)
+        })
+        .collect();
+    let mut url = url.clone();
+    if !pairs.is_empty() {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+/// Run `call`, returning its output with every provider response received meanwhile.
+pub async fn capture<F: Future>(call: F) -> (F::Output, Vec<RawResponse>) {
+    RESPONSES
+        .scope(RefCell::new(Vec::new()), async {
+            let output = call.await;
+            (output, RESPONSES.with(RefCell::take))
+        })
+        .await
+}
+
+/// Keep a response body when called within [`capture`].
+pub(super) fn record(provider: &str, url: &reqwest::Url, status: StatusCode, body: &str) {
+    let _ = RESPONSES.try_with(|responses| {
+        let mut body = serde_json::from_str(body).unwrap_or_else(|_| Value::from(body));
+        redact_value(&mut body);
+        responses.borrow_mut().push(RawResponse {
+            provider: provider.to_string(),
+            url: redact_url(url),
+            status: status.as_u16(),
+            body,
+        });
+    });
+}
+
+impl RawResponse {
+    /// Whether the provider answered with a 2xx status.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Responses pretty-printed, each under a "# provider url" line, with the status if it is an error.
+pub fn render_raw(responses: &[RawResponse]) -> String {
+    responses
+        .iter()
+        .map(|res| {
+            let body = serde_json::to_string_pretty(&res.body).unwrap_or_default();
+            let status = if res.is_success() {
+                String::new()
+            } else {
+                format!(" (HTTP {})", res.status)
+            };
+            format!("# {} {}{status}\n{body}", res.provider, res.url)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn captured_with_secrets_masked() {
+        let url = reqwest::Url::parse("https://example.com/v1?key=secret&q=Porto").unwrap();
+        record("outside", &url, StatusCode::OK, "{}");
+
+        let ((), responses) = capture(async {
+            record(
+                "test",
+                &url,
+                StatusCode::OK,
+                r#"{"query": {"apiKey": "secret", "q": "Porto"}, "temp": 16.1}"#,
+            );
+            record("test", &url, StatusCode::OK, "not json");
+        })
+        .await;
+
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].url, "https://example.com/v1?key=***&q=Porto");
+        assert_eq!(
+            render_raw(&responses[..1]),
+            "# test https://example.com/v1?key=***&q=Porto\n{\n  \"query\": {\n    \"apiKey\": \
+             \"***\",\n    \"q\": \"Porto\"\n  },\n  \"temp\": 16.1\n}"
+        );
+        assert_eq!(responses[1].body, Value::from("not json"));
+    }
+
+    #[tokio::test]
+    async fn failed_responses_are_captured() {
+        let server = MockServer::start().await;
+        Mock::given(path("/v1"))
+            .respond_with(ResponseTemplate::new(500).set_body_string(r#"{"message": "down"}"#))
+            .mount(&server)
+            .await;
+
+        let request = reqwest::Client::new().get(format!("{}/v1?key=secret", server.uri()));
+        let (result, responses) = capture(super::super::send("test", "Porto", request)).await;
+
+        assert!(result.is_err());
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].body, serde_json::json!({"message": "down"}));
+        assert!(
+            responses[0].url.ends_with("/v1?key=***"),
+            "{}",
+            responses[0].url
+        );
+        assert!(render_raw(&responses).contains("key=*** (HTTP 500)"));
+    }
+}
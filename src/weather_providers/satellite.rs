@@ -0,0 +1,252 @@
+use crate::weather_providers::error::ProviderError;
+use crate::weather_providers::{Coordinates, http_client, send};
+use reqwest::Url;
+
+const PROVIDER: &str = "satellite";
+
+/// Part of the spectrum to look at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Band {
+    /// Reflected sunlight, as the eye would see the clouds; dark at night.
+    Visible,
+    /// Heat given off by clouds and ground, day and night.
+    Infrared,
+}
+
+/// Services publishing geostationary imagery over WMS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Service {
+    /// NASA GIBS, serving GOES and Himawari.
+    Gibs,
+    /// EUMETSAT's view service, serving Meteosat.
+    Eumetsat,
+}
+
+/// A geostationary satellite and its layers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Satellite {
+    name: &'static str,
+    /// Longitude of the point below it; it sees best within about 60° of it.
+    longitude: f64,
+    service: Service,
+    visible: &'static str,
+    infrared: &'static str,
+}
+
+const SATELLITES: [Satellite; 4] = [
+    Satellite {
+        name: "GOES-West",
+        longitude: -137.0,
+        service: Service::Gibs,
+        visible: "GOES-West_ABI_Band2_Red_Visible_1km",
+        infrared: "GOES-West_ABI_Band13_Clean_Infrared",
+    },
+    Satellite {
+        name: "GOES-East",
+        longitude: -75.2,
+        service: Service::Gibs,
+        visible: "GOES-East_ABI_Band2_Red_Visible_1km",
+        infrared: "GOES-East_ABI_Band13_Clean_Infrared",
+    },
+    Satellite {
+        name: "Meteosat",
+        longitude: 0.0,
+        service: Service::Eumetsat,
+        visible: "msg_fes:vis006",
+        infrared: "msg_fes:ir108",
+    },
+    Satellite {
+        name: "Himawari",
+        longitude: 140.7,
+        service: Service::Gibs,
+        visible: "Himawari_AHI_Band3_Red_Visible_1km",
+        infrared: "Himawari_AHI_Band13_Clean_Infrared",
+    },
+];
+
+/// Distance in degrees of longitude, the short way round.
+fn longitude_distance(a: f64, b: f64) -> f64 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
+
+/// West and east edges of a region `span` degrees wide around `lon`. The services take none
+/// across the antimeridian, so near it the region is moved off-centre to end there instead.
+fn longitude_bounds(lon: f64, span: f64) -> (f64, f64) {
+    let span = span.min(360.0);
+    let lon = (lon + 180.0).rem_euclid(360.0) - 180.0;
+    let west = (lon - span / 2.0).clamp(-180.0, 180.0 - span);
+    (west, west + span)
+}
+
+/// The satellite with the most direct view of `lon`.
+fn nearest(lon: f64) -> &'static Satellite {
+    SATELLITES
+        .iter()
+        .min_by(|a, b| {
+            longitude_distance(a.longitude, lon).total_cmp(&longitude_distance(b.longitude, lon))
+        })
+        .expect("satellites listed")
+}
+
+/// The latest image of a region.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SatelliteImage {
+    /// Satellite that took it, e.g. "GOES-East".
+    pub satellite: &'static str,
+    pub png: Vec<u8>,
+}
+
+/// Client for the latest geostationary satellite image of a region, from whichever of GOES,
+/// Meteosat and Himawari looks at it most directly. Neither service needs an API key.
+#[derive(Debug, Clone)]
+pub struct SatelliteImagery {
+    client: reqwest::Client,
+    gibs_url: Url,
+    eumetsat_url: Url,
+}
+
+impl SatelliteImagery {
+    pub fn new() -> Result<Self, ProviderError> {
+        Ok(Self {
+            client: http_client()?,
+            gibs_url: Url::parse("https://gibs.earthdata.nasa.gov/wms/epsg4326/best/wms.cgi")
+                .expect("valid URL"),
+            eumetsat_url: Url::parse("https://view.eumetsat.int/geoserver/wms").expect("valid URL"),
+        })
+    }
+
+    /// Send requests of both services to `base_url` instead, e.g. a mock server in tests.
+    pub fn with_base_url(mut self, base_url: Url) -> Self {
+        self.gibs_url = base_url
+            .join("wms/epsg4326/best/wms.cgi")
+            .expect("valid URL");
+        self.eumetsat_url = base_url.join("geoserver/wms").expect("valid URL");
+        self
+    }
+
+    /// PNG of `band` centred on `center`, `span` degrees of longitude wide and `width` by
+    /// `height` pixels.
+    pub async fn latest(
+        &self,
+        center: Coordinates,
+        band: Band,
+        span: f64,
+        (width, height): (u32, u32),
+    ) -> Result<SatelliteImage, ProviderError> {
+        let satellite = nearest(center.lon);
+        let layer = match band {
+            Band::Visible => satellite.visible,
+            Band::Infrared => satellite.infrared,
+        };
+        let (west, east) = longitude_bounds(center.lon, span);
+        let half_lat = (east - west) / 2.0 * f64::from(height) / f64::from(width);
+        // WMS 1.3.0 puts latitude first for EPSG:4326.
+        let bbox = format!(
+            "{:.4},{:.4},{:.4},{:.4}",
+            (center.lat - half_lat).max(-90.0),
+            west,
+            (center.lat + half_lat).min(90.0),
+            east,
+        );
+        let mut url = match satellite.service {
+            Service::Gibs => self.gibs_url.clone(),
+            Service::Eumetsat => self.eumetsat_url.clone(),
+        };
+        url.query_pairs_mut().extend_pairs([
+            ("SERVICE", "WMS"),
+            ("REQUEST", "GetMap"),
+            ("VERSION", "1.3.0"),
+            ("LAYERS", layer),
+            ("STYLES", ""),
+            ("CRS", "EPSG:4326"),
+            ("BBOX", &bbox),
+            ("WIDTH", &width.to_string()),
+            ("HEIGHT", &height.to_string()),
+            ("FORMAT", "image/png"),
+        ]);
+
+        let location = center.to_string();
+        let res = send(PROVIDER, &location, self.client.get(url)).await?;
+        // WMS reports errors as XML with a success status.
+        let is_image = res
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("image/"));
+        if !is_image {
+            let body = res.text().await.unwrap_or_default();
+            return Err(ProviderError::ApiRequest(format!(
+                "{}: no image for {layer}: {}",
+                satellite.name,
+                body.trim()
+            )));
+        }
+        Ok(SatelliteImage {
+            satellite: satellite.name,
+            png: res.bytes().await?.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn nearest_satellite_layer() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/wms/epsg4326/best/wms.cgi"))
+            .and(query_param("LAYERS", "GOES-East_ABI_Band13_Clean_Infrared"))
+            .and(query_param("BBOX", "35.7000,-84.0000,45.7000,-64.0000"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(b"\x89PNG".to_vec(), "image/png"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/geoserver/wms"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                "<ServiceExceptionReport>Layer not found</ServiceExceptionReport>",
+                "text/xml",
+            ))
+            .mount(&server)
+            .await;
+        let imagery = SatelliteImagery::new()
+            .unwrap()
+            .with_base_url(server.uri().parse().unwrap());
+
+        let new_york = Coordinates {
+            lat: 40.7,
+            lon: -74.0,
+        };
+        let image = imagery
+            .latest(new_york, Band::Infrared, 20.0, (1000, 500))
+            .await
+            .unwrap();
+        assert_eq!(image.satellite, "GOES-East");
+        assert_eq!(image.png, b"\x89PNG");
+
+        let kyiv = Coordinates {
+            lat: 50.45,
+            lon: 30.52,
+        };
+        let error = imagery
+            .latest(kyiv, Band::Visible, 20.0, (1000, 500))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Meteosat"), "{error}");
+        assert_eq!(nearest(179.0).name, "Himawari");
+        assert_eq!(nearest(-170.0).name, "GOES-West");
+    }
+
+    #[test]
+    fn region_ends_at_the_antimeridian() {
+        assert_eq!(longitude_bounds(30.0, 20.0), (20.0, 40.0));
+        assert_eq!(longitude_bounds(175.0, 30.0), (150.0, 180.0));
+        assert_eq!(longitude_bounds(-178.0, 30.0), (-180.0, -150.0));
+        assert_eq!(longitude_bounds(190.0, 20.0), (-180.0, -160.0));
+        assert_eq!(longitude_bounds(0.0, 400.0), (-180.0, 180.0));
+    }
+}
@@ -1,6 +1,13 @@
+use crate::weather_providers::alert::{Severity, WeatherAlert};
+use crate::weather_providers::condition::{ConditionKind, WeatherCode};
 use crate::weather_providers::error::ProviderError;
-use crate::weather_providers::{WeatherData, WeatherProvider};
-use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use crate::weather_providers::parse::{ParseMode, parse};
+use crate::weather_providers::{
+    ApiVersion, Coordinates, SnowData, WeatherData, WeatherProvider, http_client, json, redact,
+    send, set_options, text, with_options,
+};
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
@@ -11,7 +18,9 @@ use tracing::{debug, info};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum WeatherResponse {
-    Current {
+    // Untagged variants are tried in order, the one with the most fields goes first.
+    Forecast {
+        forecast: Forecast,
         location: Location,
         current: WeatherCondition,
     },
@@ -19,13 +28,84 @@ pub enum WeatherResponse {
         location: Location,
         forecast: Forecast,
     },
-    Forecast {
-        forecast: Forecast,
+    Current {
         location: Location,
         current: WeatherCondition,
     },
 }
 
+/// Answer of `current.json`.
+#[derive(Debug, Deserialize)]
+struct CurrentResponse {
+    location: Location,
+    current: WeatherCondition,
+}
+
+/// Answer of `forecast.json`.
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    forecast: Forecast,
+    location: Location,
+    current: WeatherCondition,
+}
+
+/// Answer of `history.json`.
+#[derive(Debug, Deserialize)]
+struct HistoryResponse {
+    location: Location,
+    forecast: Forecast,
+}
+
+/// API endpoint a request goes to, and so the [`WeatherResponse`] variant it answers with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Endpoint {
+    Current,
+    Forecast { days: i64 },
+    History { date: NaiveDateTime },
+}
+
+impl Endpoint {
+    /// Current conditions without `date`, a forecast for future dates or history for past ones.
+    fn of(date: Option<NaiveDateTime>) -> Self {
+        let now = Local::now().naive_local();
+        match date {
+            None => Self::Current,
+            Some(date) if date > now => Self::Forecast {
+                days: forecast_days(date, now),
+            },
+            Some(date) => Self::History { date },
+        }
+    }
+
+    /// Parse `body` as this endpoint's answer. The concrete variant is parsed rather than the
+    /// untagged [`WeatherResponse`], so [`ParseMode::Lenient`] can fill in its missing fields and
+    /// [`ParseMode::Strict`] reports their path.
+    fn parse(self, body: &str, mode: ParseMode) -> Result<WeatherResponse, ProviderError> {
+        Ok(match self {
+            Self::Current => {
+                let CurrentResponse { location, current } = parse(PROVIDER, body, mode)?;
+                WeatherResponse::Current { location, current }
+            },
+            Self::Forecast { .. } => {
+                let ForecastResponse {
+                    forecast,
+                    location,
+                    current,
+                } = parse(PROVIDER, body, mode)?;
+                WeatherResponse::Forecast {
+                    forecast,
+                    location,
+                    current,
+                }
+            },
+            Self::History { .. } => {
+                let HistoryResponse { location, forecast } = parse(PROVIDER, body, mode)?;
+                WeatherResponse::History { location, forecast }
+            },
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Forecast {
     pub forecastday: Vec<ForecastDay>,
@@ -36,6 +116,30 @@ pub struct Location {
     pub name: String,
     pub region: String,
     pub country: String,
+    /// IANA timezone of the location, e.g. "Europe/Lisbon".
+    #[serde(default)]
+    pub tz_id: Option<String>,
+    #[serde(default)]
+    pub lat: Option<f64>,
+    #[serde(default)]
+    pub lon: Option<f64>,
+}
+
+impl Location {
+    fn timezone(&self) -> Option<Tz> {
+        self.tz_id.as_deref().and_then(|id| id.parse().ok())
+    }
+
+    fn coordinates(&self) -> Option<Coordinates> {
+        Some(Coordinates {
+            lat: self.lat?,
+            lon: self.lon?,
+        })
+    }
+
+    fn display_name(&self) -> String {
+        format!("{}, {}", self.name, self.country)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,8 +152,16 @@ pub struct ForecastDay {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DayCondition {
     pub avgtemp_c: f64,
+    #[serde(default)]
+    pub totalsnow_cm: Option<f64>,
     pub avghumidity: f64,
     pub maxwind_kph: f64,
+    #[serde(default)]
+    pub daily_chance_of_rain: Option<f64>,
+    #[serde(default)]
+    pub uv: Option<f64>,
+    #[serde(default)]
+    pub avgvis_km: Option<f64>,
     pub condition: ConditionFields,
 }
 
@@ -57,10 +169,20 @@ pub struct DayCondition {
 pub struct HourCondition {
     pub time: String,
     pub temp_c: f64,
+    #[serde(default)]
+    pub snow_cm: Option<f64>,
     pub wind_kph: f64,
     pub wind_degree: f64,
     pub humidity: f64,
     pub pressure_mb: f64,
+    #[serde(default)]
+    pub feelslike_c: Option<f64>,
+    #[serde(default)]
+    pub chance_of_rain: Option<f64>,
+    #[serde(default)]
+    pub uv: Option<f64>,
+    #[serde(default)]
+    pub vis_km: Option<f64>,
     pub condition: ConditionFields,
 }
 
@@ -73,12 +195,78 @@ pub struct WeatherCondition {
     pub wind_degree: f64,
     pub humidity: f64,
     pub pressure_mb: f64,
+    #[serde(default)]
+    pub feelslike_c: Option<f64>,
+    #[serde(default)]
+    pub uv: Option<f64>,
+    #[serde(default)]
+    pub vis_km: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConditionFields {
     pub text: String,
     pub icon: String,
+    #[serde(default)]
+    pub code: Option<u32>,
+}
+
+/// Response of `v1/alerts.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertsResponse {
+    pub alerts: Alerts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alerts {
+    #[serde(default)]
+    pub alert: Vec<Alert>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub event: String,
+    #[serde(default)]
+    pub headline: Option<String>,
+    /// CAP severity for NWS alerts, awareness level for MeteoAlarm ones.
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub desc: Option<String>,
+    #[serde(default)]
+    pub effective: Option<String>,
+    #[serde(default)]
+    pub expires: Option<String>,
+}
+
+impl From<Alert> for WeatherAlert {
+    fn from(alert: Alert) -> Self {
+        let time = |value: Option<String>| {
+            value
+                .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+                .map(|t| t.with_timezone(&Utc))
+        };
+        let text = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+        Self {
+            severity: Severity::classify(alert.severity.as_deref(), &alert.event),
+            event: alert.event,
+            headline: text(alert.headline),
+            description: text(alert.desc),
+            effective: time(alert.effective),
+            expires: time(alert.expires),
+        }
+    }
+}
+
+impl ConditionFields {
+    fn kind(&self) -> ConditionKind {
+        self.code
+            .map_or(ConditionKind::Unknown, ConditionKind::from_weatherapi_code)
+    }
+
+    fn wmo_code(&self) -> Option<WeatherCode> {
+        self.code.and_then(WeatherCode::from_weatherapi)
+    }
 }
 
 impl TryFrom<WeatherResponse> for WeatherData {
@@ -86,19 +274,35 @@ impl TryFrom<WeatherResponse> for WeatherData {
 
     fn try_from(resp: WeatherResponse) -> Result<Self, Self::Error> {
         match resp {
-            WeatherResponse::Current { current, location } => {
-                let datetime = parse_local_datetime(&current.last_updated)?;
-                let location = format!("{}, {}", location.name, location.country);
+            WeatherResponse::Current { current, location }
+            | WeatherResponse::Forecast {
+                current, location, ..
+            } => {
+                let tz = location.timezone();
+                let datetime = parse_local_datetime(&current.last_updated, tz)?;
 
                 Ok(WeatherData {
-                    location,
+                    location: location.display_name(),
                     datetime,
                     temp_c: current.temp_c,
                     humidity: current.humidity,
                     pressure: current.pressure_mb,
+                    kind: current.condition.kind(),
+                    code: current.condition.wmo_code(),
                     condition: current.condition.text,
                     wind_kph: current.wind_kph,
                     wind_deg: current.wind_degree,
+                    feels_like_c: current.feelslike_c,
+                    uv_index: current.uv,
+                    precipitation_probability: None,
+                    visibility_km: current.vis_km,
+                    pressure_change_3h: None,
+                    utc_offset: utc_offset(tz, &datetime),
+                    coordinates: location.coordinates(),
+                    snow: None,
+                    pollen: None,
+                    anomalies: Vec::new(),
+                    sources: Default::default(),
                 })
             },
 
@@ -107,46 +311,126 @@ impl TryFrom<WeatherResponse> for WeatherData {
                 let day = &forecast_day.day;
 
                 let first_hour = &forecast_day.hour[0];
-                let datetime = parse_local_datetime(&first_hour.time)?;
-                let location = format!("{}, {}", location.name, location.country);
+                let tz = location.timezone();
+                let datetime = parse_local_datetime(&first_hour.time, tz)?;
 
                 Ok(WeatherData {
-                    location,
+                    location: location.display_name(),
                     datetime,
                     temp_c: day.avgtemp_c,
                     humidity: day.avghumidity,
                     pressure: first_hour.pressure_mb,
                     condition: day.condition.text.clone(),
+                    kind: day.condition.kind(),
+                    code: day.condition.wmo_code(),
                     wind_kph: first_hour.wind_kph,
                     wind_deg: first_hour.wind_degree,
+                    feels_like_c: None,
+                    uv_index: day.uv,
+                    precipitation_probability: day.daily_chance_of_rain,
+                    visibility_km: day.avgvis_km,
+                    pressure_change_3h: None,
+                    utc_offset: utc_offset(tz, &datetime),
+                    coordinates: location.coordinates(),
+                    snow: fresh_snow(day.totalsnow_cm),
+                    pollen: None,
+                    anomalies: Vec::new(),
+                    sources: Default::default(),
                 })
             },
-            WeatherResponse::Forecast {
-                forecast,
-                location,
-                current,
-            } => {
-                debug!("{:?}", &forecast);
-                debug!("{:?}", &location);
-                debug!("{:?}", &current);
-
-                Ok(WeatherData::default())
-            },
         }
     }
 }
 
-/// Parse "YYYY-MM-DD HH:MM" string into `DateTime<Utc>`
-fn parse_local_datetime(date_str: &str) -> Result<DateTime<Utc>, ProviderError> {
+/// The forecast hour closest to `date`, local time of the location.
+fn forecast_hour(
+    forecast: &Forecast,
+    location: &Location,
+    date: NaiveDateTime,
+) -> Result<WeatherData, ProviderError> {
+    let hour = forecast
+        .forecastday
+        .iter()
+        .flat_map(|day| &day.hour)
+        .filter_map(|hour| {
+            let time = NaiveDateTime::parse_from_str(&hour.time, "%Y-%m-%d %H:%M").ok()?;
+            Some(((time - date).num_seconds().abs(), hour))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, hour)| hour)
+        .ok_or_else(|| ProviderError::Error("forecast contains no hours".to_string()))?;
+
+    let tz = location.timezone();
+    let datetime = parse_local_datetime(&hour.time, tz)?;
+
+    Ok(WeatherData {
+        location: location.display_name(),
+        datetime,
+        temp_c: hour.temp_c,
+        humidity: hour.humidity,
+        pressure: hour.pressure_mb,
+        condition: hour.condition.text.clone(),
+        kind: hour.condition.kind(),
+        code: hour.condition.wmo_code(),
+        wind_kph: hour.wind_kph,
+        wind_deg: hour.wind_degree,
+        feels_like_c: hour.feelslike_c,
+        uv_index: hour.uv,
+        precipitation_probability: hour.chance_of_rain,
+        visibility_km: hour.vis_km,
+        pressure_change_3h: None,
+        utc_offset: utc_offset(tz, &datetime),
+        coordinates: location.coordinates(),
+        snow: fresh_snow(hour.snow_cm),
+        pollen: None,
+        anomalies: Vec::new(),
+        sources: Default::default(),
+    })
+}
+
+/// Snowfall is the only snow field WeatherAPI reports, and only in forecasts and history.
+fn fresh_snow(snow_cm: Option<f64>) -> Option<SnowData> {
+    snow_cm.map(|cm| SnowData {
+        fresh_cm: Some(cm),
+        ..Default::default()
+    })
+}
+
+/// Days of forecast needed to cover `date`; WeatherAPI serves at most 14.
+fn forecast_days(date: NaiveDateTime, now: NaiveDateTime) -> i64 {
+    ((date.date() - now.date()).num_days() + 1).clamp(1, 14)
+}
+
+/// Parse "YYYY-MM-DD HH:MM" local time of the location into `DateTime<Utc>`.
+/// Without a known timezone the time is taken as UTC.
+fn parse_local_datetime(date_str: &str, tz: Option<Tz>) -> Result<DateTime<Utc>, ProviderError> {
     let naive = NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M")
         .map_err(|e| ProviderError::ParseDateTime(format!("Failed to parse datetime: {e}")))?;
-    Ok(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc))
+
+    let Some(tz) = tz else {
+        return Ok(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc));
+    };
+
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| ProviderError::ParseDateTime(format!("'{date_str}' does not exist in {tz}")))
+}
+
+fn utc_offset(tz: Option<Tz>, datetime: &DateTime<Utc>) -> Option<FixedOffset> {
+    tz.map(|tz| tz.offset_from_utc_datetime(&datetime.naive_utc()).fix())
 }
 
+const PROVIDER: &str = "weatherapi";
+
 /// Implementation fo `WeatherApi` '<https://www.weatherapi.com/>'
 pub struct WeatherApi {
+    client: reqwest::Client,
     api_key: String,
     base_url: Url,
+    language: Option<String>,
+    parse_mode: ParseMode,
+    options: Vec<(String, String)>,
 }
 
 impl WeatherApi {
@@ -158,15 +442,80 @@ impl WeatherApi {
             ProviderError::InvalidApiKey("WeatherApi requires API_KEY".to_string())
         })?;
 
-        Ok(Self { api_key, base_url })
+        Ok(Self {
+            client: http_client()?,
+            api_key,
+            base_url,
+            language: None,
+            parse_mode: ParseMode::default(),
+            options: Vec::new(),
+        })
+    }
+
+    /// Request condition texts in the given language, e.g. "de".
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
     }
 
-    #[cfg(test)]
+    /// Extra query parameters, e.g. `aqi`, replacing those of the same name.
+    pub fn with_options(mut self, options: Vec<(String, String)>) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = parse_mode;
+        self
+    }
+
+    /// Send requests to `base_url` instead of the public API.
     pub fn with_base_url(mut self, base_url: impl Into<Url>) -> Self {
         self.base_url = base_url.into();
         self
     }
 
+    /// URL of `endpoint` for `location`.
+    fn url(&self, location: &str, endpoint: Endpoint) -> String {
+        let mut url = match endpoint {
+            Endpoint::Current => format!(
+                "{}v1/current.json?key={}&q={}&aqi=no",
+                self.base_url, self.api_key, location
+            ),
+            Endpoint::Forecast { days } => {
+                info!("forecast");
+                format!(
+                    "{}v1/forecast.json?key={}&q={}&days={days}&aqi=no&alerts=no",
+                    self.base_url, self.api_key, location,
+                )
+            },
+            Endpoint::History { date } => {
+                info!("history");
+                format!(
+                    "{}v1/history.json?key={}&q={}&aqi=no&dt={}",
+                    self.base_url, self.api_key, location, date
+                )
+            },
+        };
+
+        if let Some(language) = &self.language {
+            url.push_str(&format!("&lang={language}"));
+        }
+
+        self.optioned(url)
+    }
+
+    /// `url` with the configured options set.
+    fn optioned(&self, url: String) -> String {
+        match Url::parse(&url) {
+            Ok(mut parsed) if !self.options.is_empty() => {
+                set_options(&mut parsed, &self.options);
+                parsed.into()
+            },
+            _ => url,
+        }
+    }
+
     async fn get_weather(
         &self,
         location: impl AsRef<str>,
@@ -184,53 +533,18 @@ impl WeatherApi {
             ));
         }
 
-        let url = date.map_or_else(
-            || {
-                format!(
-                    "{}v1/current.json?key={}&q={}&aqi=no",
-                    self.base_url,
-                    self.api_key,
-                    location.as_ref()
-                )
-            },
-            |date| {
-                let now = Local::now().naive_local();
-
-                if date > now {
-                    info!("forecast");
-                    format!(
-                        "{}v1/forecast.json?key={}&q={}&days=1&aqi=no&alerts=no",
-                        self.base_url,
-                        self.api_key,
-                        location.as_ref(),
-                    )
-                } else {
-                    info!("history");
-                    format!(
-                        "{}v1/history.json?key={}&q={}&aqi=no&dt={}",
-                        self.base_url,
-                        self.api_key,
-                        location.as_ref(),
-                        date
-                    )
-                }
-            },
-        );
-
-        let res = reqwest::get(&url)
-            .await
-            .map_err(ProviderError::Request)?
-            .error_for_status()
-            .map_err(ProviderError::Request)?;
+        let endpoint = Endpoint::of(date);
+        let url = self.url(location.as_ref(), endpoint);
+        let res = send(PROVIDER, location.as_ref(), self.client.get(&url)).await?;
 
         debug!("Status :{:#?}", res.status());
 
-        let weather_response: WeatherResponse = res.json().await?;
-        Ok(weather_response)
+        endpoint.parse(&text(PROVIDER, res).await?, self.parse_mode)
     }
 }
 
-#[async_trait::async_trait]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl WeatherProvider for WeatherApi {
     async fn fetch(
         &self,
@@ -238,17 +552,71 @@ impl WeatherProvider for WeatherApi {
         date: Option<NaiveDateTime>,
     ) -> Result<WeatherData, ProviderError> {
         let weather = self.get_weather(location, date).await?;
-        let res = WeatherData::try_from(weather)
-            .map_err(|e| ProviderError::ParseDateTime(e.to_string()))?;
+        match (weather, date) {
+            (
+                WeatherResponse::Forecast {
+                    forecast, location, ..
+                },
+                Some(date),
+            ) => forecast_hour(&forecast, &location, date),
+            (weather, _) => WeatherData::try_from(weather),
+        }
+    }
+
+    async fn fetch_alerts(&self, location: &str) -> Result<Vec<WeatherAlert>, ProviderError> {
+        if location.is_empty() {
+            return Err(ProviderError::InvalidLocation(location.to_string()));
+        }
+        let request = self
+            .client
+            .get(format!("{}v1/alerts.json", self.base_url))
+            .query(&[("key", self.api_key.as_str()), ("q", location)]);
+        let res = send(PROVIDER, location, with_options(request, &self.options)).await?;
+        let response: AlertsResponse = json(PROVIDER, res, self.parse_mode).await?;
+        Ok(response.alerts.alert.into_iter().map(Into::into).collect())
+    }
+
+    fn plan(
+        &self,
+        location: &str,
+        date: Option<NaiveDateTime>,
+    ) -> Result<Vec<String>, ProviderError> {
+        let url = Url::parse(&self.url(location, Endpoint::of(date)))
+            .map_err(|e| ProviderError::Error(format!("Invalid API URL: {e}")))?;
+        Ok(vec![redact(&url, "key")])
+    }
 
-        Ok(WeatherData { ..res })
+    fn api_version(&self) -> Option<ApiVersion> {
+        Some(ApiVersion {
+            version: "v1",
+            deprecation: None,
+        })
     }
 }
 
+/// Map a recorded response from `endpoint`, see [`crate::weather_providers::fixtures`].
+#[cfg(test)]
+pub(super) fn map_fixture(endpoint: &str, body: &str) -> Result<(), ProviderError> {
+    let endpoint = match endpoint {
+        "current" => Endpoint::Current,
+        "forecast" => Endpoint::Forecast { days: 1 },
+        "history" => Endpoint::History {
+            date: NaiveDateTime::default(),
+        },
+        "alerts" => {
+            parse::<AlertsResponse>(PROVIDER, body, ParseMode::Strict)?;
+            return Ok(());
+        },
+        _ => return Err(ProviderError::Error(format!("no mapping for {endpoint}"))),
+    };
+    WeatherData::try_from(endpoint.parse(body, ParseMode::Strict)?)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use wiremock::matchers::{method, path};
+    use wiremock::matchers::{method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     const MOCK_CURRENT_RESPONSE: &str = r#"{
@@ -317,6 +685,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lenient_fills_fields_of_the_endpoint_variant() {
+        let body = MOCK_CURRENT_RESPONSE.replace(r#""temp_c": 16.1,"#, "");
+
+        let lenient = Endpoint::Current.parse(&body, ParseMode::Lenient).unwrap();
+        let WeatherResponse::Current { location, current } = lenient else {
+            panic!("expected WeatherResponse::Current variant");
+        };
+        assert_eq!(location.name, "Porto");
+        assert_eq!(current.temp_c, 0.0);
+
+        let strict = Endpoint::Current
+            .parse(&body, ParseMode::Strict)
+            .unwrap_err();
+        assert!(
+            matches!(&strict, ProviderError::Schema { path, message, .. }
+                if path == "current" && message.contains("temp_c")),
+            "{strict}"
+        );
+    }
+
     #[tokio::test]
     async fn success_parsing() {
         let server = MockServer::start().await;
@@ -342,9 +731,50 @@ mod tests {
         assert_eq!(result.temp_c, 16.1);
         assert_eq!(result.wind_kph, 22.0);
         assert_eq!(result.condition, "Partly cloudy");
+        assert_eq!(result.kind, ConditionKind::PartlyCloudy);
         assert_eq!(result.datetime, expected_datetime);
     }
 
+    #[test]
+    fn datetime_in_location_timezone() {
+        let body = MOCK_CURRENT_RESPONSE.replace("Europe/Lisbon", "Asia/Tokyo");
+        let resp = serde_json::from_str::<WeatherResponse>(&body).unwrap();
+        let result = WeatherData::try_from(resp).unwrap();
+
+        let expected_datetime = DateTime::parse_from_rfc3339("2025-12-05T17:15:00+09:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(result.datetime, expected_datetime);
+        assert_eq!(result.utc_offset, FixedOffset::east_opt(9 * 3600));
+        assert_eq!(
+            result.local_datetime().to_string(),
+            "2025-12-05 17:15:00 +09:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn request_localized_conditions() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/current.json"))
+            .and(query_param("lang", "de"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_raw(MOCK_CURRENT_RESPONSE, "application/json"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let api = WeatherApi::new(Some("test_api_key".to_string()))
+            .unwrap()
+            .with_base_url(server.uri().parse::<Url>().unwrap())
+            .with_language(Some("de".to_string()));
+
+        assert!(api.get_weather("Porto", None).await.is_ok());
+    }
+
     #[tokio::test]
     async fn invalid_location() {
         let api = WeatherApi::new(Some("test_api_key".to_string())).unwrap();
@@ -393,4 +823,144 @@ mod tests {
         assert_eq!(result.condition, "Partly cloudy");
         assert_eq!(result.datetime, expected_datetime);
     }
+
+    #[tokio::test]
+    async fn fetch_alerts() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/alerts.json"))
+            .and(query_param("q", "Porto & Co"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(
+                r#"{"location": {"name": "Porto"}, "alerts": {"alert": [{
+                    "headline": "Orange wind warning for Porto",
+                    "severity": "Severe",
+                    "event": "Orange Wind Warning",
+                    "effective": "2025-12-05T06:00:00+00:00",
+                    "expires": "2025-12-05T21:00:00+00:00",
+                    "desc": ""
+                }, {
+                    "event": "Coastal event",
+                    "severity": "2; yellow"
+                }]}}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let api = WeatherApi::new(Some("test_api_key".to_string()))
+            .unwrap()
+            .with_base_url(server.uri().parse::<Url>().unwrap());
+        let alerts = api.fetch_alerts("Porto & Co").await.unwrap();
+
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].severity, Severity::Warning);
+        assert_eq!(alerts[0].description, None);
+        assert_eq!(
+            alerts[0].expires,
+            Some(Utc.with_ymd_and_hms(2025, 12, 5, 21, 0, 0).unwrap())
+        );
+        assert_eq!(alerts[1].severity, Severity::Watch);
+    }
+
+    #[tokio::test]
+    async fn unknown_location() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/current.json"))
+            .respond_with(ResponseTemplate::new(400).set_body_raw(
+                r#"{"error":{"code":1006,"message":"No matching location found."}}"#,
+                "application/json",
+            ))
+            .mount(&server)
+            .await;
+
+        let api = WeatherApi::new(Some("test_api_key".to_string()))
+            .unwrap()
+            .with_base_url(server.uri().parse::<Url>().unwrap());
+
+        match api.fetch("Nowhere", None).await {
+            Err(ProviderError::LocationNotFound { provider, location }) => {
+                assert_eq!(provider, "weatherapi");
+                assert_eq!(location, "Nowhere");
+            },
+            other => panic!("expected LocationNotFound, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn server_error() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+
+        let api = WeatherApi::new(Some("test_api_key".to_string()))
+            .unwrap()
+            .with_base_url(server.uri().parse::<Url>().unwrap());
+
+        assert!(matches!(
+            api.fetch("Porto", None).await,
+            Err(ProviderError::ServerError { status: 503, .. })
+        ));
+    }
+
+    #[test]
+    fn closest_forecast_hour() {
+        let hour = |time: &str, temp_c: f64| {
+            serde_json::json!({
+                "time": time, "temp_c": temp_c, "wind_kph": 10.0, "wind_degree": 90,
+                "humidity": 80, "pressure_mb": 1015.0,
+                "condition": {"text": "Light rain", "icon": "", "code": 1183}
+            })
+        };
+        let body = serde_json::json!({
+            "location": {
+                "name": "Porto", "region": "Porto", "country": "Portugal",
+                "tz_id": "Europe/Lisbon", "lat": 41.15, "lon": -8.62
+            },
+            "current": serde_json::from_str::<serde_json::Value>(MOCK_CURRENT_RESPONSE).unwrap()["current"],
+            "forecast": {"forecastday": [{
+                "date": "2025-12-06",
+                "day": {
+                    "avgtemp_c": 12.0, "avghumidity": 80, "maxwind_kph": 20.0,
+                    "condition": {"text": "Rain", "icon": "", "code": 1189}
+                },
+                "hour": [hour("2025-12-06 08:00", 9.0), hour("2025-12-06 09:00", 10.0)]
+            }]}
+        });
+
+        let resp: WeatherResponse = serde_json::from_value(body).unwrap();
+        let WeatherResponse::Forecast {
+            forecast, location, ..
+        } = resp
+        else {
+            panic!("expected WeatherResponse::Forecast variant");
+        };
+
+        let date = NaiveDateTime::parse_from_str("2025-12-06 08:40", "%Y-%m-%d %H:%M").unwrap();
+        let result = forecast_hour(&forecast, &location, date).unwrap();
+
+        assert_eq!(result.temp_c, 10.0);
+        assert_eq!(result.kind, ConditionKind::Rain);
+        assert_eq!(
+            result.coordinates,
+            Some(Coordinates {
+                lat: 41.15,
+                lon: -8.62
+            })
+        );
+    }
+
+    #[test]
+    fn days_of_forecast() {
+        let now = NaiveDateTime::parse_from_str("2025-12-06 20:00", "%Y-%m-%d %H:%M").unwrap();
+
+        assert_eq!(forecast_days(now + chrono::Duration::hours(2), now), 1);
+        assert_eq!(forecast_days(now + chrono::Duration::hours(5), now), 2);
+        assert_eq!(forecast_days(now + chrono::Duration::days(30), now), 14);
+    }
 }
@@ -0,0 +1,167 @@
+use crate::output::OutputOptions;
+use crate::output::chat::{discord_message, slack_message};
+use crate::output::json::to_json;
+use crate::weather_providers::WeatherData;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Upper bound for a single delivery.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Endpoints a fetched result is POSTed to, by payload shape.
+#[derive(Debug, Default, Clone)]
+pub struct Targets {
+    /// Receive the result as in `--format json`.
+    pub json: Vec<String>,
+    /// Slack incoming webhooks.
+    pub slack: Vec<String>,
+    /// Discord channel webhooks.
+    pub discord: Vec<String>,
+}
+
+impl Targets {
+    /// POST the result to every target.
+    pub async fn deliver(&self, data: &WeatherData, provider: &str, options: &OutputOptions) {
+        post_json(&self.json, &to_json(data, provider)).await;
+        post_json(&self.slack, &slack_message(data, provider, options)).await;
+        post_json(&self.discord, &discord_message(data, provider, options)).await;
+    }
+
+    /// POST a warning, e.g. frost, to every target.
+    ///
+    /// JSON targets receive `{"alert", "location", "title", "message"}`,
+    /// chat targets a plain message.
+    pub async fn alert(&self, alert: &str, location: &str, title: &str, message: &str) {
+        let payload = json!({
+            "alert": alert,
+            "location": location,
+            "title": title,
+            "message": message,
+        });
+        post_json(&self.json, &payload).await;
+        post_json(
+            &self.slack,
+            &json!({"text": format!("*{title}*\n{message}")}),
+        )
+        .await;
+        post_json(
+            &self.discord,
+            &json!({"content": format!("**{title}**\n{message}")}),
+        )
+        .await;
+    }
+}
+
+/// POST `payload` to every URL in `urls`.
+///
+/// Deliveries run concurrently; a failing endpoint is logged and does not affect the others.
+pub async fn post_json(urls: &[String], payload: &Value) {
+    if urls.is_empty() {
+        return;
+    }
+
+    let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("Failed to build webhook client: {e}");
+            return;
+        },
+    };
+
+    let mut deliveries = tokio::task::JoinSet::new();
+    for url in urls {
+        let request = client.post(url).json(payload);
+        let url = url.clone();
+        deliveries.spawn(async move {
+            match request.send().await.and_then(|res| res.error_for_status()) {
+                Ok(res) => debug!("Webhook {url} answered {}", res.status()),
+                Err(e) => warn!("Webhook {url} failed: {e}"),
+            }
+        });
+    }
+    deliveries.join_all().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn posts_to_every_target() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_partial_json(json!({
+                "location": "Porto, Portugal",
+                "provider": "weatherapi",
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/slack"))
+            .and(body_partial_json(json!({"blocks": [{"type": "header"}]})))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/broken"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let data = WeatherData {
+            location: "Porto, Portugal".to_string(),
+            ..Default::default()
+        };
+        let targets = Targets {
+            json: vec![
+                format!("{}/broken", server.uri()),
+                format!("{}/hook", server.uri()),
+            ],
+            slack: vec![format!("{}/slack", server.uri())],
+            ..Default::default()
+        };
+
+        targets
+            .deliver(&data, "weatherapi", &OutputOptions::default())
+            .await;
+    }
+
+    #[tokio::test]
+    async fn alerts_every_target() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(body_partial_json(
+                json!({"alert": "frost", "location": "Porto"}),
+            ))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/discord"))
+            .and(body_partial_json(json!({"content": "**Frost**\n-2°C"})))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let targets = Targets {
+            json: vec![format!("{}/hook", server.uri())],
+            discord: vec![format!("{}/discord", server.uri())],
+            ..Default::default()
+        };
+
+        targets.alert("frost", "Porto", "Frost", "-2°C").await;
+    }
+}
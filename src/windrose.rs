@@ -0,0 +1,166 @@
+use crate::i18n::tr_args;
+use crate::output::OutputOptions;
+
+/// Compass points the rose is split into, clockwise from north.
+const SECTORS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+/// Upper bounds of the speed bins in km/h; the last bin has none.
+const SPEED_BINS: [f64; 3] = [10.0, 20.0, 40.0];
+/// Wind below this speed in km/h has no direction worth counting.
+const CALM_KPH: f64 = 2.0;
+/// Width of the longest bar, in characters.
+const BAR_WIDTH: usize = 30;
+
+/// Readings counted by compass point and speed bin.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindRose {
+    /// Per sector of [`SECTORS`], the readings per bin of [`SPEED_BINS`] and the one above.
+    pub counts: [[usize; SPEED_BINS.len() + 1]; SECTORS.len()],
+    pub calm: usize,
+}
+
+impl WindRose {
+    /// Tally `winds`, each a speed in km/h and the direction it comes from in degrees.
+    pub fn from_winds(winds: &[(f64, f64)]) -> Self {
+        let mut rose = Self::default();
+        for &(kph, deg) in winds {
+            if kph < CALM_KPH {
+                rose.calm += 1;
+                continue;
+            }
+            let width = 360.0 / SECTORS.len() as f64;
+            let sector = ((deg.rem_euclid(360.0) + width / 2.0) / width) as usize % SECTORS.len();
+            let bin = SPEED_BINS
+                .iter()
+                .position(|bound| kph < *bound)
+                .unwrap_or(SPEED_BINS.len());
+            rose.counts[sector][bin] += 1;
+        }
+        rose
+    }
+
+    pub fn total(&self) -> usize {
+        self.calm + self.counts.iter().flatten().sum::<usize>()
+    }
+
+    /// The compass point the wind comes from most often, `None` without any wind.
+    pub fn prevailing(&self) -> Option<&'static str> {
+        let (i, count) = self
+            .counts
+            .iter()
+            .map(|bins| bins.iter().sum::<usize>())
+            .enumerate()
+            .max_by_key(|(_, count)| *count)?;
+        (count > 0).then_some(SECTORS[i])
+    }
+}
+
+/// Terminal wind rose for `weather windrose`: a bar per compass point, shaded by speed.
+pub fn render_windrose(
+    location: &str,
+    days: u32,
+    rose: &WindRose,
+    options: &OutputOptions,
+) -> String {
+    let shades = if options.ascii {
+        ['.', '-', '=', '#']
+    } else {
+        ['░', '▒', '▓', '█']
+    };
+    let total = rose.total();
+    let mut lines = vec![tr_args(
+        "windrose-title",
+        &[
+            ("location", location.to_string()),
+            ("count", total.to_string()),
+            ("days", days.to_string()),
+        ],
+    )];
+    if total == 0 {
+        return options.sanitize(&lines.join("\n"));
+    }
+
+    let percent = |count: usize| count as f64 * 100.0 / total as f64;
+    let longest = rose
+        .counts
+        .iter()
+        .map(|bins| bins.iter().sum::<usize>())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for (name, bins) in SECTORS.iter().zip(&rose.counts) {
+        let count: usize = bins.iter().sum();
+        // Each bin gets its share of the bar, rounded on the running total so they add up.
+        let mut bar = String::new();
+        let mut drawn = 0;
+        let mut so_far = 0;
+        for (bin, shade) in bins.iter().zip(shades) {
+            so_far += bin;
+            let end = (so_far * BAR_WIDTH + longest / 2) / longest;
+            bar.extend(std::iter::repeat_n(shade, end - drawn));
+            drawn = end;
+        }
+        let line = format!("{name:<2} {:>3.0}% {bar}", percent(count));
+        lines.push(line.trim_end().to_string());
+    }
+    lines.push(tr_args(
+        "windrose-calm",
+        &[("percent", format!("{:.0}", percent(rose.calm)))],
+    ));
+    if let Some(sector) = rose.prevailing() {
+        lines.push(tr_args(
+            "windrose-prevailing",
+            &[("direction", sector.to_string())],
+        ));
+    }
+
+    let mut lower = 0.0;
+    let mut legend: Vec<String> = SPEED_BINS
+        .iter()
+        .zip(shades)
+        .map(|(upper, shade)| {
+            let label = format!("{shade} {lower:.0}-{upper:.0}");
+            lower = *upper;
+            label
+        })
+        .collect();
+    legend.push(format!("{} {lower:.0}+ km/h", shades[SPEED_BINS.len()]));
+    lines.push(legend.join("  "));
+    options.sanitize(&lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tallied_by_direction_and_speed() {
+        let winds = [
+            (5.0, 350.0),
+            (15.0, 10.0),
+            (25.0, 20.0),
+            (50.0, 5.0),
+            (12.0, 225.0),
+            (1.0, 90.0),
+        ];
+
+        let rose = WindRose::from_winds(&winds);
+
+        assert_eq!(rose.counts[0], [1, 1, 1, 1]);
+        assert_eq!(rose.counts[5], [0, 1, 0, 0]);
+        assert_eq!(rose.calm, 1);
+        assert_eq!(rose.prevailing(), Some("N"));
+
+        let options = OutputOptions {
+            ascii: true,
+            ..Default::default()
+        };
+        let text = render_windrose("Porto", 90, &rose, &options);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "N   67% ........-------========#######");
+        assert_eq!(lines[2], "NE   0%");
+        assert_eq!(lines[6], "SW  17% --------");
+        assert_eq!(lines[9], "Calm: 17%");
+        assert_eq!(lines[10], "Prevailing wind: from N");
+        assert_eq!(lines[11], ". 0-10  - 10-20  = 20-40  # 40+ km/h");
+    }
+}
@@ -1,4 +1,5 @@
 use assert_cmd::cargo;
+use predicates::prelude::*;
 use std::fs;
 use std::path::PathBuf;
 
@@ -70,3 +71,151 @@ fn fail_to_set_not_supported_provider() {
         )));
     fs::remove_file(config_path).unwrap();
 }
+
+#[test]
+fn suggests_closest_provider() {
+    let config_path = PathBuf::from("tests/test_settings8.toml");
+    setup_test_config(&config_path);
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.arg("--config-path")
+        .arg(&config_path)
+        .arg("configure")
+        .arg("openwether")
+        .assert()
+        .stderr(predicates::str::contains("Did you mean `openweather`?"));
+    fs::remove_file(config_path).unwrap();
+}
+
+#[test]
+fn quiet_suppresses_logs() {
+    let config_path = PathBuf::from("tests/test_settings4.toml");
+    setup_test_config(&config_path);
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.env("RUST_LOG", "trace")
+        .arg("--config-path")
+        .arg(&config_path)
+        .arg("configure")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("App started"))
+        .stdout(predicates::str::contains("App started").not());
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.env("RUST_LOG", "trace")
+        .arg("--config-path")
+        .arg(&config_path)
+        .arg("--quiet")
+        .arg("configure")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("App started").not())
+        .stdout(predicates::str::contains("Available providers:"));
+    fs::remove_file(config_path).unwrap();
+}
+
+#[test]
+fn unversioned_settings_suggest_migrating() {
+    let config_path = PathBuf::from("tests/test_settings10.toml");
+    setup_test_config(&config_path);
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.arg("--config-path")
+        .arg(&config_path)
+        .arg("configure")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "uses settings version 0, run `weather config migrate`",
+        ));
+    fs::remove_file(config_path).unwrap();
+}
+
+#[test]
+fn invalid_format_exits_with_usage_code() {
+    let config_path = PathBuf::from("tests/test_settings5.toml");
+    setup_test_config(&config_path);
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.arg("--config-path")
+        .arg(&config_path)
+        .arg("--format")
+        .arg("bogus")
+        .arg("configure")
+        .assert()
+        .code(2)
+        .stderr(predicates::str::contains("Invalid output format: 'bogus'"));
+    fs::remove_file(config_path).unwrap();
+}
+
+#[test]
+fn env_file_sets_default_provider() {
+    let config_path = PathBuf::from("tests/test_settings6.toml");
+    let env_path = PathBuf::from("tests/test6.env");
+    setup_test_config(&config_path);
+    fs::write(&env_path, "WEATHER_DEFAULT_PROVIDER=openweather\n").unwrap();
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.env_remove("WEATHER_DEFAULT_PROVIDER")
+        .env_remove("DEFAULT_PROVIDER")
+        .arg("--config-path")
+        .arg(&config_path)
+        .arg("--env-file")
+        .arg(&env_path)
+        .arg("--dry-run")
+        .arg("get")
+        .arg("Porto")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Provider: OPENWEATHER"));
+    fs::remove_file(&env_path).unwrap();
+
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.arg("--config-path")
+        .arg(&config_path)
+        .arg("--env-file")
+        .arg(&env_path)
+        .arg("configure")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Failed to load .env file"));
+    fs::remove_file(config_path).unwrap();
+}
+
+#[test]
+fn provider_flag_overrides_default_provider() {
+    let config_path = PathBuf::from("tests/test_settings7.toml");
+    setup_test_config(&config_path);
+    let mut cmd = cargo::cargo_bin_cmd!();
+    cmd.env_remove("WEATHER_DEFAULT_PROVIDER")
+        .env_remove("DEFAULT_PROVIDER")
+        .arg("--config-path")
+        .arg(&config_path)
+        .arg("--dry-run")
+        .arg("get")
+        .arg("Porto")
+        .arg("-p")
+        .arg("OpenWeather")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Provider: OPENWEATHER"));
+
+    let settings = fs::read_to_string(&config_path).unwrap();
+    assert!(settings.contains(r#"default_provider = "weatherapi""#));
+    fs::remove_file(config_path).unwrap();
+}
+
+#[test]
+fn print0_separates_batch_results_with_nul() {
+    let config_path = PathBuf::from("tests/test_settings9.toml");
+    setup_test_config(&config_path);
+    let mut cmd = cargo::cargo_bin_cmd!();
+    let output = cmd
+        .arg("--config-path")
+        .arg(&config_path)
+        .args(["--quiet", "--demo", "--format", "tsv", "batch", "-0"])
+        .args(["Porto, PT", "Oslo"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let records: Vec<&str> = stdout.split_terminator('\0').collect();
+    assert_eq!(records.len(), 2, "{stdout:?}");
+    assert!(records[0].starts_with("Porto, PT\t"));
+    assert!(!stdout.contains('\n'));
+    fs::remove_file(config_path).unwrap();
+}